@@ -0,0 +1,68 @@
+use crate::combat::components::HurtBox;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Side of a spatial hash cell, in world units (a little over a ghost's hurtbox)
+const HASH_CELL_SIZE: f32 = 128.0;
+
+/// Broadphase for hit detection: every `HurtBox` bucketed by the grid cells it overlaps
+///
+/// `rebuild_spatial_hash` refills it at the start of `GameSet::Collisions`
+/// each frame, so `detect_combat_collisions` and
+/// `detect_player_enemy_collisions` only test the hurtboxes near an attack
+/// instead of every one on the field. Candidates still need the exact test
+/// (AABB or distance, plus the lane check). Buckets are emptied rather than
+/// dropped between frames, so a busy stage doesn't reallocate them.
+#[derive(Resource, Default)]
+pub struct SpatialHash {
+    cells: HashMap<IVec2, Vec<Entity>>,
+}
+
+impl SpatialHash {
+    /// Empty every bucket, keeping their allocations
+    pub fn clear(&mut self) {
+        for bucket in self.cells.values_mut() {
+            bucket.clear();
+        }
+    }
+
+    /// Add a box, given by center and size, to every cell it overlaps
+    pub fn insert(&mut self, entity: Entity, center: Vec2, size: Vec2) {
+        for cell in cells_overlapping(center, size) {
+            self.cells.entry(cell).or_default().push(entity);
+        }
+    }
+
+    /// Entities whose boxes share a cell with the box at `center`, each listed once
+    ///
+    /// A superset of the boxes that actually overlap it.
+    pub fn query(&self, center: Vec2, size: Vec2) -> Vec<Entity> {
+        let mut found: Vec<Entity> = cells_overlapping(center, size)
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+            .collect();
+        found.sort_unstable();
+        found.dedup();
+        found
+    }
+}
+
+/// Grid cells covered by a box, given by center and size
+fn cells_overlapping(center: Vec2, size: Vec2) -> impl Iterator<Item = IVec2> {
+    let half = size / 2.0;
+    let min = ((center - half) / HASH_CELL_SIZE).floor().as_ivec2();
+    let max = ((center + half) / HASH_CELL_SIZE).floor().as_ivec2();
+    (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| IVec2::new(x, y)))
+}
+
+/// Rebuild the `SpatialHash` from this frame's hurtbox positions
+pub fn rebuild_spatial_hash(
+    mut spatial_hash: ResMut<SpatialHash>,
+    hurtbox_query: Query<(Entity, &Transform, &HurtBox)>,
+) {
+    spatial_hash.clear();
+    for (entity, transform, hurtbox) in hurtbox_query.iter() {
+        spatial_hash.insert(entity, transform.translation.truncate(), hurtbox.size);
+    }
+}
@@ -0,0 +1,127 @@
+use crate::combat::components::{
+    GuardBroken, HurtBox, Invulnerable, Knockback, ScreenFlash, SpecialMeter, Stunned,
+};
+use crate::combat::grab::{Grabbed, Thrown};
+use crate::combat::messages::Team;
+use crate::enemy::{Enemy, SpawnProtected};
+use crate::game::InGame;
+use crate::input::{PlayerActions, PlayerTwoActions};
+use crate::player::{Player, PlayerState, PlayerStateType, PlayerTwo};
+use bevy::prelude::*;
+
+/// Special meter spent on a burst
+pub const BURST_COST: f32 = 50.0;
+/// Enemies within this distance of the player are blown away
+const BURST_RADIUS: f32 = 250.0;
+/// Speed enemies are pushed away at (scaled down with distance)
+const BURST_KNOCKBACK: f32 = 700.0;
+/// Seconds blown-away enemies are stunned
+const BURST_STUN_SECONDS: f32 = 0.6;
+/// Seconds the player can't be hit after bursting
+const BURST_INVULNERABLE_SECONDS: f32 = 0.75;
+
+/// Spend half the special meter to break out of trouble
+///
+/// Usable whenever the Burst action is pressed with at least `BURST_COST`
+/// meter, including in states that normally lock input: mid-attack, stunned,
+/// knocked back, guard broken, or held. Clears all of those from the player,
+/// cancels a locked state back to Idle, grants brief invulnerability, and
+/// pushes everything nearby on a different team away from the player,
+/// stunning enemies. In versus games player 2 bursts with `PlayerTwoActions`,
+/// and a nearby opponent is pushed away too (but not stunned). Not available while down or during a
+/// Super (which is already invulnerable).
+#[allow(clippy::type_complexity)]
+pub fn trigger_burst(
+    mut commands: Commands,
+    actions: Res<PlayerActions>,
+    player_two_actions: Res<PlayerTwoActions>,
+    mut player_query: Query<
+        (
+            Entity,
+            &Transform,
+            &mut PlayerState,
+            &mut SpecialMeter,
+            &Team,
+            Has<PlayerTwo>,
+        ),
+        With<Player>,
+    >,
+    target_query: Query<
+        (Entity, &Transform, &Team, Has<Enemy>),
+        (
+            With<HurtBox>,
+            Without<SpawnProtected>,
+            Without<Grabbed>,
+            Without<Thrown>,
+        ),
+    >,
+) {
+    for (player_entity, player_transform, mut state, mut meter, &team, player_two) in
+        player_query.iter_mut()
+    {
+        let burst = if player_two {
+            player_two_actions.burst
+        } else {
+            actions.burst
+        };
+        if !burst
+            || meter.value < BURST_COST
+            || matches!(
+                state.state_type(),
+                PlayerStateType::Defeat | PlayerStateType::Super
+            )
+        {
+            continue;
+        }
+
+        meter.value -= BURST_COST;
+        if state.locks_input() {
+            *state = PlayerState::transition_to(PlayerStateType::Idle);
+        }
+        commands
+            .entity(player_entity)
+            .remove::<(Stunned, Knockback, GuardBroken, Grabbed)>()
+            .insert(Invulnerable {
+                timer: Timer::from_seconds(BURST_INVULNERABLE_SECONDS, TimerMode::Once),
+            });
+
+        let center = player_transform.translation.truncate();
+        for (target, target_transform, &target_team, stun) in target_query.iter() {
+            if target_team == team {
+                continue;
+            }
+            let offset = target_transform.translation.truncate() - center;
+            let distance = offset.length();
+            if distance > BURST_RADIUS {
+                continue;
+            }
+            // A target exactly on top of the player is pushed along +x
+            let direction = offset.try_normalize().unwrap_or(Vec2::X);
+            let strength = 1.0 - 0.5 * distance / BURST_RADIUS;
+            let mut target = commands.entity(target);
+            target.insert(Knockback {
+                velocity: direction * BURST_KNOCKBACK * strength,
+            });
+            if stun {
+                target.insert(Stunned {
+                    timer: Timer::from_seconds(BURST_STUN_SECONDS, TimerMode::Once),
+                });
+            }
+        }
+
+        commands.spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.5, 0.8, 1.0, 0.5)),
+            GlobalZIndex(5),
+            ScreenFlash {
+                timer: Timer::from_seconds(0.25, TimerMode::Once),
+            },
+            DespawnOnExit(InGame),
+        ));
+    }
+}
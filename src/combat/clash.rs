@@ -0,0 +1,184 @@
+use crate::combat::components::{HitTracking, Hitbox, Knockback};
+use crate::combat::messages::{AttackWeight, Team};
+use crate::combat::systems::aabb_collision;
+use crate::game::{GameplayEvent, InGame};
+use crate::player::{JumpPhysics, PlayerState, PlayerStateType};
+use crate::world::{CameraShake, depth, same_lane};
+use bevy::prelude::*;
+
+/// Speed both clashing fighters are pushed apart at
+const CLASH_PUSHBACK: f32 = 250.0;
+/// Seconds a clash spark stays on screen
+const CLASH_SPARK_SECONDS: f32 = 0.2;
+/// Size of a clash spark at its start, in pixels
+const CLASH_SPARK_SIZE: f32 = 36.0;
+
+/// Result of two attacks meeting, for the first attack against the second
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClashOutcome {
+    /// Both attacks are cancelled
+    BothCancel,
+    /// The first attack goes through; the second is cancelled
+    FirstWins,
+    /// The second attack goes through; the first is cancelled
+    SecondWins,
+}
+
+impl AttackWeight {
+    /// Priority table for clashing attacks: heavier attacks beat lighter ones,
+    /// and equal weights cancel each other out
+    pub fn clash(self, other: AttackWeight) -> ClashOutcome {
+        use AttackWeight::{Heavy, Light, Medium};
+        match (self, other) {
+            (Heavy, Medium | Light) | (Medium, Light) => ClashOutcome::FirstWins,
+            (Medium | Light, Heavy) | (Light, Medium) => ClashOutcome::SecondWins,
+            (Light, Light) | (Medium, Medium) | (Heavy, Heavy) => ClashOutcome::BothCancel,
+        }
+    }
+}
+
+/// Brief flash where two attacks met, growing and fading out
+#[derive(Component)]
+pub struct ClashSpark {
+    pub timer: Timer,
+}
+
+/// An active hitbox as it stood before this frame's clashes were settled
+struct Swing {
+    entity: Entity,
+    center: Vec2,
+    size: Vec2,
+    depth: f32,
+    weight: AttackWeight,
+    team: Team,
+}
+
+/// Cancel attacks whose active hitboxes meet before either reaches the other's hurtbox
+///
+/// Two active hitboxes on different teams that overlap in the same lane
+/// clash: `AttackWeight::clash` picks which attack (if either) goes through.
+/// A cancelled attack drops its hitbox and returns its fighter to Idle, both
+/// fighters are pushed apart, and a spark flashes between them. Pairs where
+/// either swing has already hit the other don't clash. Runs before hit
+/// detection, so a cancelled attack can't also land this frame.
+#[allow(clippy::type_complexity)]
+pub fn detect_clashes(
+    mut commands: Commands,
+    mut swing_query: Query<(
+        Entity,
+        &Transform,
+        Option<&JumpPhysics>,
+        &mut Hitbox,
+        Option<&mut PlayerState>,
+        &mut HitTracking,
+        &Team,
+    )>,
+    mut camera_shake: ResMut<CameraShake>,
+    mut gameplay_events: MessageWriter<GameplayEvent>,
+) {
+    let swings: Vec<Swing> = swing_query
+        .iter()
+        .filter(|(_, _, _, hitbox, ..)| hitbox.active)
+        .map(
+            |(entity, transform, jump_physics, hitbox, state, _, &team)| Swing {
+                entity,
+                center: transform.translation.truncate() + hitbox.offset,
+                size: hitbox.size,
+                depth: depth(transform, jump_physics),
+                weight: state.map_or(AttackWeight::Light, |state| state.attack_weight()),
+                team,
+            },
+        )
+        .collect();
+
+    let mut cancelled = Vec::new();
+    for (i, first) in swings.iter().enumerate() {
+        for second in &swings[i + 1..] {
+            if first.team == second.team
+                || cancelled.contains(&first.entity)
+                || cancelled.contains(&second.entity)
+                || !same_lane(first.depth, second.depth)
+                || !aabb_collision(first.center, first.size, second.center, second.size)
+            {
+                continue;
+            }
+            let already_hit =
+                [(first, second), (second, first)]
+                    .iter()
+                    .any(|(attacker, target)| {
+                        swing_query
+                            .get(attacker.entity)
+                            .is_ok_and(|(.., tracking, _)| {
+                                tracking.hit_enemies.contains(&target.entity)
+                            })
+                    });
+            if already_hit {
+                continue;
+            }
+
+            let outcome = first.weight.clash(second.weight);
+            let winner = match outcome {
+                ClashOutcome::BothCancel => None,
+                ClashOutcome::FirstWins => Some(first.entity),
+                ClashOutcome::SecondWins => Some(second.entity),
+            };
+            for (swing, other) in [(first, second), (second, first)] {
+                if winner != Some(swing.entity) {
+                    cancelled.push(swing.entity);
+                }
+                let away = (swing.center.x - other.center.x).signum();
+                commands.entity(swing.entity).try_insert(Knockback {
+                    velocity: Vec2::new(away * CLASH_PUSHBACK, 0.0),
+                });
+            }
+
+            commands.spawn(clash_spark((first.center + second.center) / 2.0));
+            camera_shake.trigger(CameraShake::CLASH);
+            gameplay_events.write(GameplayEvent::AttackClash {
+                first: first.entity,
+                second: second.entity,
+                winner,
+            });
+        }
+    }
+
+    for entity in cancelled {
+        if let Ok((_, _, _, mut hitbox, state, _, _)) = swing_query.get_mut(entity) {
+            hitbox.active = false;
+            if let Some(mut state) = state {
+                *state = PlayerState::transition_to(PlayerStateType::Idle);
+            }
+        }
+    }
+}
+
+/// Spark flashed where two attacks clashed
+fn clash_spark(position: Vec2) -> impl Bundle {
+    (
+        Sprite::from_color(Color::srgb(1.0, 0.95, 0.6), Vec2::splat(CLASH_SPARK_SIZE)),
+        Transform::from_xyz(position.x, position.y, 3.0)
+            .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_4)),
+        ClashSpark {
+            timer: Timer::from_seconds(CLASH_SPARK_SECONDS, TimerMode::Once),
+        },
+        DespawnOnExit(InGame),
+    )
+}
+
+/// Grow and fade clash sparks, despawning them when they finish
+pub fn update_clash_sparks(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut spark_query: Query<(Entity, &mut ClashSpark, &mut Sprite, &mut Transform)>,
+) {
+    for (entity, mut spark, mut sprite, mut transform) in spark_query.iter_mut() {
+        spark.timer.tick(time.delta());
+        if spark.timer.is_finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let progress = spark.timer.fraction();
+        transform.scale = Vec3::splat(1.0 + progress);
+        sprite.color.set_alpha(1.0 - progress);
+    }
+}
@@ -6,6 +6,86 @@ use std::collections::HashSet;
 pub struct Health {
     pub current: i32,
     pub max: i32,
+    /// Flat damage subtracted from every hit
+    pub armor: i32,
+    /// Fraction of the damage left after armor that is ignored (capped at `MAX_RESISTANCE`)
+    pub resistance: f32,
+}
+
+impl Health {
+    /// Damage a hit of `damage` actually deals to this entity (see `mitigate_damage`)
+    pub fn mitigate(&self, damage: i32) -> i32 {
+        mitigate_damage(damage, self.armor, self.resistance)
+    }
+
+    /// Take an already mitigated hit, letting a `Shield` absorb it first
+    ///
+    /// Returns true if this hit used up the shield.
+    pub fn take_damage(&mut self, damage: i32, shield: Option<&mut Shield>) -> bool {
+        match shield {
+            Some(shield) if shield.current > 0 => {
+                self.current -= absorb_damage(damage, &mut [&mut shield.current]);
+                shield.current == 0
+            }
+            _ => {
+                self.current -= damage;
+                false
+            }
+        }
+    }
+}
+
+/// Temporary barrier layered over `Health`, granted by shield pickups
+///
+/// Absorbs damage before health does (see `Health::take_damage`) and
+/// shatters when it's used up; otherwise it fades once `timer` runs out.
+/// Granting a new shield replaces the old one.
+#[derive(Component)]
+pub struct Shield {
+    pub current: i32,
+    pub timer: Timer,
+}
+
+impl Shield {
+    pub fn new(amount: i32, seconds: f32) -> Self {
+        Self {
+            current: amount,
+            timer: Timer::from_seconds(seconds, TimerMode::Once),
+        }
+    }
+}
+
+/// Highest resistance that takes effect, so nothing becomes immune
+pub const MAX_RESISTANCE: f32 = 0.75;
+
+/// Apply a defender's armor and resistance to an incoming hit
+///
+/// Order: flat armor is subtracted first, then resistance scales what's left
+/// (rounded). A hit with any damage always deals at least 1, so heavy armor
+/// slows fights down without making them unwinnable. Attacker-side scaling
+/// (difficulty, combo decay) happens before this; blocking happens after.
+pub fn mitigate_damage(damage: i32, armor: i32, resistance: f32) -> i32 {
+    if damage <= 0 {
+        return 0;
+    }
+    let after_armor = (damage - armor.max(0)) as f32;
+    let resisted = after_armor * (1.0 - resistance.clamp(0.0, MAX_RESISTANCE));
+    (resisted.round() as i32).max(1)
+}
+
+/// Soak up a hit with layered damage pools, outermost first
+///
+/// Each layer absorbs as much of the damage as it holds and passes the rest
+/// inward. Returns what got through every layer, which the caller takes from
+/// health. Mitigation and blocking apply to the whole hit before this.
+pub fn absorb_damage(damage: i32, layers: &mut [&mut i32]) -> i32 {
+    let mut left = damage.max(0);
+    for layer in layers.iter_mut() {
+        let absorbed = left.min((**layer).max(0));
+        **layer -= absorbed;
+        left -= absorbed;
+    }
+    left
 }
 
 /// Hitbox for attack collision detection (attacker)
@@ -41,6 +121,34 @@ pub struct HitFlash {
     pub flash_duration: f32,
 }
 
+/// Attacker-side impact feedback - the player brightens and pops when an attack connects
+#[derive(Component)]
+pub struct ImpactFlash {
+    pub timer: Timer,
+}
+
+impl Default for ImpactFlash {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(Self::DURATION, TimerMode::Once),
+        }
+    }
+}
+
+impl ImpactFlash {
+    /// Seconds the brighten/pop lasts
+    pub const DURATION: f32 = 0.12;
+    /// Peak sprite brightness multiplier (fades back to 1.0)
+    pub const BRIGHTNESS: f32 = 1.6;
+    /// Peak extra scale (a quick pop out and back)
+    pub const SCALE_POP: f32 = 0.08;
+
+    /// Effect strength, 1.0 on impact fading to 0.0
+    pub fn intensity(&self) -> f32 {
+        1.0 - self.timer.fraction()
+    }
+}
+
 /// Invulnerability frames - entity cannot take damage during this window
 #[derive(Component)]
 pub struct Invulnerable {
@@ -53,3 +161,161 @@ pub struct Invulnerable {
 pub struct HitTracking {
     pub hit_enemies: HashSet<Entity>,
 }
+
+/// Guard for entities that can block (the player)
+///
+/// Blocked hits deal `chip_ratio` of their damage, accumulated until it adds up
+/// to a whole point of health. Blocking `max_blocked_hits` hits in a row breaks
+/// the guard; the count resets if nothing is blocked for `reset_timer`.
+#[derive(Component)]
+pub struct Guard {
+    pub blocked_hits: u32,
+    pub max_blocked_hits: u32,
+    pub chip_ratio: f32,
+    pub chip_accumulated: f32,
+    pub reset_timer: Timer,
+}
+
+impl Default for Guard {
+    fn default() -> Self {
+        Self {
+            blocked_hits: 0,
+            max_blocked_hits: 4,
+            chip_ratio: 0.25,
+            chip_accumulated: 0.0,
+            reset_timer: Timer::from_seconds(1.5, TimerMode::Once),
+        }
+    }
+}
+
+impl Guard {
+    /// Register a blocked hit
+    ///
+    /// Returns the chip damage to apply, or None if this hit breaks the guard.
+    pub fn block_hit(&mut self, damage: i32) -> Option<i32> {
+        self.blocked_hits += 1;
+        self.reset_timer.reset();
+
+        if self.blocked_hits >= self.max_blocked_hits {
+            self.blocked_hits = 0;
+            self.chip_accumulated = 0.0;
+            return None;
+        }
+
+        self.chip_accumulated += damage as f32 * self.chip_ratio;
+        let chip = self.chip_accumulated.floor();
+        self.chip_accumulated -= chip;
+        Some(chip as i32)
+    }
+}
+
+/// Guard broken - entity cannot block until the timer finishes
+#[derive(Component)]
+pub struct GuardBroken {
+    pub timer: Timer,
+}
+
+/// Special meter - fills as the player's attacks land, spent on a super attack
+///
+/// Decays at `decay_rate` per second once `decay_delay` has passed without a hit.
+#[derive(Component)]
+pub struct SpecialMeter {
+    pub value: f32,
+    pub max: f32,
+    /// Meter gained per landed hit
+    pub gain_per_hit: f32,
+    pub decay_rate: f32,
+    pub decay_delay: Timer,
+}
+
+impl Default for SpecialMeter {
+    fn default() -> Self {
+        Self {
+            value: 0.0,
+            max: 100.0,
+            gain_per_hit: 10.0,
+            decay_rate: 5.0,
+            decay_delay: Timer::from_seconds(3.0, TimerMode::Once),
+        }
+    }
+}
+
+impl SpecialMeter {
+    pub fn is_full(&self) -> bool {
+        self.value >= self.max
+    }
+
+    /// Add meter for a landed hit and restart the decay delay
+    pub fn add_hit(&mut self) {
+        self.value = (self.value + self.gain_per_hit).min(self.max);
+        self.decay_delay.reset();
+    }
+
+    /// Fraction of the meter filled (0.0 to 1.0)
+    pub fn fraction(&self) -> f32 {
+        self.value / self.max
+    }
+}
+
+/// Landed hits before combo damage scaling kicks in
+const COMBO_SCALING_START: u32 = 5;
+/// Damage lost per hit past `COMBO_SCALING_START`
+const COMBO_SCALING_STEP: f32 = 0.1;
+/// Long combos never scale damage below this fraction
+const COMBO_SCALING_MIN: f32 = 0.5;
+/// Hits needed for each extra point of score multiplier
+const COMBO_HITS_PER_MULTIPLIER: u32 = 5;
+/// Highest score multiplier a combo can reach
+const COMBO_MAX_MULTIPLIER: u32 = 3;
+
+/// Combo counter - hits the player lands in quick succession
+///
+/// Each landed hit restarts the `window`; the combo drops when it runs out or
+/// when the player takes an unblocked hit. Long combos score more points per
+/// kill but deal gradually less damage per hit.
+#[derive(Component)]
+pub struct ComboCounter {
+    pub hits: u32,
+    pub window: Timer,
+}
+
+impl Default for ComboCounter {
+    fn default() -> Self {
+        Self {
+            hits: 0,
+            window: Timer::from_seconds(1.5, TimerMode::Once),
+        }
+    }
+}
+
+impl ComboCounter {
+    /// Count a landed hit and restart the combo window
+    pub fn add_hit(&mut self) {
+        self.hits += 1;
+        self.window.reset();
+    }
+
+    /// Drop the combo
+    pub fn reset(&mut self) {
+        self.hits = 0;
+    }
+
+    /// Scale the damage of the next hit (never below 1)
+    pub fn scale_damage(&self, damage: i32) -> i32 {
+        let scale = (1.0
+            - COMBO_SCALING_STEP * self.hits.saturating_sub(COMBO_SCALING_START) as f32)
+            .max(COMBO_SCALING_MIN);
+        ((damage as f32 * scale).round() as i32).max(1)
+    }
+
+    /// Score multiplier for kills during this combo (x1 below 5 hits, up to x3)
+    pub fn score_multiplier(&self) -> u32 {
+        (1 + self.hits / COMBO_HITS_PER_MULTIPLIER).min(COMBO_MAX_MULTIPLIER)
+    }
+}
+
+/// Full-screen flash overlay that fades out (e.g. on a super attack)
+#[derive(Component)]
+pub struct ScreenFlash {
+    pub timer: Timer,
+}
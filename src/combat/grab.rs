@@ -0,0 +1,235 @@
+use crate::combat::components::{HurtBox, Invulnerable, Knockback, Stunned};
+use crate::combat::messages::{AttackWeight, DamageEvent, Team};
+use crate::enemy::{Enemy, EnemyState, KNOCKDOWN_SECONDS, SpawnProtected};
+use crate::player::{JumpPhysics, Player, PlayerState};
+use crate::world::{CameraShake, MoveIntent, depth, same_lane};
+use bevy::prelude::*;
+
+/// Horizontal reach in front of the player within which a ghost can be grabbed
+const GRAB_REACH: f32 = 110.0;
+/// Where a grabbed ghost is held, relative to the player (x in the facing direction)
+const HOLD_OFFSET: Vec2 = Vec2::new(70.0, 40.0);
+/// Launch velocity of a thrown ghost (x in the facing direction), in units/sec
+const THROW_VELOCITY: Vec2 = Vec2::new(700.0, 450.0);
+/// Downward acceleration of a thrown ghost, in units/sec²
+const THROW_GRAVITY: f32 = 1500.0;
+/// Damage to the thrown ghost when it lands
+const THROW_DAMAGE: i32 = 4;
+/// Damage to every other ghost caught by the landing
+const THROW_SPLASH_DAMAGE: i32 = 3;
+/// Horizontal reach of the landing impact around the thrown ghost
+const THROW_IMPACT_RADIUS: f32 = 120.0;
+
+/// Ghost the player would grab if Grab were pressed now (None if nothing is in reach)
+///
+/// Updated by `find_grab_target` once positions are final, and read by
+/// `player_input_system` the next frame.
+#[derive(Component, Default)]
+pub struct GrabTarget(pub Option<Entity>);
+
+/// A ghost held by the player, locked in front of them until it's thrown
+///
+/// Grabbed ghosts don't move, attack, or take hits.
+#[derive(Component)]
+pub struct Grabbed {
+    pub by: Entity,
+}
+
+/// A ghost flying through the air after a throw
+///
+/// `move_thrown_enemies` carries it along its arc; `land_thrown_enemies`
+/// brings it down at its lane (`ground_y`), or early if it flies into another
+/// ghost. Thrown ghosts don't move on their own, attack, or take hits.
+#[derive(Component)]
+pub struct Thrown {
+    pub thrower: Entity,
+    pub velocity: Vec2,
+    /// Depth lane the ghost was thrown along, where it lands
+    pub ground_y: f32,
+}
+
+/// Pick the nearest grabbable ghost in front of the player, in their lane
+///
+/// Only active ghosts on the ground can be grabbed, and only by a grounded player.
+#[allow(clippy::type_complexity)]
+pub fn find_grab_target(
+    mut player_query: Query<(&Transform, &JumpPhysics, &Sprite, &mut GrabTarget), With<Player>>,
+    enemy_query: Query<
+        (Entity, &Transform, &EnemyState),
+        (
+            With<Enemy>,
+            Without<Player>,
+            Without<SpawnProtected>,
+            Without<Invulnerable>,
+            Without<Grabbed>,
+            Without<Thrown>,
+        ),
+    >,
+) {
+    let Ok((player_transform, jump_physics, sprite, mut grab_target)) = player_query.single_mut()
+    else {
+        return;
+    };
+
+    if player_transform.translation.y > jump_physics.ground_y {
+        grab_target.0 = None;
+        return;
+    }
+    let facing = if sprite.flip_x { -1.0 } else { 1.0 };
+    let player_depth = depth(player_transform, Some(jump_physics));
+
+    grab_target.0 = enemy_query
+        .iter()
+        .filter(|(_, _, state)| state.is_active())
+        .filter_map(|(entity, transform, _)| {
+            let ahead = (transform.translation.x - player_transform.translation.x) * facing;
+            ((0.0..=GRAB_REACH).contains(&ahead)
+                && same_lane(player_depth, transform.translation.y))
+            .then_some((entity, ahead))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entity, _)| entity);
+}
+
+/// Take hold of the grab target on entering the Grab state
+///
+/// A target that got away in the meantime leaves the grab empty-handed.
+#[allow(clippy::type_complexity)]
+pub fn start_grab(
+    mut commands: Commands,
+    player_query: Query<(Entity, &PlayerState, &GrabTarget), (With<Player>, Changed<PlayerState>)>,
+    enemy_query: Query<(), (With<Enemy>, Without<Grabbed>, Without<Thrown>)>,
+) {
+    let Ok((player_entity, state, grab_target)) = player_query.single() else {
+        return;
+    };
+    if !matches!(state, PlayerState::Grab(_)) {
+        return;
+    }
+
+    if let Some(target) = grab_target.0
+        && enemy_query.contains(target)
+    {
+        commands
+            .entity(target)
+            .insert(Grabbed { by: player_entity })
+            .remove::<(Knockback, Stunned)>();
+    }
+}
+
+/// Keep grabbed ghosts in front of the player, and throw them when the grab ends
+///
+/// The throw launches the ghost in the player's facing direction. A player
+/// defeated mid-grab just drops the ghost.
+pub fn hold_grabbed_enemies(
+    mut commands: Commands,
+    player_query: Query<(&Transform, &JumpPhysics, &Sprite, &PlayerState), With<Player>>,
+    mut grabbed_query: Query<(Entity, &mut Transform, &Grabbed), Without<Player>>,
+) {
+    for (entity, mut transform, grabbed) in grabbed_query.iter_mut() {
+        let Ok((player_transform, jump_physics, sprite, state)) = player_query.get(grabbed.by)
+        else {
+            commands.entity(entity).remove::<Grabbed>();
+            continue;
+        };
+        let facing = if sprite.flip_x { -1.0 } else { 1.0 };
+        let ground_y = depth(player_transform, Some(jump_physics));
+
+        match state {
+            PlayerState::Grab(_) => {
+                transform.translation.x = player_transform.translation.x + facing * HOLD_OFFSET.x;
+                transform.translation.y = ground_y + HOLD_OFFSET.y;
+            }
+            PlayerState::Defeat(_) => {
+                transform.translation.y = ground_y;
+                commands.entity(entity).remove::<Grabbed>();
+            }
+            _ => {
+                commands.entity(entity).remove::<Grabbed>().insert(Thrown {
+                    thrower: grabbed.by,
+                    velocity: Vec2::new(facing * THROW_VELOCITY.x, THROW_VELOCITY.y),
+                    ground_y,
+                });
+            }
+        }
+    }
+}
+
+/// Carry thrown ghosts along their arc
+pub fn move_thrown_enemies(time: Res<Time>, mut query: Query<(&mut MoveIntent, &mut Thrown)>) {
+    for (mut intent, mut thrown) in query.iter_mut() {
+        intent.velocity += thrown.velocity;
+        thrown.velocity.y -= THROW_GRAVITY * time.delta_secs();
+    }
+}
+
+/// Bring thrown ghosts down, damaging them and every ghost caught by the impact
+///
+/// A ghost lands when it falls back to its lane or flies into another ghost.
+/// Everything hit by the landing is knocked down; the damage is credited to
+/// the thrower, so it counts toward their combo and meter.
+#[allow(clippy::type_complexity)]
+pub fn land_thrown_enemies(
+    mut commands: Commands,
+    mut thrown_query: Query<(Entity, &mut Transform, &HurtBox, &Thrown)>,
+    enemy_query: Query<
+        (Entity, &Transform, &HurtBox),
+        (
+            With<Enemy>,
+            Without<Thrown>,
+            Without<Grabbed>,
+            Without<SpawnProtected>,
+        ),
+    >,
+    team_query: Query<&Team>,
+    mut camera_shake: ResMut<CameraShake>,
+    mut damage_events: MessageWriter<DamageEvent>,
+) {
+    for (entity, mut transform, hurtbox, thrown) in thrown_query.iter_mut() {
+        let position = transform.translation.truncate();
+        let fell = position.y <= thrown.ground_y && thrown.velocity.y <= 0.0;
+        let crashed = enemy_query.iter().any(|(_, other, other_hurtbox)| {
+            let half_width = (hurtbox.size.x + other_hurtbox.size.x) / 2.0;
+            (other.translation.x - position.x).abs() < half_width
+                && same_lane(thrown.ground_y, other.translation.y)
+        });
+        if !fell && !crashed {
+            continue;
+        }
+
+        transform.translation.y = thrown.ground_y;
+        commands.entity(entity).remove::<Thrown>();
+        camera_shake.trigger(CameraShake::COMBO_FINISHER);
+
+        // Fall back to player 1 if the thrower is gone by the time the throw lands
+        let team = team_query
+            .get(thrown.thrower)
+            .copied()
+            .unwrap_or(Team::PlayerOne);
+        let victims = enemy_query
+            .iter()
+            .filter(|(_, other, _)| {
+                (other.translation.x - position.x).abs() <= THROW_IMPACT_RADIUS
+                    && same_lane(thrown.ground_y, other.translation.y)
+            })
+            .map(|(victim, _, _)| (victim, THROW_SPLASH_DAMAGE));
+
+        for (target, damage) in std::iter::once((entity, THROW_DAMAGE)).chain(victims) {
+            // Knocked down before the damage lands, so `handle_damage_events` keeps them down
+            commands.entity(target).insert((
+                EnemyState::knockdown(),
+                Invulnerable {
+                    timer: Timer::from_seconds(KNOCKDOWN_SECONDS, TimerMode::Once),
+                },
+            ));
+            damage_events.write(DamageEvent {
+                attacker: thrown.thrower,
+                target,
+                team,
+                damage,
+                weight: AttackWeight::Heavy,
+                source: position,
+            });
+        }
+    }
+}
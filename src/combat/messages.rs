@@ -1,11 +1,56 @@
 use bevy::prelude::*;
 
+/// How heavy an attack is, used to settle trades under `TradeRule::Priority`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AttackWeight {
+    /// Single punches and enemy contact
+    Light,
+    /// Kicks, the punch combo, and projectiles
+    Medium,
+    /// Kick and mixed combo finishers, and the super
+    Heavy,
+}
+
+/// Which side a fighter (or projectile) is on
+///
+/// Hits only land on a `HurtBox` of a different team, so ghosts never hurt
+/// each other and each versus player only hits the other. Player-vs-player
+/// hits (`PlayerOne` against `PlayerTwo`) get a lighter hit reaction than
+/// enemy hits on the player.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Team {
+    PlayerOne,
+    PlayerTwo,
+    Enemy,
+}
+
 /// Message sent when damage is dealt
 #[derive(Message)]
 pub struct DamageEvent {
+    pub attacker: Entity,
+    pub target: Entity,
+    /// The attacker's side
+    pub team: Team,
+    pub damage: i32,
+    pub weight: AttackWeight,
+    /// World position the hit came from (the attacker, or where a projectile was fired from)
+    pub source: Vec2,
+}
+
+/// Message sent when a hit actually deals damage, with the amount after mitigation and blocking
+#[derive(Message)]
+pub struct HitLandedEvent {
     pub attacker: Entity,
     pub target: Entity,
     pub damage: i32,
+    /// World position the hit came from (see `DamageEvent::source`)
+    pub source: Vec2,
+}
+
+/// Message sent when a hit uses up an entity's `Shield`
+#[derive(Message)]
+pub struct ShieldBrokenEvent {
+    pub entity: Entity,
 }
 
 /// Message sent when an enemy is defeated
@@ -14,6 +59,8 @@ pub struct EnemyDefeatedEvent {
     pub enemy: Entity,
 }
 
-/// Message sent when the player is defeated
+/// Message sent when a player is defeated
 #[derive(Message)]
-pub struct PlayerDefeatedEvent;
+pub struct PlayerDefeatedEvent {
+    pub player: Entity,
+}
@@ -1,6 +1,21 @@
+pub mod broadphase;
+pub mod burst;
+pub mod clash;
 pub mod components;
+pub mod grab;
 pub mod messages;
+pub mod modifiers;
+pub mod on_hit;
+pub mod plugin;
+pub mod style;
+pub mod systems;
+pub mod trades;
 
 // Re-export commonly used items
 pub use components::*;
+pub use grab::*;
 pub use messages::*;
+pub use modifiers::*;
+pub use on_hit::*;
+pub use plugin::*;
+pub use style::*;
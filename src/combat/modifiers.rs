@@ -0,0 +1,153 @@
+use bevy::prelude::*;
+
+/// Stat a `StatModifier` changes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stat {
+    /// Damage dealt by the entity's attacks
+    Damage,
+    /// Movement speed
+    Speed,
+    /// Resistance to incoming damage (damage taken is divided by it)
+    Defense,
+}
+
+/// What happens when a source that's already active is applied again
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stacking {
+    /// Restart the duration; the effect doesn't grow
+    Refresh,
+    /// Add a stack (up to `max`) and restart the duration; each stack applies the multiplier again
+    Stack { max: u32 },
+}
+
+/// A temporary (or lasting) multiplier on one stat, from a named source
+///
+/// Power-ups, enemy curses, and boss auras are all modifiers: add one to an
+/// entity's `StatModifiers` and the damage and movement systems pick it up.
+#[derive(Clone, Debug)]
+pub struct StatModifier {
+    /// Where it came from ("damage_boost", "curse"); applying the same source again follows `stacking`
+    pub source: String,
+    pub stat: Stat,
+    /// Multiplier per stack (above 1.0 strengthens the stat, below weakens it)
+    pub multiplier: f32,
+    pub stacking: Stacking,
+    /// Runs out after this long (None lasts until removed, e.g. an aura while in range)
+    pub duration: Option<Timer>,
+    stacks: u32,
+}
+
+impl StatModifier {
+    /// Modifier lasting `seconds`, refreshed if applied again
+    pub fn timed(source: impl Into<String>, stat: Stat, multiplier: f32, seconds: f32) -> Self {
+        Self {
+            source: source.into(),
+            stat,
+            multiplier,
+            stacking: Stacking::Refresh,
+            duration: Some(Timer::from_seconds(seconds, TimerMode::Once)),
+            stacks: 1,
+        }
+    }
+
+    /// Modifier lasting until its source removes it
+    pub fn lasting(source: impl Into<String>, stat: Stat, multiplier: f32) -> Self {
+        Self {
+            duration: None,
+            ..Self::timed(source, stat, multiplier, 0.0)
+        }
+    }
+
+    /// Let the modifier stack up to `max` times instead of refreshing
+    pub fn stacking(mut self, max: u32) -> Self {
+        self.stacking = Stacking::Stack { max };
+        self
+    }
+
+    /// Combined multiplier of every stack
+    fn total(&self) -> f32 {
+        self.multiplier.powi(self.stacks as i32)
+    }
+}
+
+/// Every stat modifier an entity currently has
+///
+/// Consulted by `handle_damage_events` (the attacker's Damage and the target's
+/// Defense), `player_physics_system`, and `move_enemies` (Speed). Timed
+/// modifiers are dropped by `update_stat_modifiers` when they run out.
+#[derive(Component, Default)]
+pub struct StatModifiers {
+    modifiers: Vec<StatModifier>,
+}
+
+impl StatModifiers {
+    /// Apply a modifier, following its stacking rule if its source is already active
+    pub fn add(&mut self, modifier: StatModifier) {
+        let Some(active) = self
+            .modifiers
+            .iter_mut()
+            .find(|active| active.source == modifier.source)
+        else {
+            self.modifiers.push(modifier);
+            return;
+        };
+
+        let stacks = match modifier.stacking {
+            Stacking::Refresh => 1,
+            Stacking::Stack { max } => (active.stacks + 1).min(max.max(1)),
+        };
+        *active = StatModifier { stacks, ..modifier };
+    }
+
+    /// Remove a source's modifier (no-op if it isn't active)
+    pub fn remove(&mut self, source: &str) {
+        self.modifiers.retain(|modifier| modifier.source != source);
+    }
+
+    /// Product of every active modifier on a stat (1.0 if there are none)
+    pub fn multiplier(&self, stat: Stat) -> f32 {
+        self.modifiers
+            .iter()
+            .filter(|modifier| modifier.stat == stat)
+            .map(StatModifier::total)
+            .product()
+    }
+
+    /// Scale damage by a multiplier, never taking a damaging hit below 1
+    fn scaled(damage: i32, multiplier: f32) -> i32 {
+        if damage <= 0 {
+            return damage;
+        }
+        ((damage as f32 * multiplier).round() as i32).max(1)
+    }
+
+    /// Damage dealt by an attack of `damage` base damage
+    pub fn scale_damage_dealt(&self, damage: i32) -> i32 {
+        Self::scaled(damage, self.multiplier(Stat::Damage))
+    }
+
+    /// Damage taken from a hit of `damage`
+    pub fn scale_damage_taken(&self, damage: i32) -> i32 {
+        let defense = self.multiplier(Stat::Defense);
+        if defense <= 0.0 {
+            return damage;
+        }
+        Self::scaled(damage, 1.0 / defense)
+    }
+
+    /// Tick timed modifiers and drop the ones that ran out
+    pub fn tick(&mut self, delta: std::time::Duration) {
+        self.modifiers
+            .retain_mut(|modifier| match &mut modifier.duration {
+                Some(timer) => !timer.tick(delta).is_finished(),
+                None => true,
+            });
+    }
+}
+
+/// Count down every entity's timed stat modifiers
+pub fn update_stat_modifiers(time: Res<Time>, mut query: Query<&mut StatModifiers>) {
+    for mut modifiers in query.iter_mut() {
+        modifiers.tick(time.delta());
+    }
+}
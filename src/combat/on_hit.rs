@@ -0,0 +1,179 @@
+use crate::combat::components::{Health, Shield, SpecialMeter};
+use crate::combat::messages::{
+    EnemyDefeatedEvent, HitLandedEvent, PlayerDefeatedEvent, ShieldBrokenEvent,
+};
+use crate::enemy::Enemy;
+use crate::player::Player;
+use crate::ui::{DamageNumberKind, TextStyles, damage_number};
+use bevy::prelude::*;
+
+/// Seconds between burn damage ticks
+const BURN_TICK_SECONDS: f32 = 0.5;
+/// Damage ticks in one burn (re-igniting restarts the count)
+const BURN_TICKS: u32 = 4;
+/// Damage per burn tick (before the target's armor and resistance)
+const BURN_DAMAGE: i32 = 1;
+
+/// On-hit bonuses granted by one source (a piece of gear, a buff, a trait)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct OnHitStats {
+    /// Fraction of damage dealt that heals the attacker
+    pub lifesteal: f32,
+    /// Chance (0.0 to 1.0) that a hit sets the target burning
+    pub burn_chance: f32,
+    /// Extra special meter gained per hit, on top of `SpecialMeter::gain_per_hit`
+    pub meter_gain: f32,
+}
+
+impl OnHitStats {
+    /// Stack two sources: lifesteal and meter gain add up, burn chances roll independently
+    pub fn combined(self, other: OnHitStats) -> OnHitStats {
+        OnHitStats {
+            lifesteal: self.lifesteal + other.lifesteal,
+            burn_chance: 1.0 - (1.0 - self.burn_chance) * (1.0 - other.burn_chance),
+            meter_gain: self.meter_gain + other.meter_gain,
+        }
+    }
+}
+
+/// Every on-hit bonus an entity currently has, keyed by where it came from
+///
+/// Gear, buffs, and traits add a source when they take effect and remove it
+/// when they end; `apply_on_hit_effects` applies the combined total to each hit
+/// the entity lands, so new items never need their own combat code.
+#[derive(Component, Default)]
+pub struct OnHitEffects {
+    sources: Vec<(String, OnHitStats)>,
+    /// Fractional lifesteal carried over until it adds up to a whole point of health
+    heal_accumulated: f32,
+}
+
+impl OnHitEffects {
+    /// Add (or replace) the bonuses from a named source
+    pub fn add_source(&mut self, source: impl Into<String>, stats: OnHitStats) {
+        let source = source.into();
+        self.remove_source(&source);
+        self.sources.push((source, stats));
+    }
+
+    /// Remove a source's bonuses (no-op if it isn't active)
+    pub fn remove_source(&mut self, source: &str) {
+        self.sources.retain(|(name, _)| name != source);
+    }
+
+    /// Combined bonuses from every active source
+    pub fn total(&self) -> OnHitStats {
+        self.sources
+            .iter()
+            .fold(OnHitStats::default(), |total, (_, stats)| {
+                total.combined(*stats)
+            })
+    }
+}
+
+/// Damage over time from an on-hit burn
+#[derive(Component)]
+pub struct Burning {
+    pub timer: Timer,
+    pub ticks_left: u32,
+}
+
+impl Default for Burning {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(BURN_TICK_SECONDS, TimerMode::Repeating),
+            ticks_left: BURN_TICKS,
+        }
+    }
+}
+
+/// Apply the attacker's on-hit effects to each hit that dealt damage
+///
+/// Runs after `handle_damage_events`, which sends `HitLandedEvent` with the
+/// damage left after mitigation and blocking.
+pub fn apply_on_hit_effects(
+    mut commands: Commands,
+    mut hit_events: MessageReader<HitLandedEvent>,
+    mut attacker_query: Query<(&mut OnHitEffects, &mut Health, Option<&mut SpecialMeter>)>,
+) {
+    for event in hit_events.read() {
+        let Ok((mut effects, mut health, meter)) = attacker_query.get_mut(event.attacker) else {
+            continue;
+        };
+        let total = effects.total();
+
+        effects.heal_accumulated += event.damage as f32 * total.lifesteal;
+        let heal = effects.heal_accumulated.floor();
+        effects.heal_accumulated -= heal;
+        health.current = (health.current + heal as i32).min(health.max);
+
+        if let Some(mut meter) = meter {
+            meter.value = (meter.value + total.meter_gain).min(meter.max);
+        }
+
+        if total.burn_chance > 0.0 && rand::random::<f32>() < total.burn_chance {
+            commands.entity(event.target).try_insert(Burning::default());
+        }
+    }
+}
+
+/// Deal burn damage on each tick, defeating targets it finishes off
+///
+/// Burn ticks skip `DamageEvent` so they don't stun, knock back, build combos,
+/// or trigger more on-hit effects. They still go through the target's armor,
+/// resistance, and shield.
+#[allow(clippy::too_many_arguments)]
+pub fn update_burning(
+    mut commands: Commands,
+    time: Res<Time>,
+    styles: Res<TextStyles>,
+    mut burning_query: Query<(
+        Entity,
+        &mut Burning,
+        &mut Health,
+        Option<&mut Shield>,
+        &Transform,
+    )>,
+    enemy_query: Query<(), With<Enemy>>,
+    player_query: Query<(), With<Player>>,
+    mut shield_broken_events: MessageWriter<ShieldBrokenEvent>,
+    mut enemy_defeated_events: MessageWriter<EnemyDefeatedEvent>,
+    mut player_defeated_events: MessageWriter<PlayerDefeatedEvent>,
+) {
+    for (entity, mut burning, mut health, shield, transform) in burning_query.iter_mut() {
+        // Already defeated this frame; its defeat event is on the way
+        if health.current <= 0 {
+            continue;
+        }
+
+        burning.timer.tick(time.delta());
+        if !burning.timer.just_finished() {
+            continue;
+        }
+
+        let damage = health.mitigate(BURN_DAMAGE);
+        if health.take_damage(damage, shield.map(Mut::into_inner)) {
+            commands.entity(entity).remove::<Shield>();
+            shield_broken_events.write(ShieldBrokenEvent { entity });
+        }
+        commands.spawn(damage_number(
+            &styles,
+            damage,
+            transform.translation.truncate(),
+            DamageNumberKind::Burn,
+        ));
+
+        if health.current <= 0 {
+            if enemy_query.contains(entity) {
+                enemy_defeated_events.write(EnemyDefeatedEvent { enemy: entity });
+            } else if player_query.contains(entity) {
+                player_defeated_events.write(PlayerDefeatedEvent { player: entity });
+            }
+        }
+
+        burning.ticks_left -= 1;
+        if burning.ticks_left == 0 {
+            commands.entity(entity).remove::<Burning>();
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use crate::combat::broadphase::{SpatialHash, rebuild_spatial_hash};
+use crate::combat::burst::trigger_burst;
+use crate::combat::clash::{detect_clashes, update_clash_sparks};
+use crate::combat::grab::{
+    find_grab_target, hold_grabbed_enemies, land_thrown_enemies, move_thrown_enemies, start_grab,
+};
+use crate::combat::messages::{
+    DamageEvent, EnemyDefeatedEvent, HitLandedEvent, PlayerDefeatedEvent, ShieldBrokenEvent,
+};
+use crate::combat::modifiers::update_stat_modifiers;
+use crate::combat::on_hit::{apply_on_hit_effects, update_burning};
+use crate::combat::style::update_style_meter;
+use crate::combat::systems::{
+    announce_damage_dealt, apply_damage_feedback, apply_knockback, detect_combat_collisions,
+    detect_enemy_swipes, detect_player_enemy_collisions, fill_special_meter, handle_damage_events,
+    trigger_super_attack, update_attack_hitboxes, update_combo_counter,
+    update_dash_invulnerability, update_guard_timers, update_hit_flash, update_impact_flash,
+    update_invulnerability, update_screen_flash, update_shields, update_special_meter,
+    update_stun_timers,
+};
+use crate::combat::trades::resolve_hit_trades;
+use crate::game::GameSet;
+use bevy::prelude::*;
+
+/// Hit detection, damage resolution, and the combat timers and effects it drives
+pub struct CombatPlugin;
+
+impl Plugin for CombatPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpatialHash>()
+            .add_message::<DamageEvent>()
+            .add_message::<HitLandedEvent>()
+            .add_message::<EnemyDefeatedEvent>()
+            .add_message::<PlayerDefeatedEvent>()
+            .add_message::<ShieldBrokenEvent>()
+            .add_systems(
+                Update,
+                (
+                    (
+                        trigger_burst,
+                        update_attack_hitboxes,
+                        update_dash_invulnerability,
+                        trigger_super_attack,
+                        start_grab,
+                        hold_grabbed_enemies,
+                    )
+                        .chain()
+                        .in_set(GameSet::Attacks),
+                    (
+                        rebuild_spatial_hash,
+                        land_thrown_enemies,
+                        detect_clashes,
+                        detect_combat_collisions,
+                        detect_player_enemy_collisions,
+                        detect_enemy_swipes,
+                        find_grab_target,
+                    )
+                        .chain()
+                        .in_set(GameSet::Collisions),
+                    (
+                        resolve_hit_trades,
+                        handle_damage_events,
+                        announce_damage_dealt,
+                        apply_on_hit_effects,
+                        update_burning,
+                        fill_special_meter,
+                        update_combo_counter,
+                        update_style_meter,
+                        update_special_meter,
+                        update_stun_timers,
+                        update_guard_timers,
+                        update_invulnerability,
+                        update_shields,
+                        update_stat_modifiers,
+                    )
+                        .chain()
+                        .in_set(GameSet::Damage),
+                    (
+                        update_hit_flash,
+                        update_impact_flash,
+                        apply_damage_feedback,
+                        update_screen_flash,
+                        update_clash_sparks,
+                    )
+                        .chain()
+                        .in_set(GameSet::Effects),
+                ),
+            )
+            .add_systems(
+                FixedUpdate,
+                (apply_knockback, move_thrown_enemies).in_set(GameSet::MovementIntents),
+            );
+    }
+}
@@ -0,0 +1,201 @@
+use crate::combat::messages::HitLandedEvent;
+use crate::player::{PlayerState, PlayerStateType};
+use crate::settings::Mutators;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+/// Style points of a full meter
+const STYLE_MAX: f32 = 100.0;
+/// Style points for a swing landing an attack not used in the last few swings
+const STYLE_FRESH_ATTACK: f32 = 10.0;
+/// Style points for each extra enemy caught by the same swing
+const STYLE_CROWD_HIT: f32 = 2.0;
+/// Style points lost for landing an attack used in the last few swings
+const STYLE_REPEAT_PENALTY: f32 = 4.0;
+/// Style points lost when the player takes an unblocked hit
+const STYLE_HIT_TAKEN_PENALTY: f32 = 25.0;
+/// Swings remembered when checking for repetition
+const STYLE_MEMORY: usize = 3;
+/// Seconds without landing a hit before the meter starts draining
+const STYLE_DECAY_DELAY: f32 = 2.0;
+/// Style points drained per second once the decay delay passes
+const STYLE_DECAY_RATE: f32 = 8.0;
+
+/// Style grade, from D up to S
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StyleRank {
+    D,
+    C,
+    B,
+    A,
+    S,
+}
+
+impl StyleRank {
+    /// Rank for an amount of style points (0 to `STYLE_MAX`)
+    pub fn from_points(points: f32) -> Self {
+        match points {
+            p if p >= 80.0 => StyleRank::S,
+            p if p >= 60.0 => StyleRank::A,
+            p if p >= 40.0 => StyleRank::B,
+            p if p >= 20.0 => StyleRank::C,
+            _ => StyleRank::D,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            StyleRank::D => "D",
+            StyleRank::C => "C",
+            StyleRank::B => "B",
+            StyleRank::A => "A",
+            StyleRank::S => "S",
+        }
+    }
+
+    /// Score multiplier for kills at this rank (on top of the combo multiplier)
+    pub fn score_multiplier(self) -> u32 {
+        match self {
+            StyleRank::D | StyleRank::C => 1,
+            StyleRank::B | StyleRank::A => 2,
+            StyleRank::S => 3,
+        }
+    }
+
+    /// Display color, warming up toward S
+    pub fn color(self) -> Color {
+        match self {
+            StyleRank::D => Color::srgb(0.6, 0.6, 0.6),
+            StyleRank::C => Color::srgb(0.4, 0.8, 1.0),
+            StyleRank::B => Color::srgb(0.4, 1.0, 0.5),
+            StyleRank::A => Color::srgb(1.0, 0.6, 0.1),
+            StyleRank::S => Color::srgb(1.0, 0.85, 0.2),
+        }
+    }
+}
+
+/// How stylishly the player is fighting
+///
+/// Each swing that lands scores once: an attack that wasn't used in the last
+/// `STYLE_MEMORY` swings adds points, repeating one costs points, and extra
+/// enemies caught by the same swing add a little. The meter drains after a
+/// pause in attacking and drops when the player takes an unblocked hit. The
+/// current `rank()` multiplies kill scores; `run_rank()` grades the whole run.
+#[derive(Component)]
+pub struct StyleMeter {
+    pub points: f32,
+    /// Attacks of the most recent scored swings, newest last
+    recent: VecDeque<PlayerStateType>,
+    /// The current swing already scored (later hits are crowd hits)
+    swing_scored: bool,
+    decay_delay: Timer,
+    /// Style points integrated over the run's playing time, for `run_rank`
+    point_seconds: f32,
+    seconds: f32,
+}
+
+impl Default for StyleMeter {
+    fn default() -> Self {
+        Self {
+            points: 0.0,
+            recent: VecDeque::new(),
+            swing_scored: false,
+            decay_delay: Timer::from_seconds(STYLE_DECAY_DELAY, TimerMode::Once),
+            point_seconds: 0.0,
+            seconds: 0.0,
+        }
+    }
+}
+
+impl StyleMeter {
+    /// Current rank
+    pub fn rank(&self) -> StyleRank {
+        StyleRank::from_points(self.points)
+    }
+
+    /// Fraction of the meter filled (0.0 to 1.0)
+    pub fn fraction(&self) -> f32 {
+        self.points / STYLE_MAX
+    }
+
+    /// Rank of the run so far: the average of the meter over time
+    pub fn run_rank(&self) -> StyleRank {
+        if self.seconds <= 0.0 {
+            return StyleRank::D;
+        }
+        StyleRank::from_points(self.point_seconds / self.seconds)
+    }
+
+    /// Start a new swing, so its first landed hit scores
+    fn start_swing(&mut self) {
+        self.swing_scored = false;
+    }
+
+    /// Score a landed hit from an attack
+    fn land_hit(&mut self, attack: PlayerStateType) {
+        let change = if self.swing_scored {
+            STYLE_CROWD_HIT
+        } else if self.recent.contains(&attack) {
+            -STYLE_REPEAT_PENALTY
+        } else {
+            STYLE_FRESH_ATTACK
+        };
+        if !self.swing_scored {
+            self.recent.push_back(attack);
+            if self.recent.len() > STYLE_MEMORY {
+                self.recent.pop_front();
+            }
+            self.swing_scored = true;
+        }
+        self.points = (self.points + change).clamp(0.0, STYLE_MAX);
+        self.decay_delay.reset();
+    }
+
+    /// Lose style for taking an unblocked hit
+    fn take_hit(&mut self) {
+        self.points = (self.points - STYLE_HIT_TAKEN_PENALTY).max(0.0);
+    }
+}
+
+/// Score landed hits into the attacker's style meter, drain it between attacks
+///
+/// Runs after `handle_damage_events`. A new attacking state starts a new
+/// swing. Under the pressure mutator the meter drains faster, like the combo.
+pub fn update_style_meter(
+    time: Res<Time>,
+    mutators: Res<Mutators>,
+    mut hit_landed_events: MessageReader<HitLandedEvent>,
+    mut style_query: Query<(&mut StyleMeter, Ref<PlayerState>)>,
+) {
+    for (mut style, state) in style_query.iter_mut() {
+        if state.is_changed() && state.is_attacking() {
+            style.start_swing();
+        }
+    }
+
+    for event in hit_landed_events.read() {
+        if let Ok((mut style, state)) = style_query.get_mut(event.attacker) {
+            style.land_hit(state.state_type());
+        }
+        if let Ok((mut style, state)) = style_query.get_mut(event.target)
+            && !state.is_blocking()
+        {
+            style.take_hit();
+        }
+    }
+
+    for (mut style, state) in style_query.iter_mut() {
+        // The run is over once the player is down (the game over screen keeps simulating)
+        if state.state_type() == PlayerStateType::Defeat {
+            continue;
+        }
+        let delta = time.delta_secs();
+        style.decay_delay.tick(time.delta());
+        if style.decay_delay.is_finished() {
+            let drain = STYLE_DECAY_RATE * mutators.combo_decay_rate() * delta;
+            style.points = (style.points - drain).max(0.0);
+        }
+        style.point_seconds += style.points * delta;
+        style.seconds += delta;
+    }
+}
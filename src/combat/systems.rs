@@ -0,0 +1,1036 @@
+use crate::combat::broadphase::SpatialHash;
+use crate::combat::components::{
+    ComboCounter, Guard, GuardBroken, Health, HitFlash, HitTracking, Hitbox, HurtBox, ImpactFlash,
+    Invulnerable, Knockback, ScreenFlash, Shield, SpecialMeter, Stunned,
+};
+use crate::combat::grab::{Grabbed, Thrown};
+use crate::combat::messages::{
+    AttackWeight, DamageEvent, EnemyDefeatedEvent, HitLandedEvent, PlayerDefeatedEvent,
+    ShieldBrokenEvent, Team,
+};
+use crate::combat::modifiers::StatModifiers;
+use crate::common::{AnimationIndices, AnimationTimer, Direction};
+use crate::debug::{DebugName, entity_label};
+use crate::enemy::{
+    Enemy, EnemyState, KNOCKDOWN_SECONDS, Passive, SWIPE_DAMAGE, SWIPE_RANGE, SpawnProtected,
+};
+use crate::game::{GameplayEvent, InGame};
+use crate::player::{Characters, CostumeTint, Fighter, JumpPhysics, Player, PlayerState};
+use crate::settings::{Difficulty, Mutators};
+use crate::ui::{DamageNumberKind, TextStyles, damage_number};
+use crate::world::{
+    ArenaBounds, ArenaConfined, CameraShake, MoveIntent, StageGeometry, WALL_BOUNCE_DAMPING, depth,
+    same_lane,
+};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use std::ops::RangeInclusive;
+
+/// Damage of a hit from a hitbox on a fighter without a `PlayerState`
+const DEFAULT_HIT_DAMAGE: i32 = 1;
+/// Distance within which an enemy touches a hurtbox for contact damage
+const CONTACT_RANGE: f32 = 100.0;
+/// Exponential knockback friction, per second (keeps 90% of the velocity per 60 Hz step)
+const KNOCKBACK_FRICTION: f32 = 6.3;
+
+pub fn update_attack_hitboxes(
+    mut player_query: Query<
+        (
+            &PlayerState,
+            &Sprite,
+            &AnimationIndices,
+            &mut Hitbox,
+            &Direction,
+        ),
+        With<Player>,
+    >,
+) {
+    for (state, sprite, indices, mut hitbox, direction) in player_query.iter_mut() {
+        if !state.is_attacking() {
+            hitbox.active = false;
+            continue;
+        }
+
+        // Hitbox is active during middle frames of attack animation
+        if let Some(atlas) = &sprite.texture_atlas {
+            hitbox.active = attack_active_frames(indices).contains(&atlas.index);
+
+            // Position hitbox in front of player
+            hitbox.offset = match *direction {
+                Direction::Right => Vec2::new(80.0, 0.0),
+                Direction::Left => Vec2::new(-80.0, 0.0),
+                _ => Vec2::ZERO,
+            };
+
+            // Size varies by attack type
+            hitbox.size = match *state {
+                PlayerState::Punch(_) | PlayerState::PunchCombo(_) => Vec2::new(60.0, 40.0),
+                PlayerState::Kick(_)
+                | PlayerState::KickCombo(_)
+                | PlayerState::PunchKickCombo(_) => Vec2::new(80.0, 50.0),
+                PlayerState::JumpPunch(_) => Vec2::new(50.0, 50.0),
+                PlayerState::JumpKick(_) => Vec2::new(70.0, 60.0),
+                _ => Vec2::ZERO,
+            };
+        }
+    }
+}
+
+/// Atlas frames during which an attack animation's hitbox is active (its middle third)
+///
+/// The frames before are startup and the frames after are recovery.
+pub fn attack_active_frames(indices: &AnimationIndices) -> RangeInclusive<usize> {
+    let total_frames = indices.last - indices.first;
+    indices.first + total_frames / 3..=indices.first + 2 * total_frames / 3
+}
+
+/// Do two boxes, given by center and size, overlap?
+pub fn aabb_collision(pos1: Vec2, size1: Vec2, pos2: Vec2, size2: Vec2) -> bool {
+    let half1 = size1 / 2.0;
+    let half2 = size2 / 2.0;
+    (pos1.x - half1.x < pos2.x + half2.x)
+        && (pos1.x + half1.x > pos2.x - half2.x)
+        && (pos1.y - half1.y < pos2.y + half2.y)
+        && (pos1.y + half1.y > pos2.y - half2.y)
+}
+
+/// Active hitboxes against every hurtbox on a different team
+///
+/// Each attack hits a target at most once (tracked in `HitTracking`), and only
+/// when both stand in the same lane. Players hit for their character's damage
+/// in the attacking state; fighters without a `PlayerState` deal
+/// `DEFAULT_HIT_DAMAGE` as a light hit. Only hurtboxes the `SpatialHash`
+/// places near the hitbox are tested.
+#[allow(clippy::type_complexity)]
+pub fn detect_combat_collisions(
+    spatial_hash: Res<SpatialHash>,
+    characters: Res<Characters>,
+    mut attacker_query: Query<(
+        Entity,
+        &Transform,
+        Option<&JumpPhysics>,
+        &Hitbox,
+        Option<&PlayerState>,
+        Option<&Fighter>,
+        &mut HitTracking,
+        &Team,
+    )>,
+    target_query: Query<
+        (Entity, &Transform, Option<&JumpPhysics>, &HurtBox, &Team),
+        (
+            Without<SpawnProtected>,
+            Without<Invulnerable>,
+            Without<Grabbed>,
+            Without<Thrown>,
+        ),
+    >,
+    mut damage_events: MessageWriter<DamageEvent>,
+) {
+    for (attacker, transform, jump_physics, hitbox, state, fighter, mut hit_tracking, &team) in
+        attacker_query.iter_mut()
+    {
+        if !hitbox.active {
+            continue;
+        }
+
+        let hitbox_center = transform.translation.truncate() + hitbox.offset;
+        let attacker_depth = depth(transform, jump_physics);
+
+        for candidate in spatial_hash.query(hitbox_center, hitbox.size) {
+            let Ok((target, target_transform, target_jump, hurtbox, &target_team)) =
+                target_query.get(candidate)
+            else {
+                continue;
+            };
+
+            // Skip teammates and targets already hit by the current attack
+            if target_team == team || hit_tracking.hit_enemies.contains(&target) {
+                continue;
+            }
+
+            let target_pos = target_transform.translation.truncate();
+
+            // AABB collision detection, only against targets in the attacker's lane
+            let collision = aabb_collision(hitbox_center, hitbox.size, target_pos, hurtbox.size)
+                && same_lane(attacker_depth, depth(target_transform, target_jump));
+
+            if collision {
+                // Mark target as hit by this attack
+                hit_tracking.hit_enemies.insert(target);
+
+                damage_events.write(DamageEvent {
+                    attacker,
+                    target,
+                    team,
+                    damage: match (state, fighter) {
+                        (Some(state), Some(&fighter)) => characters.get(fighter).damage(state),
+                        (Some(state), None) => state.get_damage(),
+                        (None, _) => DEFAULT_HIT_DAMAGE,
+                    },
+                    weight: state.map_or(AttackWeight::Light, PlayerState::attack_weight),
+                    source: transform.translation.truncate(),
+                });
+            }
+        }
+    }
+}
+
+/// Grant invulnerability during the middle third of the dash animation
+///
+/// Uses the same frame window as attack hitboxes; the timer covers the
+/// remaining i-frames so the window ends on time even if the dash is cut short.
+#[allow(clippy::type_complexity)]
+pub fn update_dash_invulnerability(
+    mut commands: Commands,
+    player_query: Query<
+        (
+            Entity,
+            &PlayerState,
+            &Sprite,
+            &AnimationIndices,
+            &AnimationTimer,
+        ),
+        (With<Player>, Without<Invulnerable>),
+    >,
+) {
+    for (entity, state, sprite, indices, timer) in player_query.iter() {
+        if !matches!(state, PlayerState::Dash(_)) {
+            continue;
+        }
+
+        if let Some(atlas) = &sprite.texture_atlas {
+            let frame = atlas.index;
+            let total_frames = indices.last - indices.first;
+            let mid_start = indices.first + (total_frames / 3);
+            let mid_end = indices.first + (2 * total_frames / 3);
+
+            if frame >= mid_start && frame <= mid_end {
+                let remaining_frames = (mid_end - frame + 1) as f32;
+                commands.entity(entity).insert(Invulnerable {
+                    timer: Timer::from_seconds(
+                        remaining_frames * timer.duration().as_secs_f32(),
+                        TimerMode::Once,
+                    ),
+                });
+            }
+        }
+    }
+}
+
+/// Spend a full special meter when the Super state starts
+///
+/// Damages everything on screen on a different team (every enemy, or the
+/// other player in versus games), makes the player invulnerable for the rest
+/// of the animation, and flashes the screen white.
+#[allow(clippy::type_complexity)]
+pub fn trigger_super_attack(
+    mut commands: Commands,
+    mut player_query: Query<
+        (
+            Entity,
+            &Transform,
+            &PlayerState,
+            &Fighter,
+            &mut SpecialMeter,
+            &Team,
+        ),
+        (With<Player>, Changed<PlayerState>),
+    >,
+    target_query: Query<
+        (Entity, &Transform, &Team),
+        (
+            With<HurtBox>,
+            Without<SpawnProtected>,
+            Without<Invulnerable>,
+            Without<Grabbed>,
+            Without<Thrown>,
+        ),
+    >,
+    camera_query: Query<&Transform, With<Camera2d>>,
+    window_query: Query<&Window>,
+    characters: Res<Characters>,
+    mut damage_events: MessageWriter<DamageEvent>,
+) {
+    // Visible horizontal range around the camera
+    let camera_x = camera_query
+        .single()
+        .map_or(0.0, |transform| transform.translation.x);
+    let half_width = window_query
+        .single()
+        .map_or(640.0, |window| window.width() / 2.0);
+
+    for (player_entity, player_transform, state, &fighter, mut meter, &team) in
+        player_query.iter_mut()
+    {
+        if !matches!(state, PlayerState::Super(_)) {
+            continue;
+        }
+        let character = characters.get(fighter);
+
+        meter.value = 0.0;
+
+        for (target, target_transform, &target_team) in target_query.iter() {
+            if target_team != team
+                && (target_transform.translation.x - camera_x).abs() <= half_width
+            {
+                damage_events.write(DamageEvent {
+                    attacker: player_entity,
+                    target,
+                    team,
+                    damage: character.damage(state),
+                    weight: state.attack_weight(),
+                    source: player_transform.translation.truncate(),
+                });
+            }
+        }
+
+        let anim = character.animations.config(state);
+        let duration = (anim.last_frame - anim.first_frame + 1) as f32 * anim.frame_duration;
+        commands.entity(player_entity).insert(Invulnerable {
+            timer: Timer::from_seconds(duration, TimerMode::Once),
+        });
+
+        commands.spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.8)),
+            GlobalZIndex(5),
+            ScreenFlash {
+                timer: Timer::from_seconds(0.4, TimerMode::Once),
+            },
+            DespawnOnExit(InGame),
+        ));
+    }
+}
+
+/// Enemies touching a hurtbox on a different team (the player) deal contact damage
+///
+/// Contact needs both closeness and a shared lane, so an enemy passing on a
+/// different depth can't touch the player (nor can one the player jumps over).
+/// Only hurtboxes the `SpatialHash` places within `CONTACT_RANGE` are tested.
+#[allow(clippy::type_complexity)]
+pub fn detect_player_enemy_collisions(
+    spatial_hash: Res<SpatialHash>,
+    target_query: Query<
+        (Entity, &Transform, Option<&JumpPhysics>, &Team),
+        (With<HurtBox>, Without<Invulnerable>),
+    >,
+    enemy_query: Query<
+        (Entity, &Transform, &EnemyState, &Team),
+        (
+            With<Enemy>,
+            Without<Stunned>,
+            Without<SpawnProtected>,
+            Without<Grabbed>,
+            Without<Thrown>,
+            Without<Passive>,
+        ),
+    >,
+    mut damage_events: MessageWriter<DamageEvent>,
+) {
+    for (enemy_entity, enemy_transform, enemy_state, &team) in enemy_query.iter() {
+        // Knocked-down enemies are harmless until they're back up
+        if !enemy_state.is_active() {
+            continue;
+        }
+
+        let enemy_pos = enemy_transform.translation.truncate();
+
+        let reach = Vec2::splat(CONTACT_RANGE * 2.0);
+        for candidate in spatial_hash.query(enemy_pos, reach) {
+            let Ok((target, target_transform, jump_physics, &target_team)) =
+                target_query.get(candidate)
+            else {
+                continue;
+            };
+            if target_team == team {
+                continue;
+            }
+
+            // Simple distance check
+            let target_pos = target_transform.translation.truncate();
+            let distance = target_pos.distance(enemy_pos);
+
+            if distance < CONTACT_RANGE
+                && same_lane(depth(target_transform, jump_physics), enemy_pos.y)
+            {
+                damage_events.write(DamageEvent {
+                    attacker: enemy_entity,
+                    target,
+                    team,
+                    damage: DEFAULT_HIT_DAMAGE,
+                    weight: AttackWeight::Light,
+                    source: enemy_pos,
+                });
+            }
+        }
+    }
+}
+
+/// Enemy swipes hit a hurtbox on a different team in front of the enemy,
+/// within `SWIPE_RANGE` and in its lane, once per swipe
+///
+/// A swipe that connects is marked, so its recovery isn't counted as a whiff
+/// (see `EnemyState::Recovery`). Only hurtboxes the `SpatialHash` places
+/// within `SWIPE_RANGE` are tested.
+#[allow(clippy::type_complexity)]
+pub fn detect_enemy_swipes(
+    spatial_hash: Res<SpatialHash>,
+    target_query: Query<
+        (Entity, &Transform, Option<&JumpPhysics>, &Team),
+        (With<HurtBox>, Without<Invulnerable>),
+    >,
+    mut enemy_query: Query<(Entity, &Transform, &Sprite, &mut EnemyState, &Team), With<Enemy>>,
+    mut damage_events: MessageWriter<DamageEvent>,
+) {
+    for (enemy_entity, enemy_transform, sprite, mut state, &team) in enemy_query.iter_mut() {
+        let EnemyState::Swipe { connected, .. } = &mut *state else {
+            continue;
+        };
+        if *connected {
+            continue;
+        }
+        let enemy_pos = enemy_transform.translation.truncate();
+        let facing = if sprite.flip_x { -1.0 } else { 1.0 };
+
+        let hit = spatial_hash
+            .query(enemy_pos, Vec2::splat(SWIPE_RANGE * 2.0))
+            .into_iter()
+            .filter_map(|candidate| target_query.get(candidate).ok())
+            .find(|(_, target_transform, jump_physics, target_team)| {
+                let reach = (target_transform.translation.x - enemy_pos.x) * facing;
+                **target_team != team
+                    && (0.0..=SWIPE_RANGE).contains(&reach)
+                    && same_lane(depth(target_transform, *jump_physics), enemy_pos.y)
+            });
+        if let Some((target, ..)) = hit {
+            *connected = true;
+            damage_events.write(DamageEvent {
+                attacker: enemy_entity,
+                target,
+                team,
+                damage: SWIPE_DAMAGE,
+                weight: AttackWeight::Medium,
+                source: enemy_pos,
+            });
+        }
+    }
+}
+
+/// Messages written by `handle_damage_events` about what each hit did
+#[derive(SystemParam)]
+pub struct DamageOutcomes<'w> {
+    shield_broken: MessageWriter<'w, ShieldBrokenEvent>,
+    enemy_defeated: MessageWriter<'w, EnemyDefeatedEvent>,
+    player_defeated: MessageWriter<'w, PlayerDefeatedEvent>,
+    hit_landed: MessageWriter<'w, HitLandedEvent>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle_damage_events(
+    mut commands: Commands,
+    mut damage_events: MessageReader<DamageEvent>,
+    mut health_query: Query<(&mut Health, Option<&StatModifiers>, Option<&mut Shield>)>,
+    transform_query: Query<&Transform>,
+    mut outcomes: DamageOutcomes,
+    enemy_query: Query<&EnemyState, With<Enemy>>,
+    player_query: Query<(), With<Player>>,
+    mut guard_query: Query<(&mut Guard, Option<&PlayerState>), Without<GuardBroken>>,
+    attacker_query: Query<(Option<&ComboCounter>, Option<&StatModifiers>)>,
+    attacker_state_query: Query<&PlayerState>,
+    names: Query<&DebugName>,
+    difficulty: Res<Difficulty>,
+    styles: Res<TextStyles>,
+    mut camera_shake: ResMut<CameraShake>,
+) {
+    for damage_event in damage_events.read() {
+        let Ok((mut health, target_modifiers, shield)) = health_query.get_mut(damage_event.target)
+        else {
+            continue;
+        };
+        // Already defeated (dying, or by an earlier hit this frame); its defeat event is out
+        if health.current <= 0 {
+            continue;
+        }
+        let (attacker_combo, attacker_modifiers) = attacker_query
+            .get(damage_event.attacker)
+            .unwrap_or_default();
+
+        // Enemy damage scales with difficulty
+        let mut damage = damage_event.damage;
+        if enemy_query.get(damage_event.attacker).is_ok() {
+            damage = difficulty.scale_enemy_damage(damage);
+        }
+
+        // Attack buffs and debuffs (power-ups, curses, auras)
+        if let Some(modifiers) = attacker_modifiers {
+            damage = modifiers.scale_damage_dealt(damage);
+        }
+
+        // Long player combos deal gradually less damage per hit
+        if let Some(combo) = attacker_combo {
+            damage = combo.scale_damage(damage);
+        }
+
+        // The target's armor, resistance, and defense modifiers, after attacker-side
+        // scaling and before blocking
+        damage = health.mitigate(damage);
+        if let Some(modifiers) = target_modifiers {
+            damage = modifiers.scale_damage_taken(damage);
+        }
+
+        // Blocking reduces the hit to chip damage, unless this hit breaks the guard.
+        // A guard without a player state (the blocking training dummy) is always up
+        let mut blocked = false;
+        if let Ok((mut guard, state)) = guard_query.get_mut(damage_event.target)
+            && state.is_none_or(PlayerState::is_blocking)
+        {
+            match guard.block_hit(damage) {
+                Some(chip) => {
+                    damage = chip;
+                    blocked = true;
+                }
+                None => {
+                    commands.entity(damage_event.target).insert(GuardBroken {
+                        timer: Timer::from_seconds(1.5, TimerMode::Once),
+                    });
+                    info!(
+                        "Guard broken! ({})",
+                        entity_label(&names, damage_event.target)
+                    );
+                }
+            }
+        }
+
+        // A shield soaks the hit before health does, shattering once it's used up
+        if health.take_damage(damage, shield.map(Mut::into_inner)) {
+            commands.entity(damage_event.target).remove::<Shield>();
+            outcomes.shield_broken.write(ShieldBrokenEvent {
+                entity: damage_event.target,
+            });
+        }
+        if damage > 0 {
+            outcomes.hit_landed.write(HitLandedEvent {
+                attacker: damage_event.attacker,
+                target: damage_event.target,
+                damage,
+                source: damage_event.source,
+            });
+        }
+
+        // Combat log, shown with RUST_LOG=martial_magicka=debug
+        debug!(
+            "{} hit {} for {}{} ({} health left)",
+            entity_label(&names, damage_event.attacker),
+            entity_label(&names, damage_event.target),
+            damage,
+            if blocked { " (blocked)" } else { "" },
+            health.current
+        );
+
+        // Floating damage number at the target, colored by who got hit and how hard
+        let finisher = attacker_state_query
+            .get(damage_event.attacker)
+            .is_ok_and(PlayerState::is_combo_finisher);
+        // Enemies already knocked down (e.g. by a throw) stay down instead of being stunned
+        let knockdown = attacker_state_query
+            .get(damage_event.attacker)
+            .is_ok_and(PlayerState::knocks_down)
+            || enemy_query
+                .get(damage_event.target)
+                .is_ok_and(|state| matches!(state, EnemyState::Knockdown(_)));
+        if damage > 0
+            && let Ok(target_transform) = transform_query.get(damage_event.target)
+        {
+            let kind = if player_query.get(damage_event.target).is_ok() {
+                DamageNumberKind::ToPlayer
+            } else if finisher {
+                DamageNumberKind::Finisher
+            } else {
+                DamageNumberKind::Normal
+            };
+            commands.spawn(damage_number(
+                &styles,
+                damage,
+                target_transform.translation.truncate(),
+                kind,
+            ));
+        }
+
+        // Attacker-side feedback: the player's sprite pops when their attack connects
+        if player_query.get(damage_event.attacker).is_ok() {
+            commands
+                .entity(damage_event.attacker)
+                .try_insert(ImpactFlash::default());
+        }
+
+        // Heavy impacts shake the screen: combo finishers, and the player taking a real hit
+        if finisher {
+            camera_shake.trigger(CameraShake::COMBO_FINISHER);
+        }
+        if player_query.get(damage_event.target).is_ok() && !blocked {
+            camera_shake.trigger(CameraShake::PLAYER_HIT);
+        }
+
+        // Calculate knockback direction from attacker to target
+        let knockback_dir = if let (Ok(attacker_transform), Ok(target_transform)) = (
+            transform_query.get(damage_event.attacker),
+            transform_query.get(damage_event.target),
+        ) {
+            let direction =
+                (target_transform.translation - attacker_transform.translation).normalize();
+            Vec2::new(direction.x, direction.y)
+        } else {
+            Vec2::ZERO
+        };
+
+        if health.current <= 0 {
+            // Check if target is enemy or player
+            if enemy_query.get(damage_event.target).is_ok() {
+                outcomes.enemy_defeated.write(EnemyDefeatedEvent {
+                    enemy: damage_event.target,
+                });
+            } else if player_query.get(damage_event.target).is_ok() {
+                outcomes.player_defeated.write(PlayerDefeatedEvent {
+                    player: damage_event.target,
+                });
+            }
+        } else if blocked {
+            // Blocked hit - push the attacker away and stagger it so it can't hit again immediately
+            commands.entity(damage_event.attacker).try_insert((
+                Stunned {
+                    timer: Timer::from_seconds(0.4, TimerMode::Once),
+                },
+                Knockback {
+                    velocity: -knockback_dir * 300.0,
+                },
+            ));
+            commands.entity(damage_event.target).insert(Knockback {
+                velocity: knockback_dir * 150.0, // Slight pushback on the blocker
+            });
+        } else {
+            // Entity is still alive - add hit effects
+            let is_enemy = enemy_query.get(damage_event.target).is_ok();
+            let is_player = player_query.get(damage_event.target).is_ok();
+
+            if is_enemy && knockdown {
+                // Finishers and aerial attacks knock the enemy down; it can't be hit again until it's up
+                commands.entity(damage_event.target).insert((
+                    EnemyState::knockdown(),
+                    Invulnerable {
+                        timer: Timer::from_seconds(KNOCKDOWN_SECONDS, TimerMode::Once),
+                    },
+                    Knockback {
+                        velocity: knockback_dir * 450.0,
+                    },
+                    HitFlash {
+                        timer: Timer::from_seconds(0.3, TimerMode::Once),
+                        flash_duration: 0.3,
+                    },
+                ));
+            } else if is_enemy {
+                // Enemy hit but not dead - add stun, knockback, and hit flash
+                commands.entity(damage_event.target).insert((
+                    Stunned {
+                        timer: Timer::from_seconds(0.5, TimerMode::Once),
+                    },
+                    Knockback {
+                        velocity: knockback_dir * 300.0, // Medium knockback for enemies
+                    },
+                    HitFlash {
+                        timer: Timer::from_seconds(0.3, TimerMode::Once),
+                        flash_duration: 0.3,
+                    },
+                ));
+            } else if is_player && damage_event.team != Team::Enemy {
+                // Versus hit - a shorter invulnerability and lighter knockback keep the pressure on
+                commands.entity(damage_event.target).insert((
+                    Invulnerable {
+                        timer: Timer::from_seconds(0.4, TimerMode::Once),
+                    },
+                    Knockback {
+                        velocity: knockback_dir * 300.0,
+                    },
+                    HitFlash {
+                        timer: Timer::from_seconds(0.3, TimerMode::Once),
+                        flash_duration: 0.3,
+                    },
+                ));
+            } else if is_player {
+                // Player hit - add invulnerability, knockback, and hit flash
+                commands.entity(damage_event.target).insert((
+                    Invulnerable {
+                        timer: Timer::from_seconds(1.0, TimerMode::Once),
+                    },
+                    Knockback {
+                        velocity: knockback_dir * 500.0, // Stronger knockback for player
+                    },
+                    HitFlash {
+                        timer: Timer::from_seconds(0.3, TimerMode::Once),
+                        flash_duration: 0.3,
+                    },
+                ));
+            }
+        }
+    }
+}
+
+/// Fill the special meter for each hit landed by its owner (supers don't refill it)
+///
+/// Only hits that dealt damage count, so whiffs into invulnerability or fully
+/// absorbed hits don't build meter.
+pub fn fill_special_meter(
+    mut hit_landed_events: MessageReader<HitLandedEvent>,
+    mut meter_query: Query<(&mut SpecialMeter, &PlayerState)>,
+) {
+    for event in hit_landed_events.read() {
+        if let Ok((mut meter, state)) = meter_query.get_mut(event.attacker)
+            && !matches!(state, PlayerState::Super(_))
+        {
+            meter.add_hit();
+        }
+    }
+}
+
+/// Publish `GameplayEvent::DamageDealt` for every hit that dealt damage
+///
+/// Runs after `handle_damage_events`, tagging player hits with the attack that landed them.
+pub fn announce_damage_dealt(
+    mut hit_landed_events: MessageReader<HitLandedEvent>,
+    player_query: Query<&PlayerState, With<Player>>,
+    mut gameplay_events: MessageWriter<GameplayEvent>,
+) {
+    for event in hit_landed_events.read() {
+        gameplay_events.write(GameplayEvent::DamageDealt {
+            attacker: event.attacker,
+            target: event.target,
+            damage: event.damage,
+            attack: player_query
+                .get(event.attacker)
+                .ok()
+                .map(PlayerState::state_type),
+        });
+    }
+}
+
+/// Count landed hits into the attacker's combo; drop the victim's combo on an unblocked hit
+///
+/// Runs after `handle_damage_events`, so each hit's damage is scaled by the
+/// combo as it stood before that hit; hits that dealt no damage don't count.
+/// Under the pressure mutator the combo window runs out faster while the
+/// player isn't attacking.
+pub fn update_combo_counter(
+    time: Res<Time>,
+    mutators: Res<Mutators>,
+    mut hit_landed_events: MessageReader<HitLandedEvent>,
+    mut combo_query: Query<(&mut ComboCounter, &PlayerState)>,
+) {
+    for event in hit_landed_events.read() {
+        if let Ok((mut combo, _)) = combo_query.get_mut(event.attacker) {
+            combo.add_hit();
+        }
+        if let Ok((mut combo, state)) = combo_query.get_mut(event.target)
+            && !state.is_blocking()
+        {
+            combo.reset();
+        }
+    }
+
+    for (mut combo, state) in combo_query.iter_mut() {
+        let decay = if state.is_attacking() {
+            1.0
+        } else {
+            mutators.combo_decay_rate()
+        };
+        combo.window.tick(time.delta().mul_f32(decay));
+        if combo.window.just_finished() {
+            combo.reset();
+        }
+    }
+}
+
+/// Drain the special meter once enough time has passed without landing a hit
+pub fn update_special_meter(time: Res<Time>, mut meter_query: Query<&mut SpecialMeter>) {
+    for mut meter in meter_query.iter_mut() {
+        meter.decay_delay.tick(time.delta());
+
+        if meter.decay_delay.is_finished() {
+            meter.value = (meter.value - meter.decay_rate * time.delta_secs()).max(0.0);
+        }
+    }
+}
+
+pub fn update_stun_timers(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut stunned_query: Query<(Entity, &mut Stunned)>,
+) {
+    for (entity, mut stunned) in stunned_query.iter_mut() {
+        stunned.timer.tick(time.delta());
+
+        if stunned.timer.is_finished() {
+            commands.entity(entity).remove::<Stunned>();
+        }
+    }
+}
+
+/// Reset the blocked-hit count after a pause in blocking and recover broken guards
+pub fn update_guard_timers(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut guard_query: Query<&mut Guard>,
+    mut broken_query: Query<(Entity, &mut GuardBroken)>,
+) {
+    for mut guard in guard_query.iter_mut() {
+        guard.reset_timer.tick(time.delta());
+
+        if guard.reset_timer.just_finished() {
+            guard.blocked_hits = 0;
+        }
+    }
+
+    for (entity, mut broken) in broken_query.iter_mut() {
+        broken.timer.tick(time.delta());
+
+        if broken.timer.is_finished() {
+            commands.entity(entity).remove::<GuardBroken>();
+        }
+    }
+}
+
+/// Add knockback velocity to movement intent and decay it over time
+///
+/// Knockback that would hit the stage bounds, the arena edge (for confined
+/// bodies), or an obstacle bounces off it,
+/// reflected and damped by `WALL_BOUNCE_DAMPING`, so enemies aren't pinned
+/// against the edge of the arena. Friction is an exponential decay per
+/// second, so knockback fades at the same rate whatever the tick rate.
+#[allow(clippy::type_complexity)]
+pub fn apply_knockback(
+    mut commands: Commands,
+    time: Res<Time>,
+    geometry: Res<StageGeometry>,
+    arena: Res<ArenaBounds>,
+    mut knockback_query: Query<(
+        Entity,
+        &Transform,
+        &mut Knockback,
+        &mut MoveIntent,
+        Option<&HurtBox>,
+        Option<&JumpPhysics>,
+        Has<ArenaConfined>,
+    )>,
+) {
+    for (entity, transform, mut knockback, mut intent, hurtbox, jump_physics, confined) in
+        knockback_query.iter_mut()
+    {
+        // Bounce off walls, floor, and ceiling
+        let size = hurtbox.map_or(Vec2::ZERO, |hurtbox| hurtbox.size);
+        let blocked = geometry.blocked_axes(
+            transform.translation.truncate(),
+            size,
+            knockback.velocity * time.delta_secs(),
+            confined.then_some(arena.rect),
+        );
+        if blocked.x {
+            knockback.velocity.x *= -WALL_BOUNCE_DAMPING;
+        }
+        if blocked.y {
+            knockback.velocity.y *= -WALL_BOUNCE_DAMPING;
+        }
+
+        // For grounded players, only apply horizontal knockback
+        let is_grounded =
+            jump_physics.is_some_and(|jp| (transform.translation.y - jp.ground_y).abs() < 1.0);
+
+        if is_grounded {
+            // Only apply horizontal knockback for grounded entities
+            intent.velocity.x += knockback.velocity.x;
+        } else {
+            // Apply full knockback (both X and Y) for airborne/enemy entities
+            intent.velocity += knockback.velocity;
+        }
+
+        // Decay knockback velocity (friction)
+        knockback.velocity *= (-KNOCKBACK_FRICTION * time.delta_secs()).exp();
+
+        // Remove knockback component when velocity is negligible
+        if knockback.velocity.length() < 10.0 {
+            commands.entity(entity).remove::<Knockback>();
+        }
+    }
+}
+
+/// Tick hit flash timers and remove the effect when it expires
+///
+/// Sprite color is owned by `apply_damage_feedback`, which combines the flash
+/// tint with the invulnerability blink so the two effects never fight.
+pub fn update_hit_flash(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut flash_query: Query<(Entity, &mut HitFlash)>,
+) {
+    for (entity, mut hit_flash) in flash_query.iter_mut() {
+        hit_flash.timer.tick(time.delta());
+
+        if hit_flash.timer.is_finished() {
+            commands.entity(entity).remove::<HitFlash>();
+        }
+    }
+}
+
+/// Tick impact flashes, popping the attacker's scale out and back
+///
+/// The brightening is applied by `apply_damage_feedback` with the other sprite
+/// tints. Only the player gets impact flashes, so the pop is relative to unit scale.
+pub fn update_impact_flash(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut impact_query: Query<(Entity, &mut ImpactFlash, &mut Transform)>,
+) {
+    for (entity, mut impact, mut transform) in impact_query.iter_mut() {
+        impact.timer.tick(time.delta());
+
+        // Purely visual, so it scales the Transform directly
+        let pop = (impact.timer.fraction() * std::f32::consts::PI).sin();
+        transform.scale = Vec3::splat(1.0 + ImpactFlash::SCALE_POP * pop);
+
+        if impact.timer.is_finished() {
+            transform.scale = Vec3::ONE;
+            commands.entity(entity).remove::<ImpactFlash>();
+        }
+    }
+}
+
+/// Combine hit flash, impact flash, and invulnerability blink into a single sprite color
+///
+/// The hit flash tints the sprite red (fading out), the impact flash briefly
+/// brightens an attacker whose hit landed, while invulnerability blinks the
+/// alpha at ~10 Hz and slightly desaturates. All of it is multiplied into a
+/// player's `CostumeTint`, and sprites are restored to their tint (or white)
+/// once every effect has expired.
+#[allow(clippy::type_complexity)]
+pub fn apply_damage_feedback(
+    mut feedback_query: Query<
+        (
+            &mut Sprite,
+            Option<&HitFlash>,
+            Option<&ImpactFlash>,
+            Option<&Invulnerable>,
+            Option<&CostumeTint>,
+        ),
+        Or<(With<HitFlash>, With<ImpactFlash>, With<Invulnerable>)>,
+    >,
+    mut restore_query: Query<
+        (&mut Sprite, Option<&CostumeTint>),
+        (
+            Without<HitFlash>,
+            Without<ImpactFlash>,
+            Without<Invulnerable>,
+        ),
+    >,
+    mut removed_flash: RemovedComponents<HitFlash>,
+    mut removed_impact: RemovedComponents<ImpactFlash>,
+    mut removed_invuln: RemovedComponents<Invulnerable>,
+) {
+    const BLINK_INTERVAL: f32 = 0.05; // Toggle every 50ms = 10 blinks per second
+    const BLINK_ALPHA: f32 = 0.3;
+    const INVULN_TINT: f32 = 0.85; // Slight desaturation while invulnerable
+
+    for (mut sprite, hit_flash, impact, invulnerable, tint) in feedback_query.iter_mut() {
+        // Red tint from hit flash (starts at 1.0, fades to 0.0)
+        let (mut red, mut green_blue) = (1.0, 1.0);
+        if let Some(hit_flash) = hit_flash {
+            let progress = hit_flash.timer.elapsed_secs() / hit_flash.flash_duration;
+            let intensity = (1.0 - progress).max(0.0);
+            green_blue = 1.0 - intensity * 0.7;
+        }
+
+        // White brightening from a landed hit (color channels above 1.0 brighten the sprite)
+        if let Some(impact) = impact {
+            let boost = 1.0 + (ImpactFlash::BRIGHTNESS - 1.0) * impact.intensity();
+            red *= boost;
+            green_blue *= boost;
+        }
+
+        // Alpha blink and desaturation from invulnerability
+        let mut alpha = 1.0;
+        if let Some(invulnerable) = invulnerable {
+            let blink_phase = (invulnerable.timer.elapsed_secs() / BLINK_INTERVAL) as u32;
+            if blink_phase % 2 == 1 {
+                alpha = BLINK_ALPHA;
+            }
+            red *= INVULN_TINT;
+            green_blue *= INVULN_TINT;
+        }
+
+        let tint = tint.map_or(Srgba::WHITE, |tint| tint.0.to_srgba());
+        sprite.color = Color::srgba(
+            red * tint.red,
+            green_blue * tint.green,
+            green_blue * tint.blue,
+            alpha,
+        );
+    }
+
+    // Restore entities whose last feedback effect was just removed
+    for entity in removed_flash
+        .read()
+        .chain(removed_impact.read())
+        .chain(removed_invuln.read())
+    {
+        if let Ok((mut sprite, tint)) = restore_query.get_mut(entity) {
+            sprite.color = tint.map_or(Color::WHITE, |tint| tint.0);
+        }
+    }
+}
+
+/// Fade out screen flashes and despawn them when finished
+pub fn update_screen_flash(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut flash_query: Query<(Entity, &mut ScreenFlash, &mut BackgroundColor)>,
+) {
+    for (entity, mut flash, mut background) in flash_query.iter_mut() {
+        flash.timer.tick(time.delta());
+        background.0.set_alpha(0.8 * (1.0 - flash.timer.fraction()));
+
+        if flash.timer.is_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Update invulnerability timer and remove when expired
+pub fn update_invulnerability(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut invuln_query: Query<(Entity, &mut Invulnerable)>,
+) {
+    for (entity, mut invulnerable) in invuln_query.iter_mut() {
+        invulnerable.timer.tick(time.delta());
+
+        if invulnerable.timer.is_finished() {
+            commands.entity(entity).remove::<Invulnerable>();
+        }
+    }
+}
+
+/// Count down shields and let them fade when their time runs out
+pub fn update_shields(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut shield_query: Query<(Entity, &mut Shield)>,
+) {
+    for (entity, mut shield) in shield_query.iter_mut() {
+        shield.timer.tick(time.delta());
+
+        if shield.timer.is_finished() {
+            commands.entity(entity).remove::<Shield>();
+        }
+    }
+}
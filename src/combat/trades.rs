@@ -0,0 +1,59 @@
+use crate::combat::messages::DamageEvent;
+use crate::debug::{DebugName, entity_label};
+use crate::settings::TradeRule;
+use bevy::prelude::*;
+
+/// Settle hits that two entities landed on each other in the same frame
+///
+/// Under `TradeRule::Classic` both hits land. Under `TradeRule::Priority` a
+/// hit is cancelled when its target hit back with a heavier `AttackWeight`
+/// that frame; equal weights still trade. Runs after all hit detection and
+/// before `handle_damage_events`, so every `DamageEvent` reader sees the
+/// settled hits.
+pub fn resolve_hit_trades(
+    rule: Res<TradeRule>,
+    mut damage_events: ResMut<Messages<DamageEvent>>,
+    names: Query<&DebugName>,
+) {
+    if *rule == TradeRule::Classic {
+        return;
+    }
+
+    let hits: Vec<&DamageEvent> = damage_events.iter_current_update_messages().collect();
+    let lost: Vec<usize> = (0..hits.len())
+        .filter(|&i| {
+            // Heaviest attack the target landed back on its attacker this frame
+            let counter = hits
+                .iter()
+                .filter(|other| {
+                    other.attacker == hits[i].target && other.target == hits[i].attacker
+                })
+                .map(|other| other.weight)
+                .max();
+            counter.is_some_and(|weight| weight > hits[i].weight)
+        })
+        .collect();
+    if lost.is_empty() {
+        return;
+    }
+
+    for &i in &lost {
+        debug!(
+            "{} lost a trade with {} ({:?} attack cancelled)",
+            entity_label(&names, hits[i].attacker),
+            entity_label(&names, hits[i].target),
+            hits[i].weight
+        );
+    }
+
+    // Hit detection and every DamageEvent reader run in the same frames, so
+    // earlier frames' hits have all been read; only this frame's are written back
+    let current = hits.len();
+    let drained: Vec<DamageEvent> = damage_events.drain().collect();
+    let earlier = drained.len() - current;
+    for (i, hit) in drained.into_iter().skip(earlier).enumerate() {
+        if !lost.contains(&i) {
+            damage_events.write(hit);
+        }
+    }
+}
@@ -0,0 +1,271 @@
+use crate::combat::{Health, Invulnerable, Stunned};
+use crate::debug::log_tail::LogTail;
+use crate::debug::names::DebugName;
+use crate::enemy::Enemy;
+use crate::game::{AppState, GameState};
+use crate::input::{InputAction, InputMap, PlayerActions};
+use crate::persistence::{SaveCompletedEvent, SaveQueue, data_dir};
+use crate::player::states::Emote;
+use crate::player::{Player, PlayerState};
+use crate::settings::{Difficulty, GameSpeed, Mutators};
+use bevy::prelude::*;
+use bevy::render::view::screenshot::{Screenshot, ScreenshotCaptured};
+use bevy::tasks::{IoTaskPool, Task, block_on};
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::io::{Cursor, Write as _};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Seconds of input kept for bug reports
+const INPUT_HISTORY_SECONDS: f32 = 10.0;
+/// Folder of the data directory bug reports are saved to
+const BUG_REPORTS_DIR: &str = "bug-reports";
+
+/// Rolling buffer of the player actions from the last few seconds
+#[derive(Resource, Default)]
+pub struct InputHistory {
+    /// (elapsed seconds, actions) per frame, oldest first
+    frames: VecDeque<(f32, PlayerActions)>,
+}
+
+/// Bug report options
+#[derive(Resource, Default)]
+pub struct BugReportSettings {
+    /// Open the report folder in the system file browser after capturing
+    pub open_folder: bool,
+}
+
+/// Bug report archives being built on the IO task pool, with the file each
+/// is saved to once done (None if the archive couldn't be built)
+#[derive(Resource, Default)]
+pub struct BugReportBundles {
+    building: Vec<(String, Task<Option<Vec<u8>>>)>,
+}
+
+/// Record this frame's actions and drop frames older than the history window
+///
+/// Runs right after `gather_player_actions`, in every app state.
+pub fn record_input_history(
+    time: Res<Time<Real>>,
+    actions: Res<PlayerActions>,
+    mut history: ResMut<InputHistory>,
+) {
+    let now = time.elapsed_secs();
+    history.frames.push_back((now, actions.clone()));
+    while history
+        .frames
+        .front()
+        .is_some_and(|(t, _)| now - t > INPUT_HISTORY_SECONDS)
+    {
+        history.frames.pop_front();
+    }
+}
+
+/// Capture a bug report bundle when the BugReport key (F8) is pressed
+///
+/// Saves a zip archive under `<data dir>/bug-reports/` containing a
+/// screenshot, the last 10 seconds of input, a snapshot of the game state,
+/// and the last log lines. Nothing touches the disk here: once the screenshot
+/// is captured, the archive is built on the IO task pool and saved through
+/// `SaveQueue` (see `finish_bug_reports`).
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn capture_bug_report(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    history: Res<InputHistory>,
+    log_tail: Res<LogTail>,
+    app_state: Res<State<AppState>>,
+    difficulty: Res<Difficulty>,
+    speed: Res<GameSpeed>,
+    mutators: Res<Mutators>,
+    game_state: Option<Res<GameState>>,
+    player_query: Query<(&Transform, &Health, &PlayerState, Has<Invulnerable>), With<Player>>,
+    enemy_query: Query<(&Transform, &Health, Has<Stunned>, Option<&DebugName>), With<Enemy>>,
+) {
+    if !input_map.just_pressed(InputAction::BugReport, &keyboard) {
+        return;
+    }
+
+    let Some(data_dir) = data_dir() else {
+        warn!("Bug report: no data directory available");
+        return;
+    };
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_millis());
+    let report = format!("{BUG_REPORTS_DIR}/{timestamp}.zip");
+
+    // Game state snapshot
+    let mut snapshot = String::new();
+    let _ = writeln!(snapshot, "app_state: {:?}", app_state.get());
+    let _ = writeln!(snapshot, "difficulty: {:?}", *difficulty);
+    let _ = writeln!(snapshot, "speed: {:?}", *speed);
+    let _ = writeln!(snapshot, "mutators: {:?}", *mutators);
+    if let Some(game_state) = game_state {
+        let _ = writeln!(snapshot, "score: {}", game_state.score);
+        let _ = writeln!(
+            snapshot,
+            "time_remaining: {:.2}",
+            game_state.time_remaining()
+        );
+        let _ = writeln!(snapshot, "enemies: {}", game_state.n_enemies);
+    }
+    for (transform, health, state, invulnerable) in player_query.iter() {
+        let _ = writeln!(
+            snapshot,
+            "player: state={:?} pos={} health={}/{} invulnerable={}",
+            state,
+            transform.translation.truncate(),
+            health.current,
+            health.max,
+            invulnerable
+        );
+    }
+    for (transform, health, stunned, name) in enemy_query.iter() {
+        let _ = writeln!(
+            snapshot,
+            "enemy: name={} pos={} health={}/{} stunned={}",
+            name.map_or("-", |name| name.0.as_str()),
+            transform.translation.truncate(),
+            health.current,
+            health.max,
+            stunned
+        );
+    }
+
+    // Input history, newest frame last, times relative to the capture
+    let now = history.frames.back().map_or(0.0, |(t, _)| *t);
+    let mut inputs = String::new();
+    for (t, actions) in history.frames.iter() {
+        let _ = writeln!(inputs, "{:+.3} {}", t - now, active_actions(actions));
+    }
+
+    let texts = [
+        ("state.txt", snapshot),
+        ("inputs.txt", inputs),
+        ("log.txt", log_tail.contents()),
+    ];
+    let file = report.clone();
+    commands.spawn(Screenshot::primary_window()).observe(
+        move |captured: On<ScreenshotCaptured>, mut bundles: ResMut<BugReportBundles>| {
+            let (image, texts) = (captured.image.clone(), texts.clone());
+            let task = IoTaskPool::get().spawn(async move { bundle(image, &texts) });
+            bundles.building.push((file.clone(), task));
+        },
+    );
+
+    info!("Saving bug report to {}", data_dir.join(report).display());
+}
+
+/// Queue each bug report archive for saving once it's built, and open the
+/// bug report folder once one is written (with `BugReportSettings::open_folder`)
+pub fn finish_bug_reports(
+    settings: Res<BugReportSettings>,
+    mut bundles: ResMut<BugReportBundles>,
+    mut save_queue: ResMut<SaveQueue>,
+    mut completed_events: MessageReader<SaveCompletedEvent>,
+) {
+    bundles.building.retain_mut(|(file, task)| {
+        if !task.is_finished() {
+            return true;
+        }
+        match block_on(task) {
+            Some(archive) => save_queue.queue(file, archive),
+            None => warn!("Bug report: failed to build {file}"),
+        }
+        false
+    });
+
+    for event in completed_events.read() {
+        if settings.open_folder
+            && event.error.is_none()
+            && event.file.starts_with(BUG_REPORTS_DIR)
+            && let Some(dir) = data_dir()
+        {
+            open_in_file_browser(&dir.join(BUG_REPORTS_DIR));
+        }
+    }
+}
+
+/// Zip archive of a bug report: the screenshot (if it can be encoded) and the texts
+///
+/// Entries are stored uncompressed; the PNG is already compressed and the
+/// texts are small.
+fn bundle(screenshot: Image, texts: &[(&str, String)]) -> Option<Vec<u8>> {
+    let options =
+        zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+    let mut archive = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    match encode_png(screenshot) {
+        Some(png) => {
+            archive.start_file("screenshot.png", options).ok()?;
+            archive.write_all(&png).ok()?;
+        }
+        None => warn!("Bug report: failed to encode the screenshot"),
+    }
+    for (name, contents) in texts {
+        archive.start_file(*name, options).ok()?;
+        archive.write_all(contents.as_bytes()).ok()?;
+    }
+    Some(archive.finish().ok()?.into_inner())
+}
+
+/// PNG bytes of a captured screenshot, without the alpha channel (which holds
+/// brightness when HDR is on, like Bevy's `save_to_disk`)
+fn encode_png(image: Image) -> Option<Vec<u8>> {
+    let rgb = image.try_into_dynamic().ok()?.to_rgb8();
+    let mut png = Cursor::new(Vec::new());
+    rgb.write_to(&mut png, image::ImageFormat::Png).ok()?;
+    Some(png.into_inner())
+}
+
+/// Space-separated names of the actions active in a frame ("-" if none)
+fn active_actions(actions: &PlayerActions) -> String {
+    let names: Vec<&str> = [
+        (actions.left, "left"),
+        (actions.right, "right"),
+        (actions.up, "up"),
+        (actions.down, "down"),
+        (actions.walk, "walk"),
+        (actions.jump, "jump"),
+        (actions.punch, "punch"),
+        (actions.kick, "kick"),
+        (actions.block, "block"),
+        (actions.dash, "dash"),
+        (actions.super_attack, "super"),
+        (actions.burst, "burst"),
+        (actions.grab, "grab"),
+        (actions.interact, "interact"),
+        (actions.pause, "pause"),
+        (actions.menu_up, "menu_up"),
+        (actions.menu_down, "menu_down"),
+        (actions.confirm, "confirm"),
+        (actions.back, "back"),
+    ]
+    .into_iter()
+    .filter_map(|(active, name)| active.then_some(name))
+    .chain(actions.emote.map(Emote::key))
+    .collect();
+
+    if names.is_empty() {
+        "-".to_string()
+    } else {
+        names.join(" ")
+    }
+}
+
+/// Open a folder with the platform's file browser, logging on failure
+fn open_in_file_browser(path: &Path) {
+    let program = if cfg!(target_os = "windows") {
+        "explorer"
+    } else if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+
+    if let Err(err) = std::process::Command::new(program).arg(path).spawn() {
+        warn!("Bug report: failed to open {}: {err}", path.display());
+    }
+}
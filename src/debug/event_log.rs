@@ -0,0 +1,75 @@
+use crate::debug::names::{DebugName, entity_label};
+use crate::game::GameplayEvent;
+use bevy::prelude::*;
+
+/// Log every `GameplayEvent`, shown with RUST_LOG=martial_magicka=debug
+///
+/// The stand-in telemetry consumer of the gameplay event bus: one line per
+/// event, with entities labeled by their debug names.
+pub fn log_gameplay_events(
+    mut gameplay_events: MessageReader<GameplayEvent>,
+    names: Query<&DebugName>,
+) {
+    for event in gameplay_events.read() {
+        match event {
+            GameplayEvent::StateEntered(state) => debug!("Entered {state:?}"),
+            GameplayEvent::AttackUsed { attacker, attack } => {
+                debug!("{} used {}", entity_label(&names, *attacker), attack.key());
+            }
+            GameplayEvent::DamageDealt {
+                attacker,
+                target,
+                damage,
+                ..
+            } => debug!(
+                "{} dealt {} damage to {}",
+                entity_label(&names, *attacker),
+                damage,
+                entity_label(&names, *target)
+            ),
+            GameplayEvent::PickupCollected { collector, kind } => {
+                debug!(
+                    "{} collected {}",
+                    entity_label(&names, *collector),
+                    kind.key()
+                );
+            }
+            GameplayEvent::WaveCleared { wave } => debug!("Cleared wave {wave}"),
+            GameplayEvent::WhiffPunishGraded(grade) => debug!("Whiff punish graded {grade:?}"),
+            GameplayEvent::ZoneCleared { zone } => debug!("Cleared zone {zone}"),
+            GameplayEvent::LifeLost { lives_left } => debug!("Lost a life ({lives_left} left)"),
+            GameplayEvent::ContinueUsed { continues_left } => {
+                debug!("Continued ({continues_left} continues left)")
+            }
+            GameplayEvent::StageCleared { stage } => debug!("Cleared stage {stage}"),
+            GameplayEvent::BossPhase { phase } => debug!("Boss entered phase {phase}"),
+            GameplayEvent::AttackClash {
+                first,
+                second,
+                winner,
+            } => match winner {
+                Some(winner) => debug!(
+                    "{} and {} clashed; {} won",
+                    entity_label(&names, *first),
+                    entity_label(&names, *second),
+                    entity_label(&names, *winner)
+                ),
+                None => debug!(
+                    "{} and {} clashed and cancelled out",
+                    entity_label(&names, *first),
+                    entity_label(&names, *second)
+                ),
+            },
+            GameplayEvent::AnimationCue {
+                entity,
+                cue,
+                surface,
+            } => debug!(
+                "{} {} on {}",
+                entity_label(&names, *entity),
+                cue.key(),
+                surface.key()
+            ),
+        }
+    }
+}
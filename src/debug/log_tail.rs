@@ -0,0 +1,69 @@
+use bevy::log::BoxedLayer;
+use bevy::log::tracing_subscriber::fmt::{self, MakeWriter};
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use std::io;
+use std::sync::{Arc, Mutex};
+
+/// Log lines kept for bug reports
+const LOG_TAIL_LINES: usize = 500;
+
+/// Ring buffer of the most recent log lines, for bug reports
+///
+/// Filled by the layer `log_tail_layer` adds to Bevy's `LogPlugin`, so it
+/// holds the same lines (at the same `RUST_LOG` filter) as the console,
+/// without colors. Empty if the app was built without that layer.
+#[derive(Resource, Clone, Default)]
+pub struct LogTail {
+    lines: Arc<Mutex<VecDeque<String>>>,
+}
+
+impl LogTail {
+    /// The kept lines, oldest first, one per line
+    pub fn contents(&self) -> String {
+        let lines = self.lines.lock().unwrap_or_else(|err| err.into_inner());
+        let mut contents = String::new();
+        for line in lines.iter() {
+            contents.push_str(line);
+            contents.push('\n');
+        }
+        contents
+    }
+
+    fn push(&self, line: &str) {
+        let mut lines = self.lines.lock().unwrap_or_else(|err| err.into_inner());
+        if lines.len() == LOG_TAIL_LINES {
+            lines.pop_front();
+        }
+        lines.push_back(line.to_string());
+    }
+}
+
+impl io::Write for LogTail {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // Each formatted event arrives in one write
+        for line in String::from_utf8_lossy(buf).lines() {
+            self.push(line);
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for LogTail {
+    type Writer = LogTail;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+/// `LogPlugin::custom_layer` that copies every log line into the `LogTail` resource
+pub fn log_tail_layer(app: &mut App) -> Option<BoxedLayer> {
+    let tail = LogTail::default();
+    app.insert_resource(tail.clone());
+    Some(Box::new(fmt::layer().with_ansi(false).with_writer(tail)))
+}
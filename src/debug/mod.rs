@@ -0,0 +1,11 @@
+pub mod bug_report;
+pub mod event_log;
+pub mod log_tail;
+pub mod names;
+pub mod plugin;
+pub mod telemetry;
+
+// Re-export commonly used items
+pub use log_tail::*;
+pub use names::*;
+pub use plugin::*;
@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Human-readable label for an entity ("Player", "Ghost #12", "Fireball")
+///
+/// Spawned together with Bevy's `Name` (which entity inspectors show) via
+/// `debug_name`, and used by the combat log and bug reports.
+#[derive(Component, Clone, Debug)]
+pub struct DebugName(pub String);
+
+/// Per-label spawn counters for numbered debug names
+///
+/// Counts run for the whole session, so a label never repeats in one log.
+#[derive(Resource, Default)]
+pub struct DebugNameCounters {
+    next: HashMap<String, u32>,
+}
+
+impl DebugNameCounters {
+    /// Next numbered label for a kind of entity ("Ghost" -> "Ghost #12")
+    pub fn numbered(&mut self, base: &str) -> String {
+        let count = self.next.entry(base.to_string()).or_insert(0);
+        *count += 1;
+        format!("{base} #{count}")
+    }
+}
+
+/// `DebugName` plus a matching Bevy `Name`, for spawn bundles
+pub fn debug_name(label: impl Into<String>) -> impl Bundle {
+    let label = label.into();
+    (Name::new(label.clone()), DebugName(label))
+}
+
+/// Label for an entity in log output: its debug name, or the raw id if it has none
+pub fn entity_label(names: &Query<&DebugName>, entity: Entity) -> String {
+    names
+        .get(entity)
+        .map_or_else(|_| entity.to_string(), |name| name.0.clone())
+}
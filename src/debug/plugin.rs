@@ -0,0 +1,54 @@
+use crate::debug::bug_report::{
+    BugReportBundles, BugReportSettings, InputHistory, capture_bug_report, finish_bug_reports,
+    record_input_history,
+};
+use crate::debug::event_log::log_gameplay_events;
+use crate::debug::log_tail::LogTail;
+use crate::debug::names::DebugNameCounters;
+use crate::debug::telemetry::{
+    CombatTelemetry, record_combat_telemetry, reset_combat_telemetry, restart_combat_telemetry,
+    spawn_telemetry_overlay, toggle_telemetry_overlay, update_telemetry_overlay,
+};
+use crate::game::{GameSet, InGame};
+use crate::input::gather_player_actions;
+use bevy::prelude::*;
+
+/// Input history, bug report capture, the gameplay event log, combat telemetry, and numbered
+/// debug names
+pub struct DebugPlugin;
+
+impl Plugin for DebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputHistory>()
+            .init_resource::<BugReportSettings>()
+            .init_resource::<LogTail>()
+            .init_resource::<BugReportBundles>()
+            .init_resource::<DebugNameCounters>()
+            .init_resource::<CombatTelemetry>()
+            .add_systems(
+                OnEnter(InGame),
+                (reset_combat_telemetry, spawn_telemetry_overlay).chain(),
+            )
+            .add_systems(
+                Update,
+                (record_input_history, capture_bug_report)
+                    .chain()
+                    .after(gather_player_actions)
+                    .in_set(GameSet::Input),
+            )
+            .add_systems(
+                Update,
+                (
+                    finish_bug_reports,
+                    log_gameplay_events,
+                    toggle_telemetry_overlay
+                        .after(gather_player_actions)
+                        .in_set(GameSet::Input),
+                    (record_combat_telemetry, update_telemetry_overlay)
+                        .chain()
+                        .in_set(GameSet::Hud),
+                    restart_combat_telemetry.in_set(GameSet::Restart),
+                ),
+            );
+    }
+}
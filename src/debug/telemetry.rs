@@ -0,0 +1,293 @@
+use crate::debug::names::DebugName;
+use crate::game::{GameplayEvent, InGame, RestartGameEvent};
+use crate::input::{InputAction, InputMap};
+use crate::player::Player;
+use crate::ui::text_style::{TextRole, TextStyles};
+use bevy::prelude::*;
+use std::collections::{HashMap, VecDeque};
+
+/// Seconds of damage output kept for the DPS graph (one bar per second)
+const DPS_HISTORY_SECONDS: usize = 30;
+/// Height of the DPS graph in pixels
+const GRAPH_HEIGHT: f32 = 80.0;
+/// Width of one DPS graph bar in pixels
+const GRAPH_BAR_WIDTH: f32 = 8.0;
+/// Width in characters of the longest distribution bar
+const DISTRIBUTION_WIDTH: usize = 16;
+
+/// Combat numbers of the current run, gathered from the gameplay event bus for
+/// the telemetry overlay (Telemetry key, F9)
+///
+/// Always recorded, so the overlay shows the whole run when opened mid-fight.
+/// Reset at the start of every run.
+#[derive(Resource, Default)]
+pub struct CombatTelemetry {
+    /// Whether the overlay is shown
+    pub visible: bool,
+    /// Damage the players dealt in each of the last `DPS_HISTORY_SECONDS` seconds, oldest first
+    dps: VecDeque<i32>,
+    /// Damage dealt so far in the current second
+    current_damage: i32,
+    /// Game seconds into the current second
+    current_elapsed: f32,
+    /// Damage the players took, by source (debug name without its number)
+    damage_taken: HashMap<String, i32>,
+    /// Attacks the players used, by `PlayerStateType::key`
+    attacks_used: HashMap<&'static str, u32>,
+}
+
+impl CombatTelemetry {
+    /// Forget the last run's numbers, keeping the overlay's visibility
+    fn reset(&mut self) {
+        *self = Self {
+            visible: self.visible,
+            ..default()
+        };
+    }
+
+    /// Advance the DPS clock, closing off every second that has passed
+    fn tick(&mut self, delta: f32) {
+        self.current_elapsed += delta;
+        while self.current_elapsed >= 1.0 {
+            self.current_elapsed -= 1.0;
+            self.dps.push_back(self.current_damage);
+            self.current_damage = 0;
+            if self.dps.len() > DPS_HISTORY_SECONDS {
+                self.dps.pop_front();
+            }
+        }
+    }
+}
+
+/// Root of the telemetry overlay
+#[derive(Component)]
+pub struct TelemetryOverlay;
+
+/// One second's bar in the DPS graph (0 is the oldest)
+#[derive(Component)]
+pub struct DpsBar(pub usize);
+
+/// Text of the telemetry overlay below the graph
+#[derive(Component)]
+pub struct TelemetryText;
+
+/// Debug name without its spawn number ("Ghost #12" -> "Ghost")
+fn source_label(names: &Query<&DebugName>, entity: Entity) -> String {
+    names.get(entity).map_or_else(
+        |_| "Unknown".to_string(),
+        |name| name.0.split(" #").next().unwrap_or(&name.0).to_string(),
+    )
+}
+
+/// Tally the players' damage per second, the damage they take by source, and
+/// the attacks they use, from `GameplayEvent`s
+pub fn record_combat_telemetry(
+    time: Res<Time>,
+    mut telemetry: ResMut<CombatTelemetry>,
+    mut gameplay_events: MessageReader<GameplayEvent>,
+    names: Query<&DebugName>,
+    player_query: Query<(), With<Player>>,
+) {
+    telemetry.tick(time.delta_secs());
+    for event in gameplay_events.read() {
+        match event {
+            GameplayEvent::DamageDealt {
+                attacker,
+                target,
+                damage,
+                ..
+            } => {
+                if player_query.contains(*attacker) {
+                    telemetry.current_damage += damage;
+                }
+                if player_query.contains(*target) {
+                    *telemetry
+                        .damage_taken
+                        .entry(source_label(&names, *attacker))
+                        .or_default() += damage;
+                }
+            }
+            GameplayEvent::AttackUsed { attack, .. } => {
+                *telemetry.attacks_used.entry(attack.key()).or_default() += 1;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Start every run with empty telemetry
+///
+/// Runs on entering InGame.
+pub fn reset_combat_telemetry(mut telemetry: ResMut<CombatTelemetry>) {
+    telemetry.reset();
+}
+
+/// Empty the telemetry on `RestartGameEvent`, which restarts without leaving InGame
+pub fn restart_combat_telemetry(
+    mut telemetry: ResMut<CombatTelemetry>,
+    mut restart_events: MessageReader<RestartGameEvent>,
+) {
+    if restart_events.read().count() > 0 {
+        telemetry.reset();
+    }
+}
+
+/// Spawn the (hidden unless toggled on) telemetry overlay on the right edge
+///
+/// Runs on entering InGame: a title, the DPS graph, and a text block for
+/// damage taken and attack usage.
+pub fn spawn_telemetry_overlay(
+    mut commands: Commands,
+    styles: Res<TextStyles>,
+    telemetry: Res<CombatTelemetry>,
+) {
+    let visibility = if telemetry.visible {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(20.0),
+                top: Val::Px(120.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(8.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+            GlobalZIndex(8),
+            visibility,
+            TelemetryOverlay,
+            DespawnOnExit(InGame),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("TELEMETRY  DPS (last 30s)"),
+                styles.font(TextRole::Small),
+                TextColor(Color::srgb(1.0, 0.85, 0.2)),
+            ));
+            parent
+                .spawn(Node {
+                    height: Val::Px(GRAPH_HEIGHT),
+                    align_items: AlignItems::FlexEnd,
+                    column_gap: Val::Px(1.0),
+                    ..default()
+                })
+                .with_children(|graph| {
+                    for index in 0..DPS_HISTORY_SECONDS {
+                        graph.spawn((
+                            Node {
+                                width: Val::Px(GRAPH_BAR_WIDTH),
+                                height: Val::Px(0.0),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.9, 0.4, 0.2)),
+                            DpsBar(index),
+                        ));
+                    }
+                });
+            parent.spawn((
+                Text::new(""),
+                styles.font(TextRole::Small),
+                TextColor(Color::WHITE),
+                TelemetryText,
+            ));
+        });
+}
+
+/// Show or hide the overlay on the Telemetry key (F9)
+pub fn toggle_telemetry_overlay(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    mut telemetry: ResMut<CombatTelemetry>,
+    mut overlay_query: Query<&mut Visibility, With<TelemetryOverlay>>,
+) {
+    if !input_map.just_pressed(InputAction::Telemetry, &keyboard) {
+        return;
+    }
+    telemetry.visible = !telemetry.visible;
+    for mut visibility in overlay_query.iter_mut() {
+        *visibility = if telemetry.visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+/// Lines of `name  ### count (pct%)`, largest first, with bars scaled to the largest
+fn distribution<K: AsRef<str>>(counts: impl IntoIterator<Item = (K, i64)>) -> String {
+    let mut counts: Vec<(K, i64)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.as_ref().cmp(b.0.as_ref())));
+    let total: i64 = counts.iter().map(|(_, count)| count).sum();
+    let largest = counts.first().map_or(1, |(_, count)| (*count).max(1));
+    if total == 0 {
+        return "  (none yet)\n".to_string();
+    }
+    counts
+        .iter()
+        .map(|(name, count)| {
+            let bar = "#".repeat((*count * DISTRIBUTION_WIDTH as i64 / largest) as usize);
+            format!(
+                "  {:<14} {:<width$} {} ({}%)\n",
+                name.as_ref(),
+                bar,
+                count,
+                count * 100 / total,
+                width = DISTRIBUTION_WIDTH
+            )
+        })
+        .collect()
+}
+
+/// Redraw the overlay from `CombatTelemetry` while it's shown
+pub fn update_telemetry_overlay(
+    telemetry: Res<CombatTelemetry>,
+    mut bar_query: Query<(&DpsBar, &mut Node)>,
+    mut text_query: Query<&mut Text, With<TelemetryText>>,
+) {
+    if !telemetry.visible {
+        return;
+    }
+
+    // Bars are right-aligned, so the newest second is always the rightmost bar
+    let offset = DPS_HISTORY_SECONDS - telemetry.dps.len();
+    let peak = telemetry.dps.iter().copied().max().unwrap_or(0).max(1);
+    for (bar, mut node) in bar_query.iter_mut() {
+        let damage = bar
+            .0
+            .checked_sub(offset)
+            .and_then(|index| telemetry.dps.get(index))
+            .copied()
+            .unwrap_or(0);
+        node.height = Val::Px(GRAPH_HEIGHT * damage as f32 / peak as f32);
+    }
+
+    let recent = telemetry.dps.iter().rev().take(5).sum::<i32>() as f32
+        / telemetry.dps.len().clamp(1, 5) as f32;
+    let text = format!(
+        "DPS now {:.1}  peak {}\n\nDamage taken by source\n{}\nAttack usage\n{}",
+        recent,
+        telemetry.dps.iter().copied().max().unwrap_or(0),
+        distribution(
+            telemetry
+                .damage_taken
+                .iter()
+                .map(|(source, damage)| (source.as_str(), *damage as i64))
+        ),
+        distribution(
+            telemetry
+                .attacks_used
+                .iter()
+                .map(|(attack, count)| (*attack, *count as i64))
+        ),
+    );
+    for mut overlay_text in text_query.iter_mut() {
+        if overlay_text.0 != text {
+            overlay_text.0 = text.clone();
+        }
+    }
+}
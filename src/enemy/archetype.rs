@@ -0,0 +1,123 @@
+use crate::persistence::parse_key_values;
+use crate::pickup::LootTable;
+use bevy::prelude::*;
+use std::str::FromStr;
+
+/// Intro card, bestiary, and spawn data for each archetype (`<key>.<field>` lines)
+const ARCHETYPE_DATA: &str = include_str!("../../assets/enemies.txt");
+
+/// Kind of enemy, set when it spawns
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EnemyArchetype {
+    /// Melee ghost that chases the player
+    Ghost,
+    /// Smaller ranged ghost that throws fireballs (has `RangedAttacker`)
+    FireGhost,
+}
+
+impl EnemyArchetype {
+    /// Every archetype, in bestiary order
+    pub const ALL: [EnemyArchetype; 2] = [EnemyArchetype::Ghost, EnemyArchetype::FireGhost];
+
+    /// Stable name used in save files and assets/enemies.txt
+    pub fn key(self) -> &'static str {
+        match self {
+            EnemyArchetype::Ghost => "ghost",
+            EnemyArchetype::FireGhost => "fire_ghost",
+        }
+    }
+
+    /// Parse a name written by `key`
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "ghost" => Some(EnemyArchetype::Ghost),
+            "fire_ghost" => Some(EnemyArchetype::FireGhost),
+            _ => None,
+        }
+    }
+
+    /// Display name for the intro card
+    pub fn name(self) -> &'static str {
+        self.data("name").unwrap_or(self.key())
+    }
+
+    /// One-line threat description for the intro card
+    pub fn threat(self) -> &'static str {
+        self.data("threat").unwrap_or_default()
+    }
+
+    /// How it hurts the player, for the bestiary
+    pub fn attack(self) -> &'static str {
+        self.data("attack").unwrap_or_default()
+    }
+
+    /// How it moves, for the bestiary
+    pub fn movement(self) -> &'static str {
+        self.data("movement").unwrap_or_default()
+    }
+
+    /// Lore text, for the bestiary
+    pub fn flavor(self) -> &'static str {
+        self.data("flavor").unwrap_or_default()
+    }
+
+    /// Threat cost counted against the spawn budget (at least 1)
+    pub fn threat_cost(self) -> u32 {
+        self.number("cost").max(1)
+    }
+
+    /// Relative chance of spawning when affordable
+    pub fn spawn_weight(self) -> u32 {
+        self.number("weight")
+    }
+
+    /// Drop table rolled when it's defeated (drops nothing if unset)
+    pub fn loot_table(self) -> LootTable {
+        LootTable::parse(self.data("loot").unwrap_or_default())
+    }
+
+    /// Flat damage subtracted from each hit it takes (0 if unset)
+    pub fn armor(self) -> i32 {
+        self.parsed("armor").unwrap_or(0)
+    }
+
+    /// Fraction of damage (after armor) it ignores (0.0 if unset)
+    pub fn resistance(self) -> f32 {
+        self.parsed("resistance").unwrap_or(0.0)
+    }
+
+    /// Sprite (and hurtbox) scale (1.0 if unset)
+    pub fn scale(self) -> f32 {
+        self.parsed("scale").unwrap_or(1.0)
+    }
+
+    /// Voice takes of a bark (`<archetype>.<bark>_bark`, e.g. `death_bark`) with
+    /// their weights (1 if unset), in file order; malformed weights count as 1
+    pub fn bark_takes(self, bark: &str) -> Vec<(&'static str, u32)> {
+        self.data(&format!("{bark}_bark"))
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|take| !take.is_empty())
+            .map(|take| match take.split_once(':') {
+                Some((name, weight)) => (name.trim(), weight.trim().parse().unwrap_or(1)),
+                None => (take, 1),
+            })
+            .collect()
+    }
+
+    /// Numeric field, 1 if missing or malformed
+    fn number(self, field: &str) -> u32 {
+        self.parsed(field).unwrap_or(1)
+    }
+
+    fn parsed<T: FromStr>(self, field: &str) -> Option<T> {
+        self.data(field).and_then(|value| value.parse().ok())
+    }
+
+    fn data(self, field: &str) -> Option<&'static str> {
+        parse_key_values(ARCHETYPE_DATA)
+            .get(format!("{}.{}", self.key(), field).as_str())
+            .copied()
+    }
+}
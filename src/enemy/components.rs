@@ -4,8 +4,39 @@ use bevy::prelude::*;
 #[derive(Component)]
 pub struct Enemy;
 
-/// Enemy AI state
+/// Enemy still travelling in from its off-screen spawn point
+///
+/// It can't be hit (not even by the super) and deals no damage, so enemies
+/// can't be killed or hurt the player before they are on screen. It fades in
+/// as it enters the arena; `update_spawn_protection` removes it once the
+/// enemy is `ArenaConfined`.
 #[derive(Component)]
-pub enum EnemyState {
-    Move,
+pub struct SpawnProtected;
+
+/// Enemy with its AI switched off: it doesn't move or deal contact damage
+///
+/// Used by the training dummy while it's set to stand still or block. It can
+/// still be hit, knocked back, and knocked down.
+#[derive(Component)]
+pub struct Passive;
+
+/// Ranged enemy - keeps its distance and throws fireballs at the player
+#[derive(Component)]
+pub struct RangedAttacker {
+    /// Time between throws
+    pub cooldown: Timer,
+    /// Stops approaching once this close to the player (horizontally)
+    pub preferred_distance: f32,
+    /// Only throws when the player is within this horizontal distance
+    pub attack_range: f32,
+}
+
+impl Default for RangedAttacker {
+    fn default() -> Self {
+        Self {
+            cooldown: Timer::from_seconds(2.5, TimerMode::Repeating),
+            preferred_distance: 500.0,
+            attack_range: 900.0,
+        }
+    }
 }
@@ -0,0 +1,180 @@
+use crate::combat::{Grabbed, Stunned, Thrown};
+use crate::enemy::components::{Enemy, RangedAttacker};
+use crate::enemy::state::EnemyState;
+use crate::player::{JumpPhysics, Player};
+use crate::world::depth;
+use bevy::prelude::*;
+use std::f32::consts::PI;
+use std::ops::RangeInclusive;
+
+/// Horizontal distance from the player of an attacking (near) slot, inside contact range
+const NEAR_DISTANCE: f32 = 60.0;
+/// Horizontal distance from the player of a waiting (far) slot
+const FAR_DISTANCE: f32 = 260.0;
+/// Depth gap between ghosts waiting on the same side
+const FAR_DEPTH_SPACING: f32 = 70.0;
+/// How far a circling ghost swings out of the player's lane (wider than `LANE_TOLERANCE`)
+const CIRCLE_DEPTH: f32 = 90.0;
+/// Seconds a circling ghost spends swinging around before it closes in
+const CIRCLE_SECONDS: f32 = 1.5;
+/// Chance a ghost circles first when it is promoted to an attacking slot
+const CIRCLE_CHANCE: f32 = 0.5;
+
+/// How close to the player an approach slot is
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SlotRing {
+    /// Next to the player, close enough to touch
+    Near,
+    /// Waiting at a distance for an attacking slot to open up
+    Far,
+}
+
+/// Where a melee ghost is heading relative to the player
+///
+/// `assign_approach_slots` gives each side of the player one attacking (near)
+/// slot and sends the other ghosts to far slots spread through the depth
+/// lane, so they surround the player instead of clumping on one spot.
+/// `move_enemies` steers toward `target`.
+#[derive(Component)]
+pub struct ApproachSlot {
+    /// -1.0 left of the player, 1.0 right of the player
+    pub side: f32,
+    pub ring: SlotRing,
+    /// Offset from the player's lane, so waiting ghosts don't stack up
+    pub depth_offset: f32,
+    /// Seconds left swinging around out of the player's lane before closing in
+    pub circling: f32,
+}
+
+impl ApproachSlot {
+    /// Slot on the side of the player the ghost starts on
+    pub fn facing(side: f32) -> Self {
+        Self {
+            side,
+            ring: SlotRing::Far,
+            depth_offset: 0.0,
+            circling: 0.0,
+        }
+    }
+
+    /// World position of the slot around a player at `player_x` in lane `player_depth`
+    ///
+    /// Kept inside the stage's depth lane.
+    pub fn target(&self, player_x: f32, player_depth: f32, lane: &RangeInclusive<f32>) -> Vec2 {
+        if self.circling > 0.0 {
+            // Swing out of the player's lane (toward whichever edge has room) and back in
+            // at a distance, coming around in an arc
+            let progress = 1.0 - self.circling / CIRCLE_SECONDS;
+            let toward = if player_depth + CIRCLE_DEPTH <= *lane.end() {
+                1.0
+            } else {
+                -1.0
+            };
+            let swing = (progress * PI).sin() * CIRCLE_DEPTH * toward;
+            return Vec2::new(
+                player_x + self.side * FAR_DISTANCE,
+                (player_depth + swing).clamp(*lane.start(), *lane.end()),
+            );
+        }
+
+        let distance = match self.ring {
+            SlotRing::Near => NEAR_DISTANCE,
+            SlotRing::Far => FAR_DISTANCE,
+        };
+        Vec2::new(
+            player_x + self.side * distance,
+            (player_depth + self.depth_offset).clamp(*lane.start(), *lane.end()),
+        )
+    }
+}
+
+/// Hand out approach slots: one attacker per side, the rest waiting around the player
+///
+/// Ghosts keep their side, except that the farthest ghost on a side with
+/// two or more extra ghosts circles around to flank from the other side. The
+/// nearest ghost on each side takes the attacking slot, sometimes circling
+/// first. Stunned, knocked-down, and grabbed or thrown ghosts don't hold slots.
+#[allow(clippy::type_complexity)]
+pub fn assign_approach_slots(
+    time: Res<Time>,
+    player_query: Query<(&Transform, &JumpPhysics), With<Player>>,
+    mut enemy_query: Query<
+        (&Transform, &EnemyState, &mut ApproachSlot),
+        (
+            With<Enemy>,
+            Without<RangedAttacker>,
+            Without<Stunned>,
+            Without<Player>,
+            Without<Grabbed>,
+            Without<Thrown>,
+        ),
+    >,
+) {
+    let Ok((player_transform, jump_physics)) = player_query.single() else {
+        return;
+    };
+    let player_pos = Vec2::new(
+        player_transform.translation.x,
+        depth(player_transform, Some(jump_physics)),
+    );
+
+    let mut ghosts: Vec<_> = enemy_query
+        .iter_mut()
+        .filter(|(_, state, _)| state.is_active())
+        .map(|(transform, _, slot)| {
+            let distance = transform.translation.truncate().distance(player_pos);
+            (distance, slot)
+        })
+        .collect();
+    ghosts.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    for (_, slot) in ghosts.iter_mut() {
+        slot.circling = (slot.circling - time.delta_secs()).max(0.0);
+    }
+
+    // Flank: the farthest ghost on a crowded side circles around to the other one
+    let count = |ghosts: &[(f32, Mut<ApproachSlot>)], side: f32| {
+        ghosts.iter().filter(|(_, slot)| slot.side == side).count()
+    };
+    let (left, right) = (count(&ghosts, -1.0), count(&ghosts, 1.0));
+    if left.abs_diff(right) >= 2 {
+        let crowded = if left > right { -1.0 } else { 1.0 };
+        if let Some((_, slot)) = ghosts
+            .iter_mut()
+            .rev()
+            .find(|(_, slot)| slot.side == crowded)
+        {
+            slot.side = -crowded;
+            slot.circling = CIRCLE_SECONDS;
+        }
+    }
+
+    for side in [-1.0, 1.0] {
+        let side_ghosts = ghosts.iter_mut().filter(|(_, slot)| slot.side == side);
+        for (waiting, (_, slot)) in side_ghosts.enumerate() {
+            let ring = if waiting == 0 {
+                SlotRing::Near
+            } else {
+                SlotRing::Far
+            };
+
+            // Newly promoted attackers sometimes circle in instead of walking straight up
+            if ring == SlotRing::Near
+                && slot.ring == SlotRing::Far
+                && rand::random::<f32>() < CIRCLE_CHANCE
+            {
+                slot.circling = CIRCLE_SECONDS;
+            }
+
+            slot.ring = ring;
+            slot.depth_offset = match ring {
+                SlotRing::Near => 0.0,
+                // Fan waiting ghosts out above and below the player's lane
+                SlotRing::Far => {
+                    let step = waiting.div_ceil(2) as f32 * FAR_DEPTH_SPACING;
+                    if waiting % 2 == 1 { step } else { -step }
+                }
+            };
+        }
+    }
+}
@@ -1,4 +1,15 @@
+pub mod archetype;
 pub mod components;
+pub mod coordination;
+pub mod plugin;
+pub mod spawning;
+pub mod state;
+pub mod systems;
+pub mod weakened;
 
 // Re-export commonly used items
+pub use archetype::*;
 pub use components::*;
+pub use plugin::*;
+pub use spawning::*;
+pub use state::*;
@@ -0,0 +1,45 @@
+use crate::enemy::coordination::assign_approach_slots;
+use crate::enemy::systems::{
+    move_enemies, ranged_enemy_attacks, spawn_enemy, update_enemy_states, update_spawn_protection,
+};
+use crate::enemy::weakened::{drip_weakened_enemies, watch_enemy_health};
+use crate::game::{AppState, GameSet, round_fighting};
+use crate::levels::stage_waves;
+use crate::settings::wave_mode;
+use crate::world::confine_arrived_enemies;
+use bevy::prelude::*;
+
+/// Enemy spawning (arcade and endless games, stopped for arcade bosses) and AI
+pub struct EnemyPlugin;
+
+impl Plugin for EnemyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                update_spawn_protection
+                    .after(confine_arrived_enemies)
+                    .in_set(GameSet::Arena),
+                ranged_enemy_attacks.in_set(GameSet::Attacks),
+                (watch_enemy_health, update_enemy_states)
+                    .chain()
+                    .in_set(GameSet::DamageReactions),
+                drip_weakened_enemies.in_set(GameSet::Effects),
+                spawn_enemy
+                    .run_if(
+                        in_state(AppState::Playing)
+                            .and(round_fighting)
+                            .and(wave_mode)
+                            .and(stage_waves),
+                    )
+                    .in_set(GameSet::Spawning),
+            ),
+        )
+        .add_systems(
+            FixedUpdate,
+            (assign_approach_slots, move_enemies)
+                .chain()
+                .in_set(GameSet::MovementIntents),
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use crate::enemy::archetype::EnemyArchetype;
+use bevy::prelude::*;
+use rand::Rng;
+use std::collections::VecDeque;
+
+/// Horizontal distance of the spawn points past the arena's edges
+pub const SPAWN_MARGIN: f32 = 800.0;
+/// Max enemies in a row allowed on the same side before the other side is forced
+const MAX_SAME_SIDE_STREAK: usize = 2;
+/// A player this close to an arena edge counts as cornered against it
+const CORNER_MARGIN: f32 = 200.0;
+/// Length of each match section; the threat budget grows at every new section
+const THREAT_SECTION_SECONDS: f32 = 30.0;
+/// Extra threat allowed per completed section
+const THREAT_GROWTH_PER_SECTION: u32 = 1;
+/// Endless mode: the spawn interval is multiplied by this every section
+const ENDLESS_INTERVAL_FACTOR: f32 = 0.85;
+/// Endless mode: the spawn interval never drops below this
+const ENDLESS_MIN_INTERVAL: f32 = 0.4;
+/// Endless mode: extra enemy health per section, as a fraction of the base health
+const ENDLESS_HEALTH_GROWTH: f32 = 0.25;
+/// Seconds of standing still before the pressure mutator calls in a hurry-up ghost
+const HURRY_UP_SECONDS: f32 = 6.0;
+/// Movement within this distance still counts as standing still
+const HURRY_UP_STILL_DISTANCE: f32 = 40.0;
+
+/// Index of the match section at `elapsed_secs` into the match (0 for the first)
+pub fn match_section(elapsed_secs: f32) -> u32 {
+    (elapsed_secs / THREAT_SECTION_SECONDS) as u32
+}
+
+/// Threat budget for the current match section
+///
+/// Starts at the difficulty's base budget and grows every
+/// `THREAT_SECTION_SECONDS`, so later sections field bigger mixes.
+pub fn section_threat_budget(base_budget: u32, elapsed_secs: f32) -> u32 {
+    base_budget + match_section(elapsed_secs) * THREAT_GROWTH_PER_SECTION
+}
+
+/// Spawn interval `elapsed_secs` into an endless run
+///
+/// Shrinks by `ENDLESS_INTERVAL_FACTOR` every section, down to `ENDLESS_MIN_INTERVAL`.
+pub fn endless_spawn_interval(base_interval: f32, elapsed_secs: f32) -> f32 {
+    let sections = match_section(elapsed_secs) as i32;
+    (base_interval * ENDLESS_INTERVAL_FACTOR.powi(sections)).max(ENDLESS_MIN_INTERVAL)
+}
+
+/// Enemy health `elapsed_secs` into an endless run, growing every section without limit
+pub fn endless_enemy_health(base_health: i32, elapsed_secs: f32) -> i32 {
+    let growth = 1.0 + ENDLESS_HEALTH_GROWTH * match_section(elapsed_secs) as f32;
+    (base_health as f32 * growth).round() as i32
+}
+
+/// Side of the stage an enemy enters from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpawnSide {
+    Left,
+    Right,
+}
+
+impl SpawnSide {
+    pub fn opposite(self) -> Self {
+        match self {
+            SpawnSide::Left => SpawnSide::Right,
+            SpawnSide::Right => SpawnSide::Left,
+        }
+    }
+
+    /// X coordinate of this side's spawn point, off-screen past the level's `arena`
+    pub fn spawn_x(self, arena: Rect) -> f32 {
+        match self {
+            SpawnSide::Left => arena.min.x - SPAWN_MARGIN,
+            SpawnSide::Right => arena.max.x + SPAWN_MARGIN,
+        }
+    }
+}
+
+/// Picks which side each enemy spawns on, and which archetype it is
+///
+/// Sides are random, with two fairness rules: never spawn on the edge the
+/// player is cornered against (the enemy would appear right behind them), and
+/// never spawn more than `MAX_SAME_SIDE_STREAK` enemies in a row on one side.
+/// Archetypes are picked by weight (the stage's spawn table) from those whose
+/// threat cost still fits in the section's budget, so waves get varied but
+/// balanced mixes. Under the
+/// pressure mutator it also watches for a player standing still (see `hurry_up_due`).
+#[derive(Resource, Default)]
+pub struct SpawnDirector {
+    /// Most recent spawn sides, newest last
+    recent_sides: VecDeque<SpawnSide>,
+    /// Where the player has been standing, and since when (match seconds)
+    still_since: Option<(Vec2, f32)>,
+}
+
+impl SpawnDirector {
+    /// Choose a spawn side for the next enemy and record it in the history
+    ///
+    /// `arena` is the playfield the player is confined to.
+    pub fn choose_side(&mut self, player_x: f32, arena: Rect) -> SpawnSide {
+        let side = if player_x < arena.min.x + CORNER_MARGIN {
+            SpawnSide::Right
+        } else if player_x > arena.max.x - CORNER_MARGIN {
+            SpawnSide::Left
+        } else if let Some(streak_side) = self.streak_side() {
+            streak_side.opposite()
+        } else if rand::random::<bool>() {
+            SpawnSide::Left
+        } else {
+            SpawnSide::Right
+        };
+
+        self.recent_sides.push_back(side);
+        if self.recent_sides.len() > MAX_SAME_SIDE_STREAK {
+            self.recent_sides.pop_front();
+        }
+        side
+    }
+
+    /// Choose the archetype for the next enemy, or None if nothing fits the budget
+    ///
+    /// `budget_left` is the section's threat budget minus the threat already
+    /// alive, and `weight` gives each archetype's relative chance.
+    pub fn choose_archetype(
+        &self,
+        budget_left: u32,
+        weight: impl Fn(EnemyArchetype) -> u32,
+    ) -> Option<EnemyArchetype> {
+        let affordable: Vec<(EnemyArchetype, u32)> = EnemyArchetype::ALL
+            .into_iter()
+            .filter(|archetype| archetype.threat_cost() <= budget_left)
+            .map(|archetype| (archetype, weight(archetype)))
+            .collect();
+        let total_weight: u32 = affordable.iter().map(|(_, weight)| weight).sum();
+        if total_weight == 0 {
+            return None;
+        }
+
+        let mut roll = rand::thread_rng().gen_range(0..total_weight);
+        for (archetype, weight) in affordable {
+            if roll < weight {
+                return Some(archetype);
+            }
+            roll -= weight;
+        }
+        None
+    }
+
+    /// Has the player stood still for `HURRY_UP_SECONDS`?
+    ///
+    /// Called every spawn check with the player's position and the match time.
+    /// Reporting a hurry-up restarts the count, so a player who keeps standing
+    /// still gets another one `HURRY_UP_SECONDS` later.
+    pub fn hurry_up_due(&mut self, player_pos: Vec2, now: f32) -> bool {
+        match self.still_since {
+            Some((anchor, since)) if anchor.distance(player_pos) < HURRY_UP_STILL_DISTANCE => {
+                let due = now - since >= HURRY_UP_SECONDS;
+                if due {
+                    self.still_since = Some((player_pos, now));
+                }
+                due
+            }
+            _ => {
+                self.still_since = Some((player_pos, now));
+                false
+            }
+        }
+    }
+
+    /// The side of the last `MAX_SAME_SIDE_STREAK` spawns, if they were all the same
+    fn streak_side(&self) -> Option<SpawnSide> {
+        let first = *self.recent_sides.front()?;
+        let full = self.recent_sides.len() == MAX_SAME_SIDE_STREAK;
+        (full && self.recent_sides.iter().all(|&side| side == first)).then_some(first)
+    }
+}
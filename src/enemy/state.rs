@@ -0,0 +1,179 @@
+use bevy::prelude::*;
+
+/// Seconds a knocked-down enemy stays down (invulnerable the whole time)
+pub const KNOCKDOWN_SECONDS: f32 = 0.8;
+/// Seconds spent toppling over at the start of a knockdown
+const FALL_SECONDS: f32 = 0.2;
+/// Seconds the get-up animation takes before the enemy resumes its AI
+const GET_UP_SECONDS: f32 = 0.5;
+/// Seconds an enemy winds up before its swipe comes out
+const WINDUP_SECONDS: f32 = 0.4;
+/// Seconds an enemy's swipe can hit
+const SWIPE_SECONDS: f32 = 0.15;
+/// Seconds an enemy is stuck after a swipe
+const RECOVERY_SECONDS: f32 = 0.6;
+/// Reach of a swipe in front of the enemy
+pub const SWIPE_RANGE: f32 = 170.0;
+/// Damage of a swipe that connects
+pub const SWIPE_DAMAGE: i32 = 2;
+
+const WINDUP_TINT: Color = Color::srgb(1.0, 0.85, 0.3);
+const SWIPE_TINT: Color = Color::srgb(1.0, 0.35, 0.35);
+const RECOVERY_TINT: Color = Color::srgb(0.45, 0.65, 1.0);
+
+/// Seconds a defeated enemy takes to dissipate before it's despawned
+const DEATH_SECONDS: f32 = 0.6;
+/// Seconds per float-cycle frame while limping along `Weakened`
+const LIMP_FRAME_SECONDS: f32 = 0.16;
+
+/// Enemy AI state
+#[derive(Component)]
+pub enum EnemyState {
+    /// Approaching (or, for ranged ghosts, keeping its distance from) the player
+    Move,
+    /// Knocked over by a combo finisher or aerial attack and lying on the ground
+    Knockdown(Timer),
+    /// Rising after a knockdown
+    GetUp(Timer),
+    /// Winding up a swipe (tinted yellow); a stun stuffs it
+    Windup(Timer),
+    /// Swiping (tinted red): `detect_enemy_swipes` hits a target in reach once
+    Swipe { timer: Timer, connected: bool },
+    /// Stuck after a swipe (tinted blue); `whiffed` if the swipe hit no one
+    Recovery { timer: Timer, whiffed: bool },
+    /// Defeated and dissipating; it has no hurtbox and is despawned when the timer ends
+    Dying {
+        timer: Timer,
+        /// Sprite scale it shrinks away from
+        scale: f32,
+    },
+}
+
+/// Sprite sheet frames an enemy state plays
+pub struct EnemyAnimation {
+    pub first: usize,
+    pub last: usize,
+    pub frame_seconds: f32,
+}
+
+impl EnemyState {
+    pub fn knockdown() -> Self {
+        EnemyState::Knockdown(Timer::from_seconds(KNOCKDOWN_SECONDS, TimerMode::Once))
+    }
+
+    pub fn get_up() -> Self {
+        EnemyState::GetUp(Timer::from_seconds(GET_UP_SECONDS, TimerMode::Once))
+    }
+
+    /// Start a swipe: wind up, swipe, recover, then back to `Move`
+    pub fn windup() -> Self {
+        EnemyState::Windup(Timer::from_seconds(WINDUP_SECONDS, TimerMode::Once))
+    }
+
+    pub fn swipe() -> Self {
+        EnemyState::Swipe {
+            timer: Timer::from_seconds(SWIPE_SECONDS, TimerMode::Once),
+            connected: false,
+        }
+    }
+
+    pub fn recovery(whiffed: bool) -> Self {
+        EnemyState::Recovery {
+            timer: Timer::from_seconds(RECOVERY_SECONDS, TimerMode::Once),
+            whiffed,
+        }
+    }
+
+    /// Whether the enemy is winding up or swiping, which a stun cancels
+    pub fn is_attacking(&self) -> bool {
+        matches!(self, EnemyState::Windup(_) | EnemyState::Swipe { .. })
+    }
+
+    /// Telegraph color of an attack phase (None outside a swipe)
+    pub fn tint(&self) -> Option<Color> {
+        match self {
+            EnemyState::Windup(_) => Some(WINDUP_TINT),
+            EnemyState::Swipe { .. } => Some(SWIPE_TINT),
+            EnemyState::Recovery { .. } => Some(RECOVERY_TINT),
+            _ => None,
+        }
+    }
+
+    pub fn dying(scale: f32) -> Self {
+        EnemyState::Dying {
+            timer: Timer::from_seconds(DEATH_SECONDS, TimerMode::Once),
+            scale,
+        }
+    }
+
+    /// Whether the enemy has been defeated and is on its way out
+    pub fn is_dying(&self) -> bool {
+        matches!(self, EnemyState::Dying { .. })
+    }
+
+    /// Whether the enemy's AI runs (moving, attacking, and contact damage)
+    pub fn is_active(&self) -> bool {
+        matches!(self, EnemyState::Move)
+    }
+
+    /// Frames of the ghost sheet this state plays, slowed to a limp when `weakened`
+    pub fn animation(&self, weakened: bool) -> EnemyAnimation {
+        match self {
+            EnemyState::Move => EnemyAnimation {
+                first: 1,
+                last: 11,
+                frame_seconds: if weakened { LIMP_FRAME_SECONDS } else { 0.1 },
+            },
+            // Limp on a single frame while down
+            EnemyState::Knockdown(_) => EnemyAnimation {
+                first: 0,
+                last: 0,
+                frame_seconds: 0.1,
+            },
+            // Flutter quickly through the float cycle while rising
+            EnemyState::GetUp(_) => EnemyAnimation {
+                first: 1,
+                last: 11,
+                frame_seconds: 0.05,
+            },
+            // Hold still while winding up and recovering; the swipe is a fast flutter
+            EnemyState::Windup(_) | EnemyState::Recovery { .. } => EnemyAnimation {
+                first: 1,
+                last: 1,
+                frame_seconds: 0.1,
+            },
+            EnemyState::Swipe { .. } => EnemyAnimation {
+                first: 1,
+                last: 11,
+                frame_seconds: 0.015,
+            },
+            // The ghost sheet has no death art: spin once through the float cycle as it fades away
+            EnemyState::Dying { .. } => EnemyAnimation {
+                first: 1,
+                last: 11,
+                frame_seconds: DEATH_SECONDS / 11.0,
+            },
+        }
+    }
+
+    /// How far the enemy is tipped over, from 0.0 (upright) to 1.0 (lying flat)
+    pub fn tilt(&self) -> f32 {
+        match self {
+            EnemyState::Move => 0.0,
+            EnemyState::Knockdown(timer) => (timer.elapsed_secs() / FALL_SECONDS).min(1.0),
+            EnemyState::GetUp(timer) => 1.0 - timer.fraction(),
+            EnemyState::Windup(_)
+            | EnemyState::Swipe { .. }
+            | EnemyState::Recovery { .. }
+            | EnemyState::Dying { .. } => 0.0,
+        }
+    }
+
+    /// Sprite scale a dying enemy has shrunk to (None while it's alive)
+    pub fn dying_scale(&self) -> Option<f32> {
+        match self {
+            EnemyState::Dying { timer, scale } => Some(scale * (1.0 - timer.fraction())),
+            _ => None,
+        }
+    }
+}
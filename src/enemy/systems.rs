@@ -0,0 +1,406 @@
+use crate::combat::{Grabbed, HurtBox, Stat, StatModifiers, Stunned, Team, Thrown};
+use crate::common::{AnimationIndices, AnimationTimer, Direction};
+use crate::enemy::archetype::EnemyArchetype;
+use crate::enemy::components::{Enemy, Passive, RangedAttacker, SpawnProtected};
+use crate::enemy::coordination::ApproachSlot;
+use crate::enemy::spawning::{
+    SpawnDirector, endless_enemy_health, endless_spawn_interval, section_threat_budget,
+};
+use crate::enemy::state::EnemyState;
+use crate::enemy::weakened::Weakened;
+use crate::game::GameState;
+use crate::levels::{Levels, StageProgress};
+use crate::player::{JumpPhysics, Player};
+use crate::prefab::PrefabSpawner;
+use crate::projectile::fireball;
+use crate::settings::{Difficulty, GameMode, MatchSettings, Mutators};
+use crate::ui::{TextStyles, score_popup};
+use crate::world::{
+    ArenaBounds, ArenaConfined, CameraLock, MoveIntent, NavigationField, StageGeometry, depth,
+};
+use bevy::prelude::*;
+use rand::Rng;
+use std::f32::consts::FRAC_PI_2;
+
+/// Opacity of a spawn-protected enemy that hasn't entered the arena at all
+const SPAWN_FADE_MIN_ALPHA: f32 = 0.3;
+/// Distance within which ghosts steer apart so they don't overlap
+const SEPARATION_DISTANCE: f32 = 100.0;
+/// Speed of the push between two ghosts on top of each other
+const SEPARATION_SPEED: f32 = 80.0;
+
+/// Spawn the next enemy once the spawn interval has passed and the threat budget allows it
+///
+/// Archetypes come from the current stage's spawn table. Endless runs shorten
+/// the interval and toughen enemies every threat section.
+/// Nothing spawns while `MatchSettings::max_enemies` are alive, whatever the
+/// budget (only the pressure mutator's hurry-ups go over it). In a scrolling
+/// stage's combat zone, spawns stop once the zone's enemies are all out, and
+/// come in past the edges of the locked zone instead of the arena.
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_enemy(
+    mut commands: Commands,
+    mut prefabs: PrefabSpawner,
+    mut game_state: ResMut<GameState>,
+    mut spawn_director: ResMut<SpawnDirector>,
+    arena: Res<ArenaBounds>,
+    geometry: Res<StageGeometry>,
+    lock: Res<CameraLock>,
+    difficulty: Res<Difficulty>,
+    mode: Res<GameMode>,
+    match_settings: Res<MatchSettings>,
+    mutators: Res<Mutators>,
+    levels: Res<Levels>,
+    mut progress: ResMut<StageProgress>,
+    styles: Res<TextStyles>,
+    player_query: Query<&Transform, With<Player>>,
+    enemy_query: Query<(&EnemyArchetype, &EnemyState)>,
+) {
+    if progress.zone_spawns_left == Some(0) {
+        return;
+    }
+    let now = game_state.timer.elapsed_secs();
+    let player_pos = player_query
+        .single()
+        .map_or(Vec2::ZERO, |transform| transform.translation.truncate());
+
+    // Endless runs escalate with every section survived
+    let (spawn_interval, enemy_health) = match *mode {
+        GameMode::Endless => (
+            endless_spawn_interval(game_state.spawn_interval, now),
+            endless_enemy_health(difficulty.enemy_health(), now),
+        ),
+        _ => (game_state.spawn_interval, difficulty.enemy_health()),
+    };
+
+    // Pressure mutator: standing still too long calls in an extra ghost, ignoring
+    // the spawn interval, enemy cap, and threat budget
+    let hurry_up = mutators.pressure && spawn_director.hurry_up_due(player_pos, now);
+    let live_enemies = enemy_query
+        .iter()
+        .filter(|(_, state)| !state.is_dying())
+        .count();
+    if !hurry_up
+        && (now - game_state.last_spawn_time < spawn_interval
+            || live_enemies >= match_settings.max_enemies as usize)
+    {
+        return;
+    }
+
+    // Fill the section's threat budget with a weighted mix of archetypes
+    let budget_left = if hurry_up {
+        u32::MAX
+    } else {
+        let budget = section_threat_budget(game_state.threat_budget, now);
+        let live_threat: u32 = enemy_query
+            .iter()
+            .filter(|(_, state)| !state.is_dying())
+            .map(|(archetype, _)| archetype.threat_cost())
+            .sum();
+        budget.saturating_sub(live_threat)
+    };
+    let stage = levels.stage(progress.index);
+    let Some(archetype) =
+        spawn_director.choose_archetype(budget_left, |archetype| stage.spawn_weight(archetype))
+    else {
+        return;
+    };
+    if hurry_up {
+        commands.spawn(score_popup(&styles, "HURRY UP!".to_string(), player_pos));
+    }
+
+    // Pick a fair spawn side (no long same-side streaks, never behind a cornered player)
+    let side = spawn_director.choose_side(player_pos.x, arena.rect);
+    let spawn_x = side.spawn_x(lock.area.unwrap_or(geometry.arena));
+    let spawn_y = rand::thread_rng().gen_range(geometry.depth_lane.clone());
+    prefabs.spawn_enemy(archetype, Vec2::new(spawn_x, spawn_y), enemy_health);
+    game_state.n_enemies += 1;
+    game_state.last_spawn_time = now;
+    if let Some(left) = &mut progress.zone_spawns_left {
+        *left -= 1;
+    }
+}
+
+/// Steer each ghost toward its approach slot (or, for ranged ghosts, throwing range)
+///
+/// Ghosts walk around obstacles and hazards by following the `NavigationField`
+/// whenever the straight line to their target is blocked. They also push away
+/// from each other within `SEPARATION_DISTANCE`, so a crowd spreads out
+/// instead of stacking into one sprite.
+#[allow(clippy::type_complexity)]
+pub fn move_enemies(
+    geometry: Res<StageGeometry>,
+    navigation: Res<NavigationField>,
+    player_query: Query<(&Transform, &JumpPhysics), With<Player>>,
+    mut enemy_query: Query<
+        (
+            Entity,
+            &mut Direction,
+            &Transform,
+            &mut MoveIntent,
+            &mut Sprite,
+            &EnemyState,
+            Option<&ApproachSlot>,
+            Option<&RangedAttacker>,
+            Option<&StatModifiers>,
+        ),
+        (
+            With<Enemy>,
+            Without<Stunned>,
+            Without<Player>,
+            Without<Grabbed>,
+            Without<Thrown>,
+            Without<Passive>,
+        ),
+    >,
+) {
+    let Ok((player_transform, jump_physics)) = player_query.single() else {
+        return;
+    };
+    let player_x = player_transform.translation.x;
+    let player_depth = depth(player_transform, Some(jump_physics));
+
+    let positions: Vec<(Entity, Vec2)> = enemy_query
+        .iter()
+        .map(|(entity, _, transform, ..)| (entity, transform.translation.truncate()))
+        .collect();
+
+    for (entity, mut dir, transform, mut intent, mut sprite, state, slot, ranged, modifiers) in
+        enemy_query.iter_mut()
+    {
+        if !state.is_active() {
+            continue;
+        }
+        let position = transform.translation.truncate();
+
+        // Face the player, with hysteresis (avoid rapid switching while passing by)
+        let x_diff = player_x - position.x;
+        if x_diff > 150.0 {
+            *dir = Direction::Right;
+        } else if x_diff < -150.0 {
+            *dir = Direction::Left;
+        }
+        // Keep current direction if within threshold
+
+        let target = match (ranged, slot) {
+            // Ranged ghosts stop approaching once close enough to throw, still facing the player
+            (Some(ranged), _) if x_diff.abs() < ranged.preferred_distance => {
+                sprite.flip_x = x_diff < 0.0;
+                Vec2::new(position.x, player_depth)
+            }
+            (_, Some(slot)) => slot.target(player_x, player_depth, &geometry.depth_lane),
+            _ => Vec2::new(player_x, player_depth),
+        };
+
+        // Move toward the target, horizontally and along the depth axis
+        let speed = modifiers.map_or(1.0, |modifiers| modifiers.multiplier(Stat::Speed));
+        let to_target = navigation.waypoint(position, target) - position;
+        if to_target.x > 10.0 {
+            intent.velocity.x += 150. * speed;
+        } else if to_target.x < -10.0 {
+            intent.velocity.x -= 150. * speed;
+        }
+        if to_target.y > 10.0 {
+            intent.velocity.y += 50. * speed;
+        } else if to_target.y < -10.0 {
+            intent.velocity.y -= 50. * speed;
+        }
+
+        // Keep some space from the other ghosts, pushing harder the closer they are
+        let separation: Vec2 = positions
+            .iter()
+            .filter(|(other, _)| *other != entity)
+            .map(|(_, other_position)| position - *other_position)
+            .filter(|away| away.length() < SEPARATION_DISTANCE)
+            .map(|away| away.normalize_or_zero() * (1.0 - away.length() / SEPARATION_DISTANCE))
+            .sum();
+        intent.velocity += separation * SEPARATION_SPEED;
+
+        match *dir {
+            Direction::Right => sprite.flip_x = false,
+            Direction::Left => sprite.flip_x = true,
+            Direction::None => { /* Do nothing */ }
+        }
+    }
+}
+
+/// Ranged ghosts throw a fireball whenever their cooldown is up and the player is in range
+#[allow(clippy::type_complexity)]
+pub fn ranged_enemy_attacks(
+    mut commands: Commands,
+    time: Res<Time>,
+    player_query: Query<&Transform, With<Player>>,
+    mut enemy_query: Query<
+        (Entity, &Transform, &EnemyState, &mut RangedAttacker, &Team),
+        (
+            With<Enemy>,
+            Without<Stunned>,
+            Without<SpawnProtected>,
+            Without<Grabbed>,
+            Without<Thrown>,
+        ),
+    >,
+) {
+    let Ok(player_transform) = player_query.single() else {
+        return;
+    };
+
+    for (entity, transform, state, mut ranged, &team) in enemy_query.iter_mut() {
+        ranged.cooldown.tick(time.delta());
+
+        let x_diff = player_transform.translation.x - transform.translation.x;
+        if ranged.cooldown.just_finished()
+            && state.is_active()
+            && x_diff.abs() <= ranged.attack_range
+        {
+            commands.spawn(fireball(
+                entity,
+                team,
+                transform.translation.truncate(),
+                x_diff < 0.0,
+                1,
+            ));
+        }
+    }
+}
+
+/// Fade spawn-protected enemies in as they enter the arena, ending protection on arrival
+///
+/// Runs after `confine_arrived_enemies`, so protection ends the frame the
+/// enemy is fully inside the arena.
+#[allow(clippy::type_complexity)]
+pub fn update_spawn_protection(
+    mut commands: Commands,
+    arena: Res<ArenaBounds>,
+    mut enemy_query: Query<
+        (
+            Entity,
+            &Transform,
+            &HurtBox,
+            &mut Sprite,
+            Has<ArenaConfined>,
+        ),
+        With<SpawnProtected>,
+    >,
+) {
+    for (entity, transform, hurtbox, mut sprite, confined) in enemy_query.iter_mut() {
+        if confined {
+            commands.entity(entity).remove::<SpawnProtected>();
+            sprite.color = Color::WHITE;
+            continue;
+        }
+
+        let body = Rect::from_center_size(transform.translation.truncate(), hurtbox.size);
+        let inside = body.intersect(arena.rect).width().max(0.0) / body.width();
+        let alpha = SPAWN_FADE_MIN_ALPHA + (1.0 - SPAWN_FADE_MIN_ALPHA) * inside;
+        sprite.color = Color::WHITE.with_alpha(alpha);
+    }
+}
+
+/// Advance knockdowns (fall over, lie still, get up) and swipes (wind up,
+/// swipe, recover), then hand back to the AI
+///
+/// Switches each enemy's animation when its state changes, tints it through
+/// a swipe, and tips the sprite over away from the player while it is down.
+/// `handle_damage_events` starts the knockdown (with `Invulnerable` for as
+/// long as it lasts); a stun during the wind-up or swipe cancels it.
+/// `Weakened` enemies float along on the slower limping cycle. Dying enemies
+/// (set by `handle_enemy_defeat`) shrink away and are despawned when done.
+#[allow(clippy::type_complexity)]
+pub fn update_enemy_states(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut enemy_query: Query<
+        (
+            Entity,
+            &mut EnemyState,
+            &mut AnimationIndices,
+            &mut AnimationTimer,
+            &mut Sprite,
+            &mut Transform,
+            Has<Stunned>,
+            Has<Weakened>,
+        ),
+        With<Enemy>,
+    >,
+) {
+    for (
+        entity,
+        mut state,
+        mut indices,
+        mut animation_timer,
+        mut sprite,
+        mut transform,
+        stunned,
+        weakened,
+    ) in enemy_query.iter_mut()
+    {
+        // A stun stuffs a swipe before it comes out (or while it's out)
+        let stuffed = stunned && state.is_attacking();
+        let tinted = state.tint().is_some();
+        let next = match &mut *state {
+            _ if stuffed => Some(EnemyState::Move),
+            EnemyState::Move => None,
+            EnemyState::Knockdown(timer) => timer
+                .tick(time.delta())
+                .is_finished()
+                .then(EnemyState::get_up),
+            EnemyState::GetUp(timer) => timer
+                .tick(time.delta())
+                .is_finished()
+                .then_some(EnemyState::Move),
+            EnemyState::Windup(timer) => timer
+                .tick(time.delta())
+                .is_finished()
+                .then(EnemyState::swipe),
+            EnemyState::Swipe { timer, connected } => timer
+                .tick(time.delta())
+                .is_finished()
+                .then(|| EnemyState::recovery(!*connected)),
+            EnemyState::Recovery { timer, .. } => timer
+                .tick(time.delta())
+                .is_finished()
+                .then_some(EnemyState::Move),
+            EnemyState::Dying { timer, .. } => {
+                if timer.tick(time.delta()).is_finished() {
+                    commands.entity(entity).despawn();
+                    continue;
+                }
+                None
+            }
+        };
+        if let Some(next) = next {
+            *state = next;
+        }
+
+        // Play the new state's frames (a knockdown or death is inserted as a new component)
+        let animation = state.animation(weakened);
+        if indices.first != animation.first
+            || indices.last != animation.last
+            || animation_timer.duration().as_secs_f32() != animation.frame_seconds
+        {
+            *indices = AnimationIndices {
+                first: animation.first,
+                last: animation.last,
+            };
+            animation_timer.0 = Timer::from_seconds(animation.frame_seconds, TimerMode::Repeating);
+            if let Some(atlas) = &mut sprite.texture_atlas {
+                atlas.index = animation.first;
+            }
+        }
+
+        // Telegraph swipes (hit flashes still draw over the tint), then back to normal
+        match state.tint() {
+            Some(tint) => sprite.color = tint,
+            None if tinted => sprite.color = Color::WHITE,
+            None => {}
+        }
+
+        // Fall backwards, away from the player it faces
+        let away = if sprite.flip_x { -1.0 } else { 1.0 };
+        transform.rotation = Quat::from_rotation_z(away * state.tilt() * FRAC_PI_2);
+        if let Some(scale) = state.dying_scale() {
+            transform.scale = Vec3::splat(scale);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use crate::combat::{Health, Stat, StatModifier, StatModifiers};
+use crate::enemy::components::Enemy;
+use crate::enemy::state::EnemyState;
+use crate::particle::{ParticleBurst, ParticleKind};
+use bevy::prelude::*;
+use rand::Rng;
+use std::ops::Range;
+
+/// Health fraction below which an enemy is `Weakened`
+pub const WEAKENED_HEALTH_FRACTION: f32 = 0.3;
+/// Speed multiplier of a weakened enemy's limp
+const LIMP_SPEED: f32 = 0.6;
+/// `StatModifier` source of the limp
+const LIMP_SOURCE: &str = "weakened";
+/// Seconds between drips, picked at random
+const DRIP_INTERVAL: Range<f32> = 0.25..0.6;
+/// Color of the cracks drawn over a weakened ghost
+const CRACK_COLOR: Color = Color::srgba(0.08, 0.08, 0.12, 0.8);
+/// Cracks over the ghost sprite, as (center, length, angle) in the ghost's unscaled space
+const CRACKS: [(Vec2, f32, f32); 4] = [
+    (Vec2::new(-14.0, 12.0), 34.0, 0.9),
+    (Vec2::new(6.0, 20.0), 22.0, -0.4),
+    (Vec2::new(10.0, -6.0), 28.0, -0.7),
+    (Vec2::new(-4.0, -20.0), 18.0, 0.3),
+];
+
+/// Enemy below `WEAKENED_HEALTH_FRACTION` of its health: cracked, limping, and dripping
+///
+/// `watch_enemy_health` adds and removes it as health crosses the threshold,
+/// along with the crack overlay (a child of the enemy, so it follows its
+/// scale and knockdown tilt) and the limp: a lasting Speed `StatModifier`
+/// plus the slower float cycle `EnemyState::animation` plays while weakened.
+#[derive(Component)]
+pub struct Weakened {
+    /// The crack overlay drawn over the enemy
+    pub cracks: Entity,
+    /// Time until the next drip
+    pub drip: Timer,
+}
+
+/// Health-threshold watcher: weaken enemies that drop below
+/// `WEAKENED_HEALTH_FRACTION` of their health, and restore ones healed back above it
+#[allow(clippy::type_complexity)]
+pub fn watch_enemy_health(
+    mut commands: Commands,
+    mut enemy_query: Query<
+        (
+            Entity,
+            &Health,
+            &EnemyState,
+            Option<&Weakened>,
+            Option<&mut StatModifiers>,
+        ),
+        (With<Enemy>, Changed<Health>),
+    >,
+) {
+    for (entity, health, state, weakened, modifiers) in enemy_query.iter_mut() {
+        // Defeated enemies dissipate as they are (see `EnemyState::Dying`); don't
+        // weaken or restore them on the way out
+        if state.is_dying() || health.current <= 0 {
+            continue;
+        }
+        let low = (health.current as f32) < health.max as f32 * WEAKENED_HEALTH_FRACTION;
+
+        match (low, weakened) {
+            (true, None) => {
+                let limp = StatModifier::lasting(LIMP_SOURCE, Stat::Speed, LIMP_SPEED);
+                match modifiers {
+                    Some(mut modifiers) => modifiers.add(limp),
+                    None => {
+                        let mut modifiers = StatModifiers::default();
+                        modifiers.add(limp);
+                        commands.entity(entity).insert(modifiers);
+                    }
+                }
+
+                let cracks = spawn_cracks(&mut commands);
+                commands.entity(entity).add_child(cracks).insert(Weakened {
+                    cracks,
+                    drip: drip_timer(),
+                });
+            }
+            (false, Some(weakened)) => {
+                if let Some(mut modifiers) = modifiers {
+                    modifiers.remove(LIMP_SOURCE);
+                }
+                commands.entity(weakened.cracks).despawn();
+                commands.entity(entity).remove::<Weakened>();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Dark crack lines over a ghost: an empty parent with a thin sprite per crack
+fn spawn_cracks(commands: &mut Commands) -> Entity {
+    commands
+        .spawn((Transform::from_xyz(0.0, 0.0, 0.01), Visibility::default()))
+        .with_children(|parent| {
+            for (center, length, angle) in CRACKS {
+                parent.spawn((
+                    Sprite::from_color(CRACK_COLOR, Vec2::new(length, 3.0)),
+                    Transform::from_translation(center.extend(0.0))
+                        .with_rotation(Quat::from_rotation_z(angle)),
+                ));
+            }
+        })
+        .id()
+}
+
+fn drip_timer() -> Timer {
+    Timer::from_seconds(rand::thread_rng().gen_range(DRIP_INTERVAL), TimerMode::Once)
+}
+
+/// Let a drip fall from under each weakened enemy every so often
+pub fn drip_weakened_enemies(
+    time: Res<Time>,
+    mut enemy_query: Query<(&Transform, &mut Weakened)>,
+    mut bursts: MessageWriter<ParticleBurst>,
+) {
+    let mut rng = rand::thread_rng();
+    for (transform, mut weakened) in enemy_query.iter_mut() {
+        if !weakened.drip.tick(time.delta()).is_finished() {
+            continue;
+        }
+        weakened.drip = drip_timer();
+
+        let offset = Vec2::new(rng.gen_range(-20.0..20.0), -30.0) * transform.scale.truncate();
+        bursts.write(ParticleBurst {
+            kind: ParticleKind::Drip,
+            position: transform.translation.truncate() + offset,
+            direction: Vec2::NEG_Y,
+        });
+    }
+}
@@ -0,0 +1,68 @@
+use crate::game::state::AppState;
+use crate::pickup::PickupKind;
+use crate::player::{AnimationCue, PlayerStateType};
+use crate::stats::PunishGrade;
+use crate::world::Surface;
+use bevy::prelude::*;
+
+/// Something that happened in play, published on one shared bus
+///
+/// Features that only react to play (stats, the debug event log, and later
+/// achievements, challenges, audio cues, or telemetry) read this message
+/// instead of each adding a hook to combat, pickup, or state code. Every
+/// variant has exactly one publisher, named on the variant.
+#[derive(Message, Clone, Debug)]
+pub enum GameplayEvent {
+    /// The app entered a new state (`announce_state_entered`)
+    StateEntered(AppState),
+    /// The player started an attack (`announce_attacks`)
+    AttackUsed {
+        attacker: Entity,
+        attack: PlayerStateType,
+    },
+    /// A hit dealt damage after mitigation and blocking (`announce_damage_dealt`)
+    DamageDealt {
+        attacker: Entity,
+        target: Entity,
+        damage: i32,
+        /// The attack that landed it, when the attacker is the player
+        attack: Option<PlayerStateType>,
+    },
+    /// The player collected a pickup (`collect_pickups`)
+    PickupCollected { collector: Entity, kind: PickupKind },
+    /// The player survived a whole threat section of the match (`count_down`)
+    ///
+    /// Spawning is continuous, so each section (see `match_section`) counts
+    /// as a wave; `wave` is the number of the section just finished, from 1.
+    WaveCleared { wave: u32 },
+    /// A whiff-punish drill chance ended, punished or too slow (`grade_whiff_punishes`)
+    WhiffPunishGraded(PunishGrade),
+    /// The player beat every enemy of a scrolling stage's combat zone
+    /// (`update_zones`); `zone` counts from 1
+    ZoneCleared { zone: u32 },
+    /// The player was knocked out with a life to spare and will respawn
+    /// (`handle_player_defeat`); `lives_left` counts the life they come back on
+    LifeLost { lives_left: u32 },
+    /// The player spent a continue after losing their last life
+    /// (`take_continue`)
+    ContinueUsed { continues_left: u32 },
+    /// The player cleared an arcade stage (`update_stage`); `stage` counts from 1
+    StageCleared { stage: u32 },
+    /// A stage boss entered a new phase (`run_boss_encounter`); `phase` counts
+    /// from 1, so the first phase change enters phase 2
+    BossPhase { phase: u32 },
+    /// Two attacks met and clashed (`detect_clashes`); `winner` is the attacker
+    /// whose attack went through, or `None` if both were cancelled
+    AttackClash {
+        first: Entity,
+        second: Entity,
+        winner: Option<Entity>,
+    },
+    /// A player's animation reached a cue frame (`announce_animation_cues`),
+    /// on the `surface` under their feet
+    AnimationCue {
+        entity: Entity,
+        cue: AnimationCue,
+        surface: Surface,
+    },
+}
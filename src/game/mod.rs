@@ -0,0 +1,13 @@
+pub mod messages;
+pub mod plugin;
+pub mod round;
+pub mod sets;
+pub mod state;
+pub mod systems;
+
+// Re-export commonly used items
+pub use messages::*;
+pub use plugin::*;
+pub use round::*;
+pub use sets::*;
+pub use state::*;
@@ -0,0 +1,75 @@
+use crate::game::messages::GameplayEvent;
+use crate::game::round::{lift_knockout_freeze, restart_round, round_fighting, update_round};
+use crate::game::sets::GameSet;
+use crate::game::state::{AppState, InGame, RestartGameEvent, gameplay_running};
+use crate::game::systems::{
+    animate_sprite, announce_state_entered, count_down, handle_enemy_defeat, handle_player_defeat,
+    handle_restart, respawn_players, setup, start_match, take_continue,
+};
+use crate::settings::wave_mode;
+use bevy::prelude::*;
+
+/// App states, update phases, and the match lifecycle (round ceremonies, timer, scoring, restart)
+pub struct GamePlugin;
+
+impl Plugin for GamePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_state::<AppState>()
+            .add_computed_state::<InGame>()
+            .add_message::<RestartGameEvent>()
+            .add_message::<GameplayEvent>()
+            .configure_sets(Update, (GameSet::Input, GameSet::Menus).chain())
+            .configure_sets(
+                Update,
+                (
+                    GameSet::PlayerControl,
+                    GameSet::Arena,
+                    GameSet::Movement,
+                    GameSet::Attacks,
+                    GameSet::Collisions,
+                    GameSet::Interactions,
+                    GameSet::Damage,
+                    GameSet::DamageReactions,
+                    GameSet::Defeat,
+                    GameSet::Effects,
+                    GameSet::Spawning,
+                    GameSet::Hud,
+                    GameSet::Restart,
+                )
+                    .chain()
+                    .after(GameSet::Menus)
+                    .run_if(gameplay_running),
+            )
+            .configure_sets(
+                FixedUpdate,
+                (GameSet::MovementIntents, GameSet::Movement)
+                    .chain()
+                    .run_if(gameplay_running),
+            )
+            .add_systems(Startup, setup)
+            // State setup/teardown (entities tagged DespawnOnExit are removed automatically)
+            .add_systems(OnEnter(InGame), start_match)
+            .add_systems(OnExit(InGame), lift_knockout_freeze)
+            .add_systems(
+                Update,
+                (
+                    announce_state_entered
+                        .run_if(state_changed::<AppState>)
+                        .after(GameSet::Menus),
+                    (handle_enemy_defeat, handle_player_defeat, respawn_players)
+                        .chain()
+                        .in_set(GameSet::Defeat),
+                    animate_sprite.in_set(GameSet::Effects),
+                    (
+                        update_round,
+                        take_continue,
+                        count_down.run_if(round_fighting.and(wave_mode)),
+                    )
+                        .chain()
+                        .run_if(in_state(AppState::Playing))
+                        .in_set(GameSet::Spawning),
+                    (handle_restart, restart_round).in_set(GameSet::Restart),
+                ),
+            );
+    }
+}
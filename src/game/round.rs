@@ -0,0 +1,260 @@
+use crate::game::state::{AppState, GameState, RestartGameEvent};
+use crate::settings::{GameMode, GameSpeed};
+use crate::world::{CameraDirective, CameraDirector};
+use bevy::prelude::*;
+
+/// Real-time seconds the "ROUND n" call shows before the fight starts (input locked)
+const ROUND_CALL_SECONDS: f32 = 1.2;
+/// Real-time seconds "FIGHT!" stays up once the fight has started
+const FIGHT_BANNER_SECONDS: f32 = 0.6;
+/// Real-time seconds the world freezes on the knockout hit
+const KO_FREEZE_SECONDS: f32 = 0.3;
+/// Game speed multiplier for the rest of the knockout ceremony
+const KO_SLOW_MOTION: f32 = 0.25;
+/// Camera zoom (orthographic scale) while closing in on the knocked-out player
+const KO_ZOOM: f32 = 0.8;
+/// Real-time seconds of each step of the knockout camera sequence
+const KO_PAN_SECONDS: f32 = 0.8;
+const KO_ZOOM_SECONDS: f32 = 0.3;
+const KO_HOLD_SECONDS: f32 = 0.5;
+const KO_RETURN_SECONDS: f32 = 0.4;
+/// The knockout ceremony lasts as long as its camera sequence
+const KO_SECONDS: f32 = KO_PAN_SECONDS + KO_ZOOM_SECONDS + KO_HOLD_SECONDS + KO_RETURN_SECONDS;
+/// Real-time seconds "TIME!" holds the fight when a versus round runs out of time
+const TIME_UP_SECONDS: f32 = 1.5;
+/// Real-time seconds the player has to take a continue before GameOver
+const CONTINUE_SECONDS: f32 = 10.0;
+
+/// Where a round is in its ceremony
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundPhase {
+    /// "ROUND n" call: no input, spawns, or match timer yet
+    Intro,
+    /// The round is being fought
+    Fight,
+    /// The player was knocked out: freeze-frame, then a slow pan before GameOver
+    KnockOut,
+    /// The player lost their last life with continues left: the continue
+    /// countdown runs before GameOver (see `take_continue`)
+    Continue,
+    /// A versus round's timer ran out: "TIME!" holds the fighters in place
+    TimeUp,
+    /// Ceremony finished and the game over screen is up
+    Over,
+}
+
+/// Round-state machine driving the round-start and round-end ceremonies
+///
+/// A survival match is a single round; a versus match runs rounds until one
+/// player has won it (see src/versus/). Ceremony timings use real time, so
+/// the game speed setting and the knockout slow motion don't stretch them.
+#[derive(Resource)]
+pub struct Round {
+    pub number: u32,
+    pub phase: RoundPhase,
+    /// Real-time seconds since the current phase started
+    pub elapsed: f32,
+    /// Another round follows this one, so its ending doesn't open GameOver
+    pub continues: bool,
+    /// Name of the arcade stage this round is fought on, called with the stage number
+    pub stage: Option<String>,
+}
+
+impl Round {
+    /// Start round `number` with its call, locking input with a camera hold for as long
+    pub fn begin(number: u32, director: &mut CameraDirector) -> Self {
+        director.play([CameraDirective::Hold {
+            seconds: ROUND_CALL_SECONDS,
+        }]);
+        Self {
+            number,
+            phase: RoundPhase::Intro,
+            elapsed: 0.0,
+            continues: false,
+            stage: None,
+        }
+    }
+
+    /// Start arcade stage `number` with its call ("STAGE 2" and the stage name)
+    pub fn begin_stage(number: u32, name: &str, director: &mut CameraDirector) -> Self {
+        Self {
+            stage: Some(name.to_string()),
+            ..Self::begin(number, director)
+        }
+    }
+
+    /// A round already being fought, with no call or "FIGHT!" banner (training mode)
+    pub fn practice() -> Self {
+        Self {
+            number: 1,
+            phase: RoundPhase::Fight,
+            elapsed: FIGHT_BANNER_SECONDS,
+            continues: false,
+            stage: None,
+        }
+    }
+
+    fn enter(&mut self, phase: RoundPhase) {
+        self.phase = phase;
+        self.elapsed = 0.0;
+    }
+
+    /// Text for the round banner, if any should show right now
+    pub fn banner(&self) -> Option<String> {
+        match self.phase {
+            RoundPhase::Intro => Some(match &self.stage {
+                Some(name) => format!("STAGE {}\n{}", self.number, name.to_uppercase()),
+                None => format!("ROUND {}", self.number),
+            }),
+            RoundPhase::Fight if self.elapsed < FIGHT_BANNER_SECONDS => Some("FIGHT!".to_string()),
+            RoundPhase::KnockOut => Some("K.O.".to_string()),
+            RoundPhase::TimeUp => Some("TIME!".to_string()),
+            RoundPhase::Fight | RoundPhase::Continue | RoundPhase::Over => None,
+        }
+    }
+
+    /// Whole seconds left on the continue countdown (rounded up)
+    pub fn continue_seconds_left(&self) -> u32 {
+        (CONTINUE_SECONDS - self.elapsed).max(0.0).ceil() as u32
+    }
+
+    /// Get back to the fight after a continue, with a fresh "FIGHT!"
+    pub fn resume(&mut self) {
+        self.enter(RoundPhase::Fight);
+    }
+
+    /// End the round without a knockout (the last arcade stage was cleared)
+    pub fn finish(&mut self) {
+        self.enter(RoundPhase::Over);
+    }
+
+    /// Stop a versus round whose timer ran out, holding the camera (and input) on "TIME!"
+    pub fn time_up(&mut self, director: &mut CameraDirector) {
+        self.enter(RoundPhase::TimeUp);
+        director.play([CameraDirective::Hold {
+            seconds: TIME_UP_SECONDS,
+        }]);
+    }
+
+    /// Freeze the world on the knockout hit and slowly close in on the player
+    ///
+    /// Returns false if the round is not being fought (e.g. the player was hit
+    /// again while already down).
+    pub fn knock_out(
+        &mut self,
+        director: &mut CameraDirector,
+        time: &mut Time<Virtual>,
+        player_position: Vec2,
+    ) -> bool {
+        if self.phase != RoundPhase::Fight {
+            return false;
+        }
+        self.enter(RoundPhase::KnockOut);
+        time.pause();
+
+        // Directives run on real time, so the pan plays at full speed over the slowed world
+        director.play([
+            CameraDirective::PanTo {
+                target: player_position,
+                seconds: KO_PAN_SECONDS,
+            },
+            CameraDirective::Zoom {
+                scale: KO_ZOOM,
+                seconds: KO_ZOOM_SECONDS,
+            },
+            CameraDirective::Hold {
+                seconds: KO_HOLD_SECONDS,
+            },
+            CameraDirective::Return {
+                seconds: KO_RETURN_SECONDS,
+            },
+        ]);
+        true
+    }
+}
+
+/// Run condition: the round has started and nobody has been knocked out yet
+pub fn round_fighting(round: Res<Round>) -> bool {
+    round.phase == RoundPhase::Fight
+}
+
+/// Advance the round ceremonies: end the call, lift the freeze-frame, and open GameOver
+///
+/// A round that `continues` ends in `Over` without GameOver; the versus
+/// systems start the next one from there. A knockout with continues left on
+/// `GameState` runs the continue countdown first, and opens GameOver if it
+/// runs out.
+pub fn update_round(
+    real_time: Res<Time<Real>>,
+    speed: Res<GameSpeed>,
+    game_state: Res<GameState>,
+    mut round: ResMut<Round>,
+    mut time: ResMut<Time<Virtual>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    round.elapsed += real_time.delta_secs();
+
+    match round.phase {
+        RoundPhase::Intro if round.elapsed >= ROUND_CALL_SECONDS => {
+            round.enter(RoundPhase::Fight);
+        }
+        RoundPhase::KnockOut => {
+            if round.elapsed >= KO_FREEZE_SECONDS && time.is_paused() {
+                time.unpause();
+                time.set_relative_speed(speed.multiplier() * KO_SLOW_MOTION);
+            }
+            // The camera is back home by now, so the game over screen opens on the usual framing
+            if round.elapsed >= KO_SECONDS {
+                time.set_relative_speed(speed.multiplier());
+                if !round.continues && game_state.continues_left > 0 {
+                    round.enter(RoundPhase::Continue);
+                } else {
+                    round.enter(RoundPhase::Over);
+                    if !round.continues {
+                        next_state.set(AppState::GameOver);
+                    }
+                }
+            }
+        }
+        RoundPhase::Continue if round.elapsed >= CONTINUE_SECONDS => {
+            round.enter(RoundPhase::Over);
+            next_state.set(AppState::GameOver);
+        }
+        RoundPhase::TimeUp if round.elapsed >= TIME_UP_SECONDS => {
+            round.enter(RoundPhase::Over);
+            if !round.continues {
+                next_state.set(AppState::GameOver);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Start over from the round call when the game restarts (straight into the fight in training)
+pub fn restart_round(
+    mut restart_events: MessageReader<RestartGameEvent>,
+    mut round: ResMut<Round>,
+    mut director: ResMut<CameraDirector>,
+    speed: Res<GameSpeed>,
+    mode: Res<GameMode>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    if restart_events.is_empty() {
+        return;
+    }
+    restart_events.clear();
+
+    *round = match *mode {
+        GameMode::Arcade | GameMode::Endless | GameMode::Versus => Round::begin(1, &mut director),
+        GameMode::Training => Round::practice(),
+    };
+    time.unpause();
+    time.set_relative_speed(speed.multiplier());
+}
+
+/// Lift a knockout freeze-frame when leaving the game mid-ceremony
+///
+/// `apply_game_speed` also runs on leaving InGame and undoes the slow motion.
+pub fn lift_knockout_freeze(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+
+/// Update phases, in the order they run each frame
+///
+/// `Input` and `Menus` run in every state. The rest are gameplay phases: they
+/// run only while the world simulates (see `gameplay_running`), after menus
+/// have handled their input. Plugins place their systems in a phase instead
+/// of ordering against each other's systems directly.
+///
+/// `MovementIntents` and `Movement` are also the physics phases in
+/// `FixedUpdate`, which runs before `Update` each frame.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameSet {
+    /// Game speed, stage mirroring, action gathering, and debug capture
+    Input,
+    /// Menu navigation and per-screen input handling
+    Menus,
+    /// Player input, state machine, and sprite updates
+    PlayerControl,
+    /// Arena bounds and confinement
+    Arena,
+    /// Systems that write `MoveIntent` (player physics, AI, projectiles, knockback), in `FixedUpdate` only
+    MovementIntents,
+    /// The single movement resolution pass (`FixedUpdate`) and depth sorting (`Update`)
+    Movement,
+    /// Attack hitboxes and attack-triggered effects
+    Attacks,
+    /// Hit detection that sends `DamageEvent`s
+    Collisions,
+    /// Interact-button focus and input, sending `InteractEvent`s
+    Interactions,
+    /// Damage resolution and the combat timers it drives
+    Damage,
+    /// Stats, loot, and cleanup that react to this frame's damage
+    DamageReactions,
+    /// Scoring defeated enemies and ending the game on player defeat
+    Defeat,
+    /// Visual effects, camera, and sprite animation
+    Effects,
+    /// Match timer and enemy spawning
+    Spawning,
+    /// HUD and overlay updates
+    Hud,
+    /// Full game reset on restart
+    Restart,
+}
@@ -0,0 +1,147 @@
+use crate::settings::{Difficulty, GameMode, MatchSettings, Mutators};
+use crate::ui::FINAL_RUSH_SECONDS;
+use bevy::prelude::*;
+use std::time::Duration;
+
+/// Top-level application state
+///
+/// Gameplay systems run in Playing; the world keeps animating (but takes no
+/// input, spawns, or timer ticks) in GameOver; everything freezes in Paused.
+/// StageClear holds the frozen world between arcade stages, behind the stage
+/// clear screen. Rest is the between-run screen shown after GameOver, with
+/// gameplay torn down.
+/// Bestiary, Extras, and Advanced are menu screens opened from the main menu;
+/// CharacterSelect comes between picking a mode there and Playing.
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AppState {
+    #[default]
+    MainMenu,
+    Playing,
+    Paused,
+    GameOver,
+    StageClear,
+    Rest,
+    Bestiary,
+    Extras,
+    Advanced,
+    CharacterSelect,
+}
+
+/// Computed state that exists while a game is in progress (Playing, Paused,
+/// GameOver, or StageClear)
+///
+/// Gameplay entities (player, enemies, HUD) are spawned on entering InGame and
+/// tagged `DespawnOnExit(InGame)`, so returning to the main menu tears them down.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct InGame;
+
+impl ComputedStates for InGame {
+    type SourceStates = AppState;
+
+    fn compute(sources: AppState) -> Option<Self> {
+        match sources {
+            AppState::Playing | AppState::Paused | AppState::GameOver | AppState::StageClear => {
+                Some(InGame)
+            }
+            AppState::MainMenu
+            | AppState::Rest
+            | AppState::Bestiary
+            | AppState::Extras
+            | AppState::Advanced
+            | AppState::CharacterSelect => None,
+        }
+    }
+}
+
+/// Run condition: world simulation runs while playing and during the game over screen
+pub fn gameplay_running(state: Res<State<AppState>>) -> bool {
+    matches!(state.get(), AppState::Playing | AppState::GameOver)
+}
+
+#[derive(Resource)]
+pub struct GameState {
+    pub score: u32,
+    pub n_enemies: u32,
+    pub timer: Timer,
+    pub last_spawn_time: f32,
+    pub game_duration: f32, // Total game time in seconds (MatchSettings::duration)
+    pub threat_budget: u32, // Base threat budget; grows each match section
+    pub spawn_interval: f32, // Seconds between enemy spawns
+    /// Where a knocked-out player with a life to spare comes back (None for
+    /// their starting spot); set at the start of each stage and each scrolling
+    /// stage zone
+    pub checkpoint: Option<Vec2>,
+    /// Continues the run can still take once its last life is lost (none in
+    /// versus and training)
+    pub continues_left: u32,
+}
+
+impl GameState {
+    /// Fresh game state with the match length from `MatchSettings` and spawn limits taken
+    /// from the difficulty (or the settings' spawn interval, if set)
+    ///
+    /// Endless runs get a timer that never finishes, so it only counts up.
+    /// Arcade and endless runs get the settings' continues.
+    pub fn new(difficulty: Difficulty, mode: GameMode, settings: &MatchSettings) -> Self {
+        let (timer, game_duration) = match mode {
+            GameMode::Endless => (Timer::new(Duration::MAX, TimerMode::Once), f32::INFINITY),
+            _ => (
+                Timer::from_seconds(settings.duration, TimerMode::Once),
+                settings.duration,
+            ),
+        };
+        Self {
+            score: 0,
+            n_enemies: 0,
+            timer,
+            last_spawn_time: 0.0,
+            game_duration,
+            threat_budget: difficulty.threat_budget(),
+            spawn_interval: settings
+                .spawn_interval
+                .unwrap_or_else(|| difficulty.spawn_interval()),
+            checkpoint: None,
+            continues_left: match mode {
+                GameMode::Arcade | GameMode::Endless => settings.continues,
+                GameMode::Versus | GameMode::Training => 0,
+            },
+        }
+    }
+
+    /// Seconds left on the match timer
+    pub fn time_remaining(&self) -> f32 {
+        (self.game_duration - self.timer.elapsed_secs()).max(0.0)
+    }
+
+    /// True during the last seconds of the match, when score gains are doubled
+    pub fn in_final_rush(&self) -> bool {
+        let time_remaining = self.time_remaining();
+        time_remaining > 0.0 && time_remaining <= FINAL_RUSH_SECONDS
+    }
+}
+
+/// Lives left in the run, counting the one being played
+///
+/// A knockout with lives to spare respawns the player at
+/// `GameState::checkpoint`; only losing the last one ends the game. Versus
+/// and training have a single life, so a knockout ends the round as before.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct Lives {
+    pub remaining: u32,
+}
+
+impl Lives {
+    /// Lives for a new game: `MatchSettings::lives` in arcade and endless runs
+    /// (one under hardcore)
+    pub fn new(mode: GameMode, settings: &MatchSettings, mutators: Mutators) -> Self {
+        let remaining = match mode {
+            GameMode::Arcade | GameMode::Endless => mutators.lives(settings.lives),
+            GameMode::Versus | GameMode::Training => 1,
+        };
+        Self { remaining }
+    }
+}
+
+/// Message requesting a full game reset (from the game over screen or pause menu)
+#[derive(Message)]
+pub struct RestartGameEvent;
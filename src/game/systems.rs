@@ -0,0 +1,446 @@
+use crate::combat::{
+    Burning, ComboCounter, EnemyDefeatedEvent, Guard, GuardBroken, Health, HitFlash, HurtBox,
+    ImpactFlash, Invulnerable, Knockback, PlayerDefeatedEvent, Shield, SpecialMeter, StatModifiers,
+    StyleMeter,
+};
+use crate::common::{AnimationIndices, AnimationTimer};
+use crate::enemy::{Enemy, EnemyState, SpawnDirector, match_section};
+use crate::game::messages::GameplayEvent;
+use crate::game::round::{Round, RoundPhase};
+use crate::game::state::{AppState, GameState, Lives, RestartGameEvent};
+use crate::input::PlayerActions;
+use crate::levels::StageBoss;
+use crate::pickup::{LootPity, Pickup};
+use crate::player::spawn::player_start;
+use crate::player::{ComboWindow, JumpPhysics, Player, PlayerState, PlayerStateType, PlayerTwo};
+use crate::prefab::{PrefabSpawner, stage_layers, stage_layout};
+use crate::projectile::Projectile;
+use crate::settings::{ComboAssist, Difficulty, GameMode, MatchSettings, Mutators};
+use crate::stats::{LifetimeStats, RunRecords};
+use crate::ui::extras::background_music;
+use crate::ui::{FINAL_RUSH_MULTIPLIER, TextStyles, score_popup};
+use crate::world::CameraDirector;
+use bevy::prelude::*;
+
+/// Seconds a knocked-out player with a life to spare stays down before respawning
+const RESPAWN_DELAY: f32 = 1.5;
+/// Seconds a respawned player can't be hurt
+const RESPAWN_INVULNERABILITY: f32 = 3.0;
+/// Enemies within this distance of the player are cleared away when they continue
+const CONTINUE_CLEAR_RADIUS: f32 = 400.0;
+
+/// The looping background music (see `background_music`), turned up and down by
+/// `apply_audio_settings` and switchable from the extras screen
+#[derive(Component)]
+pub struct BackgroundMusic {
+    /// Index into `SOUNDTRACK`
+    pub track: usize,
+}
+
+/// Spawn the camera, the stage props and layers listed in assets/stage.txt, and
+/// background music
+pub fn setup(mut commands: Commands, mut prefabs: PrefabSpawner, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2d);
+    for (prefab, position) in stage_layout() {
+        prefabs.spawn(prefab, position);
+    }
+    for layer in stage_layers() {
+        prefabs.spawn_layer(&layer);
+    }
+
+    // Play background music on loop
+    commands.spawn(background_music(&asset_server, 0));
+}
+
+/// Fresh score, timer, lives, spawn director, and loot pity for a new match, opening
+/// with the round call (training skips it)
+pub fn start_match(
+    mut commands: Commands,
+    difficulty: Res<Difficulty>,
+    mode: Res<GameMode>,
+    match_settings: Res<MatchSettings>,
+    mutators: Res<Mutators>,
+    mut director: ResMut<CameraDirector>,
+) {
+    commands.insert_resource(GameState::new(*difficulty, *mode, &match_settings));
+    commands.insert_resource(Lives::new(*mode, &match_settings, *mutators));
+    commands.insert_resource(SpawnDirector::default());
+    commands.insert_resource(LootPity::default());
+    commands.insert_resource(match *mode {
+        GameMode::Arcade | GameMode::Endless | GameMode::Versus => Round::begin(1, &mut director),
+        GameMode::Training => Round::practice(),
+    });
+}
+
+/// Run the match timer, announcing each threat section survived as a cleared wave
+///
+/// In endless runs the timer never finishes; it only counts the time survived.
+/// In arcade runs it's the stage clock, and `update_stage` ends the stage
+/// when it runs out.
+pub fn count_down(
+    time: Res<Time>,
+    mut game_state: ResMut<GameState>,
+    mut gameplay_events: MessageWriter<GameplayEvent>,
+) {
+    let section = match_section(game_state.timer.elapsed_secs());
+    game_state.timer.tick(time.delta());
+    if match_section(game_state.timer.elapsed_secs()) > section {
+        gameplay_events.write(GameplayEvent::WaveCleared { wave: section + 1 });
+    }
+}
+
+/// Publish `GameplayEvent::StateEntered` whenever the app state changes
+pub fn announce_state_entered(
+    state: Res<State<AppState>>,
+    mut gameplay_events: MessageWriter<GameplayEvent>,
+) {
+    gameplay_events.write(GameplayEvent::StateEntered(*state.get()));
+}
+
+pub fn animate_sprite(
+    time: Res<Time>,
+    mut query: Query<(
+        &AnimationIndices,
+        &mut AnimationTimer,
+        &mut Sprite,
+        Option<&PlayerState>,
+    )>,
+) {
+    for (indices, mut timer, mut sprite, player_state) in &mut query {
+        timer.tick(time.delta());
+
+        if timer.just_finished()
+            && let Some(atlas) = &mut sprite.texture_atlas
+        {
+            // Check if player is in Defeat state - freeze on last frame
+            let is_defeated =
+                player_state.is_some_and(|state| matches!(state, PlayerState::Defeat(_)));
+
+            if is_defeated && atlas.index == indices.last {
+                // Freeze on last frame of defeat animation
+                atlas.index = indices.last;
+            } else {
+                // Normal animation loop
+                atlas.index = if atlas.index == indices.last {
+                    indices.first
+                } else {
+                    atlas.index + 1
+                };
+            }
+        }
+    }
+}
+
+/// Score each defeated enemy and start its death
+///
+/// The enemy loses its `HurtBox` and switches to `EnemyState::Dying`, which
+/// stops its AI; `update_enemy_states` despawns it once it has dissipated.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_enemy_defeat(
+    mut commands: Commands,
+    styles: Res<TextStyles>,
+    mut events: MessageReader<EnemyDefeatedEvent>,
+    mut game_state: ResMut<GameState>,
+    match_settings: Res<MatchSettings>,
+    mutators: Res<Mutators>,
+    transform_query: Query<&Transform>,
+    combo_query: Query<(&ComboCounter, &StyleMeter), With<Player>>,
+) {
+    for event in events.read() {
+        // Kills are worth more during the final rush, long combos, a high style rank,
+        // and hardcore, with a popup to show it
+        let mut multiplier = combo_query.single().map_or(1, |(combo, style)| {
+            combo.score_multiplier() * style.rank().score_multiplier()
+        }) * mutators.score_multiplier();
+        if game_state.in_final_rush() {
+            multiplier *= FINAL_RUSH_MULTIPLIER;
+        }
+        let points = match_settings.score_per_kill * multiplier;
+        if multiplier > 1
+            && let Ok(transform) = transform_query.get(event.enemy)
+        {
+            commands.spawn(score_popup(
+                &styles,
+                format!("+{} x{}", points, multiplier),
+                transform.translation.truncate(),
+            ));
+        }
+
+        // Score now, and let it dissipate: no more hits, AI, or contact damage
+        let scale = transform_query
+            .get(event.enemy)
+            .map_or(1.0, |transform| transform.scale.x);
+        commands
+            .entity(event.enemy)
+            .remove::<HurtBox>()
+            .insert(EnemyState::dying(scale));
+        game_state.n_enemies -= 1;
+        game_state.score += points;
+    }
+}
+
+/// A knocked-out player waiting to come back at the checkpoint
+#[derive(Component)]
+pub struct Respawning {
+    pub timer: Timer,
+}
+
+/// Knock the player out, costing a life
+///
+/// With lives to spare the player stays down for `RESPAWN_DELAY` and then
+/// `respawn_players` brings them back; losing the last life starts the
+/// round's knockout ceremony, and `update_round` switches to GameOver once
+/// it's over (unless another versus round follows).
+#[allow(clippy::too_many_arguments)]
+pub fn handle_player_defeat(
+    mut commands: Commands,
+    mut events: MessageReader<PlayerDefeatedEvent>,
+    game_state: Res<GameState>,
+    mut lives: ResMut<Lives>,
+    mut round: ResMut<Round>,
+    mut director: ResMut<CameraDirector>,
+    mut time: ResMut<Time<Virtual>>,
+    mut gameplay_events: MessageWriter<GameplayEvent>,
+    mut player_query: Query<(&mut PlayerState, &Transform), With<Player>>,
+) {
+    for event in events.read() {
+        let Ok((mut state, transform)) = player_query.get_mut(event.player) else {
+            continue;
+        };
+
+        // Transition player to Defeat state
+        *state = PlayerState::transition_to(PlayerStateType::Defeat);
+
+        lives.remaining = lives.remaining.saturating_sub(1);
+        if lives.remaining > 0 {
+            commands.entity(event.player).insert(Respawning {
+                timer: Timer::from_seconds(RESPAWN_DELAY, TimerMode::Once),
+            });
+            gameplay_events.write(GameplayEvent::LifeLost {
+                lives_left: lives.remaining,
+            });
+            continue;
+        }
+
+        if round.knock_out(&mut director, &mut time, transform.translation.truncate()) {
+            println!("GAME OVER! Final Score: {}", game_state.score);
+        }
+    }
+}
+
+/// Bring knocked-out players back at the checkpoint once their `Respawning`
+/// delay is up, with full health and a few seconds of invulnerability
+///
+/// Score, meter, combo, and style carry on from where they were.
+#[allow(clippy::type_complexity)]
+pub fn respawn_players(
+    mut commands: Commands,
+    time: Res<Time>,
+    game_state: Res<GameState>,
+    mutators: Res<Mutators>,
+    mut player_query: Query<
+        (
+            Entity,
+            &mut Respawning,
+            &mut Health,
+            &mut PlayerState,
+            &mut Transform,
+            &mut JumpPhysics,
+            Has<PlayerTwo>,
+        ),
+        With<Player>,
+    >,
+) {
+    for (
+        entity,
+        mut respawning,
+        mut health,
+        mut state,
+        mut transform,
+        mut jump_physics,
+        player_two,
+    ) in player_query.iter_mut()
+    {
+        if !respawning.timer.tick(time.delta()).is_finished() {
+            continue;
+        }
+
+        let checkpoint = game_state
+            .checkpoint
+            .unwrap_or_else(|| player_start(&mutators, player_two));
+        health.current = health.max;
+        *state = PlayerState::transition_to(PlayerStateType::Idle);
+        transform.translation = checkpoint.extend(1.0);
+        transform.scale = Vec3::ONE;
+        jump_physics.velocity_y = 0.0;
+        jump_physics.ground_y = -100.0;
+        commands
+            .entity(entity)
+            .remove::<Respawning>()
+            .remove::<Knockback>()
+            .remove::<Burning>()
+            .insert(Invulnerable {
+                timer: Timer::from_seconds(RESPAWN_INVULNERABILITY, TimerMode::Once),
+            });
+    }
+}
+
+/// Spend a continue when the player presses confirm during the continue
+/// countdown
+///
+/// The player gets back up where they fell with full health, a fresh set of
+/// lives, and a few seconds of invulnerability, and the enemies around them
+/// dissipate (without scoring; bosses stay). The round then picks up with a
+/// new "FIGHT!".
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn take_continue(
+    mut commands: Commands,
+    actions: Res<PlayerActions>,
+    mode: Res<GameMode>,
+    match_settings: Res<MatchSettings>,
+    mutators: Res<Mutators>,
+    mut game_state: ResMut<GameState>,
+    mut lives: ResMut<Lives>,
+    mut round: ResMut<Round>,
+    mut gameplay_events: MessageWriter<GameplayEvent>,
+    mut player_query: Query<(Entity, &mut Health, &mut PlayerState, &Transform), With<Player>>,
+    enemy_query: Query<(Entity, &Transform, &EnemyState), (With<Enemy>, Without<StageBoss>)>,
+) {
+    if round.phase != RoundPhase::Continue || !actions.confirm || game_state.continues_left == 0 {
+        return;
+    }
+
+    game_state.continues_left -= 1;
+    *lives = Lives::new(*mode, &match_settings, *mutators);
+    round.resume();
+    gameplay_events.write(GameplayEvent::ContinueUsed {
+        continues_left: game_state.continues_left,
+    });
+
+    for (entity, mut health, mut state, transform) in player_query.iter_mut() {
+        health.current = health.max;
+        *state = PlayerState::transition_to(PlayerStateType::Idle);
+        commands
+            .entity(entity)
+            .remove::<Knockback>()
+            .remove::<Burning>()
+            .insert(Invulnerable {
+                timer: Timer::from_seconds(RESPAWN_INVULNERABILITY, TimerMode::Once),
+            });
+
+        let position = transform.translation.truncate();
+        for (enemy, enemy_transform, enemy_state) in enemy_query.iter() {
+            if enemy_state.is_dying()
+                || enemy_transform.translation.truncate().distance(position) > CONTINUE_CLEAR_RADIUS
+            {
+                continue;
+            }
+            commands
+                .entity(enemy)
+                .remove::<HurtBox>()
+                .insert(EnemyState::dying(enemy_transform.scale.x));
+            game_state.n_enemies -= 1;
+        }
+    }
+}
+
+/// Reset game state, player, and enemies when a restart is requested
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn handle_restart(
+    mut commands: Commands,
+    mut restart_events: MessageReader<RestartGameEvent>,
+    mut game_state: ResMut<GameState>,
+    mut lives: ResMut<Lives>,
+    mut spawn_director: ResMut<SpawnDirector>,
+    mut loot_pity: ResMut<LootPity>,
+    mut records: ResMut<RunRecords>,
+    stats: Res<LifetimeStats>,
+    difficulty: Res<Difficulty>,
+    mode: Res<GameMode>,
+    match_settings: Res<MatchSettings>,
+    mutators: Res<Mutators>,
+    combo_assist: Res<ComboAssist>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut player_query: Query<
+        (
+            Entity,
+            &mut Health,
+            &mut PlayerState,
+            &mut Transform,
+            &mut JumpPhysics,
+            &mut ComboWindow,
+            Has<PlayerTwo>,
+        ),
+        With<Player>,
+    >,
+    enemy_query: Query<Entity, Or<(With<Enemy>, With<Projectile>, With<Pickup>)>>,
+) {
+    if restart_events.is_empty() {
+        return;
+    }
+    restart_events.clear();
+
+    // Reset game state
+    *game_state = GameState::new(*difficulty, *mode, &match_settings);
+    *lives = Lives::new(*mode, &match_settings, *mutators);
+    *spawn_director = SpawnDirector::default();
+    *loot_pity = LootPity::default();
+    records.start_run(&stats);
+    next_state.set(AppState::Playing);
+
+    // Reset the player (both players in versus)
+    for (
+        player_entity,
+        mut health,
+        mut state,
+        mut transform,
+        mut jump_physics,
+        mut combo_window,
+        player_two,
+    ) in player_query.iter_mut()
+    {
+        // Reset health
+        health.current = health.max;
+
+        // Reset state to Idle
+        *state = PlayerState::transition_to(PlayerStateType::Idle);
+
+        // Reset position (and any impact pop in progress)
+        transform.translation = player_start(&mutators, player_two).extend(1.0);
+        transform.scale = Vec3::ONE;
+
+        // Reset jump physics (back to the starting lane)
+        jump_physics.velocity_y = 0.0;
+        jump_physics.ground_y = -100.0;
+        jump_physics.has_used_aerial_attack = false;
+
+        // Reset combo window
+        *combo_window = ComboWindow::new(*combo_assist);
+
+        // Remove any active combat effects
+        commands
+            .entity(player_entity)
+            .remove::<Respawning>()
+            .remove::<Invulnerable>()
+            .remove::<Knockback>()
+            .remove::<HitFlash>()
+            .remove::<ImpactFlash>()
+            .remove::<Burning>()
+            .remove::<Shield>()
+            .remove::<GuardBroken>()
+            .insert((
+                Guard::default(),
+                SpecialMeter::default(),
+                ComboCounter::default(),
+                StatModifiers::default(),
+                StyleMeter::default(),
+            ));
+    }
+
+    // Despawn all enemies, their projectiles, and any dropped pickups
+    for enemy_entity in enemy_query.iter() {
+        commands.entity(enemy_entity).despawn();
+    }
+
+    println!("Game restarted!");
+}
@@ -0,0 +1,178 @@
+use crate::input::bindings::{InputAction, InputMap};
+use crate::input::profiles::GamepadBindings;
+use crate::player::states::Emote;
+use bevy::prelude::*;
+
+/// Stick deflection below this is treated as neutral
+const STICK_DEADZONE: f32 = 0.2;
+/// Stick deflection below this (but above the deadzone) walks instead of runs
+const STICK_WALK_THRESHOLD: f32 = 0.6;
+
+/// Logical player actions for the current frame, independent of input device
+///
+/// Built each frame by merging every connected input source, so keyboard and
+/// gamepads can be used simultaneously. Held actions (`left`, `right`, `up`,
+/// `down`, `walk`, `block`, `interact`) are true while pressed; trigger actions (`jump`, `punch`, `kick`,
+/// `dash`, `super_attack`, `burst`, `grab`, `emote`, `pause`, and the menu actions) are only true
+/// on the frame they were pressed.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct PlayerActions {
+    pub left: bool,
+    pub right: bool,
+    /// Step into the background along the depth lane
+    pub up: bool,
+    /// Step toward the camera along the depth lane
+    pub down: bool,
+    pub walk: bool,
+    pub jump: bool,
+    pub punch: bool,
+    pub kick: bool,
+    pub block: bool,
+    pub dash: bool,
+    pub super_attack: bool,
+    pub burst: bool,
+    pub grab: bool,
+    /// Held so hold-to-use interactions can time the hold
+    pub interact: bool,
+    /// Emote key pressed this frame (played only while idle)
+    pub emote: Option<Emote>,
+    pub pause: bool,
+    /// Move menu focus up (Up / W, d-pad or stick up)
+    pub menu_up: bool,
+    /// Move menu focus down (Down / S, d-pad or stick down)
+    pub menu_down: bool,
+    /// Press the focused menu button (Enter / Space, gamepad South)
+    pub confirm: bool,
+    /// Leave the current menu (Escape, gamepad East)
+    pub back: bool,
+}
+
+/// Player 2's actions in versus games, built by `gather_player_two_actions`
+///
+/// Only the fighting actions are ever set; menus and pause belong to player 1.
+#[derive(Resource, Clone, Debug, Default, Deref, DerefMut)]
+pub struct PlayerTwoActions(pub PlayerActions);
+
+impl PlayerActions {
+    /// Combine actions from another input source into this one
+    pub fn merge(&mut self, other: &PlayerActions) {
+        self.left |= other.left;
+        self.right |= other.right;
+        self.up |= other.up;
+        self.down |= other.down;
+        self.walk |= other.walk;
+        self.jump |= other.jump;
+        self.punch |= other.punch;
+        self.kick |= other.kick;
+        self.block |= other.block;
+        self.dash |= other.dash;
+        self.super_attack |= other.super_attack;
+        self.burst |= other.burst;
+        self.grab |= other.grab;
+        self.interact |= other.interact;
+        self.emote = self.emote.or(other.emote);
+        self.pause |= other.pause;
+        self.menu_up |= other.menu_up;
+        self.menu_down |= other.menu_down;
+        self.confirm |= other.confirm;
+        self.back |= other.back;
+    }
+
+    /// Keep only the menu actions (and pause), dropping everything that moves a fighter
+    pub fn menu_only(&self) -> Self {
+        Self {
+            pause: self.pause,
+            menu_up: self.menu_up,
+            menu_down: self.menu_down,
+            confirm: self.confirm,
+            back: self.back,
+            ..default()
+        }
+    }
+
+    /// Keep only the fighting actions, dropping pause and menu navigation
+    pub fn fighting_only(&self) -> Self {
+        Self {
+            pause: false,
+            menu_up: false,
+            menu_down: false,
+            confirm: false,
+            back: false,
+            ..self.clone()
+        }
+    }
+
+    /// Read actions from the keyboard using the current key bindings
+    pub fn from_keyboard(keyboard: &ButtonInput<KeyCode>, input_map: &InputMap) -> Self {
+        Self {
+            left: input_map.pressed(InputAction::MoveLeft, keyboard),
+            right: input_map.pressed(InputAction::MoveRight, keyboard),
+            up: input_map.pressed(InputAction::MoveUp, keyboard),
+            down: input_map.pressed(InputAction::MoveDown, keyboard),
+            walk: input_map.pressed(InputAction::Walk, keyboard),
+            jump: input_map.just_pressed(InputAction::Jump, keyboard),
+            punch: input_map.just_pressed(InputAction::Punch, keyboard),
+            kick: input_map.just_pressed(InputAction::Kick, keyboard),
+            block: input_map.pressed(InputAction::Block, keyboard),
+            dash: input_map.just_pressed(InputAction::Dash, keyboard),
+            super_attack: input_map.just_pressed(InputAction::Super, keyboard),
+            burst: input_map.just_pressed(InputAction::Burst, keyboard),
+            grab: input_map.just_pressed(InputAction::Grab, keyboard),
+            interact: input_map.pressed(InputAction::Interact, keyboard),
+            emote: [
+                (InputAction::EmoteWave, Emote::Wave),
+                (InputAction::EmoteFlex, Emote::Flex),
+                (InputAction::EmoteBow, Emote::Bow),
+            ]
+            .into_iter()
+            .find(|(action, _)| input_map.just_pressed(*action, keyboard))
+            .map(|(_, emote)| emote),
+            pause: input_map.just_pressed(InputAction::Pause, keyboard),
+            // Menu navigation uses fixed keys so rebinding can't lock you out of menus
+            menu_up: keyboard.any_just_pressed([KeyCode::ArrowUp, KeyCode::KeyW]),
+            menu_down: keyboard.any_just_pressed([KeyCode::ArrowDown, KeyCode::KeyS]),
+            // Alt+Enter toggles fullscreen (`toggle_fullscreen`) instead
+            confirm: keyboard.any_just_pressed([KeyCode::Enter, KeyCode::Space])
+                && !keyboard.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]),
+            back: keyboard.just_pressed(KeyCode::Escape),
+        }
+    }
+
+    /// Read actions from a gamepad using its profile's button bindings
+    ///
+    /// Left stick or d-pad moves (up/down along the depth lane); the buttons
+    /// follow `bindings` (see `GamepadBindings::default` for the standard layout).
+    /// A light stick tilt or holding the Walk button walks instead of runs.
+    /// In menus the d-pad moves focus, South confirms, and East goes back,
+    /// whatever the bindings (stick menu movement is edge-detected in
+    /// `gather_player_actions`).
+    pub fn from_gamepad(gamepad: &Gamepad, bindings: &GamepadBindings) -> Self {
+        let stick_x = gamepad.left_stick().x;
+        let stick_y = gamepad.left_stick().y;
+        let stick_tilted = stick_x.abs() > STICK_DEADZONE;
+
+        Self {
+            left: gamepad.pressed(GamepadButton::DPadLeft) || stick_x < -STICK_DEADZONE,
+            right: gamepad.pressed(GamepadButton::DPadRight) || stick_x > STICK_DEADZONE,
+            up: gamepad.pressed(GamepadButton::DPadUp) || stick_y > STICK_DEADZONE,
+            down: gamepad.pressed(GamepadButton::DPadDown) || stick_y < -STICK_DEADZONE,
+            walk: bindings.pressed(InputAction::Walk, gamepad)
+                || (stick_tilted && stick_x.abs() < STICK_WALK_THRESHOLD),
+            jump: bindings.just_pressed(InputAction::Jump, gamepad),
+            punch: bindings.just_pressed(InputAction::Punch, gamepad),
+            kick: bindings.just_pressed(InputAction::Kick, gamepad),
+            block: bindings.pressed(InputAction::Block, gamepad),
+            dash: bindings.just_pressed(InputAction::Dash, gamepad),
+            super_attack: bindings.just_pressed(InputAction::Super, gamepad),
+            burst: bindings.just_pressed(InputAction::Burst, gamepad),
+            grab: bindings.just_pressed(InputAction::Grab, gamepad),
+            interact: bindings.pressed(InputAction::Interact, gamepad),
+            emote: None,
+            pause: bindings.just_pressed(InputAction::Pause, gamepad),
+            menu_up: gamepad.just_pressed(GamepadButton::DPadUp),
+            menu_down: gamepad.just_pressed(GamepadButton::DPadDown),
+            confirm: gamepad.just_pressed(GamepadButton::South),
+            back: gamepad.just_pressed(GamepadButton::East),
+        }
+    }
+}
@@ -0,0 +1,332 @@
+use bevy::prelude::*;
+use bevy::reflect::{DynamicEnum, DynamicVariant};
+use std::collections::HashMap;
+
+/// Logical actions the player can perform, independent of physical keys
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InputAction {
+    MoveLeft,
+    MoveRight,
+    /// Step into the background along the depth lane
+    MoveUp,
+    /// Step toward the camera along the depth lane
+    MoveDown,
+    /// Held with a direction to walk instead of run
+    Walk,
+    Jump,
+    Punch,
+    Kick,
+    /// Held to guard against incoming hits
+    Block,
+    /// Dodge dash (also triggered by double-tapping a direction)
+    Dash,
+    /// Spend a full special meter on a screen-clearing super attack
+    Super,
+    /// Spend meter on a defensive burst, usable even while stunned or mid-attack
+    Burst,
+    /// Grab the ghost in front of the player and throw it
+    Grab,
+    /// Use whatever the player is standing at (pick up an item, revive a partner)
+    Interact,
+    /// Toggle the pause menu
+    Pause,
+    /// Capture a bug report bundle (screenshot, recent input, state snapshot)
+    BugReport,
+    /// Show or hide the combat telemetry overlay (for balancing sessions)
+    Telemetry,
+    /// Switch between windowed and fullscreen (Alt+Enter also works)
+    Fullscreen,
+    /// Training mode: put both fighters back at full health in their starting spots
+    TrainingReset,
+    /// Training mode: switch what the dummy does (stand still, block, attack)
+    DummyBehavior,
+    /// Play an emote while standing idle (see `Emote`)
+    EmoteWave,
+    EmoteFlex,
+    EmoteBow,
+}
+
+impl InputAction {
+    pub const ALL: [InputAction; 23] = [
+        InputAction::MoveLeft,
+        InputAction::MoveRight,
+        InputAction::MoveUp,
+        InputAction::MoveDown,
+        InputAction::Walk,
+        InputAction::Jump,
+        InputAction::Punch,
+        InputAction::Kick,
+        InputAction::Block,
+        InputAction::Dash,
+        InputAction::Super,
+        InputAction::Burst,
+        InputAction::Grab,
+        InputAction::Interact,
+        InputAction::Pause,
+        InputAction::BugReport,
+        InputAction::Telemetry,
+        InputAction::Fullscreen,
+        InputAction::TrainingReset,
+        InputAction::DummyBehavior,
+        InputAction::EmoteWave,
+        InputAction::EmoteFlex,
+        InputAction::EmoteBow,
+    ];
+
+    /// Stable name used in the saved settings file
+    pub fn key(self) -> &'static str {
+        match self {
+            InputAction::MoveLeft => "move_left",
+            InputAction::MoveRight => "move_right",
+            InputAction::MoveUp => "move_up",
+            InputAction::MoveDown => "move_down",
+            InputAction::Walk => "walk",
+            InputAction::Jump => "jump",
+            InputAction::Punch => "punch",
+            InputAction::Kick => "kick",
+            InputAction::Block => "block",
+            InputAction::Dash => "dash",
+            InputAction::Super => "super",
+            InputAction::Burst => "burst",
+            InputAction::Grab => "grab",
+            InputAction::Interact => "interact",
+            InputAction::Pause => "pause",
+            InputAction::BugReport => "bug_report",
+            InputAction::Telemetry => "telemetry",
+            InputAction::Fullscreen => "fullscreen",
+            InputAction::TrainingReset => "training_reset",
+            InputAction::DummyBehavior => "dummy_behavior",
+            InputAction::EmoteWave => "emote_wave",
+            InputAction::EmoteFlex => "emote_flex",
+            InputAction::EmoteBow => "emote_bow",
+        }
+    }
+}
+
+/// Keyboard bindings for each logical action
+///
+/// An action can be bound to several keys; it is active when any of them is.
+/// Rebind at runtime with `rebind` (replace) or `add_binding` (append).
+#[derive(Resource, Clone, Debug)]
+pub struct InputMap {
+    bindings: HashMap<InputAction, Vec<KeyCode>>,
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        let mut map = Self {
+            bindings: HashMap::new(),
+        };
+        map.rebind(InputAction::MoveLeft, KeyCode::KeyA);
+        map.rebind(InputAction::MoveRight, KeyCode::KeyD);
+        map.rebind(InputAction::MoveUp, KeyCode::KeyW);
+        map.rebind(InputAction::MoveDown, KeyCode::KeyS);
+        map.rebind(InputAction::Walk, KeyCode::ShiftLeft);
+        map.add_binding(InputAction::Walk, KeyCode::ShiftRight);
+        map.rebind(InputAction::Jump, KeyCode::Space);
+        map.rebind(InputAction::Punch, KeyCode::ArrowUp);
+        map.rebind(InputAction::Kick, KeyCode::ArrowDown);
+        map.rebind(InputAction::Block, KeyCode::KeyF);
+        map.rebind(InputAction::Dash, KeyCode::KeyE);
+        map.rebind(InputAction::Super, KeyCode::KeyQ);
+        map.rebind(InputAction::Burst, KeyCode::KeyV);
+        map.rebind(InputAction::Grab, KeyCode::KeyC);
+        map.rebind(InputAction::Interact, KeyCode::KeyG);
+        map.rebind(InputAction::Pause, KeyCode::Escape);
+        map.rebind(InputAction::BugReport, KeyCode::F8);
+        map.rebind(InputAction::Telemetry, KeyCode::F9);
+        map.rebind(InputAction::Fullscreen, KeyCode::F11);
+        map.rebind(InputAction::TrainingReset, KeyCode::KeyR);
+        map.rebind(InputAction::DummyBehavior, KeyCode::KeyT);
+        map.rebind(InputAction::EmoteWave, KeyCode::Digit1);
+        map.rebind(InputAction::EmoteFlex, KeyCode::Digit2);
+        map.rebind(InputAction::EmoteBow, KeyCode::Digit3);
+        map
+    }
+}
+
+/// Keyboard bindings for player 2 in versus games
+///
+/// Defaults to the numpad (see `InputMap::player_two`) so both players can
+/// share one keyboard.
+#[derive(Resource, Clone, Debug, Deref, DerefMut)]
+pub struct PlayerTwoInputMap(pub InputMap);
+
+impl Default for PlayerTwoInputMap {
+    fn default() -> Self {
+        Self(InputMap::player_two())
+    }
+}
+
+impl InputMap {
+    /// Numpad layout for player 2, clear of every player 1 key
+    ///
+    /// 4/6 move, 8/5 step along the depth lane, 0 jumps, 7 punches, 9 kicks,
+    /// 1 blocks, 3 dashes, 2 grabs, + supers, - bursts, and . walks; / * and
+    /// Enter play the wave, flex, and bow emotes. Player 2 has no pause,
+    /// interact, or training keys; those stay with player 1.
+    pub fn player_two() -> Self {
+        let mut map = Self {
+            bindings: HashMap::new(),
+        };
+        map.rebind(InputAction::MoveLeft, KeyCode::Numpad4);
+        map.rebind(InputAction::MoveRight, KeyCode::Numpad6);
+        map.rebind(InputAction::MoveUp, KeyCode::Numpad8);
+        map.rebind(InputAction::MoveDown, KeyCode::Numpad5);
+        map.rebind(InputAction::Walk, KeyCode::NumpadDecimal);
+        map.rebind(InputAction::Jump, KeyCode::Numpad0);
+        map.rebind(InputAction::Punch, KeyCode::Numpad7);
+        map.rebind(InputAction::Kick, KeyCode::Numpad9);
+        map.rebind(InputAction::Block, KeyCode::Numpad1);
+        map.rebind(InputAction::Dash, KeyCode::Numpad3);
+        map.rebind(InputAction::Super, KeyCode::NumpadAdd);
+        map.rebind(InputAction::Burst, KeyCode::NumpadSubtract);
+        map.rebind(InputAction::Grab, KeyCode::Numpad2);
+        map.rebind(InputAction::EmoteWave, KeyCode::NumpadDivide);
+        map.rebind(InputAction::EmoteFlex, KeyCode::NumpadMultiply);
+        map.rebind(InputAction::EmoteBow, KeyCode::NumpadEnter);
+        map
+    }
+
+    /// Replace all bindings for an action with a single key
+    ///
+    /// Warns if another action already uses the key (see `conflicts`).
+    pub fn rebind(&mut self, action: InputAction, key: KeyCode) {
+        self.warn_conflicts(action, key);
+        self.bindings.insert(action, vec![key]);
+    }
+
+    /// Add an extra key for an action, keeping existing bindings
+    ///
+    /// Warns if another action already uses the key (see `conflicts`).
+    pub fn add_binding(&mut self, action: InputAction, key: KeyCode) {
+        self.warn_conflicts(action, key);
+        let keys = self.bindings.entry(action).or_default();
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+
+    /// Actions other than `action` already bound to `key`
+    ///
+    /// Both would fire on one press, so a remap should move or clear them.
+    pub fn conflicts(&self, action: InputAction, key: KeyCode) -> Vec<InputAction> {
+        InputAction::ALL
+            .into_iter()
+            .filter(|&other| other != action && self.keys(other).contains(&key))
+            .collect()
+    }
+
+    fn warn_conflicts(&self, action: InputAction, key: KeyCode) {
+        let conflicts = self.conflicts(action, key);
+        if !conflicts.is_empty() {
+            warn!("Binding {key:?} to {action:?} conflicts with {conflicts:?}");
+        }
+    }
+
+    /// Saved form of the bindings: one `<prefix><action>=<keys>` line per bound
+    /// action (`InputAction::key`, comma-separated `KeyCode` names)
+    pub fn to_lines(&self, prefix: &str) -> String {
+        let mut lines = String::new();
+        for action in InputAction::ALL {
+            let keys: Vec<String> = self
+                .keys(action)
+                .iter()
+                .map(|key| format!("{key:?}"))
+                .collect();
+            if !keys.is_empty() {
+                lines.push_str(&format!("{prefix}{}={}\n", action.key(), keys.join(", ")));
+            }
+        }
+        lines
+    }
+
+    /// Every key bound to more than one action, with those actions
+    pub fn all_conflicts(&self) -> Vec<(KeyCode, Vec<InputAction>)> {
+        let mut conflicts: Vec<(KeyCode, Vec<InputAction>)> = Vec::new();
+        for action in InputAction::ALL {
+            for &key in self.keys(action) {
+                if conflicts.iter().any(|(seen, _)| *seen == key) {
+                    continue;
+                }
+                let actions: Vec<InputAction> = InputAction::ALL
+                    .into_iter()
+                    .filter(|other| self.keys(*other).contains(&key))
+                    .collect();
+                if actions.len() > 1 {
+                    conflicts.push((key, actions));
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// Apply bindings saved by `to_lines`, returning how many actions were rebound
+    ///
+    /// A saved action replaces all of its keys; actions not in `values` keep
+    /// theirs. Malformed lines are skipped, and conflicts in the result
+    /// reported, with a warning.
+    pub fn apply_lines(&mut self, values: &HashMap<&str, &str>, prefix: &str) -> usize {
+        let mut applied = 0;
+        for action in InputAction::ALL {
+            let Some(value) = values.get(format!("{prefix}{}", action.key()).as_str()) else {
+                continue;
+            };
+            let Some(keys) = value
+                .split(',')
+                .map(|name| parse_key_code(name.trim()))
+                .collect::<Option<Vec<_>>>()
+                .filter(|keys| !keys.is_empty())
+            else {
+                warn!("Can't parse the {action:?} keys: {value}");
+                continue;
+            };
+            self.bindings.insert(action, keys);
+            applied += 1;
+        }
+        for (key, actions) in self.all_conflicts() {
+            warn!("{key:?} is bound to several actions: {actions:?}");
+        }
+        applied
+    }
+
+    /// Keys currently bound to an action
+    pub fn keys(&self, action: InputAction) -> &[KeyCode] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Is any key bound to this action held?
+    pub fn pressed(&self, action: InputAction, keyboard: &ButtonInput<KeyCode>) -> bool {
+        keyboard.any_pressed(self.keys(action).iter().copied())
+    }
+
+    /// Name of the first key bound to an action, for on-screen prompts ("?" if unbound)
+    pub fn key_label(&self, action: InputAction) -> String {
+        self.keys(action)
+            .first()
+            .map_or("?".to_string(), |key| key_label(*key))
+    }
+
+    /// Was any key bound to this action pressed this frame?
+    pub fn just_pressed(&self, action: InputAction, keyboard: &ButtonInput<KeyCode>) -> bool {
+        keyboard.any_just_pressed(self.keys(action).iter().copied())
+    }
+}
+
+/// Short on-screen name for a key ("G", "5", "Space")
+pub fn key_label(key: KeyCode) -> String {
+    let name = format!("{key:?}");
+    name.strip_prefix("Key")
+        .or_else(|| name.strip_prefix("Digit"))
+        .unwrap_or(&name)
+        .to_string()
+}
+
+/// Key with the given variant name ("KeyA", "Space"), as written by `{key:?}`
+///
+/// Used to read keys back from the saved settings file. Only named keys
+/// parse; `Unidentified` native keys can't be saved.
+pub fn parse_key_code(name: &str) -> Option<KeyCode> {
+    let variant = DynamicEnum::new(name.to_string(), DynamicVariant::Unit);
+    KeyCode::from_reflect(&variant)
+}
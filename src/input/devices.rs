@@ -0,0 +1,69 @@
+use crate::game::AppState;
+use bevy::input::gamepad::GamepadConnectionEvent;
+use bevy::prelude::*;
+
+/// Input device options, chosen on the advanced settings screen
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DeviceSettings {
+    /// Pause the game when a gamepad disconnects mid-run
+    pub pause_on_disconnect: bool,
+}
+
+impl Default for DeviceSettings {
+    fn default() -> Self {
+        Self {
+            pause_on_disconnect: true,
+        }
+    }
+}
+
+/// Present while the game is paused because a gamepad disconnected
+///
+/// The pause screen shows the reconnect prompt instead of its menu while this
+/// exists. It is removed whenever the game leaves Paused.
+#[derive(Resource)]
+pub struct ControllerDisconnected;
+
+/// Pause a running game when a gamepad disconnects (if `DeviceSettings` allows it)
+pub fn pause_on_gamepad_disconnect(
+    mut commands: Commands,
+    settings: Res<DeviceSettings>,
+    mut connection_events: MessageReader<GamepadConnectionEvent>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let disconnected = connection_events
+        .read()
+        .any(GamepadConnectionEvent::disconnected);
+    if disconnected && settings.pause_on_disconnect {
+        commands.insert_resource(ControllerDisconnected);
+        next_state.set(AppState::Paused);
+    }
+}
+
+/// Resume a disconnect pause once input returns: the gamepad reconnects, or
+/// any key or gamepad button is pressed
+///
+/// The press that resumes only wakes the game; gameplay is frozen this frame,
+/// so it doesn't also land as an attack.
+pub fn resume_on_input_returned(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut connection_events: MessageReader<GamepadConnectionEvent>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let reconnected = connection_events
+        .read()
+        .any(GamepadConnectionEvent::connected);
+    let pressed = keyboard.get_just_pressed().next().is_some()
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.get_just_pressed().next().is_some());
+    if reconnected || pressed {
+        next_state.set(AppState::Playing);
+    }
+}
+
+/// Forget a disconnect pause when leaving Paused by any route
+pub fn clear_controller_disconnected(mut commands: Commands) {
+    commands.remove_resource::<ControllerDisconnected>();
+}
@@ -0,0 +1,14 @@
+pub mod actions;
+pub mod bindings;
+pub mod devices;
+pub mod plugin;
+pub mod profiles;
+pub mod systems;
+
+// Re-export commonly used items
+pub use actions::*;
+pub use bindings::*;
+pub use devices::*;
+pub use plugin::*;
+pub use profiles::*;
+pub use systems::*;
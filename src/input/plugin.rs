@@ -0,0 +1,39 @@
+use crate::game::{AppState, GameSet};
+use crate::input::actions::{PlayerActions, PlayerTwoActions};
+use crate::input::bindings::{InputMap, PlayerTwoInputMap};
+use crate::input::devices::{
+    ControllerDisconnected, DeviceSettings, clear_controller_disconnected,
+    pause_on_gamepad_disconnect, resume_on_input_returned,
+};
+use crate::input::profiles::{GamepadProfiles, register_gamepad_profiles};
+use crate::input::systems::{gather_player_actions, gather_player_two_actions};
+use crate::settings::versus_mode;
+use bevy::prelude::*;
+
+/// Keyboard bindings and per-controller gamepad profiles, resolved into `PlayerActions`
+/// (and `PlayerTwoActions` in versus games) each frame, and gamepad connection handling
+pub struct InputPlugin;
+
+impl Plugin for InputPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputMap>()
+            .init_resource::<PlayerTwoInputMap>()
+            .init_resource::<GamepadProfiles>()
+            .init_resource::<PlayerActions>()
+            .init_resource::<PlayerTwoActions>()
+            .init_resource::<DeviceSettings>()
+            .add_systems(OnExit(AppState::Paused), clear_controller_disconnected)
+            .add_systems(
+                Update,
+                (
+                    (register_gamepad_profiles, gather_player_actions).chain(),
+                    gather_player_two_actions.run_if(versus_mode),
+                    pause_on_gamepad_disconnect.run_if(in_state(AppState::Playing)),
+                    resume_on_input_returned.run_if(
+                        in_state(AppState::Paused).and(resource_exists::<ControllerDisconnected>),
+                    ),
+                )
+                    .in_set(GameSet::Input),
+            );
+    }
+}
@@ -0,0 +1,265 @@
+use crate::input::bindings::{InputAction, InputMap};
+use crate::persistence::{SaveQueue, list_data_files, parse_key_values, read_data_file};
+use bevy::prelude::*;
+use bevy::reflect::{DynamicEnum, DynamicVariant};
+use std::collections::{BTreeMap, HashMap};
+
+/// Folder in the data directory that binding profiles are exported to and imported from
+pub const BINDINGS_DIR: &str = "bindings";
+/// Exported keyboard profile, in `BINDINGS_DIR`
+const KEYBOARD_PROFILE_FILE: &str = "keyboard.txt";
+/// Prefix of exported gamepad profiles, in `BINDINGS_DIR` (`gamepad-<profile>.txt`)
+const GAMEPAD_PROFILE_PREFIX: &str = "gamepad-";
+
+/// Gamepad button for each action on one kind of controller
+///
+/// Covers the button actions; the left stick and d-pad always move, and
+/// South/East always confirm/go back in menus, so a remap can't lock anyone
+/// out of the menus. Rebind at runtime with `rebind`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GamepadBindings {
+    buttons: HashMap<InputAction, GamepadButton>,
+}
+
+impl Default for GamepadBindings {
+    /// South jumps, West punches, North kicks, the right bumper blocks, East
+    /// dashes, the right trigger supers, clicking the left stick bursts,
+    /// clicking the right stick grabs, the left trigger interacts, the left
+    /// bumper walks, and Start pauses
+    fn default() -> Self {
+        let mut bindings = Self {
+            buttons: HashMap::new(),
+        };
+        bindings.rebind(InputAction::Walk, GamepadButton::LeftTrigger);
+        bindings.rebind(InputAction::Jump, GamepadButton::South);
+        bindings.rebind(InputAction::Punch, GamepadButton::West);
+        bindings.rebind(InputAction::Kick, GamepadButton::North);
+        bindings.rebind(InputAction::Block, GamepadButton::RightTrigger);
+        bindings.rebind(InputAction::Dash, GamepadButton::East);
+        bindings.rebind(InputAction::Super, GamepadButton::RightTrigger2);
+        bindings.rebind(InputAction::Burst, GamepadButton::LeftThumb);
+        bindings.rebind(InputAction::Grab, GamepadButton::RightThumb);
+        bindings.rebind(InputAction::Interact, GamepadButton::LeftTrigger2);
+        bindings.rebind(InputAction::Pause, GamepadButton::Start);
+        bindings
+    }
+}
+
+impl GamepadBindings {
+    /// Button bound to an action, if any
+    pub fn button(&self, action: InputAction) -> Option<GamepadButton> {
+        self.buttons.get(&action).copied()
+    }
+
+    /// Bind an action to a button, replacing its old one
+    ///
+    /// Warns if another action already uses the button (see `conflicts`).
+    pub fn rebind(&mut self, action: InputAction, button: GamepadButton) {
+        let conflicts = self.conflicts(action, button);
+        if !conflicts.is_empty() {
+            warn!("Binding {button:?} to {action:?} conflicts with {conflicts:?}");
+        }
+        self.buttons.insert(action, button);
+    }
+
+    /// Actions other than `action` already bound to `button`
+    pub fn conflicts(&self, action: InputAction, button: GamepadButton) -> Vec<InputAction> {
+        InputAction::ALL
+            .into_iter()
+            .filter(|&other| other != action && self.button(other) == Some(button))
+            .collect()
+    }
+
+    /// Is the action's button held?
+    pub fn pressed(&self, action: InputAction, gamepad: &Gamepad) -> bool {
+        self.button(action)
+            .is_some_and(|button| gamepad.pressed(button))
+    }
+
+    /// Was the action's button pressed this frame?
+    pub fn just_pressed(&self, action: InputAction, gamepad: &Gamepad) -> bool {
+        self.button(action)
+            .is_some_and(|button| gamepad.just_pressed(button))
+    }
+
+    /// Saved form of the bindings: one `<prefix><action>=<button>` line per bound action
+    pub fn to_lines(&self, prefix: &str) -> String {
+        let mut lines = String::new();
+        for action in InputAction::ALL {
+            if let Some(button) = self.button(action) {
+                lines.push_str(&format!("{prefix}{}={button:?}\n", action.key()));
+            }
+        }
+        lines
+    }
+
+    /// Apply bindings saved by `to_lines`, returning how many actions were rebound
+    ///
+    /// Malformed lines are skipped, and conflicts in the result reported, with a warning.
+    pub fn apply_lines(&mut self, values: &HashMap<&str, &str>, prefix: &str) -> usize {
+        let mut applied = 0;
+        for action in InputAction::ALL {
+            let Some(value) = values.get(format!("{prefix}{}", action.key()).as_str()) else {
+                continue;
+            };
+            let Some(button) = parse_gamepad_button(value) else {
+                warn!("Can't parse the {action:?} button: {value}");
+                continue;
+            };
+            self.buttons.insert(action, button);
+            applied += 1;
+        }
+        let mut checked = Vec::new();
+        for button in InputAction::ALL
+            .into_iter()
+            .filter_map(|action| self.button(action))
+        {
+            if checked.contains(&button) {
+                continue;
+            }
+            checked.push(button);
+            let actions: Vec<InputAction> = InputAction::ALL
+                .into_iter()
+                .filter(|action| self.button(*action) == Some(button))
+                .collect();
+            if actions.len() > 1 {
+                warn!("{button:?} is bound to several actions: {actions:?}");
+            }
+        }
+        applied
+    }
+}
+
+/// Button with the given variant name ("South", "LeftTrigger2"), as written by `{button:?}`
+pub fn parse_gamepad_button(name: &str) -> Option<GamepadButton> {
+    let variant = DynamicEnum::new(name.to_string(), DynamicVariant::Unit);
+    GamepadButton::from_reflect(&variant)
+}
+
+/// Profile name for a controller: its device name in lowercase, with runs of
+/// anything but letters and digits turned into `-` ("Xbox Wireless Controller"
+/// -> "xbox-wireless-controller")
+pub fn gamepad_profile_name(device_name: &str) -> String {
+    device_name
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Gamepad bindings per kind of controller, saved with the other settings
+///
+/// Each controller uses the profile named after its device name (see
+/// `gamepad_profile_name`), so identical controllers share one. A profile is
+/// created with the default layout the first time its controller connects.
+/// Controllers without a name use the default layout.
+#[derive(Resource, Default)]
+pub struct GamepadProfiles {
+    profiles: BTreeMap<String, GamepadBindings>,
+    /// Layout for controllers without a profile
+    default: GamepadBindings,
+}
+
+impl GamepadProfiles {
+    /// Bindings for a controller's profile (the default layout if it has none)
+    pub fn bindings(&self, profile: Option<&str>) -> &GamepadBindings {
+        profile
+            .and_then(|profile| self.profiles.get(profile))
+            .unwrap_or(&self.default)
+    }
+
+    /// Bindings for a profile, creating it with the default layout if needed
+    pub fn bindings_mut(&mut self, profile: &str) -> &mut GamepadBindings {
+        self.profiles.entry(profile.to_string()).or_default()
+    }
+
+    /// Saved form of every profile: `<prefix><profile>.<action>=<button>` lines
+    pub fn to_lines(&self, prefix: &str) -> String {
+        self.profiles
+            .iter()
+            .map(|(name, bindings)| bindings.to_lines(&format!("{prefix}{name}.")))
+            .collect()
+    }
+
+    /// Apply profiles saved by `to_lines`, creating any that don't exist yet
+    pub fn apply_lines(&mut self, values: &HashMap<&str, &str>, prefix: &str) {
+        let names: Vec<&str> = values
+            .keys()
+            .filter_map(|key| key.strip_prefix(prefix)?.rsplit_once('.'))
+            .map(|(name, _)| name)
+            .collect();
+        for name in names {
+            self.bindings_mut(name)
+                .apply_lines(values, &format!("{prefix}{name}."));
+        }
+    }
+}
+
+/// Create a profile for each newly connected controller
+///
+/// Runs every frame in the input phase; a controller's `Name` is its device name.
+pub fn register_gamepad_profiles(
+    mut profiles: ResMut<GamepadProfiles>,
+    gamepad_query: Query<&Name, Added<Gamepad>>,
+) {
+    for name in gamepad_query.iter() {
+        let profile = gamepad_profile_name(name.as_str());
+        if !profile.is_empty() {
+            profiles.bindings_mut(&profile);
+        }
+    }
+}
+
+/// Write the keyboard bindings and every gamepad profile to `BINDINGS_DIR`,
+/// returning how many files were queued
+///
+/// `keyboard.txt` has `key.<action>` lines like the settings file, and each
+/// `gamepad-<profile>.txt` has `button.<action>` lines, so they can be
+/// edited by hand, shared, and read back with `import_binding_profiles`.
+pub fn export_binding_profiles(
+    input_map: &InputMap,
+    profiles: &GamepadProfiles,
+    save_queue: &mut SaveQueue,
+) -> usize {
+    save_queue.queue(
+        &format!("{BINDINGS_DIR}/{KEYBOARD_PROFILE_FILE}"),
+        format!("# Keyboard bindings\n{}", input_map.to_lines("key.")),
+    );
+    for (name, bindings) in &profiles.profiles {
+        save_queue.queue(
+            &format!("{BINDINGS_DIR}/{GAMEPAD_PROFILE_PREFIX}{name}.txt"),
+            format!(
+                "# Gamepad bindings for {name}\n{}",
+                bindings.to_lines("button.")
+            ),
+        );
+    }
+    1 + profiles.profiles.len()
+}
+
+/// Read every profile file in `BINDINGS_DIR` back in, returning how many were applied
+///
+/// `keyboard.txt` replaces the keyboard bindings it lists, and each
+/// `gamepad-<profile>.txt` the bindings of that profile (created if it's a
+/// controller that hasn't connected yet). Other files are ignored.
+pub fn import_binding_profiles(input_map: &mut InputMap, profiles: &mut GamepadProfiles) -> usize {
+    let mut imported = 0;
+    for file in list_data_files(BINDINGS_DIR) {
+        let Some(contents) = read_data_file(&format!("{BINDINGS_DIR}/{file}")) else {
+            continue;
+        };
+        let values = parse_key_values(&contents);
+        if file == KEYBOARD_PROFILE_FILE {
+            input_map.apply_lines(&values, "key.");
+            imported += 1;
+        } else if let Some(name) = file
+            .strip_prefix(GAMEPAD_PROFILE_PREFIX)
+            .and_then(|name| name.strip_suffix(".txt"))
+        {
+            profiles.bindings_mut(name).apply_lines(&values, "button.");
+            imported += 1;
+        }
+    }
+    imported
+}
@@ -0,0 +1,168 @@
+use crate::game::InGame;
+use crate::input::actions::{PlayerActions, PlayerTwoActions};
+use crate::input::bindings::{InputMap, PlayerTwoInputMap};
+use crate::input::profiles::{GamepadProfiles, gamepad_profile_name};
+use crate::settings::{GameMode, Mutators};
+use crate::world::Cinematic;
+use bevy::prelude::*;
+
+/// Max seconds between two presses of the same direction to count as a double-tap
+const DOUBLE_TAP_WINDOW: f32 = 0.25;
+/// Vertical stick deflection that counts as a menu up/down press
+const MENU_STICK_THRESHOLD: f32 = 0.5;
+
+/// Time of the most recent left/right press, used to detect double-tap dashes
+#[derive(Default)]
+pub struct DoubleTapTracker {
+    last_left_press: Option<f32>,
+    last_right_press: Option<f32>,
+}
+
+impl DoubleTapTracker {
+    /// Record a direction press and report whether it completes a double-tap
+    fn press(last_press: &mut Option<f32>, now: f32) -> bool {
+        let double_tap = last_press.is_some_and(|last| now - last <= DOUBLE_TAP_WINDOW);
+        // A completed double-tap consumes the press so a triple-tap doesn't dash twice
+        *last_press = if double_tap { None } else { Some(now) };
+        double_tap
+    }
+
+    /// Add `dash` on a double-tapped direction
+    ///
+    /// Direction presses are rising edges against last frame's actions.
+    fn detect(&mut self, actions: &mut PlayerActions, previous: &PlayerActions, now: f32) {
+        if actions.left && !previous.left {
+            actions.dash |= Self::press(&mut self.last_left_press, now);
+        }
+        if actions.right && !previous.right {
+            actions.dash |= Self::press(&mut self.last_right_press, now);
+        }
+    }
+}
+
+/// The gamepad that drives player 2, if any
+///
+/// Only in a running versus game with at least two gamepads connected: the
+/// first gamepad stays with player 1 and the second goes to player 2. Outside
+/// versus every gamepad drives player 1, so menus work from any pad.
+fn player_two_gamepad(
+    mode: &GameMode,
+    in_game: bool,
+    gamepads: &Query<(Entity, &Gamepad, Option<&Name>)>,
+) -> Option<Entity> {
+    if *mode != GameMode::Versus || !in_game {
+        return None;
+    }
+    gamepads.iter().map(|(entity, ..)| entity).nth(1)
+}
+
+/// Read a gamepad with its profile's bindings (see `GamepadProfiles`)
+fn gamepad_actions(
+    profiles: &GamepadProfiles,
+    gamepad: &Gamepad,
+    name: Option<&Name>,
+) -> PlayerActions {
+    let profile = name.map(|name| gamepad_profile_name(name.as_str()));
+    PlayerActions::from_gamepad(gamepad, profiles.bindings(profile.as_deref()))
+}
+
+/// Gather player actions from every input source into the PlayerActions resource
+///
+/// Runs before any system that reads player input. Each device is read
+/// independently and merged, so a keyboard and any number of gamepads can
+/// drive the player at the same time. Double-tapping a direction on any
+/// device also sets `dash`. Mirror mode swaps left and right here, so every
+/// state reads the mirrored controls without knowing about it. Pushing a stick
+/// up or down past `MENU_STICK_THRESHOLD` sets `menu_up`/`menu_down` once per push.
+/// During a `Cinematic` camera sequence only pause and menu actions get through.
+/// In a versus game the second gamepad belongs to player 2 and is skipped here.
+#[allow(clippy::too_many_arguments)]
+pub fn gather_player_actions(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    gamepad_profiles: Res<GamepadProfiles>,
+    gamepads: Query<(Entity, &Gamepad, Option<&Name>)>,
+    time: Res<Time>,
+    mutators: Res<Mutators>,
+    mode: Res<GameMode>,
+    in_game: Option<Res<State<InGame>>>,
+    cinematic: Option<Res<Cinematic>>,
+    mut double_tap: Local<DoubleTapTracker>,
+    mut last_stick_dir: Local<i8>,
+    mut actions: ResMut<PlayerActions>,
+) {
+    let mut merged = PlayerActions::from_keyboard(&keyboard, &input_map);
+    let mut stick_dir = 0;
+    let player_two_pad = player_two_gamepad(&mode, in_game.is_some(), &gamepads);
+
+    for (entity, gamepad, name) in gamepads.iter() {
+        if Some(entity) == player_two_pad {
+            continue;
+        }
+        merged.merge(&gamepad_actions(&gamepad_profiles, gamepad, name));
+
+        let stick_y = gamepad.left_stick().y;
+        if stick_y > MENU_STICK_THRESHOLD {
+            stick_dir = 1;
+        } else if stick_y < -MENU_STICK_THRESHOLD {
+            stick_dir = -1;
+        }
+    }
+
+    // Stick menu movement fires when the stick enters a direction, not while held
+    if stick_dir != *last_stick_dir {
+        merged.menu_up |= stick_dir == 1;
+        merged.menu_down |= stick_dir == -1;
+    }
+    *last_stick_dir = stick_dir;
+
+    if mutators.mirror {
+        std::mem::swap(&mut merged.left, &mut merged.right);
+    }
+
+    if cinematic.is_some() {
+        merged = merged.menu_only();
+    }
+
+    double_tap.detect(&mut merged, &actions, time.elapsed_secs());
+    *actions = merged;
+}
+
+/// Gather player 2's actions (versus games only) from their keys and gamepad
+///
+/// Same rules as `gather_player_actions`: mirror mode swaps left and right,
+/// double-tapping dashes, and a `Cinematic` freezes the fighter. Player 2
+/// never gets pause or menu actions.
+#[allow(clippy::too_many_arguments)]
+pub fn gather_player_two_actions(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    input_map: Res<PlayerTwoInputMap>,
+    gamepad_profiles: Res<GamepadProfiles>,
+    gamepads: Query<(Entity, &Gamepad, Option<&Name>)>,
+    time: Res<Time>,
+    mutators: Res<Mutators>,
+    mode: Res<GameMode>,
+    in_game: Option<Res<State<InGame>>>,
+    cinematic: Option<Res<Cinematic>>,
+    mut double_tap: Local<DoubleTapTracker>,
+    mut actions: ResMut<PlayerTwoActions>,
+) {
+    let mut merged = PlayerActions::from_keyboard(&keyboard, &input_map);
+    if let Some(pad) = player_two_gamepad(&mode, in_game.is_some(), &gamepads)
+        && let Ok((_, gamepad, name)) = gamepads.get(pad)
+    {
+        merged.merge(&gamepad_actions(&gamepad_profiles, gamepad, name));
+    }
+    let mut merged = merged.fighting_only();
+
+    if mutators.mirror {
+        std::mem::swap(&mut merged.left, &mut merged.right);
+    }
+
+    if cinematic.is_some() {
+        merged = PlayerActions::default();
+    }
+
+    double_tap.detect(&mut merged, &actions, time.elapsed_secs());
+    actions.0 = merged;
+}
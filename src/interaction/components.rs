@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+
+/// How the interact button triggers an interaction
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InteractionTrigger {
+    /// A single press
+    Press,
+    /// Held for this many seconds without letting go
+    Hold(f32),
+}
+
+/// Something the player can use with the interact button when standing next to it
+///
+/// While the player is within `range` of it in the same lane, a prompt such as
+/// "Press G to pick up" floats above it. Completing the interaction sends an
+/// `InteractEvent`; the feature that spawned the entity (pickups, weapons,
+/// props, revives) reacts to it.
+#[derive(Component, Clone, Debug)]
+pub struct Interactable {
+    /// What the interaction does, shown after the key ("pick up", "revive")
+    pub action: String,
+    pub trigger: InteractionTrigger,
+    /// Horizontal distance from the player at which the prompt appears
+    pub range: f32,
+}
+
+impl Interactable {
+    /// Interaction triggered by pressing the interact button
+    pub fn press(action: impl Into<String>, range: f32) -> Self {
+        Self {
+            action: action.into(),
+            trigger: InteractionTrigger::Press,
+            range,
+        }
+    }
+
+    /// Interaction triggered by holding the interact button for `seconds`
+    pub fn hold(action: impl Into<String>, seconds: f32, range: f32) -> Self {
+        Self {
+            action: action.into(),
+            trigger: InteractionTrigger::Hold(seconds),
+            range,
+        }
+    }
+
+    /// Prompt text for the given key ("Press G to pick up", "Hold G to revive")
+    pub fn prompt(&self, key: &str) -> String {
+        let verb = match self.trigger {
+            InteractionTrigger::Press => "Press",
+            InteractionTrigger::Hold(_) => "Hold",
+        };
+        format!("{verb} {key} to {}", self.action)
+    }
+}
+
+/// The interactable the player is standing at, if any, and how long interact has been held
+#[derive(Resource, Default)]
+pub struct InteractionFocus {
+    pub target: Option<Entity>,
+    /// Seconds the interact button has been held on the current target
+    pub held: f32,
+    /// Set after a hold completes, so the button must be released before the next one
+    pub needs_release: bool,
+}
+
+/// World-space prompt text shown above the focused interactable
+#[derive(Component)]
+pub struct InteractionPrompt;
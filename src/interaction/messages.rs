@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+
+/// Message sent when the player completes an interaction
+#[derive(Message)]
+pub struct InteractEvent {
+    /// The `Interactable` that was used
+    pub target: Entity,
+}
@@ -0,0 +1,9 @@
+pub mod components;
+pub mod messages;
+pub mod plugin;
+pub mod systems;
+
+// Re-export commonly used items
+pub use components::*;
+pub use messages::*;
+pub use plugin::*;
@@ -0,0 +1,30 @@
+use crate::game::{AppState, GameSet, InGame};
+use crate::interaction::components::InteractionFocus;
+use crate::interaction::messages::InteractEvent;
+use crate::interaction::systems::{
+    clear_interaction_focus, handle_interaction_input, spawn_interaction_prompt,
+    update_interaction_focus, update_interaction_prompt,
+};
+use bevy::prelude::*;
+
+/// Contextual interact-button prompts and the interactions they trigger
+pub struct InteractionPlugin;
+
+impl Plugin for InteractionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InteractionFocus>()
+            .add_message::<InteractEvent>()
+            .add_systems(OnEnter(InGame), spawn_interaction_prompt)
+            .add_systems(OnExit(AppState::Playing), clear_interaction_focus)
+            .add_systems(
+                Update,
+                (
+                    (update_interaction_focus, handle_interaction_input)
+                        .chain()
+                        .run_if(in_state(AppState::Playing))
+                        .in_set(GameSet::Interactions),
+                    update_interaction_prompt.in_set(GameSet::Hud),
+                ),
+            );
+    }
+}
@@ -0,0 +1,144 @@
+use crate::game::InGame;
+use crate::input::{InputAction, InputMap, PlayerActions};
+use crate::interaction::components::{
+    Interactable, InteractionFocus, InteractionPrompt, InteractionTrigger,
+};
+use crate::interaction::messages::InteractEvent;
+use crate::player::{JumpPhysics, Player};
+use crate::ui::text_style::{TextRole, TextStyles};
+use crate::world::{depth, same_lane};
+use bevy::prelude::*;
+
+/// Height of the prompt above the interactable's position
+const PROMPT_OFFSET_Y: f32 = 50.0;
+
+/// Focus the nearest interactable within range in the player's lane
+///
+/// Switching targets (or walking away) resets any hold in progress.
+pub fn update_interaction_focus(
+    mut focus: ResMut<InteractionFocus>,
+    player_query: Query<(&Transform, &JumpPhysics), With<Player>>,
+    interactable_query: Query<(Entity, &Transform, &Interactable)>,
+) {
+    let target = player_query
+        .single()
+        .ok()
+        .and_then(|(player_transform, jump_physics)| {
+            let player_depth = depth(player_transform, Some(jump_physics));
+            interactable_query
+                .iter()
+                .filter_map(|(entity, transform, interactable)| {
+                    let distance = (transform.translation.x - player_transform.translation.x).abs();
+                    (distance <= interactable.range
+                        && same_lane(player_depth, transform.translation.y))
+                    .then_some((entity, distance))
+                })
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(entity, _)| entity)
+        });
+
+    if target != focus.target {
+        focus.target = target;
+        focus.held = 0.0;
+    }
+}
+
+/// Trigger the focused interaction on a press, or once the hold time is reached
+pub fn handle_interaction_input(
+    time: Res<Time>,
+    actions: Res<PlayerActions>,
+    mut focus: ResMut<InteractionFocus>,
+    mut was_pressed: Local<bool>,
+    interactable_query: Query<&Interactable>,
+    mut interact_events: MessageWriter<InteractEvent>,
+) {
+    let just_pressed = actions.interact && !*was_pressed;
+    *was_pressed = actions.interact;
+
+    if !actions.interact {
+        focus.held = 0.0;
+        focus.needs_release = false;
+        return;
+    }
+
+    let Some(target) = focus.target else {
+        return;
+    };
+    let Ok(interactable) = interactable_query.get(target) else {
+        return;
+    };
+
+    match interactable.trigger {
+        InteractionTrigger::Press => {
+            if just_pressed {
+                interact_events.write(InteractEvent { target });
+            }
+        }
+        InteractionTrigger::Hold(seconds) => {
+            if focus.needs_release {
+                return;
+            }
+            focus.held += time.delta_secs();
+            if focus.held >= seconds {
+                interact_events.write(InteractEvent { target });
+                focus.held = 0.0;
+                focus.needs_release = true;
+            }
+        }
+    }
+}
+
+/// Drop the focus when play stops (pause or game over), hiding the prompt
+pub fn clear_interaction_focus(mut focus: ResMut<InteractionFocus>) {
+    *focus = InteractionFocus::default();
+}
+
+/// Spawn the (initially hidden) interaction prompt
+///
+/// Runs on entering InGame alongside the HUD.
+pub fn spawn_interaction_prompt(mut commands: Commands, styles: Res<TextStyles>) {
+    commands.spawn((
+        Text2d::new(""),
+        styles.font(TextRole::Dialogue),
+        TextColor(Color::WHITE),
+        Transform::from_xyz(0.0, 0.0, 6.0),
+        Visibility::Hidden,
+        InteractionPrompt,
+        DespawnOnExit(InGame),
+    ));
+}
+
+/// Place the prompt above the focused interactable, showing hold progress
+pub fn update_interaction_prompt(
+    focus: Res<InteractionFocus>,
+    input_map: Res<InputMap>,
+    interactable_query: Query<(&Transform, &Interactable), Without<InteractionPrompt>>,
+    mut prompt_query: Query<
+        (&mut Text2d, &mut Transform, &mut Visibility),
+        With<InteractionPrompt>,
+    >,
+) {
+    let Ok((mut text, mut transform, mut visibility)) = prompt_query.single_mut() else {
+        return;
+    };
+    let Some((target_transform, interactable)) = focus
+        .target
+        .and_then(|target| interactable_query.get(target).ok())
+    else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+
+    let mut prompt = interactable.prompt(&input_map.key_label(InputAction::Interact));
+    if let InteractionTrigger::Hold(seconds) = interactable.trigger
+        && focus.held > 0.0
+    {
+        prompt = format!("{prompt} ({:.0}%)", focus.held / seconds * 100.0);
+    }
+    if text.0 != prompt {
+        text.0 = prompt;
+    }
+    transform.translation.x = target_transform.translation.x;
+    transform.translation.y = target_transform.translation.y + PROMPT_OFFSET_Y;
+    *visibility = Visibility::Inherited;
+}
@@ -0,0 +1,150 @@
+use crate::combat::{Health, Invulnerable};
+use crate::game::GameplayEvent;
+use crate::levels::progression::{StageBoss, StageProgress};
+use crate::levels::stages::Levels;
+use crate::particle::{ParticleBurst, ParticleKind};
+use crate::player::Player;
+use crate::sound::{MusicStem, music_stem};
+use crate::ui::{TextStyles, score_popup};
+use crate::world::{CameraDirective, CameraDirector, Hazard, HazardKind, StageGeometry, hazard};
+use bevy::prelude::*;
+
+/// Seconds the boss and players are untouchable while a phase change plays out
+/// (as long as its camera sequence)
+const PHASE_CHANGE_SECONDS: f32 = 2.2;
+/// Screen shake when a phase change hits
+const PHASE_CHANGE_SHAKE: f32 = 16.0;
+
+/// One change to the arena when a boss enters a new phase
+#[derive(Clone, Debug)]
+pub enum EncounterAction {
+    /// Break a section of ground away, leaving a pit (x and depth, like a
+    /// stage's surface patches)
+    Collapse(Rect),
+    /// Spawn a hazard over a patch of ground
+    Hazard(HazardKind, Rect),
+    /// Replace the music stems playing over the background music (empty to
+    /// stop them)
+    Music(Vec<String>),
+}
+
+/// A phase of a boss fight, entered when the boss's health drops to a
+/// fraction of its max
+#[derive(Clone, Debug)]
+pub struct BossPhase {
+    /// Fraction of max health at or below which the phase starts
+    pub health_below: f32,
+    /// Text called over the boss as the phase starts (None for none)
+    pub call: Option<String>,
+    pub actions: Vec<EncounterAction>,
+}
+
+/// How far a boss has gone through its stage's `Stage::boss_phases`
+#[derive(Component, Default)]
+pub struct BossEncounter {
+    /// Phase changes already played (0 while in the opening phase)
+    pub phases_entered: usize,
+}
+
+/// Play the boss's next phase change once its health falls far enough
+///
+/// A phase change pans the camera to the boss and shakes it while the arena
+/// changes: ground collapses into pits, hazards appear, and the music stems
+/// switch over. New hazards are added to `StageGeometry::hazards` so enemies
+/// route around them. The boss and players can't be hurt until the camera is back.
+/// Only one phase starts per frame, so a big hit past two thresholds plays
+/// both in turn.
+#[allow(clippy::too_many_arguments)]
+pub fn run_boss_encounter(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    levels: Res<Levels>,
+    progress: Res<StageProgress>,
+    styles: Res<TextStyles>,
+    mut director: ResMut<CameraDirector>,
+    mut geometry: ResMut<StageGeometry>,
+    mut bursts: MessageWriter<ParticleBurst>,
+    mut gameplay_events: MessageWriter<GameplayEvent>,
+    mut boss_query: Query<(Entity, &Health, &Transform, &mut BossEncounter), With<StageBoss>>,
+    player_query: Query<Entity, With<Player>>,
+    stem_query: Query<Entity, With<MusicStem>>,
+) {
+    let stage = levels.stage(progress.index);
+    for (boss, health, transform, mut encounter) in boss_query.iter_mut() {
+        let Some(phase) = stage.boss_phases.get(encounter.phases_entered) else {
+            continue;
+        };
+        if health.current <= 0 || health.current as f32 > health.max as f32 * phase.health_below {
+            continue;
+        }
+        encounter.phases_entered += 1;
+
+        for action in &phase.actions {
+            match action {
+                EncounterAction::Collapse(area) => {
+                    commands.spawn(hazard(HazardKind::Pit, *area));
+                    geometry.hazards.push(*area);
+                    bursts.write(ParticleBurst {
+                        kind: ParticleKind::Debris,
+                        position: area.center(),
+                        direction: Vec2::Y,
+                    });
+                }
+                EncounterAction::Hazard(kind, area) => {
+                    commands.spawn(hazard(*kind, *area));
+                    geometry.hazards.push(*area);
+                }
+                EncounterAction::Music(stems) => {
+                    for entity in stem_query.iter() {
+                        commands.entity(entity).despawn();
+                    }
+                    for key in stems {
+                        commands.spawn(music_stem(&asset_server, key));
+                    }
+                }
+            }
+        }
+
+        let position = transform.translation.truncate();
+        director.play([
+            CameraDirective::PanTo {
+                target: position,
+                seconds: 0.5,
+            },
+            CameraDirective::Shake {
+                amplitude: PHASE_CHANGE_SHAKE,
+            },
+            CameraDirective::Hold { seconds: 1.2 },
+            CameraDirective::Return { seconds: 0.5 },
+        ]);
+        for entity in player_query.iter().chain([boss]) {
+            commands.entity(entity).insert(Invulnerable {
+                timer: Timer::from_seconds(PHASE_CHANGE_SECONDS, TimerMode::Once),
+            });
+        }
+        if let Some(call) = &phase.call {
+            commands.spawn(score_popup(&styles, call.clone(), position));
+        }
+        gameplay_events.write(GameplayEvent::BossPhase {
+            phase: encounter.phases_entered as u32 + 1,
+        });
+    }
+}
+
+/// Clear away what a boss encounter left in the arena: its hazards and music
+/// stems, putting `StageGeometry::hazards` back to the stage's own
+///
+/// Runs on entering StageClear and GameOver, on restart, and on leaving InGame.
+#[allow(clippy::type_complexity)]
+pub fn clear_boss_encounter(
+    mut commands: Commands,
+    levels: Res<Levels>,
+    progress: Res<StageProgress>,
+    mut geometry: ResMut<StageGeometry>,
+    leftover_query: Query<Entity, Or<(With<Hazard>, With<MusicStem>)>>,
+) {
+    geometry.hazards = levels.stage(progress.index).geometry().hazards;
+    for entity in leftover_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,9 @@
+pub mod encounter;
+pub mod plugin;
+pub mod progression;
+pub mod stages;
+
+// Re-export commonly used items
+pub use plugin::*;
+pub use progression::*;
+pub use stages::*;
@@ -0,0 +1,65 @@
+use crate::game::systems::{count_down, handle_restart, start_match};
+use crate::game::{AppState, GameSet, InGame, RestartGameEvent, restart_round, round_fighting};
+use crate::levels::encounter::{clear_boss_encounter, run_boss_encounter};
+use crate::levels::progression::{
+    StageProgress, apply_stage, clear_stage_field, enter_next_stage, reset_stage_progress,
+    restart_stages, start_stages, update_stage, update_zones,
+};
+use crate::levels::stages::Levels;
+use crate::player::spawn::spawn_player;
+use crate::settings::arcade_mode;
+use bevy::prelude::*;
+
+/// Arcade stages: each stage's art, geometry, and enemies, its clock or
+/// scrolling zones, its boss (with the boss's scripted phase changes), and the
+/// stage clear screen between stages (see src/ui/stage_clear.rs)
+pub struct LevelsPlugin;
+
+impl Plugin for LevelsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Levels::load())
+            .init_resource::<StageProgress>()
+            .add_systems(
+                OnEnter(InGame),
+                start_stages.after(start_match).after(spawn_player),
+            )
+            .add_systems(
+                OnExit(InGame),
+                (clear_boss_encounter, reset_stage_progress).chain(),
+            )
+            .add_systems(
+                OnEnter(AppState::StageClear),
+                (clear_stage_field, clear_boss_encounter),
+            )
+            .add_systems(OnEnter(AppState::GameOver), clear_boss_encounter)
+            .add_systems(
+                OnTransition {
+                    exited: AppState::StageClear,
+                    entered: AppState::Playing,
+                },
+                enter_next_stage,
+            )
+            .add_systems(
+                Update,
+                (
+                    apply_stage.in_set(GameSet::Input),
+                    (update_zones, update_stage, run_boss_encounter)
+                        .chain()
+                        .after(count_down)
+                        .run_if(
+                            in_state(AppState::Playing)
+                                .and(round_fighting)
+                                .and(arcade_mode),
+                        )
+                        .in_set(GameSet::Spawning),
+                    (
+                        restart_stages,
+                        clear_boss_encounter.run_if(on_message::<RestartGameEvent>),
+                    )
+                        .after(handle_restart)
+                        .after(restart_round)
+                        .in_set(GameSet::Restart),
+                ),
+            );
+    }
+}
@@ -0,0 +1,488 @@
+use crate::combat::{EnemyDefeatedEvent, Invulnerable, Knockback};
+use crate::debug::debug_name;
+use crate::enemy::{Enemy, EnemyState, SpawnDirector};
+use crate::game::{AppState, GameState, GameplayEvent, RestartGameEvent, Round};
+use crate::levels::encounter::BossEncounter;
+use crate::levels::stages::{Levels, Stage};
+use crate::pickup::Pickup;
+use crate::player::spawn::player_start;
+use crate::player::{JumpPhysics, Player, PlayerState, PlayerStateType, PlayerTwo};
+use crate::prefab::{PrefabSpawner, StageProp};
+use crate::projectile::Projectile;
+use crate::settings::{Difficulty, GameMode, MatchSettings, Mutators};
+use crate::ui::{TextStyles, score_popup};
+use crate::world::{CameraDirector, CameraLock, StageGeometry};
+use bevy::prelude::*;
+use rand::Rng;
+use std::time::Duration;
+
+/// How far into a scrolling stage's first zone the players start
+const ZONE_START_INSET: f32 = 100.0;
+/// How far into a combat zone a player walks before it locks
+const ZONE_TRIGGER_INSET: f32 = 200.0;
+
+/// Where an arcade run is in its current stage
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StagePhase {
+    /// The stage clock is running and enemies spawn in waves (or, on a
+    /// scrolling stage, the camera is locked on a combat zone while its
+    /// enemies spawn)
+    #[default]
+    Waves,
+    /// Scrolling stages: the camera is free and the players walk on to the
+    /// next zone (no spawns; the "GO" prompt is up)
+    Advancing,
+    /// The clock ran out and the boss is on the field (no more wave spawns)
+    Boss,
+    /// The stage was cleared: the stage clear screen is up, or the run is won
+    Cleared,
+}
+
+/// The stage being fought and how far into it the run is
+///
+/// Only arcade runs move past the first stage. Reset to the first stage on
+/// restart and on leaving InGame, so the menus show the graveyard.
+#[derive(Resource, Default)]
+pub struct StageProgress {
+    /// Index into `Levels`
+    pub index: usize,
+    pub phase: StagePhase,
+    /// Scrolling stages: the zone being fought or walked to (the boss's area
+    /// once it's past the last zone)
+    pub zone: usize,
+    /// Enemies still to spawn in the locked zone (None outside zone fights,
+    /// where the clock and threat budget decide)
+    pub zone_spawns_left: Option<u32>,
+}
+
+impl StageProgress {
+    /// Stage number for banners and screens (from 1)
+    pub fn number(&self) -> u32 {
+        self.index as u32 + 1
+    }
+
+    /// True once the last stage has been cleared
+    pub fn completed(&self, levels: &Levels) -> bool {
+        self.phase == StagePhase::Cleared && self.index + 1 >= levels.count()
+    }
+
+    /// Start stage `index`: advancing to its first zone if it scrolls (arcade
+    /// only), otherwise straight into its waves
+    fn begin(index: usize, stage: &Stage, mode: GameMode) -> Self {
+        let phase = if mode == GameMode::Arcade && stage.is_scrolling() {
+            StagePhase::Advancing
+        } else {
+            StagePhase::Waves
+        };
+        Self {
+            index,
+            phase,
+            ..default()
+        }
+    }
+
+    /// Is it time for the boss: the clock ran out, or on a scrolling stage,
+    /// the players reached the boss's area
+    fn boss_due(&self, stage: &Stage, game_state: &GameState) -> bool {
+        if stage.is_scrolling() {
+            self.zone >= stage.zones.len()
+        } else {
+            game_state.timer.is_finished()
+        }
+    }
+}
+
+/// The current stage's boss
+#[derive(Component)]
+pub struct StageBoss;
+
+/// Run condition: wave spawns are open (not during a boss fight or after a clear)
+pub fn stage_waves(progress: Res<StageProgress>) -> bool {
+    progress.phase == StagePhase::Waves
+}
+
+/// Put the stage clock and threat budget of `stage` on the match
+///
+/// Scrolling stages have no time limit, so their clock only counts up.
+fn begin_stage_clock(
+    game_state: &mut GameState,
+    stage: &Stage,
+    difficulty: Difficulty,
+    mode: GameMode,
+    settings: &MatchSettings,
+) {
+    game_state.threat_budget = difficulty.threat_budget() + stage.extra_threat;
+    if mode != GameMode::Arcade {
+        return;
+    }
+    if stage.is_scrolling() {
+        game_state.timer = Timer::new(Duration::MAX, TimerMode::Once);
+        game_state.game_duration = f32::INFINITY;
+    } else {
+        let duration = stage.duration.unwrap_or(settings.duration);
+        game_state.timer = Timer::from_seconds(duration, TimerMode::Once);
+        game_state.game_duration = duration;
+    }
+    game_state.last_spawn_time = 0.0;
+}
+
+/// Where a player starts `stage`: the left end of a scrolling stage in
+/// arcade, otherwise their usual spot
+fn stage_start(stage: &Stage, mode: GameMode, mutators: &Mutators, player_two: bool) -> Vec2 {
+    let start = player_start(mutators, player_two);
+    match stage.zones.first() {
+        Some(zone) if mode == GameMode::Arcade => Vec2::new(zone.start + ZONE_START_INSET, start.y),
+        _ => start,
+    }
+}
+
+/// Put the run on the first stage, with its clock and (in arcade) its call,
+/// and the players at its start
+#[allow(clippy::too_many_arguments)]
+fn begin_first_stage(
+    levels: &Levels,
+    progress: &mut StageProgress,
+    game_state: &mut GameState,
+    round: &mut Round,
+    lock: &mut CameraLock,
+    difficulty: Difficulty,
+    mode: GameMode,
+    settings: &MatchSettings,
+    mutators: &Mutators,
+    player_query: &mut Query<(&mut Transform, Has<PlayerTwo>), With<Player>>,
+) {
+    let stage = levels.stage(0);
+    *progress = StageProgress::begin(0, stage, mode);
+    lock.area = None;
+    begin_stage_clock(game_state, stage, difficulty, mode, settings);
+    game_state.checkpoint = Some(stage_start(stage, mode, mutators, false));
+    if mode == GameMode::Arcade {
+        round.stage = Some(stage.name.clone());
+    }
+    for (mut transform, player_two) in player_query.iter_mut() {
+        let start = stage_start(stage, mode, mutators, player_two);
+        transform.translation = start.extend(transform.translation.z);
+    }
+}
+
+/// Start every game on the first stage
+///
+/// Runs on entering InGame, after `start_match` and `spawn_player`.
+#[allow(clippy::too_many_arguments)]
+pub fn start_stages(
+    levels: Res<Levels>,
+    mut progress: ResMut<StageProgress>,
+    mut game_state: ResMut<GameState>,
+    mut round: ResMut<Round>,
+    mut lock: ResMut<CameraLock>,
+    difficulty: Res<Difficulty>,
+    mode: Res<GameMode>,
+    match_settings: Res<MatchSettings>,
+    mutators: Res<Mutators>,
+    mut player_query: Query<(&mut Transform, Has<PlayerTwo>), With<Player>>,
+) {
+    begin_first_stage(
+        &levels,
+        &mut progress,
+        &mut game_state,
+        &mut round,
+        &mut lock,
+        *difficulty,
+        *mode,
+        &match_settings,
+        &mutators,
+        &mut player_query,
+    );
+}
+
+/// Go back to the first stage when the game restarts
+///
+/// Runs after `handle_restart` and `restart_round`, which reset the match,
+/// the players, and the round call.
+#[allow(clippy::too_many_arguments)]
+pub fn restart_stages(
+    mut restart_events: MessageReader<RestartGameEvent>,
+    levels: Res<Levels>,
+    mut progress: ResMut<StageProgress>,
+    mut game_state: ResMut<GameState>,
+    mut round: ResMut<Round>,
+    mut lock: ResMut<CameraLock>,
+    difficulty: Res<Difficulty>,
+    mode: Res<GameMode>,
+    match_settings: Res<MatchSettings>,
+    mutators: Res<Mutators>,
+    mut player_query: Query<(&mut Transform, Has<PlayerTwo>), With<Player>>,
+) {
+    if restart_events.is_empty() {
+        return;
+    }
+    restart_events.clear();
+    begin_first_stage(
+        &levels,
+        &mut progress,
+        &mut game_state,
+        &mut round,
+        &mut lock,
+        *difficulty,
+        *mode,
+        &match_settings,
+        &mutators,
+        &mut player_query,
+    );
+}
+
+/// Back to the first stage on leaving InGame, so the menus show the graveyard
+pub fn reset_stage_progress(mut progress: ResMut<StageProgress>) {
+    *progress = StageProgress::default();
+}
+
+/// Dress the world as the current stage whenever the stage changes: swap the
+/// backdrop and foreground art and replace the `StageGeometry`
+///
+/// Runs in every state, so the first frame sets up the first stage.
+pub fn apply_stage(
+    levels: Res<Levels>,
+    progress: Res<StageProgress>,
+    asset_server: Res<AssetServer>,
+    mut geometry: ResMut<StageGeometry>,
+    mut applied: Local<Option<usize>>,
+    mut prop_query: Query<(&StageProp, &mut Sprite, &mut Visibility)>,
+) {
+    if *applied == Some(progress.index) {
+        return;
+    }
+    *applied = Some(progress.index);
+
+    let stage = levels.stage(progress.index);
+    *geometry = stage.geometry();
+    for (prop, mut sprite, mut visibility) in prop_query.iter_mut() {
+        let image = match prop {
+            StageProp::Backdrop => Some(&stage.backdrop),
+            StageProp::Foreground => stage.foreground.as_ref(),
+        };
+        *visibility = match image {
+            Some(path) => {
+                sprite.image = asset_server.load(path.clone());
+                Visibility::Inherited
+            }
+            None => Visibility::Hidden,
+        };
+        sprite.color = stage.tint;
+    }
+}
+
+/// Move a scrolling stage along: lock the camera on each combat zone as a
+/// player walks into it, and free it once the zone's enemies are beaten
+///
+/// Arcade only, before `update_stage`. A zone is beaten once all of its
+/// enemies have spawned and none are left standing; the start of the next
+/// zone becomes the checkpoint. Walking into the boss's
+/// area past the last zone locks the camera there too, and `update_stage`
+/// calls the boss in.
+#[allow(clippy::too_many_arguments)]
+pub fn update_zones(
+    levels: Res<Levels>,
+    mut progress: ResMut<StageProgress>,
+    mut lock: ResMut<CameraLock>,
+    mut game_state: ResMut<GameState>,
+    mut gameplay_events: MessageWriter<GameplayEvent>,
+    mutators: Res<Mutators>,
+    player_query: Query<&Transform, With<Player>>,
+    enemy_query: Query<&EnemyState, With<Enemy>>,
+) {
+    let stage = levels.stage(progress.index);
+    if !stage.is_scrolling() {
+        return;
+    }
+
+    match progress.phase {
+        StagePhase::Advancing => {
+            let area = stage.zone_area(progress.zone);
+            if !player_query
+                .iter()
+                .any(|transform| transform.translation.x >= area.min.x + ZONE_TRIGGER_INSET)
+            {
+                return;
+            }
+            lock.area = Some(area);
+            progress.phase = StagePhase::Waves;
+            progress.zone_spawns_left = Some(
+                stage
+                    .zones
+                    .get(progress.zone)
+                    .map_or(0, |zone| zone.enemies),
+            );
+            // The first enemy comes right away
+            game_state.last_spawn_time =
+                game_state.timer.elapsed_secs() - game_state.spawn_interval;
+        }
+        StagePhase::Waves if progress.zone < stage.zones.len() => {
+            let standing = enemy_query.iter().any(|state| !state.is_dying());
+            if progress.zone_spawns_left != Some(0) || standing {
+                return;
+            }
+            gameplay_events.write(GameplayEvent::ZoneCleared {
+                zone: progress.zone as u32 + 1,
+            });
+            lock.area = None;
+            progress.zone += 1;
+            progress.zone_spawns_left = None;
+            progress.phase = StagePhase::Advancing;
+            game_state.checkpoint = Some(Vec2::new(
+                stage.zone_area(progress.zone).min.x + ZONE_START_INSET,
+                player_start(&mutators, false).y,
+            ));
+        }
+        _ => {}
+    }
+}
+
+/// End the stage when its clock runs out (or a scrolling stage's players
+/// reach its end), calling in its boss if it has one, and clear it once the
+/// boss is defeated
+///
+/// Arcade only. The boss's phase changes are played by `run_boss_encounter`.
+/// A cleared stage opens the stage clear screen, or GameOver after the last
+/// stage.
+#[allow(clippy::too_many_arguments)]
+pub fn update_stage(
+    mut commands: Commands,
+    mut prefabs: PrefabSpawner,
+    levels: Res<Levels>,
+    mut progress: ResMut<StageProgress>,
+    mut game_state: ResMut<GameState>,
+    mut spawn_director: ResMut<SpawnDirector>,
+    mut round: ResMut<Round>,
+    geometry: Res<StageGeometry>,
+    lock: Res<CameraLock>,
+    difficulty: Res<Difficulty>,
+    styles: Res<TextStyles>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut defeated_events: MessageReader<EnemyDefeatedEvent>,
+    mut gameplay_events: MessageWriter<GameplayEvent>,
+    boss_query: Query<(), With<StageBoss>>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    let boss_defeated = defeated_events
+        .read()
+        .any(|event| boss_query.contains(event.enemy));
+    let stage = levels.stage(progress.index);
+    let cleared = match progress.phase {
+        StagePhase::Waves if progress.boss_due(stage, &game_state) => match stage.boss {
+            Some(boss) => {
+                let player_pos = player_query
+                    .single()
+                    .map_or(Vec2::ZERO, |transform| transform.translation.truncate());
+                let area = lock.area.unwrap_or(geometry.arena);
+                let side = spawn_director.choose_side(player_pos.x, area);
+                let position = Vec2::new(
+                    side.spawn_x(area),
+                    rand::thread_rng().gen_range(geometry.depth_lane.clone()),
+                );
+                let health = (difficulty.enemy_health() as f32 * stage.boss_health).round() as i32;
+                let name = stage.boss_name.as_deref().unwrap_or(boss.name());
+                prefabs.spawn_enemy(boss, position, health.max(1)).insert((
+                    StageBoss,
+                    BossEncounter::default(),
+                    debug_name(format!("{name} (Boss)")),
+                ));
+                game_state.n_enemies += 1;
+                commands.spawn(score_popup(
+                    &styles,
+                    format!("BOSS: {}", name.to_uppercase()),
+                    player_pos,
+                ));
+                progress.phase = StagePhase::Boss;
+                false
+            }
+            None => true,
+        },
+        StagePhase::Boss => boss_defeated,
+        _ => false,
+    };
+    if !cleared {
+        return;
+    }
+
+    progress.phase = StagePhase::Cleared;
+    gameplay_events.write(GameplayEvent::StageCleared {
+        stage: progress.number(),
+    });
+    if progress.completed(&levels) {
+        round.finish();
+        next_state.set(AppState::GameOver);
+        info!("All stages cleared! Final score: {}", game_state.score);
+    } else {
+        next_state.set(AppState::StageClear);
+        info!("Stage {} cleared!", progress.number());
+    }
+}
+
+/// Clear the field for the next stage: enemies, their projectiles, and pickups
+///
+/// Runs on entering StageClear.
+#[allow(clippy::type_complexity)]
+pub fn clear_stage_field(
+    mut commands: Commands,
+    mut game_state: ResMut<GameState>,
+    field_query: Query<Entity, Or<(With<Enemy>, With<Projectile>, With<Pickup>)>>,
+) {
+    for entity in field_query.iter() {
+        commands.entity(entity).despawn();
+    }
+    game_state.n_enemies = 0;
+}
+
+/// Move on to the next stage as the stage clear screen closes
+///
+/// The score, health, meter, and style carry over; the players go back to
+/// their starting spots (the left end, on a scrolling stage), the stage clock
+/// and spawns start over, and the next stage is called. Runs on the
+/// StageClear -> Playing transition.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn enter_next_stage(
+    mut commands: Commands,
+    levels: Res<Levels>,
+    mut progress: ResMut<StageProgress>,
+    mut game_state: ResMut<GameState>,
+    mut spawn_director: ResMut<SpawnDirector>,
+    mut round: ResMut<Round>,
+    mut director: ResMut<CameraDirector>,
+    mut lock: ResMut<CameraLock>,
+    difficulty: Res<Difficulty>,
+    mode: Res<GameMode>,
+    match_settings: Res<MatchSettings>,
+    mutators: Res<Mutators>,
+    mut player_query: Query<
+        (
+            Entity,
+            &mut PlayerState,
+            &mut Transform,
+            &mut JumpPhysics,
+            Has<PlayerTwo>,
+        ),
+        With<Player>,
+    >,
+) {
+    let index = progress.index + 1;
+    let stage = levels.stage(index);
+    *progress = StageProgress::begin(index, stage, *mode);
+    lock.area = None;
+    begin_stage_clock(&mut game_state, stage, *difficulty, *mode, &match_settings);
+    game_state.checkpoint = Some(stage_start(stage, *mode, &mutators, false));
+    *spawn_director = SpawnDirector::default();
+    *round = Round::begin_stage(progress.number(), &stage.name, &mut director);
+
+    for (entity, mut state, mut transform, mut jump_physics, player_two) in player_query.iter_mut()
+    {
+        *state = PlayerState::transition_to(PlayerStateType::Idle);
+        transform.translation = stage_start(stage, *mode, &mutators, player_two).extend(1.0);
+        transform.scale = Vec3::ONE;
+        jump_physics.velocity_y = 0.0;
+        jump_physics.ground_y = -100.0;
+        commands
+            .entity(entity)
+            .remove::<Knockback>()
+            .remove::<Invulnerable>();
+    }
+}
@@ -0,0 +1,376 @@
+use crate::enemy::EnemyArchetype;
+use crate::levels::encounter::{BossPhase, EncounterAction};
+use crate::persistence::{parse_key_values, parsed};
+use crate::world::{HazardKind, StageGeometry, Surface};
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::fs;
+
+/// Stage definitions, relative to the working directory (like the asset folder)
+const LEVELS_PATH: &str = "assets/levels.txt";
+/// Horizontal room between the arena's edges and the stage bounds, for the
+/// off-screen enemy spawn points (see `SPAWN_MARGIN`)
+const BOUNDS_MARGIN: f32 = 900.0;
+
+/// A stretch of a scrolling stage where the camera locks until its enemies are beaten
+#[derive(Clone, Copy, Debug)]
+pub struct Zone {
+    /// Left edge (world x)
+    pub start: f32,
+    /// Right edge (world x)
+    pub end: f32,
+    /// Enemies that spawn while it's locked
+    pub enemies: u32,
+}
+
+/// One stage of an arcade run: its art, playfield, enemies, and length
+#[derive(Clone, Debug)]
+pub struct Stage {
+    pub name: String,
+    /// Background image under assets/
+    pub backdrop: String,
+    /// Foreground image under assets/ (None for none)
+    pub foreground: Option<String>,
+    /// Color multiplied into the stage art
+    pub tint: Color,
+    /// Size of the arena (the stage art), centered on the origin
+    pub arena_size: Vec2,
+    /// What the ground is made of outside `patches`
+    pub ground: Surface,
+    /// Patches of other ground, as in `StageGeometry::surfaces`
+    pub patches: Vec<(Rect, Surface)>,
+    /// Seconds on the stage clock (None for `MatchSettings::duration`)
+    pub duration: Option<f32>,
+    /// Threat budget on top of the difficulty's
+    pub extra_threat: u32,
+    /// Spawn weight of each archetype (empty for the weights in assets/enemies.txt)
+    pub spawn_table: Vec<(EnemyArchetype, u32)>,
+    /// Combat zones from left to right (empty for a fixed arena on a clock)
+    pub zones: Vec<Zone>,
+    /// Boss that arrives when the stage clock runs out, or at the end of a
+    /// scrolling stage (None to clear the stage then)
+    pub boss: Option<EnemyArchetype>,
+    /// Boss health as a multiple of the difficulty's enemy health
+    pub boss_health: f32,
+    /// Name the boss is called by (None for its archetype's name)
+    pub boss_name: Option<String>,
+    /// The boss fight's phase changes, in order of falling health
+    pub boss_phases: Vec<BossPhase>,
+}
+
+impl Default for Stage {
+    /// The graveyard, as it was before there were stages
+    fn default() -> Self {
+        let geometry = StageGeometry::default();
+        Self {
+            name: "The Graveyard".to_string(),
+            backdrop: "graveyard.png".to_string(),
+            foreground: Some("graveyard_foreground.png".to_string()),
+            tint: Color::WHITE,
+            arena_size: geometry.arena.size(),
+            ground: geometry.ground,
+            patches: geometry.surfaces,
+            duration: None,
+            extra_threat: 0,
+            spawn_table: Vec::new(),
+            zones: Vec::new(),
+            boss: None,
+            boss_health: 5.0,
+            boss_name: None,
+            boss_phases: Vec::new(),
+        }
+    }
+}
+
+impl Stage {
+    /// Relative chance of `archetype` spawning on this stage when affordable
+    pub fn spawn_weight(&self, archetype: EnemyArchetype) -> u32 {
+        if self.spawn_table.is_empty() {
+            return archetype.spawn_weight();
+        }
+        self.spawn_table
+            .iter()
+            .find(|(listed, _)| *listed == archetype)
+            .map_or(0, |(_, weight)| *weight)
+    }
+
+    /// Is this a side-scrolling stage, fought zone by zone instead of on a clock?
+    pub fn is_scrolling(&self) -> bool {
+        !self.zones.is_empty()
+    }
+
+    /// Area the camera locks to for zone `index`, full arena height; past the
+    /// last zone, the boss's area from the end of the last zone to the end of
+    /// the arena
+    pub fn zone_area(&self, index: usize) -> Rect {
+        let arena = Rect::from_center_size(Vec2::ZERO, self.arena_size);
+        let (start, end) = match self.zones.get(index) {
+            Some(zone) => (zone.start, zone.end),
+            None => (
+                self.zones.last().map_or(arena.min.x, |zone| zone.end),
+                arena.max.x,
+            ),
+        };
+        Rect::new(start, arena.min.y, end, arena.max.y)
+    }
+
+    /// The stage's world geometry: its arena, bounds wide enough for the
+    /// spawn points, and its ground
+    pub fn geometry(&self) -> StageGeometry {
+        let defaults = StageGeometry::default();
+        let arena = Rect::from_center_size(Vec2::ZERO, self.arena_size);
+        StageGeometry {
+            bounds: Rect::new(
+                arena.min.x - BOUNDS_MARGIN,
+                defaults.bounds.min.y.min(arena.min.y),
+                arena.max.x + BOUNDS_MARGIN,
+                defaults.bounds.max.y.max(arena.max.y),
+            ),
+            arena,
+            ground: self.ground,
+            surfaces: self.patches.clone(),
+            ..defaults
+        }
+    }
+}
+
+/// Every stage, in the order an arcade run fights through them
+///
+/// Loaded once at startup from assets/levels.txt; each stage starts from the
+/// compiled graveyard (`Stage::default`) and applies the values set for it,
+/// so stages can be added or tuned without recompiling. Endless, versus, and
+/// training games stay on the first stage.
+#[derive(Resource)]
+pub struct Levels {
+    stages: Vec<Stage>,
+}
+
+impl Levels {
+    /// Load assets/levels.txt (just the graveyard if it can't be read)
+    pub fn load() -> Self {
+        let contents = fs::read_to_string(LEVELS_PATH).unwrap_or_else(|err| {
+            warn!("Using the compiled graveyard stage ({LEVELS_PATH}: {err})");
+            String::new()
+        });
+        Self::from_key_values(&parse_key_values(&contents))
+    }
+
+    /// Stage `index` (the last stage past the end)
+    pub fn stage(&self, index: usize) -> &Stage {
+        &self.stages[index.min(self.stages.len() - 1)]
+    }
+
+    /// Number of stages in an arcade run (at least 1)
+    pub fn count(&self) -> usize {
+        self.stages.len()
+    }
+
+    fn from_key_values(values: &HashMap<&str, &str>) -> Self {
+        let mut stages: Vec<Stage> = values
+            .get("stages")
+            .into_iter()
+            .flat_map(|keys| keys.split(','))
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(|key| stage_from_key_values(key, values))
+            .collect();
+        if stages.is_empty() {
+            stages.push(Stage::default());
+        }
+
+        Self { stages }
+    }
+}
+
+/// Build one stage from its `<key>.<field>` values on top of the graveyard
+fn stage_from_key_values(key: &str, values: &HashMap<&str, &str>) -> Stage {
+    let field = |name: &str| {
+        let field_key = format!("{key}.{name}");
+        values
+            .get(field_key.as_str())
+            .map(|value| (field_key, *value))
+    };
+    let mut stage = Stage {
+        name: key.to_string(),
+        ..default()
+    };
+
+    if let Some((_, name)) = field("name") {
+        stage.name = name.to_string();
+    }
+    if let Some((_, backdrop)) = field("backdrop") {
+        stage.backdrop = backdrop.to_string();
+    }
+    if let Some((_, foreground)) = field("foreground") {
+        stage.foreground = Some(foreground.to_string()).filter(|path| !path.is_empty());
+    }
+    if let Some((field_key, value)) = field("tint") {
+        match Srgba::hex(value) {
+            Ok(color) => stage.tint = color.into(),
+            Err(_) => warn!("{field_key}: can't parse color {value:?}"),
+        }
+    }
+    if let Some(width) = field("width").and_then(parsed::<f32>) {
+        stage.arena_size.x = width.max(1.0);
+    }
+    if let Some(height) = field("height").and_then(parsed::<f32>) {
+        stage.arena_size.y = height.max(1.0);
+    }
+    if let Some(ground) = field("ground").and_then(parsed_surface) {
+        stage.ground = ground;
+    }
+    if let Some((field_key, value)) = field("patches") {
+        stage.patches = value
+            .split(',')
+            .map(str::trim)
+            .filter(|patch| !patch.is_empty())
+            .filter_map(|patch| {
+                let parsed = parse_patch(patch);
+                if parsed.is_none() {
+                    warn!("{field_key}: can't parse patch {patch:?}");
+                }
+                parsed
+            })
+            .collect();
+    }
+    if let Some(duration) = field("duration").and_then(parsed::<f32>) {
+        stage.duration = Some(duration).filter(|duration| *duration > 0.0);
+    }
+    if let Some(threat) = field("threat").and_then(parsed) {
+        stage.extra_threat = threat;
+    }
+    if let Some((field_key, value)) = field("spawns") {
+        stage.spawn_table = value
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .filter_map(|entry| {
+                let parsed = entry.split_once(':').and_then(|(archetype, weight)| {
+                    Some((
+                        EnemyArchetype::from_key(archetype.trim())?,
+                        weight.trim().parse().ok()?,
+                    ))
+                });
+                if parsed.is_none() {
+                    warn!("{field_key}: can't parse spawn {entry:?}");
+                }
+                parsed
+            })
+            .collect();
+    }
+    if let Some((field_key, value)) = field("zones") {
+        stage.zones = list(value)
+            .filter_map(|zone| warn_unparsed(&field_key, zone, parse_zone(zone)))
+            .collect();
+        stage.zones.sort_by(|a, b| a.start.total_cmp(&b.start));
+    }
+    if let Some((field_key, value)) = field("boss") {
+        stage.boss = EnemyArchetype::from_key(value);
+        if stage.boss.is_none() && !value.is_empty() {
+            warn!("{field_key}: unknown archetype {value:?}");
+        }
+    }
+    if let Some(health) = field("boss_health").and_then(parsed::<f32>) {
+        stage.boss_health = health.max(0.1);
+    }
+    if let Some((_, name)) = field("boss_name") {
+        stage.boss_name = Some(name.to_string()).filter(|name| !name.is_empty());
+    }
+    // Phases count from 2 (the fight opens in phase 1) and stop at the first gap
+    for number in 2.. {
+        let Some(at) = field(&format!("phase{number}.at")) else {
+            break;
+        };
+        let Some(health_below) = parsed::<f32>(at) else {
+            continue;
+        };
+        let phase_field = |name: &str| field(&format!("phase{number}.{name}"));
+        let mut phase = BossPhase {
+            health_below: health_below.clamp(0.0, 1.0),
+            call: phase_field("call")
+                .map(|(_, call)| call.to_string())
+                .filter(|call| !call.is_empty()),
+            actions: Vec::new(),
+        };
+        if let Some((field_key, value)) = phase_field("collapse") {
+            phase.actions.extend(
+                list(value)
+                    .filter_map(|rect| warn_unparsed(&field_key, rect, parse_rect(rect)))
+                    .map(EncounterAction::Collapse),
+            );
+        }
+        if let Some((field_key, value)) = phase_field("hazards") {
+            phase.actions.extend(
+                list(value)
+                    .filter_map(|entry| warn_unparsed(&field_key, entry, parse_hazard(entry)))
+                    .map(|(kind, area)| EncounterAction::Hazard(kind, area)),
+            );
+        }
+        if let Some((_, value)) = phase_field("music") {
+            phase.actions.push(EncounterAction::Music(
+                list(value).map(str::to_string).collect(),
+            ));
+        }
+        stage.boss_phases.push(phase);
+    }
+
+    stage
+}
+
+/// `<surface> <x1> <y1> <x2> <y2>`
+fn parse_patch(patch: &str) -> Option<(Rect, Surface)> {
+    let (surface, rect) = patch.trim().split_once(' ')?;
+    Some((parse_rect(rect)?, Surface::from_key(surface)?))
+}
+
+/// `<hazard> <x1> <y1> <x2> <y2>`
+fn parse_hazard(entry: &str) -> Option<(HazardKind, Rect)> {
+    let (kind, rect) = entry.trim().split_once(' ')?;
+    Some((HazardKind::from_key(kind)?, parse_rect(rect)?))
+}
+
+/// `<start> <end> <enemies>`
+fn parse_zone(zone: &str) -> Option<Zone> {
+    let mut fields = zone.split_whitespace();
+    let start: f32 = fields.next()?.parse().ok()?;
+    let end: f32 = fields.next()?.parse().ok()?;
+    let enemies = fields.next()?.parse().ok()?;
+    (start < end).then_some(Zone {
+        start,
+        end,
+        enemies,
+    })
+}
+
+/// `<x1> <y1> <x2> <y2>`
+fn parse_rect(rect: &str) -> Option<Rect> {
+    let mut fields = rect.split_whitespace();
+    let mut corner = || fields.next()?.parse::<f32>().ok();
+    let (x1, y1, x2, y2) = (corner()?, corner()?, corner()?, corner()?);
+    Some(Rect::new(x1, y1, x2, y2))
+}
+
+/// Non-empty entries of a comma-separated list
+fn list(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+}
+
+/// Pass a parsed list entry through, warning if it couldn't be parsed
+fn warn_unparsed<T>(key: &str, entry: &str, parsed: Option<T>) -> Option<T> {
+    if parsed.is_none() {
+        warn!("{key}: can't parse {entry:?}");
+    }
+    parsed
+}
+
+/// Parse a surface key, warning (and returning None) if it's unknown
+fn parsed_surface((key, value): (String, &str)) -> Option<Surface> {
+    let surface = Surface::from_key(value);
+    if surface.is_none() {
+        warn!("{key}: unknown surface {value:?}");
+    }
+    surface
+}
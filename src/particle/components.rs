@@ -0,0 +1,113 @@
+use bevy::prelude::*;
+use std::f32::consts::PI;
+use std::ops::RangeInclusive;
+
+/// Kind of particle burst, which decides how its particles look and move
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParticleKind {
+    /// Sparks thrown off a hit, away from the attacker
+    ImpactSpark,
+    /// Puff a defeated ghost dissipates into, drifting upward
+    Dissipation,
+    /// Drop of ectoplasm falling from a `Weakened` enemy
+    Drip,
+    /// Shards of a `Shield` breaking, flying out in every direction
+    ShieldShatter,
+    /// Chunks of ground thrown up where a boss phase collapses it
+    Debris,
+}
+
+/// How a burst of one kind spawns its particles
+pub struct Emitter {
+    pub count: usize,
+    /// Launch speed, picked at random per particle
+    pub speed: RangeInclusive<f32>,
+    /// Largest angle a particle strays from the burst's direction, in radians
+    pub spread: f32,
+    /// Seconds each particle lives, picked at random per particle
+    pub lifetime: RangeInclusive<f32>,
+    pub size: Vec2,
+    /// Downward acceleration in units per second squared (negative floats upward)
+    pub gravity: f32,
+    pub color: Color,
+    /// Scale a particle has shrunk to when it dies
+    pub end_scale: f32,
+}
+
+impl ParticleKind {
+    pub fn emitter(self) -> Emitter {
+        match self {
+            ParticleKind::ImpactSpark => Emitter {
+                count: 8,
+                speed: 250.0..=500.0,
+                spread: 0.7,
+                lifetime: 0.2..=0.35,
+                size: Vec2::splat(6.0),
+                gravity: 900.0,
+                color: Color::srgb(1.0, 0.9, 0.5),
+                end_scale: 0.2,
+            },
+            ParticleKind::Dissipation => Emitter {
+                count: 16,
+                speed: 40.0..=140.0,
+                spread: PI,
+                lifetime: 0.5..=0.9,
+                size: Vec2::splat(14.0),
+                gravity: -120.0,
+                color: Color::srgba(0.8, 0.9, 1.0, 0.8),
+                end_scale: 0.3,
+            },
+            ParticleKind::Drip => Emitter {
+                count: 1,
+                speed: 30.0..=50.0,
+                spread: 0.0,
+                lifetime: 0.7..=0.7,
+                size: Vec2::new(5.0, 8.0),
+                gravity: 300.0,
+                color: Color::srgba(0.55, 0.95, 0.75, 0.9),
+                end_scale: 0.6,
+            },
+            ParticleKind::ShieldShatter => Emitter {
+                count: 12,
+                speed: 150.0..=350.0,
+                spread: PI,
+                lifetime: 0.3..=0.5,
+                size: Vec2::new(10.0, 4.0),
+                gravity: 600.0,
+                color: Color::srgba(0.4, 0.7, 1.0, 0.9),
+                end_scale: 0.4,
+            },
+            ParticleKind::Debris => Emitter {
+                count: 18,
+                speed: 200.0..=450.0,
+                spread: 1.0,
+                lifetime: 0.5..=0.9,
+                size: Vec2::splat(12.0),
+                gravity: 1200.0,
+                color: Color::srgb(0.35, 0.3, 0.3),
+                end_scale: 0.5,
+            },
+        }
+    }
+}
+
+/// Message asking for a burst of particles, spawned by `spawn_particle_bursts`
+/// within the `ParticleBudget`
+#[derive(Message)]
+pub struct ParticleBurst {
+    pub kind: ParticleKind,
+    pub position: Vec2,
+    /// Direction the particles fly in, before the emitter's spread
+    pub direction: Vec2,
+}
+
+/// Short-lived sprite that flies under gravity, shrinking and fading until it's gone
+#[derive(Component)]
+pub struct Particle {
+    pub timer: Timer,
+    pub velocity: Vec2,
+    pub gravity: f32,
+    /// Alpha the sprite started with
+    pub alpha: f32,
+    pub end_scale: f32,
+}
@@ -0,0 +1,25 @@
+use crate::game::GameSet;
+use crate::particle::components::ParticleBurst;
+use crate::particle::systems::{
+    emit_defeat_puffs, emit_impact_sparks, emit_shield_shatters, spawn_particle_bursts,
+    update_particles,
+};
+use bevy::prelude::*;
+
+/// Particle bursts: impact sparks, defeat puffs, shield shatters, and any other module's `ParticleBurst`s
+pub struct ParticlePlugin;
+
+impl Plugin for ParticlePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<ParticleBurst>().add_systems(
+            Update,
+            (
+                (emit_impact_sparks, emit_defeat_puffs, emit_shield_shatters)
+                    .in_set(GameSet::DamageReactions),
+                (spawn_particle_bursts, update_particles)
+                    .chain()
+                    .in_set(GameSet::Effects),
+            ),
+        );
+    }
+}
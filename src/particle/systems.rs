@@ -0,0 +1,126 @@
+use crate::combat::{DamageEvent, EnemyDefeatedEvent, ShieldBrokenEvent};
+use crate::game::InGame;
+use crate::particle::components::{Particle, ParticleBurst, ParticleKind};
+use crate::settings::ParticleBudget;
+use bevy::prelude::*;
+use rand::Rng;
+
+/// Depth particles are drawn at, in front of the fighters
+const PARTICLE_Z: f32 = 3.0;
+
+/// Throw sparks off every hit, away from where it came from
+pub fn emit_impact_sparks(
+    mut damage_events: MessageReader<DamageEvent>,
+    mut bursts: MessageWriter<ParticleBurst>,
+    transform_query: Query<&Transform>,
+) {
+    for event in damage_events.read() {
+        let Ok(transform) = transform_query.get(event.target) else {
+            continue;
+        };
+        let position = transform.translation.truncate();
+        bursts.write(ParticleBurst {
+            kind: ParticleKind::ImpactSpark,
+            position,
+            direction: (position - event.source).normalize_or(Vec2::Y),
+        });
+    }
+}
+
+/// Dissipate every defeated ghost into a puff
+///
+/// Runs before `handle_enemy_defeat` despawns the enemy, while its position is still known.
+pub fn emit_defeat_puffs(
+    mut defeated_events: MessageReader<EnemyDefeatedEvent>,
+    mut bursts: MessageWriter<ParticleBurst>,
+    transform_query: Query<&Transform>,
+) {
+    for event in defeated_events.read() {
+        if let Ok(transform) = transform_query.get(event.enemy) {
+            bursts.write(ParticleBurst {
+                kind: ParticleKind::Dissipation,
+                position: transform.translation.truncate(),
+                direction: Vec2::Y,
+            });
+        }
+    }
+}
+
+/// Shatter every shield that broke into shards
+pub fn emit_shield_shatters(
+    mut shield_broken_events: MessageReader<ShieldBrokenEvent>,
+    mut bursts: MessageWriter<ParticleBurst>,
+    transform_query: Query<&Transform>,
+) {
+    for event in shield_broken_events.read() {
+        if let Ok(transform) = transform_query.get(event.entity) {
+            bursts.write(ParticleBurst {
+                kind: ParticleKind::ShieldShatter,
+                position: transform.translation.truncate(),
+                direction: Vec2::Y,
+            });
+        }
+    }
+}
+
+/// Spawn the particles of this frame's bursts, cutting them short at the `ParticleBudget`
+pub fn spawn_particle_bursts(
+    mut commands: Commands,
+    budget: Res<ParticleBudget>,
+    mut bursts: MessageReader<ParticleBurst>,
+    particle_query: Query<(), With<Particle>>,
+) {
+    let mut remaining = budget
+        .max_particles()
+        .saturating_sub(particle_query.iter().count());
+    let mut rng = rand::thread_rng();
+
+    for burst in bursts.read() {
+        let emitter = burst.kind.emitter();
+        let count = emitter.count.min(remaining);
+        remaining -= count;
+
+        let heading = burst.direction.to_angle();
+        for _ in 0..count {
+            let angle = heading + rng.gen_range(-emitter.spread..=emitter.spread);
+            let speed = rng.gen_range(emitter.speed.clone());
+            let lifetime = rng.gen_range(emitter.lifetime.clone());
+            commands.spawn((
+                Particle {
+                    timer: Timer::from_seconds(lifetime, TimerMode::Once),
+                    velocity: Vec2::from_angle(angle) * speed,
+                    gravity: emitter.gravity,
+                    alpha: emitter.color.alpha(),
+                    end_scale: emitter.end_scale,
+                },
+                Sprite::from_color(emitter.color, emitter.size),
+                Transform::from_translation(burst.position.extend(PARTICLE_Z)),
+                DespawnOnExit(InGame),
+            ));
+        }
+    }
+}
+
+/// Fly, shrink, and fade particles, despawning them when they're done
+pub fn update_particles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut particle_query: Query<(Entity, &mut Particle, &mut Transform, &mut Sprite)>,
+) {
+    let delta = time.delta_secs();
+    for (entity, mut particle, mut transform, mut sprite) in particle_query.iter_mut() {
+        particle.timer.tick(time.delta());
+        if particle.timer.is_finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        // Purely visual, so particles move their Transform directly instead of using MoveIntent
+        particle.velocity.y -= particle.gravity * delta;
+        transform.translation += (particle.velocity * delta).extend(0.0);
+
+        let progress = particle.timer.fraction();
+        transform.scale = Vec3::splat(1.0 + (particle.end_scale - 1.0) * progress);
+        sprite.color.set_alpha(particle.alpha * (1.0 - progress));
+    }
+}
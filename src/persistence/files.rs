@@ -0,0 +1,95 @@
+use bevy::log::warn;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Directory name used under the platform data directory
+const APP_DIR_NAME: &str = "martial-magicka";
+
+/// Platform-appropriate directory for saved data
+///
+/// `%APPDATA%` on Windows, `~/Library/Application Support` on macOS, and
+/// `$XDG_DATA_HOME` (falling back to `~/.local/share`) elsewhere. Returns None
+/// if the relevant environment variables are missing.
+pub fn data_dir() -> Option<PathBuf> {
+    let base = if cfg!(target_os = "windows") {
+        PathBuf::from(std::env::var_os("APPDATA")?)
+    } else if cfg!(target_os = "macos") {
+        PathBuf::from(std::env::var_os("HOME")?).join("Library/Application Support")
+    } else if let Some(xdg) = std::env::var_os("XDG_DATA_HOME") {
+        PathBuf::from(xdg)
+    } else {
+        PathBuf::from(std::env::var_os("HOME")?).join(".local/share")
+    };
+    Some(base.join(APP_DIR_NAME))
+}
+
+/// Read a file from the data directory (None if it doesn't exist yet)
+pub fn read_data_file(name: &str) -> Option<String> {
+    fs::read_to_string(data_dir()?.join(name)).ok()
+}
+
+/// Names of the files in a folder of the data directory, sorted (empty if it doesn't exist)
+pub fn list_data_files(folder: &str) -> Vec<String> {
+    let Some(entries) = data_dir().and_then(|dir| fs::read_dir(dir.join(folder)).ok()) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+    names.sort();
+    names
+}
+
+/// Write a file to the data directory, creating the directory (and any folder
+/// in `name`, like `bindings/keyboard.txt`) if needed
+///
+/// Blocks on the disk: game code saves through `SaveQueue` instead. Writes
+/// a temporary file and renames it over the old one, so an interrupted
+/// write never leaves a half-written save behind.
+pub fn write_data_file(name: &str, contents: &[u8]) -> io::Result<()> {
+    let path = data_dir()
+        .ok_or_else(|| io::Error::other("no data directory"))?
+        .join(name);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let temp = path.with_file_name(format!(
+        "{}.tmp",
+        path.file_name().unwrap_or_default().to_string_lossy()
+    ));
+    fs::write(&temp, contents)?;
+    fs::rename(temp, path)
+}
+
+/// Parse `key=value` lines, skipping blank lines and `#` comments
+pub fn parse_key_values(contents: &str) -> HashMap<&str, &str> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .collect()
+}
+
+/// Parse a `(key, value)` field, warning (and returning None) if it's malformed
+pub fn parsed<T: FromStr>((key, value): (String, &str)) -> Option<T> {
+    let parsed = value.parse().ok();
+    if parsed.is_none() {
+        warn!("{key}: can't parse {value:?}");
+    }
+    parsed
+}
+
+/// Current Unix time in seconds (0 if the clock is before 1970)
+pub fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
@@ -0,0 +1,8 @@
+pub mod files;
+pub mod plugin;
+pub mod save_queue;
+
+// Re-export commonly used items
+pub use files::*;
+pub use plugin::*;
+pub use save_queue::*;
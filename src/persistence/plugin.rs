@@ -0,0 +1,16 @@
+use crate::persistence::save_queue::{
+    SaveCompletedEvent, SaveQueue, flush_save_queue_on_exit, process_save_queue,
+};
+use bevy::prelude::*;
+
+/// Background save-file writes (see `SaveQueue`)
+pub struct PersistencePlugin;
+
+impl Plugin for PersistencePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SaveQueue>()
+            .add_message::<SaveCompletedEvent>()
+            .add_systems(PostUpdate, process_save_queue)
+            .add_systems(Last, flush_save_queue_on_exit);
+    }
+}
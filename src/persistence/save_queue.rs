@@ -0,0 +1,108 @@
+use crate::persistence::files::write_data_file;
+use bevy::prelude::*;
+use bevy::tasks::{IoTaskPool, Task, block_on};
+use std::collections::HashMap;
+use std::io;
+
+/// Real seconds a queued save waits for newer saves of the same file before it's written
+const COALESCE_SECONDS: f32 = 0.5;
+
+/// Save-file writes waiting for, or running on, the IO task pool
+///
+/// Saving never touches the disk on the main thread: `queue` hands the
+/// contents over, and `process_save_queue` writes them on the IO task pool.
+/// A save replaces any unwritten contents of the same file, so rapid
+/// consecutive saves (every click of a settings button) coalesce into one
+/// write of the latest contents, `COALESCE_SECONDS` after the first. A file
+/// is never written by two tasks at once.
+#[derive(Resource, Default)]
+pub struct SaveQueue {
+    /// Latest unwritten contents and real seconds waited so far, by file name
+    pending: HashMap<String, (Vec<u8>, f32)>,
+    /// Writes running on the IO task pool, by file name
+    in_flight: HashMap<String, Task<io::Result<()>>>,
+}
+
+impl SaveQueue {
+    /// Queue `contents` (text, or bytes like a bug report archive) to be
+    /// written to `name` in the data directory
+    pub fn queue(&mut self, name: &str, contents: impl Into<Vec<u8>>) {
+        let waited = self.pending.get(name).map_or(0.0, |(_, waited)| *waited);
+        self.pending
+            .insert(name.to_string(), (contents.into(), waited));
+    }
+}
+
+/// Sent when a queued save has been written, or has failed to be
+#[derive(Message)]
+pub struct SaveCompletedEvent {
+    /// File name in the data directory
+    pub file: String,
+    /// Why the write failed (None if it succeeded)
+    pub error: Option<String>,
+}
+
+/// Report a finished write, logging (not failing) on error
+fn completed(file: String, result: io::Result<()>) -> SaveCompletedEvent {
+    let error = result.err().map(|err| {
+        warn!("Failed to save {file}: {err}");
+        err.to_string()
+    });
+    SaveCompletedEvent { file, error }
+}
+
+/// Report finished writes, and start the queued ones that have waited `COALESCE_SECONDS`
+///
+/// Runs every frame in `PostUpdate`, after anything that saves. Uses real
+/// time, so saves made while paused or in slow motion aren't held back.
+pub fn process_save_queue(
+    time: Res<Time<Real>>,
+    mut queue: ResMut<SaveQueue>,
+    mut completed_events: MessageWriter<SaveCompletedEvent>,
+) {
+    let SaveQueue { pending, in_flight } = &mut *queue;
+
+    let finished: Vec<String> = in_flight
+        .iter()
+        .filter(|(_, task)| task.is_finished())
+        .map(|(file, _)| file.clone())
+        .collect();
+    for file in finished {
+        if let Some(task) = in_flight.remove(&file) {
+            completed_events.write(completed(file, block_on(task)));
+        }
+    }
+
+    let delta = time.delta_secs();
+    pending.retain(|file, (contents, waited)| {
+        *waited += delta;
+        if *waited < COALESCE_SECONDS || in_flight.contains_key(file) {
+            return true;
+        }
+        let (name, contents) = (file.clone(), std::mem::take(contents));
+        let task = IoTaskPool::get().spawn(async move { write_data_file(&name, &contents) });
+        in_flight.insert(file.clone(), task);
+        false
+    });
+}
+
+/// Finish every write still in flight or queued before the app exits
+///
+/// Runs in `Last`, blocking the final frame, so quitting right after a save
+/// (closing the window on the game over screen) never loses it.
+pub fn flush_save_queue_on_exit(
+    mut exit_events: MessageReader<AppExit>,
+    mut queue: ResMut<SaveQueue>,
+) {
+    if exit_events.read().count() == 0 {
+        return;
+    }
+    // In-flight writes first, so a newer queued save of the same file lands last
+    for (file, task) in queue.in_flight.drain() {
+        completed(file, block_on(task));
+    }
+    for (file, (contents, _)) in queue.pending.drain() {
+        let result = write_data_file(&file, &contents);
+        completed(file, result);
+    }
+}
@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+
+/// What a pickup gives the player when collected
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PickupKind {
+    /// Restores `HEAL_AMOUNT` health (up to max), eaten by holding interact
+    /// for `HEAL_HOLD_SECONDS`
+    Heal,
+    /// Adds `METER_AMOUNT` to the special meter
+    Meter,
+    /// Adds `SCORE_AMOUNT` points
+    Score,
+    /// Multiplies attack damage by `DAMAGE_BOOST` for `BOOST_SECONDS`
+    DamageBoost,
+    /// Multiplies running speed by `SPEED_BOOST` for `BOOST_SECONDS`, stacking
+    /// up to `SPEED_BOOST_STACKS` times
+    SpeedBoost,
+    /// Grants a `Shield` of `SHIELD_AMOUNT` for `SHIELD_SECONDS`
+    Shield,
+}
+
+impl PickupKind {
+    /// Health restored by a heal pickup
+    pub const HEAL_AMOUNT: i32 = 2;
+    /// Seconds interact must be held to eat a heal pickup
+    pub const HEAL_HOLD_SECONDS: f32 = 0.5;
+    /// Special meter added by a meter pickup
+    pub const METER_AMOUNT: f32 = 25.0;
+    /// Points added by a score gem
+    pub const SCORE_AMOUNT: u32 = 25;
+    /// How long a power-up lasts
+    pub const BOOST_SECONDS: f32 = 8.0;
+    /// Damage multiplier of a damage boost
+    pub const DAMAGE_BOOST: f32 = 2.0;
+    /// Speed multiplier of a speed boost
+    pub const SPEED_BOOST: f32 = 1.4;
+    /// Speed boosts that can stack at once
+    pub const SPEED_BOOST_STACKS: u32 = 2;
+    /// Damage a shield pickup's shield absorbs
+    pub const SHIELD_AMOUNT: i32 = 3;
+    /// How long a shield lasts if it isn't broken
+    pub const SHIELD_SECONDS: f32 = 12.0;
+
+    /// Name used in loot tables (assets/enemies.txt)
+    pub fn key(self) -> &'static str {
+        match self {
+            PickupKind::Heal => "heal",
+            PickupKind::Meter => "meter",
+            PickupKind::Score => "score",
+            PickupKind::DamageBoost => "damage_boost",
+            PickupKind::SpeedBoost => "speed_boost",
+            PickupKind::Shield => "shield",
+        }
+    }
+
+    /// Parse a name written by `key`
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "heal" => Some(PickupKind::Heal),
+            "meter" => Some(PickupKind::Meter),
+            "score" => Some(PickupKind::Score),
+            "damage_boost" => Some(PickupKind::DamageBoost),
+            "speed_boost" => Some(PickupKind::SpeedBoost),
+            "shield" => Some(PickupKind::Shield),
+            _ => None,
+        }
+    }
+
+    /// Collected by walking over it instead of with the interact button
+    ///
+    /// Score gems, power-ups, and shields are grabbed on the move; heal and
+    /// meter pickups wait to be used when they're needed.
+    pub fn collected_on_touch(self) -> bool {
+        matches!(
+            self,
+            PickupKind::Score
+                | PickupKind::DamageBoost
+                | PickupKind::SpeedBoost
+                | PickupKind::Shield
+        )
+    }
+}
+
+/// An item lying in the arena, collected with the interact button (see `Interactable`)
+/// or by touching it (see `PickupKind::collected_on_touch`)
+#[derive(Component)]
+pub struct Pickup {
+    pub kind: PickupKind,
+    /// Despawns when this runs out
+    pub lifetime: Timer,
+}
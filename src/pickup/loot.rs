@@ -0,0 +1,108 @@
+use crate::pickup::components::PickupKind;
+use bevy::prelude::*;
+use rand::Rng;
+use std::ops::RangeInclusive;
+
+/// Kills without a heal drop after which the next kill is guaranteed to drop one
+pub const PITY_HEAL_KILLS: u32 = 12;
+
+/// One weighted outcome of a loot roll
+#[derive(Clone, Debug, PartialEq)]
+pub struct LootEntry {
+    /// Dropped item, or None for "nothing"
+    pub item: Option<PickupKind>,
+    pub weight: u32,
+    /// How many of the item drop
+    pub quantity: RangeInclusive<u32>,
+}
+
+/// Weighted drop table for an enemy archetype
+///
+/// Written in assets/enemies.txt as comma-separated `item:weight[:min-max]`
+/// entries, where `item` is a `PickupKind` key or `nothing` and the quantity
+/// defaults to 1 (e.g. `nothing:10, heal:1, meter:2:1-2`). Malformed entries
+/// are skipped.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LootTable {
+    pub entries: Vec<LootEntry>,
+}
+
+impl LootTable {
+    /// Parse a table from its assets/enemies.txt form (empty if nothing is valid)
+    pub fn parse(spec: &str) -> Self {
+        let entries = spec
+            .split(',')
+            .filter_map(|entry| {
+                let mut parts = entry.trim().split(':');
+                let item = match parts.next()? {
+                    "nothing" => None,
+                    key => Some(PickupKind::from_key(key)?),
+                };
+                let weight = parts.next()?.parse().ok()?;
+                let quantity = match parts.next() {
+                    Some(range) => {
+                        let (min, max) = range.split_once('-').unwrap_or((range, range));
+                        min.parse().ok()?..=max.parse().ok()?
+                    }
+                    None => 1..=1,
+                };
+                Some(LootEntry {
+                    item,
+                    weight,
+                    quantity,
+                })
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// Pick an entry by weight; returns the item and how many drop (None for nothing)
+    pub fn roll(&self) -> Option<(PickupKind, u32)> {
+        let total: u32 = self.entries.iter().map(|entry| entry.weight).sum();
+        if total == 0 {
+            return None;
+        }
+        let mut rng = rand::thread_rng();
+        let mut pick = rng.gen_range(0..total);
+        let entry = self.entries.iter().find(|entry| {
+            if pick < entry.weight {
+                return true;
+            }
+            pick -= entry.weight;
+            false
+        })?;
+        let quantity = if entry.quantity.is_empty() {
+            1
+        } else {
+            rng.gen_range(entry.quantity.clone())
+        };
+        entry.item.map(|item| (item, quantity))
+    }
+}
+
+/// Pity counter for heal drops, reset each run
+///
+/// Counts kills since the last heal drop; once it reaches `PITY_HEAL_KILLS`
+/// the next kill drops a heal whatever its table rolls.
+#[derive(Resource, Default)]
+pub struct LootPity {
+    pub kills_since_heal: u32,
+}
+
+impl LootPity {
+    /// Apply the pity rule to a kill's roll and update the counter
+    pub fn adjust(&mut self, roll: Option<(PickupKind, u32)>) -> Option<(PickupKind, u32)> {
+        let roll = match roll {
+            Some((PickupKind::Heal, _)) => roll,
+            _ if self.kills_since_heal + 1 >= PITY_HEAL_KILLS => Some((PickupKind::Heal, 1)),
+            _ => roll,
+        };
+
+        if matches!(roll, Some((PickupKind::Heal, _))) {
+            self.kills_since_heal = 0;
+        } else {
+            self.kills_since_heal += 1;
+        }
+        roll
+    }
+}
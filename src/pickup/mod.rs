@@ -0,0 +1,9 @@
+pub mod components;
+pub mod loot;
+pub mod plugin;
+pub mod systems;
+
+// Re-export commonly used items
+pub use components::*;
+pub use loot::*;
+pub use plugin::*;
@@ -0,0 +1,17 @@
+use crate::game::GameSet;
+use crate::pickup::systems::{collect_pickups, drop_loot, update_pickups};
+use bevy::prelude::*;
+
+/// Loot drops from defeated enemies, and their collection
+pub struct PickupPlugin;
+
+impl Plugin for PickupPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (drop_loot, collect_pickups, update_pickups)
+                .chain()
+                .in_set(GameSet::DamageReactions),
+        );
+    }
+}
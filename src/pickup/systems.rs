@@ -0,0 +1,221 @@
+use crate::combat::{
+    EnemyDefeatedEvent, Health, HurtBox, Shield, SpecialMeter, Stat, StatModifier, StatModifiers,
+};
+use crate::debug::debug_name;
+use crate::enemy::EnemyArchetype;
+use crate::game::{GameState, GameplayEvent, InGame};
+use crate::interaction::{InteractEvent, Interactable};
+use crate::pickup::components::{Pickup, PickupKind};
+use crate::pickup::loot::LootPity;
+use crate::player::{JumpPhysics, Player};
+use crate::settings::Mutators;
+use crate::ui::{TextStyles, score_popup};
+use crate::world::{DepthSorted, depth, same_lane};
+use bevy::prelude::*;
+
+const PICKUP_SIZE: Vec2 = Vec2::new(24.0, 24.0);
+/// Seconds a pickup stays in the arena before vanishing
+const PICKUP_LIFETIME: f32 = 8.0;
+/// Blink during the last seconds so players know it's about to vanish
+const PICKUP_BLINK_SECONDS: f32 = 2.0;
+/// Horizontal distance at which the player can pick up a pickup in its lane
+const PICKUP_RADIUS: f32 = 60.0;
+/// Horizontal gap between pickups dropped together
+const PICKUP_SPACING: f32 = 30.0;
+
+/// Pickup bundle lying at `position`
+///
+/// Pickups that aren't `collected_on_touch` also need an `Interactable`
+/// (see `drop_loot`).
+pub fn pickup(kind: PickupKind, position: Vec2) -> impl Bundle {
+    let (color, label) = match kind {
+        PickupKind::Heal => (Color::srgb(0.3, 1.0, 0.4), "Heal pickup"),
+        PickupKind::Meter => (Color::srgb(1.0, 0.85, 0.2), "Meter pickup"),
+        PickupKind::Score => (Color::srgb(0.4, 0.8, 1.0), "Score gem"),
+        PickupKind::DamageBoost => (Color::srgb(1.0, 0.3, 0.3), "Damage boost"),
+        PickupKind::SpeedBoost => (Color::srgb(0.8, 0.4, 1.0), "Speed boost"),
+        PickupKind::Shield => (Color::srgb(0.2, 0.45, 1.0), "Shield"),
+    };
+    (
+        Sprite::from_color(color, PICKUP_SIZE),
+        Transform::from_xyz(position.x, position.y, 2.0),
+        debug_name(label),
+        DepthSorted,
+        Pickup {
+            kind,
+            lifetime: Timer::from_seconds(PICKUP_LIFETIME, TimerMode::Once),
+        },
+        DespawnOnExit(InGame),
+    )
+}
+
+/// Roll each defeated enemy's loot table (with the heal pity rule) and drop the result
+///
+/// Runs before `handle_enemy_defeat` despawns the enemy. Shields don't drop
+/// under hardcore, where a single hit is meant to be fatal.
+pub fn drop_loot(
+    mut commands: Commands,
+    mut defeated_events: MessageReader<EnemyDefeatedEvent>,
+    enemy_query: Query<(&EnemyArchetype, &Transform)>,
+    mutators: Res<Mutators>,
+    mut pity: ResMut<LootPity>,
+) {
+    for event in defeated_events.read() {
+        let Ok((archetype, transform)) = enemy_query.get(event.enemy) else {
+            continue;
+        };
+        let Some((kind, quantity)) = pity.adjust(archetype.loot_table().roll()) else {
+            continue;
+        };
+        if kind == PickupKind::Shield && mutators.hardcore {
+            continue;
+        }
+
+        let center = transform.translation.truncate();
+        for i in 0..quantity {
+            let offset = (i as f32 - (quantity - 1) as f32 / 2.0) * PICKUP_SPACING;
+            let mut entity = commands.spawn(pickup(kind, center + Vec2::new(offset, 0.0)));
+            if !kind.collected_on_touch() {
+                // Eating takes a moment, so healing in the middle of a fight is a risk
+                entity.insert(match kind {
+                    PickupKind::Heal => {
+                        Interactable::hold("eat", PickupKind::HEAL_HOLD_SECONDS, PICKUP_RADIUS)
+                    }
+                    _ => Interactable::press("pick up", PICKUP_RADIUS),
+                });
+            }
+        }
+    }
+}
+
+/// Apply pickups the player picks up with the interact button or walks over
+///
+/// Touch pickups are collected when they overlap the player's `HurtBox` in
+/// the player's lane. A shield placed on the stage is picked up without
+/// effect under hardcore.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn collect_pickups(
+    mut commands: Commands,
+    mut interact_events: MessageReader<InteractEvent>,
+    mut player_query: Query<
+        (
+            Entity,
+            &Transform,
+            &JumpPhysics,
+            &HurtBox,
+            &mut Health,
+            &mut SpecialMeter,
+            &mut StatModifiers,
+        ),
+        With<Player>,
+    >,
+    pickup_query: Query<(Entity, &Transform, &Pickup)>,
+    mut game_state: ResMut<GameState>,
+    mutators: Res<Mutators>,
+    styles: Res<TextStyles>,
+    mut gameplay_events: MessageWriter<GameplayEvent>,
+) {
+    let Ok((
+        player_entity,
+        player_transform,
+        jump_physics,
+        hurtbox,
+        mut health,
+        mut meter,
+        mut modifiers,
+    )) = player_query.single_mut()
+    else {
+        return;
+    };
+    let player_pos = player_transform.translation.truncate();
+    let player_depth = depth(player_transform, Some(jump_physics));
+
+    let pressed = interact_events.read().map(|event| event.target);
+    let touched = pickup_query
+        .iter()
+        .filter(|(_, transform, pickup)| {
+            let offset = transform.translation.x - player_pos.x;
+            pickup.kind.collected_on_touch()
+                && offset.abs() < (hurtbox.size.x + PICKUP_SIZE.x) / 2.0
+                && same_lane(player_depth, transform.translation.y)
+        })
+        .map(|(entity, _, _)| entity);
+    let collected: Vec<Entity> = pressed.chain(touched).collect();
+
+    for entity in collected {
+        let Ok((_, transform, pickup)) = pickup_query.get(entity) else {
+            continue;
+        };
+
+        match pickup.kind {
+            PickupKind::Heal => {
+                health.current = (health.current + PickupKind::HEAL_AMOUNT).min(health.max);
+            }
+            PickupKind::Meter => {
+                meter.value = (meter.value + PickupKind::METER_AMOUNT).min(meter.max);
+            }
+            PickupKind::Score => {
+                let points = PickupKind::SCORE_AMOUNT * mutators.score_multiplier();
+                game_state.score += points;
+                commands.spawn(score_popup(
+                    &styles,
+                    format!("+{points}"),
+                    transform.translation.truncate(),
+                ));
+            }
+            // Picking up a damage boost that's already running restarts it,
+            // and a second speed boost stacks on the first
+            PickupKind::DamageBoost => modifiers.add(StatModifier::timed(
+                "damage_boost",
+                Stat::Damage,
+                PickupKind::DAMAGE_BOOST,
+                PickupKind::BOOST_SECONDS,
+            )),
+            PickupKind::SpeedBoost => modifiers.add(
+                StatModifier::timed(
+                    "speed_boost",
+                    Stat::Speed,
+                    PickupKind::SPEED_BOOST,
+                    PickupKind::BOOST_SECONDS,
+                )
+                .stacking(PickupKind::SPEED_BOOST_STACKS),
+            ),
+            PickupKind::Shield if mutators.hardcore => {}
+            // A new shield replaces what's left of the old one
+            PickupKind::Shield => {
+                commands.entity(player_entity).insert(Shield::new(
+                    PickupKind::SHIELD_AMOUNT,
+                    PickupKind::SHIELD_SECONDS,
+                ));
+            }
+        }
+        gameplay_events.write(GameplayEvent::PickupCollected {
+            collector: player_entity,
+            kind: pickup.kind,
+        });
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Blink pickups near the end of their lifetime and despawn them when it runs out
+pub fn update_pickups(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut pickup_query: Query<(Entity, &mut Pickup, &mut Visibility)>,
+) {
+    for (entity, mut pickup, mut visibility) in pickup_query.iter_mut() {
+        pickup.lifetime.tick(time.delta());
+
+        if pickup.lifetime.is_finished() {
+            commands.entity(entity).despawn();
+        } else if pickup.lifetime.remaining_secs() < PICKUP_BLINK_SECONDS {
+            // Toggle about 8 times per second
+            let on = ((pickup.lifetime.remaining_secs() * 8.0) as u32).is_multiple_of(2);
+            *visibility = if on {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use super::config::{AnimationConfig, AnimationCue};
+use super::state::PlayerState;
+use crate::persistence::{parse_key_values, parsed};
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::fs;
+
+/// Animation config for every player state (and Idle fidget) of one character,
+/// loaded once at startup
+///
+/// Keyed by `PlayerState::animation_key`. Starts from each state's compiled
+/// `get_animation_config` and applies the values in the character's
+/// animations file (assets/animations.txt for the default fighter) on top, so
+/// timing and frame ranges can be tuned without recompiling. Missing or
+/// malformed values keep the compiled default. Every sheet a library names is
+/// preloaded by `PlayerSpriteSheets`. Frame cues (footsteps, landings) work
+/// the same way, starting from `PlayerState::animation_cues`.
+pub struct AnimationLibrary {
+    configs: HashMap<&'static str, AnimationConfig>,
+    cues: HashMap<&'static str, Vec<(usize, AnimationCue)>>,
+}
+
+impl AnimationLibrary {
+    /// Load an animations file, relative to the working directory (compiled
+    /// defaults only if it can't be read)
+    pub fn load(path: &str) -> Self {
+        let contents = fs::read_to_string(path).unwrap_or_else(|err| {
+            warn!("Using compiled animation defaults ({path}: {err})");
+            String::new()
+        });
+        Self::from_key_values(&parse_key_values(&contents))
+    }
+
+    /// Animation config for a player state
+    pub fn config(&self, state: &PlayerState) -> AnimationConfig {
+        self.configs
+            .get(state.animation_key())
+            .cloned()
+            .unwrap_or_else(|| state.get_animation_config())
+    }
+
+    /// Frames of a player state's animation that publish a cue when reached
+    pub fn cues(&self, state: &PlayerState) -> &[(usize, AnimationCue)] {
+        self.cues
+            .get(state.animation_key())
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Every sprite sheet the library's animations use
+    pub fn sheets(&self) -> impl Iterator<Item = &str> {
+        self.configs
+            .values()
+            .map(|config| config.sprite_path.as_str())
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn from_key_values(values: &HashMap<&str, &str>) -> Self {
+        let defaults: Vec<(&'static str, AnimationConfig, Vec<(usize, AnimationCue)>)> =
+            PlayerState::animation_variants()
+                .map(|state| {
+                    (
+                        state.animation_key(),
+                        state.get_animation_config(),
+                        state.animation_cues(),
+                    )
+                })
+                .collect();
+        let mut configs = HashMap::new();
+        let mut cues = HashMap::new();
+        for (animation, default, default_cues) in defaults {
+            let field = |name: &str| {
+                let key = format!("{animation}.{name}");
+                values.get(key.as_str()).map(|value| (key, *value))
+            };
+            let mut config = default.clone();
+
+            if let Some((_, sheet)) = field("sheet") {
+                config.sprite_path = sheet.to_string();
+            }
+            if let Some(first) = field("first").and_then(parsed) {
+                config.first_frame = first;
+            }
+            if let Some(last) = field("last").and_then(parsed) {
+                config.last_frame = last;
+            }
+            if let Some(seconds) = field("frame_seconds").and_then(parsed::<f32>) {
+                if seconds > 0.0 {
+                    config.frame_duration = seconds;
+                } else {
+                    warn!("{animation}.frame_seconds must be positive");
+                }
+            }
+            if config.last_frame < config.first_frame {
+                warn!("{animation}: last frame is before first frame, using defaults");
+                config.first_frame = default.first_frame;
+                config.last_frame = default.last_frame;
+            }
+
+            // A cue line replaces every compiled frame of that cue
+            let mut frame_cues = default_cues;
+            for cue in AnimationCue::ALL {
+                if let Some((key, frames)) = field(cue.key()) {
+                    frame_cues.retain(|&(_, existing)| existing != cue);
+                    frame_cues.extend(cue_frames(&key, frames).map(|frame| (frame, cue)));
+                }
+            }
+            frame_cues.retain(|&(frame, cue)| {
+                let playable = (config.first_frame..=config.last_frame).contains(&frame);
+                if !playable {
+                    warn!(
+                        "{animation}.{}: frame {frame} is outside the animation",
+                        cue.key()
+                    );
+                }
+                playable
+            });
+
+            cues.insert(animation, frame_cues);
+            configs.insert(animation, config);
+        }
+
+        Self { configs, cues }
+    }
+}
+
+/// Frame numbers of a comma-separated cue field, skipping (and warning about) malformed ones
+fn cue_frames<'a>(key: &'a str, frames: &'a str) -> impl Iterator<Item = usize> + 'a {
+    frames
+        .split(',')
+        .map(str::trim)
+        .filter(|frame| !frame.is_empty())
+        .filter_map(move |frame| parsed((key.to_string(), frame)))
+}
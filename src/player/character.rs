@@ -0,0 +1,286 @@
+use super::animation_library::AnimationLibrary;
+use super::state::PlayerState;
+use crate::combat::OnHitStats;
+use crate::persistence::{parse_key_values, parsed};
+use crate::stats::{LifetimeStats, Unlock};
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::fs;
+
+/// Character roster file, relative to the working directory (like the asset folder)
+const CHARACTERS_PATH: &str = "assets/characters.txt";
+/// Animations file (under assets/) of a fighter that doesn't set one
+const DEFAULT_ANIMATIONS: &str = "animations.txt";
+/// Jump force of a fighter that doesn't set one
+const DEFAULT_JUMP_FORCE: f32 = 1000.0;
+/// What unlocks the `hardcore_costume`
+const HARDCORE_COSTUME_UNLOCK: Unlock = Unlock::HardcoreStagesCleared(1);
+
+/// A playable fighter: sprite sheets and frame data, damage, and movement
+///
+/// Every fighter shares the player state machine; a definition only changes
+/// what each state looks like, how long it lasts, and what it hits for.
+pub struct CharacterDefinition {
+    /// Shown on the character select screen
+    pub name: String,
+    /// One line under the name on the character select screen
+    pub description: String,
+    /// Sheets, frame ranges, timing, and cues for every state
+    pub animations: AnimationLibrary,
+    /// Multiplier on walking, running, dashing, and lane-stepping speed
+    pub speed: f32,
+    /// Upward velocity at the start of a jump
+    pub jump_force: f32,
+    /// Damage by `PlayerState::animation_key`, replacing the compiled `get_damage`
+    damage: HashMap<&'static str, i32>,
+    /// Trait bonuses to every hit the fighter lands (all zero for none)
+    pub on_hit: OnHitStats,
+}
+
+impl CharacterDefinition {
+    /// Damage dealt by a hit (or super) in `state`
+    pub fn damage(&self, state: &PlayerState) -> i32 {
+        self.damage
+            .get(state.animation_key())
+            .copied()
+            .unwrap_or_else(|| state.get_damage())
+    }
+}
+
+impl Default for CharacterDefinition {
+    fn default() -> Self {
+        Self {
+            name: "Fighter".to_string(),
+            description: String::new(),
+            animations: AnimationLibrary::load(&format!("assets/{DEFAULT_ANIMATIONS}")),
+            speed: 1.0,
+            jump_force: DEFAULT_JUMP_FORCE,
+            damage: HashMap::new(),
+            on_hit: OnHitStats::default(),
+        }
+    }
+}
+
+/// Which character a player fights as (an index into `Characters`)
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Fighter(pub usize);
+
+/// A palette swap: a tint multiplied into every sheet of whichever fighter
+/// wears it
+#[derive(Clone, Debug)]
+pub struct Costume {
+    /// Shown on the character select screen's costume button
+    pub name: String,
+    /// White for the sheets' own colors
+    pub tint: Color,
+    /// Progress needed before the costume button offers it
+    pub unlock: Unlock,
+}
+
+impl Costume {
+    fn new(name: &str, tint: Color) -> Self {
+        Self {
+            name: name.to_string(),
+            tint,
+            unlock: Unlock::Always,
+        }
+    }
+}
+
+/// Tint of the costume a player wears, multiplied into their sprite under any
+/// hit flash or blink (see `apply_damage_feedback`)
+#[derive(Component, Clone, Copy, Debug)]
+pub struct CostumeTint(pub Color);
+
+/// Every playable fighter from assets/characters.txt, and the ones picked on
+/// the character select screen
+///
+/// Falls back to a single fighter animated by assets/animations.txt if the
+/// roster can't be read or lists no one. Costumes are shared by every fighter
+/// and fall back to the original colors and a shadow palette; the hardcore
+/// costume comes after them, locked until a stage is cleared on hardcore.
+#[derive(Resource)]
+pub struct Characters {
+    roster: Vec<CharacterDefinition>,
+    costumes: Vec<Costume>,
+    /// Picked fighter of player 1 and player 2
+    selected: [Fighter; 2],
+    /// Picked costume (an index into `costumes`) of player 1 and player 2
+    selected_costumes: [usize; 2],
+}
+
+impl Characters {
+    /// Load assets/characters.txt and each character's animations file
+    pub fn load() -> Self {
+        let contents = fs::read_to_string(CHARACTERS_PATH).unwrap_or_else(|err| {
+            warn!("Using the default fighter only ({CHARACTERS_PATH}: {err})");
+            String::new()
+        });
+        Self::from_key_values(&parse_key_values(&contents))
+    }
+
+    /// Definition of a fighter (the first one for an unknown index)
+    pub fn get(&self, fighter: Fighter) -> &CharacterDefinition {
+        self.roster.get(fighter.0).unwrap_or(&self.roster[0])
+    }
+
+    /// Every fighter, in the order the character select screen lists them
+    pub fn iter(&self) -> impl Iterator<Item = (Fighter, &CharacterDefinition)> {
+        self.roster
+            .iter()
+            .enumerate()
+            .map(|(index, character)| (Fighter(index), character))
+    }
+
+    /// Fighter picked by player 1 (or player 2)
+    pub fn selected(&self, player_two: bool) -> Fighter {
+        self.selected[usize::from(player_two)]
+    }
+
+    /// Pick a fighter for player 1 (or player 2)
+    ///
+    /// Player 2 picking player 1's fighter in player 1's costume is moved on
+    /// to the next costume, so the two can always be told apart.
+    pub fn select(&mut self, fighter: Fighter, player_two: bool, stats: &LifetimeStats) {
+        self.selected[usize::from(player_two)] = fighter;
+        if player_two
+            && fighter == self.selected[0]
+            && self.selected_costumes[1] == self.selected_costumes[0]
+        {
+            self.select_next_costume(true, stats);
+        }
+    }
+
+    /// Costume worn by player 1 (or player 2)
+    pub fn costume(&self, player_two: bool) -> &Costume {
+        &self.costumes[self.selected_costumes[usize::from(player_two)]]
+    }
+
+    /// Every costume, in the order the costume button cycles them (locked ones included)
+    pub fn costumes(&self) -> &[Costume] {
+        &self.costumes
+    }
+
+    /// Cycle player 1's (or player 2's) costume, skipping locked ones
+    pub fn select_next_costume(&mut self, player_two: bool, stats: &LifetimeStats) {
+        let current = self.selected_costumes[usize::from(player_two)];
+        let next = (1..=self.costumes.len())
+            .map(|offset| (current + offset) % self.costumes.len())
+            .find(|&index| self.costumes[index].unlock.is_met(stats))
+            .unwrap_or(current);
+        self.selected_costumes[usize::from(player_two)] = next;
+    }
+
+    fn from_key_values(values: &HashMap<&str, &str>) -> Self {
+        let mut roster: Vec<CharacterDefinition> = values
+            .get("characters")
+            .into_iter()
+            .flat_map(|keys| keys.split(','))
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(|key| character_from_key_values(key, values))
+            .collect();
+        if roster.is_empty() {
+            roster.push(CharacterDefinition::default());
+        }
+
+        let mut costumes: Vec<Costume> = values
+            .get("costumes")
+            .into_iter()
+            .flat_map(|costumes| costumes.split(','))
+            .map(str::trim)
+            .filter(|costume| !costume.is_empty())
+            .filter_map(|costume| {
+                let parsed = parse_costume(costume);
+                if parsed.is_none() {
+                    warn!("costumes: can't parse costume {costume:?}");
+                }
+                parsed
+            })
+            .collect();
+        if costumes.len() < 2 {
+            if values.contains_key("costumes") {
+                warn!("costumes: fewer than two costumes, using the defaults");
+            }
+            costumes = vec![
+                Costume::new("Classic", Color::WHITE),
+                Costume::new("Shadow", Color::srgb(0.6, 0.62, 0.85)),
+            ];
+        }
+        if let Some(costume) = values.get("hardcore_costume") {
+            match parse_costume(costume) {
+                Some(costume) => costumes.push(Costume {
+                    unlock: HARDCORE_COSTUME_UNLOCK,
+                    ..costume
+                }),
+                None => warn!("hardcore_costume: can't parse costume {costume:?}"),
+            }
+        }
+
+        Self {
+            roster,
+            costumes,
+            selected: [Fighter(0); 2],
+            selected_costumes: [0; 2],
+        }
+    }
+}
+
+/// Parse a `<name> <#rrggbb>` costume (the name may have spaces)
+fn parse_costume(costume: &str) -> Option<Costume> {
+    let (name, tint) = costume.rsplit_once(' ')?;
+    let tint = Srgba::hex(tint.trim()).ok()?;
+    Some(Costume::new(name.trim(), tint.into()))
+}
+
+/// Build one character from its `<key>.<field>` values
+fn character_from_key_values(key: &str, values: &HashMap<&str, &str>) -> CharacterDefinition {
+    let field = |name: &str| {
+        let field_key = format!("{key}.{name}");
+        values
+            .get(field_key.as_str())
+            .map(|value| (field_key, *value))
+    };
+
+    let animations = field("animations").map_or(DEFAULT_ANIMATIONS, |(_, path)| path);
+    let mut character = CharacterDefinition {
+        name: field("name").map_or(key.to_string(), |(_, name)| name.to_string()),
+        description: field("description").map_or(String::new(), |(_, line)| line.to_string()),
+        animations: AnimationLibrary::load(&format!("assets/{animations}")),
+        speed: 1.0,
+        jump_force: DEFAULT_JUMP_FORCE,
+        damage: HashMap::new(),
+        on_hit: OnHitStats::default(),
+    };
+    if let Some(speed) = field("speed").and_then(parsed::<f32>) {
+        if speed > 0.0 {
+            character.speed = speed;
+        } else {
+            warn!("{key}.speed must be positive");
+        }
+    }
+    if let Some(jump_force) = field("jump").and_then(parsed::<f32>) {
+        if jump_force > 0.0 {
+            character.jump_force = jump_force;
+        } else {
+            warn!("{key}.jump must be positive");
+        }
+    }
+    for state in PlayerState::animation_variants() {
+        let animation = state.animation_key();
+        if let Some(damage) = field(&format!("damage.{animation}")).and_then(parsed::<i32>) {
+            character.damage.insert(animation, damage.max(0));
+        }
+    }
+    if let Some(lifesteal) = field("lifesteal").and_then(parsed::<f32>) {
+        character.on_hit.lifesteal = lifesteal.max(0.0);
+    }
+    if let Some(burn_chance) = field("burn_chance").and_then(parsed::<f32>) {
+        character.on_hit.burn_chance = burn_chance.clamp(0.0, 1.0);
+    }
+    if let Some(meter_gain) = field("meter_gain").and_then(parsed::<f32>) {
+        character.on_hit.meter_gain = meter_gain.max(0.0);
+    }
+
+    character
+}
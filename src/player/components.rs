@@ -1,10 +1,17 @@
 use crate::player::config::PlayerStateType;
+use crate::settings::ComboAssist;
 use bevy::prelude::*;
 
 /// Marker component for the player entity
 #[derive(Component)]
 pub struct Player;
 
+/// Marks the second player in a versus game (alongside `Player`)
+///
+/// Player 2 reads `PlayerTwoActions` instead of `PlayerActions`.
+#[derive(Component)]
+pub struct PlayerTwo;
+
 /// Jump physics component - handles vertical movement and jump state
 #[derive(Component)]
 pub struct JumpPhysics {
@@ -23,4 +30,25 @@ pub struct ComboWindow {
     pub last_attack: Option<PlayerStateType>,
     /// Queued combo state - will transition after current animation finishes
     pub queued_combo: Option<PlayerStateType>,
+    /// Attack press that couldn't be used yet, retried until its timer runs out
+    pub buffered: Option<(BufferedAttack, Timer)>,
+}
+
+impl ComboWindow {
+    /// Fresh combo window timed by the combo assist setting
+    pub fn new(assist: ComboAssist) -> Self {
+        Self {
+            timer: Timer::from_seconds(assist.combo_window_seconds(), TimerMode::Once),
+            last_attack: None,
+            queued_combo: None,
+            buffered: None,
+        }
+    }
+}
+
+/// Attack button held in the input buffer (see `ComboAssist::buffer_seconds`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BufferedAttack {
+    Punch,
+    Kick,
 }
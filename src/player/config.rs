@@ -3,12 +3,35 @@
 /// Animation configuration for a player state
 #[derive(Clone, Debug)]
 pub struct AnimationConfig {
-    pub sprite_path: &'static str,
+    /// Sprite sheet under assets/
+    pub sprite_path: String,
     pub first_frame: usize,
     pub last_frame: usize,
     pub frame_duration: f32,
 }
 
+/// Moment tagged on an animation frame, published when the frame is reached
+/// (see `AnimationLibrary::cues`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AnimationCue {
+    /// A foot comes down while walking or running
+    Footstep,
+    /// The player comes down from a jump
+    Landing,
+}
+
+impl AnimationCue {
+    pub const ALL: [AnimationCue; 2] = [AnimationCue::Footstep, AnimationCue::Landing];
+
+    /// Field name in assets/animations.txt (`<state>.<key>=<frames>`)
+    pub fn key(self) -> &'static str {
+        match self {
+            AnimationCue::Footstep => "footstep",
+            AnimationCue::Landing => "landing",
+        }
+    }
+}
+
 /// Physics behavior configuration for a player state
 #[derive(Clone, Debug, Default)]
 pub struct PhysicsConfig {
@@ -20,6 +43,10 @@ pub struct PhysicsConfig {
     pub apply_gravity: bool,
     /// Cannot move during this state (e.g., attacks)
     pub locks_movement: bool,
+    /// Fixed horizontal speed in the facing direction, ignoring input (0.0 unless dashing)
+    pub dash_speed: f32,
+    /// Speed when stepping up/down the depth lane with W/S (0.0 for states that can't)
+    pub depth_speed: f32,
 }
 
 /// Input context passed to state's handle_input method
@@ -27,16 +54,59 @@ pub struct PhysicsConfig {
 pub struct InputContext {
     pub left: bool,
     pub right: bool,
+    /// Step into the background (W)
+    pub depth_up: bool,
+    /// Step toward the camera (S)
+    pub depth_down: bool,
     pub shift: bool,
     pub space: bool,
     pub up_arrow: bool,
     pub down_arrow: bool,
+    /// Block held (false while the guard is broken)
+    pub block: bool,
+    /// Dash pressed (dedicated key or double-tapped direction)
+    pub dash: bool,
+    /// Super attack pressed
+    pub super_attack: bool,
+    /// Special meter is full (a super can be started)
+    pub meter_full: bool,
+    /// Grab pressed
+    pub grab: bool,
+    /// A ghost is within grab reach (see `GrabTarget`)
+    pub can_grab: bool,
     /// True if player has already used an aerial attack during this jump
     pub has_used_aerial_attack: bool,
     /// Current animation frame index
     pub current_frame: usize,
     /// Total frames in animation (last_frame + 1)
     pub total_frames: usize,
+    /// Number of enemies hit by the current state's attack so far
+    pub hit_count: usize,
+    /// Fraction of a whiffed attack that must play before a combo can be queued
+    /// (see `ComboAssist::whiff_queue_fraction`)
+    pub whiff_queue_fraction: f32,
+}
+
+impl InputContext {
+    /// Any movement direction held (horizontal or along the depth lane)
+    pub fn is_moving(&self) -> bool {
+        self.left || self.right || self.depth_up || self.depth_down
+    }
+
+    /// True if the current attack has connected with at least one enemy
+    pub fn hit_confirmed(&self) -> bool {
+        self.hit_count > 0
+    }
+
+    /// Can a combo be queued right now?
+    ///
+    /// A landed hit opens the combo window immediately (hit-confirm cancel);
+    /// a whiffed attack must play `whiff_queue_fraction` of its animation first
+    /// (the second half, unless the combo assist setting changes it).
+    pub fn can_queue_combo(&self) -> bool {
+        self.hit_confirmed()
+            || self.current_frame >= (self.total_frames as f32 * self.whiff_queue_fraction) as usize
+    }
 }
 
 /// Update context passed to state's update method
@@ -51,7 +121,7 @@ pub struct UpdateContext {
 }
 
 /// Lightweight enum representing state types (for transitions)
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum PlayerStateType {
     Idle,
     IdleToWalk,
@@ -61,6 +131,10 @@ pub enum PlayerStateType {
     Jump,
     Fall,
     Land,
+    Block,
+    Dash,
+    Super,
+    Grab,
     Punch,
     PunchCombo,
     Kick,
@@ -71,6 +145,58 @@ pub enum PlayerStateType {
     Defeat,
 }
 
+impl PlayerStateType {
+    /// Every state type, in declaration order
+    pub const ALL: [PlayerStateType; 20] = [
+        PlayerStateType::Idle,
+        PlayerStateType::IdleToWalk,
+        PlayerStateType::IdleToRun,
+        PlayerStateType::Walk,
+        PlayerStateType::Run,
+        PlayerStateType::Jump,
+        PlayerStateType::Fall,
+        PlayerStateType::Land,
+        PlayerStateType::Block,
+        PlayerStateType::Dash,
+        PlayerStateType::Super,
+        PlayerStateType::Grab,
+        PlayerStateType::Punch,
+        PlayerStateType::PunchCombo,
+        PlayerStateType::Kick,
+        PlayerStateType::KickCombo,
+        PlayerStateType::PunchKickCombo,
+        PlayerStateType::JumpPunch,
+        PlayerStateType::JumpKick,
+        PlayerStateType::Defeat,
+    ];
+
+    /// Stable name used in assets/animations.txt
+    pub fn key(self) -> &'static str {
+        match self {
+            PlayerStateType::Idle => "idle",
+            PlayerStateType::IdleToWalk => "idle_to_walk",
+            PlayerStateType::IdleToRun => "idle_to_run",
+            PlayerStateType::Walk => "walk",
+            PlayerStateType::Run => "run",
+            PlayerStateType::Jump => "jump",
+            PlayerStateType::Fall => "fall",
+            PlayerStateType::Land => "land",
+            PlayerStateType::Block => "block",
+            PlayerStateType::Dash => "dash",
+            PlayerStateType::Super => "super",
+            PlayerStateType::Grab => "grab",
+            PlayerStateType::Punch => "punch",
+            PlayerStateType::PunchCombo => "punch_combo",
+            PlayerStateType::Kick => "kick",
+            PlayerStateType::KickCombo => "kick_combo",
+            PlayerStateType::PunchKickCombo => "punch_kick_combo",
+            PlayerStateType::JumpPunch => "jump_punch",
+            PlayerStateType::JumpKick => "jump_kick",
+            PlayerStateType::Defeat => "defeat",
+        }
+    }
+}
+
 /// State transition result
 #[derive(Clone, Debug)]
 pub enum StateTransition {
@@ -1,10 +1,17 @@
+pub mod animation_library;
+pub mod character;
 pub mod components;
 pub mod config;
+pub mod plugin;
+pub mod spawn;
+pub mod sprites;
 pub mod state;
 pub mod states;
 pub mod systems;
 
 // Re-export commonly used items
+pub use character::*;
 pub use components::*;
 pub use config::*;
+pub use plugin::*;
 pub use state::PlayerState;
@@ -0,0 +1,39 @@
+use crate::game::{AppState, GameSet, InGame};
+use crate::player::character::Characters;
+use crate::player::spawn::spawn_player;
+use crate::player::sprites::load_player_sprite_sheets;
+use crate::player::systems::{
+    announce_animation_cues, announce_attacks, clear_hit_tracking_on_state_change,
+    initialize_jump_physics, player_input_system, player_physics_system,
+    player_sprite_update_system, player_state_update_system,
+};
+use bevy::prelude::*;
+
+/// Player spawning, input handling, state machine, animation, and fixed-step physics
+pub struct PlayerPlugin;
+
+impl Plugin for PlayerPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Characters::load())
+            .add_systems(Startup, load_player_sprite_sheets)
+            .add_systems(OnEnter(InGame), spawn_player)
+            .add_systems(
+                Update,
+                ((
+                    player_input_system.run_if(in_state(AppState::Playing)),
+                    initialize_jump_physics,
+                    clear_hit_tracking_on_state_change,
+                    player_state_update_system,
+                    player_sprite_update_system,
+                    announce_attacks,
+                    announce_animation_cues,
+                )
+                    .chain()
+                    .in_set(GameSet::PlayerControl),),
+            )
+            .add_systems(
+                FixedUpdate,
+                player_physics_system.in_set(GameSet::MovementIntents),
+            );
+    }
+}
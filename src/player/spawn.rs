@@ -0,0 +1,17 @@
+use crate::prefab::{Prefab, PrefabSpawner};
+use crate::settings::Mutators;
+use bevy::prelude::*;
+
+/// Where a player starts a match or round: player 1 on the left, player 2 on the right
+pub fn player_start(mutators: &Mutators, player_two: bool) -> Vec2 {
+    let x = if player_two { 200. } else { -200. };
+    Vec2::new(mutators.mirror_x(x), -200.)
+}
+
+/// Spawn the player at the start of a match
+///
+/// Runs on entering InGame; the player is tagged `DespawnOnExit(InGame)` and
+/// torn down when returning to the main menu.
+pub fn spawn_player(mut prefabs: PrefabSpawner, mutators: Res<Mutators>) {
+    prefabs.spawn(Prefab::Player, player_start(&mutators, false));
+}
@@ -0,0 +1,42 @@
+use crate::player::character::Characters;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Preloaded player sprite sheet handles to prevent flickering during state transitions
+///
+/// Holds every sheet named by any character's animations, keyed by its path
+/// under assets/.
+#[derive(Resource)]
+pub struct PlayerSpriteSheets {
+    sheets: HashMap<String, Handle<Image>>,
+}
+
+impl PlayerSpriteSheets {
+    /// Get the sprite handle for a given sprite path
+    pub fn get_handle(&self, sprite_path: &str) -> Handle<Image> {
+        match self.sheets.get(sprite_path) {
+            Some(handle) => handle.clone(),
+            None => panic!("Unknown sprite path: {}", sprite_path),
+        }
+    }
+}
+
+/// Load every player sprite sheet at startup
+pub fn load_player_sprite_sheets(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    characters: Res<Characters>,
+) {
+    // Preload all player sprite sheets to prevent flickering during transitions
+    let mut sheets = HashMap::new();
+    for (_, character) in characters.iter() {
+        for path in character.animations.sheets() {
+            sheets
+                .entry(path.to_string())
+                .or_insert_with(|| asset_server.load(path.to_string()));
+        }
+    }
+
+    // Insert preloaded sprite sheets as a resource
+    commands.insert_resource(PlayerSpriteSheets { sheets });
+}
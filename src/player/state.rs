@@ -1,5 +1,6 @@
 use super::config::*;
 use super::states::*;
+use crate::combat::AttackWeight;
 use bevy::prelude::Component;
 
 /// Trait that all player states must implement
@@ -34,6 +35,13 @@ pub trait StateLogic: Send + Sync + Clone {
         false
     }
 
+    /// Is this a guarding state?
+    ///
+    /// Default: false. Override in Block so incoming damage is reduced
+    fn is_blocking(&self) -> bool {
+        false
+    }
+
     /// Get the damage dealt by this state
     ///
     /// Default: 0. Override in attack states to return damage amount
@@ -43,7 +51,7 @@ pub trait StateLogic: Send + Sync + Clone {
 }
 
 /// Player state component - each variant holds a state behavior object
-#[derive(Component, Clone)]
+#[derive(Component, Clone, Debug)]
 pub enum PlayerState {
     Idle(IdleStateData),
     IdleToWalk(IdleToWalkStateData),
@@ -53,6 +61,10 @@ pub enum PlayerState {
     Jump(JumpStateData),
     Fall(FallStateData),
     Land(LandStateData),
+    Block(BlockStateData),
+    Dash(DashStateData),
+    Super(SuperStateData),
+    Grab(GrabStateData),
     Punch(PunchStateData),
     PunchCombo(PunchComboStateData),
     Kick(KickStateData),
@@ -75,6 +87,10 @@ impl PlayerState {
             PlayerState::Jump(s) => s.handle_input(input),
             PlayerState::Fall(s) => s.handle_input(input),
             PlayerState::Land(s) => s.handle_input(input),
+            PlayerState::Block(s) => s.handle_input(input),
+            PlayerState::Dash(s) => s.handle_input(input),
+            PlayerState::Super(s) => s.handle_input(input),
+            PlayerState::Grab(s) => s.handle_input(input),
             PlayerState::Punch(s) => s.handle_input(input),
             PlayerState::PunchCombo(s) => s.handle_input(input),
             PlayerState::Kick(s) => s.handle_input(input),
@@ -97,6 +113,10 @@ impl PlayerState {
             PlayerState::Jump(s) => s.update(ctx),
             PlayerState::Fall(s) => s.update(ctx),
             PlayerState::Land(s) => s.update(ctx),
+            PlayerState::Block(s) => s.update(ctx),
+            PlayerState::Dash(s) => s.update(ctx),
+            PlayerState::Super(s) => s.update(ctx),
+            PlayerState::Grab(s) => s.update(ctx),
             PlayerState::Punch(s) => s.update(ctx),
             PlayerState::PunchCombo(s) => s.update(ctx),
             PlayerState::Kick(s) => s.update(ctx),
@@ -119,6 +139,10 @@ impl PlayerState {
             PlayerState::Jump(s) => s.get_animation_config(),
             PlayerState::Fall(s) => s.get_animation_config(),
             PlayerState::Land(s) => s.get_animation_config(),
+            PlayerState::Block(s) => s.get_animation_config(),
+            PlayerState::Dash(s) => s.get_animation_config(),
+            PlayerState::Super(s) => s.get_animation_config(),
+            PlayerState::Grab(s) => s.get_animation_config(),
             PlayerState::Punch(s) => s.get_animation_config(),
             PlayerState::PunchCombo(s) => s.get_animation_config(),
             PlayerState::Kick(s) => s.get_animation_config(),
@@ -141,6 +165,10 @@ impl PlayerState {
             PlayerState::Jump(s) => s.get_physics_config(),
             PlayerState::Fall(s) => s.get_physics_config(),
             PlayerState::Land(s) => s.get_physics_config(),
+            PlayerState::Block(s) => s.get_physics_config(),
+            PlayerState::Dash(s) => s.get_physics_config(),
+            PlayerState::Super(s) => s.get_physics_config(),
+            PlayerState::Grab(s) => s.get_physics_config(),
             PlayerState::Punch(s) => s.get_physics_config(),
             PlayerState::PunchCombo(s) => s.get_physics_config(),
             PlayerState::Kick(s) => s.get_physics_config(),
@@ -168,6 +196,10 @@ impl PlayerState {
             PlayerState::Jump(s) => s.is_attacking(),
             PlayerState::Fall(s) => s.is_attacking(),
             PlayerState::Land(s) => s.is_attacking(),
+            PlayerState::Block(s) => s.is_attacking(),
+            PlayerState::Dash(s) => s.is_attacking(),
+            PlayerState::Super(s) => s.is_attacking(),
+            PlayerState::Grab(s) => s.is_attacking(),
             PlayerState::Punch(s) => s.is_attacking(),
             PlayerState::PunchCombo(s) => s.is_attacking(),
             PlayerState::Kick(s) => s.is_attacking(),
@@ -179,6 +211,71 @@ impl PlayerState {
         }
     }
 
+    /// Is this a guarding state?
+    pub fn is_blocking(&self) -> bool {
+        match self {
+            PlayerState::Idle(s) => s.is_blocking(),
+            PlayerState::IdleToWalk(s) => s.is_blocking(),
+            PlayerState::IdleToRun(s) => s.is_blocking(),
+            PlayerState::Walk(s) => s.is_blocking(),
+            PlayerState::Run(s) => s.is_blocking(),
+            PlayerState::Jump(s) => s.is_blocking(),
+            PlayerState::Fall(s) => s.is_blocking(),
+            PlayerState::Land(s) => s.is_blocking(),
+            PlayerState::Block(s) => s.is_blocking(),
+            PlayerState::Dash(s) => s.is_blocking(),
+            PlayerState::Super(s) => s.is_blocking(),
+            PlayerState::Grab(s) => s.is_blocking(),
+            PlayerState::Punch(s) => s.is_blocking(),
+            PlayerState::PunchCombo(s) => s.is_blocking(),
+            PlayerState::Kick(s) => s.is_blocking(),
+            PlayerState::KickCombo(s) => s.is_blocking(),
+            PlayerState::PunchKickCombo(s) => s.is_blocking(),
+            PlayerState::JumpPunch(s) => s.is_blocking(),
+            PlayerState::JumpKick(s) => s.is_blocking(),
+            PlayerState::Defeat(s) => s.is_blocking(),
+        }
+    }
+
+    /// Is this the last hit of an attack chain (a combo state)?
+    pub fn is_combo_finisher(&self) -> bool {
+        matches!(
+            self,
+            PlayerState::PunchCombo(_) | PlayerState::KickCombo(_) | PlayerState::PunchKickCombo(_)
+        )
+    }
+
+    /// Does a hit from this state knock enemies down (combo finishers and aerial attacks)?
+    pub fn knocks_down(&self) -> bool {
+        self.is_combo_finisher()
+            || matches!(self, PlayerState::JumpPunch(_) | PlayerState::JumpKick(_))
+    }
+
+    /// Weight of this state's attack when it trades hits with an enemy
+    pub fn attack_weight(&self) -> AttackWeight {
+        match self {
+            PlayerState::Kick(_) | PlayerState::JumpKick(_) | PlayerState::PunchCombo(_) => {
+                AttackWeight::Medium
+            }
+            PlayerState::KickCombo(_) | PlayerState::PunchKickCombo(_) | PlayerState::Super(_) => {
+                AttackWeight::Heavy
+            }
+            _ => AttackWeight::Light,
+        }
+    }
+
+    /// Frames of this state's compiled animation that publish an `AnimationCue`
+    ///
+    /// assets/animations.txt can retag them (see `AnimationLibrary::cues`).
+    pub fn animation_cues(&self) -> Vec<(usize, AnimationCue)> {
+        match self {
+            PlayerState::Walk(_) => vec![(3, AnimationCue::Footstep), (9, AnimationCue::Footstep)],
+            PlayerState::Run(_) => vec![(2, AnimationCue::Footstep), (6, AnimationCue::Footstep)],
+            PlayerState::Land(_) => vec![(1, AnimationCue::Landing)],
+            _ => Vec::new(),
+        }
+    }
+
     /// Get damage dealt by this state
     pub fn get_damage(&self) -> i32 {
         match self {
@@ -190,6 +287,10 @@ impl PlayerState {
             PlayerState::Jump(s) => s.get_damage(),
             PlayerState::Fall(s) => s.get_damage(),
             PlayerState::Land(s) => s.get_damage(),
+            PlayerState::Block(s) => s.get_damage(),
+            PlayerState::Dash(s) => s.get_damage(),
+            PlayerState::Super(s) => s.get_damage(),
+            PlayerState::Grab(s) => s.get_damage(),
             PlayerState::Punch(s) => s.get_damage(),
             PlayerState::PunchCombo(s) => s.get_damage(),
             PlayerState::Kick(s) => s.get_damage(),
@@ -201,10 +302,63 @@ impl PlayerState {
         }
     }
 
+    /// The lightweight type of this state (the inverse of `transition_to`)
+    pub fn state_type(&self) -> PlayerStateType {
+        match self {
+            PlayerState::Idle(_) => PlayerStateType::Idle,
+            PlayerState::IdleToWalk(_) => PlayerStateType::IdleToWalk,
+            PlayerState::IdleToRun(_) => PlayerStateType::IdleToRun,
+            PlayerState::Walk(_) => PlayerStateType::Walk,
+            PlayerState::Run(_) => PlayerStateType::Run,
+            PlayerState::Jump(_) => PlayerStateType::Jump,
+            PlayerState::Fall(_) => PlayerStateType::Fall,
+            PlayerState::Land(_) => PlayerStateType::Land,
+            PlayerState::Block(_) => PlayerStateType::Block,
+            PlayerState::Dash(_) => PlayerStateType::Dash,
+            PlayerState::Super(_) => PlayerStateType::Super,
+            PlayerState::Grab(_) => PlayerStateType::Grab,
+            PlayerState::Punch(_) => PlayerStateType::Punch,
+            PlayerState::PunchCombo(_) => PlayerStateType::PunchCombo,
+            PlayerState::Kick(_) => PlayerStateType::Kick,
+            PlayerState::KickCombo(_) => PlayerStateType::KickCombo,
+            PlayerState::PunchKickCombo(_) => PlayerStateType::PunchKickCombo,
+            PlayerState::JumpPunch(_) => PlayerStateType::JumpPunch,
+            PlayerState::JumpKick(_) => PlayerStateType::JumpKick,
+            PlayerState::Defeat(_) => PlayerStateType::Defeat,
+        }
+    }
+
+    /// Name of the animation this state is playing in assets/animations.txt
+    ///
+    /// The state type's key, except for Idle fidgets and emotes, which have their own.
+    pub fn animation_key(&self) -> &'static str {
+        match self {
+            PlayerState::Idle(s) => s.animation_key(),
+            _ => self.state_type().key(),
+        }
+    }
+
+    /// One state per animation: every state type plus each Idle fidget and emote
+    pub fn animation_variants() -> impl Iterator<Item = PlayerState> {
+        PlayerStateType::ALL
+            .into_iter()
+            .map(PlayerState::transition_to)
+            .chain(
+                IdleFidget::ALL
+                    .into_iter()
+                    .map(|fidget| PlayerState::Idle(IdleStateData::fidgeting(fidget))),
+            )
+            .chain(
+                Emote::ALL
+                    .into_iter()
+                    .map(|emote| PlayerState::Idle(IdleStateData::emoting(emote))),
+            )
+    }
+
     /// Factory method to create new state instances
     pub fn transition_to(state_type: PlayerStateType) -> Self {
         match state_type {
-            PlayerStateType::Idle => PlayerState::Idle(IdleStateData),
+            PlayerStateType::Idle => PlayerState::Idle(IdleStateData::default()),
             PlayerStateType::IdleToWalk => PlayerState::IdleToWalk(IdleToWalkStateData),
             PlayerStateType::IdleToRun => PlayerState::IdleToRun(IdleToRunStateData),
             PlayerStateType::Walk => PlayerState::Walk(WalkStateData),
@@ -212,6 +366,10 @@ impl PlayerState {
             PlayerStateType::Jump => PlayerState::Jump(JumpStateData),
             PlayerStateType::Fall => PlayerState::Fall(FallStateData),
             PlayerStateType::Land => PlayerState::Land(LandStateData),
+            PlayerStateType::Block => PlayerState::Block(BlockStateData),
+            PlayerStateType::Dash => PlayerState::Dash(DashStateData),
+            PlayerStateType::Super => PlayerState::Super(SuperStateData),
+            PlayerStateType::Grab => PlayerState::Grab(GrabStateData),
             PlayerStateType::Punch => PlayerState::Punch(PunchStateData),
             PlayerStateType::PunchCombo => PlayerState::PunchCombo(PunchComboStateData),
             PlayerStateType::Kick => PlayerState::Kick(KickStateData),
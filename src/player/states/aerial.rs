@@ -29,7 +29,7 @@ impl StateLogic for JumpPunchStateData {
 
     fn get_animation_config(&self) -> AnimationConfig {
         AnimationConfig {
-            sprite_path: "player/jump-punch-sheet.png",
+            sprite_path: "player/jump-punch-sheet.png".into(),
             first_frame: 1,
             last_frame: 17, // 18 frames total
             frame_duration: 0.02,
@@ -42,6 +42,8 @@ impl StateLogic for JumpPunchStateData {
             air_control: true,     // Can steer with A/D during attack
             apply_gravity: false,  // Freeze height during attack (arcade-style)
             locks_movement: false, // Air control is allowed
+            dash_speed: 0.0,
+            depth_speed: 0.0,
         }
     }
 
@@ -82,7 +84,7 @@ impl StateLogic for JumpKickStateData {
 
     fn get_animation_config(&self) -> AnimationConfig {
         AnimationConfig {
-            sprite_path: "player/jump-kick-sheet.png",
+            sprite_path: "player/jump-kick-sheet.png".into(),
             first_frame: 1,
             last_frame: 19, // 20 frames total
             frame_duration: 0.02,
@@ -95,6 +97,8 @@ impl StateLogic for JumpKickStateData {
             air_control: true,     // Can steer with A/D during attack
             apply_gravity: false,  // Freeze height during attack (arcade-style)
             locks_movement: false, // Air control is allowed
+            dash_speed: 0.0,
+            depth_speed: 0.0,
         }
     }
 
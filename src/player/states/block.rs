@@ -0,0 +1,50 @@
+use crate::player::config::*;
+use crate::player::state::StateLogic;
+
+/// Block state - player holds a guard while the Block input is held
+///
+/// Incoming damage is reduced to chip damage while blocking (see
+/// `handle_damage_events`). Releasing Block returns to Idle.
+#[derive(Clone, Default, Debug)]
+pub struct BlockStateData;
+
+impl StateLogic for BlockStateData {
+    fn handle_input(&self, input: &InputContext) -> StateTransition {
+        // Drop the guard as soon as Block is released (or the guard breaks)
+        if !input.block {
+            return StateTransition::To(PlayerStateType::Idle);
+        }
+
+        StateTransition::None
+    }
+
+    fn update(&self, _ctx: &UpdateContext) -> StateTransition {
+        // Block only ends through input
+        StateTransition::None
+    }
+
+    fn get_animation_config(&self) -> AnimationConfig {
+        // No dedicated block art yet: loop the crouched, fists-up frames of the landing sheet
+        AnimationConfig {
+            sprite_path: "player/landing-sheet.png".into(),
+            first_frame: 10,
+            last_frame: 13,
+            frame_duration: 0.15,
+        }
+    }
+
+    fn get_physics_config(&self) -> PhysicsConfig {
+        PhysicsConfig {
+            ground_speed: 0.0,
+            air_control: false,
+            apply_gravity: false,
+            locks_movement: false, // Must stay false so releasing Block is seen
+            dash_speed: 0.0,
+            depth_speed: 0.0,
+        }
+    }
+
+    fn is_blocking(&self) -> bool {
+        true
+    }
+}
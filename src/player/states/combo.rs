@@ -24,7 +24,7 @@ impl StateLogic for PunchKickComboStateData {
 
     fn get_animation_config(&self) -> AnimationConfig {
         AnimationConfig {
-            sprite_path: "player/punch-kick-combo-sheet.png",
+            sprite_path: "player/punch-kick-combo-sheet.png".into(),
             first_frame: 1,
             last_frame: 16, // 17 frames total
             frame_duration: 0.03,
@@ -37,6 +37,8 @@ impl StateLogic for PunchKickComboStateData {
             air_control: false,
             apply_gravity: false,
             locks_movement: true, // Cannot move during attack
+            dash_speed: 0.0,
+            depth_speed: 0.0,
         }
     }
 
@@ -0,0 +1,44 @@
+use crate::player::config::*;
+use crate::player::state::StateLogic;
+
+/// Dash state - short burst of horizontal speed in the facing direction
+///
+/// The player is invulnerable during the middle third of the animation
+/// (see `update_dash_invulnerability`). Returns to Idle when it finishes.
+#[derive(Clone, Default, Debug)]
+pub struct DashStateData;
+
+impl StateLogic for DashStateData {
+    fn handle_input(&self, _input: &InputContext) -> StateTransition {
+        // Dash is committed once started
+        StateTransition::None
+    }
+
+    fn update(&self, ctx: &UpdateContext) -> StateTransition {
+        if ctx.animation_finished {
+            return StateTransition::To(PlayerStateType::Idle);
+        }
+        StateTransition::None
+    }
+
+    fn get_animation_config(&self) -> AnimationConfig {
+        // No dedicated dash art yet: play the run cycle at double speed
+        AnimationConfig {
+            sprite_path: "player/run-sheet.png".into(),
+            first_frame: 1,
+            last_frame: 7, // 8 frames total
+            frame_duration: 0.05,
+        }
+    }
+
+    fn get_physics_config(&self) -> PhysicsConfig {
+        PhysicsConfig {
+            ground_speed: 0.0,
+            air_control: false,
+            apply_gravity: false,
+            locks_movement: true, // Direction is fixed for the whole dash
+            dash_speed: 900.0,
+            depth_speed: 0.0,
+        }
+    }
+}
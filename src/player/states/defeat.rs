@@ -20,7 +20,7 @@ impl StateLogic for DefeatStateData {
 
     fn get_animation_config(&self) -> AnimationConfig {
         AnimationConfig {
-            sprite_path: "player/defeat-sheet.png",
+            sprite_path: "player/defeat-sheet.png".into(),
             first_frame: 1,
             last_frame: 20, // 21 frames total (6720px / 320px)
             frame_duration: 0.1,
@@ -33,6 +33,8 @@ impl StateLogic for DefeatStateData {
             air_control: false,
             apply_gravity: false,
             locks_movement: true, // Cannot move when defeated
+            dash_speed: 0.0,
+            depth_speed: 0.0,
         }
     }
 }
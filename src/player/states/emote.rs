@@ -0,0 +1,53 @@
+use crate::player::config::AnimationConfig;
+
+/// Gesture the player can play on command while standing idle
+///
+/// Emotes play inside the Idle state (see `IdleStateData::emote`), so they're
+/// only available when the player is safe to stand around, and any other
+/// input cancels them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Emote {
+    Wave,
+    Flex,
+    Bow,
+}
+
+impl Emote {
+    pub const ALL: [Emote; 3] = [Emote::Wave, Emote::Flex, Emote::Bow];
+
+    /// Stable name used in assets/animations.txt (as `emote_<name>`)
+    pub fn key(self) -> &'static str {
+        match self {
+            Emote::Wave => "emote_wave",
+            Emote::Flex => "emote_flex",
+            Emote::Bow => "emote_bow",
+        }
+    }
+
+    // No dedicated emote art yet: each borrows a slow stretch of an existing sheet
+    pub(crate) fn animation_config(self) -> AnimationConfig {
+        match self {
+            // Arm raised and lowered from the punch
+            Emote::Wave => AnimationConfig {
+                sprite_path: "player/punch-sheet.png".into(),
+                first_frame: 1,
+                last_frame: 6,
+                frame_duration: 0.12,
+            },
+            // Wind-up of the punch combo, held
+            Emote::Flex => AnimationConfig {
+                sprite_path: "player/punch-combo-sheet.png".into(),
+                first_frame: 2,
+                last_frame: 4,
+                frame_duration: 0.3,
+            },
+            // Crouch of the landing
+            Emote::Bow => AnimationConfig {
+                sprite_path: "player/landing-sheet.png".into(),
+                first_frame: 6,
+                last_frame: 14,
+                frame_duration: 0.12,
+            },
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use crate::player::config::*;
+use crate::player::state::StateLogic;
+
+/// Grab state - holds a ghost in front of the player, then throws it
+///
+/// Only entered with a ghost in reach. `start_grab` takes hold of it on
+/// entering the state, and `hold_grabbed_enemies` throws it once the state
+/// ends. Not an `is_attacking` state: the throw does the damage on landing.
+#[derive(Clone, Default, Debug)]
+pub struct GrabStateData;
+
+impl StateLogic for GrabStateData {
+    fn handle_input(&self, _input: &InputContext) -> StateTransition {
+        // The throw is committed once the grab starts
+        StateTransition::None
+    }
+
+    fn update(&self, ctx: &UpdateContext) -> StateTransition {
+        if ctx.animation_finished {
+            return StateTransition::To(PlayerStateType::Idle);
+        }
+        StateTransition::None
+    }
+
+    fn get_animation_config(&self) -> AnimationConfig {
+        // No dedicated grab art yet: play the punch combo slowly, ending on the throw
+        AnimationConfig {
+            sprite_path: "player/punch-combo-sheet.png".into(),
+            first_frame: 1,
+            last_frame: 7, // 8 frames total
+            frame_duration: 0.1,
+        }
+    }
+
+    fn get_physics_config(&self) -> PhysicsConfig {
+        PhysicsConfig {
+            ground_speed: 0.0,
+            air_control: false,
+            apply_gravity: false,
+            locks_movement: true, // Cannot move while holding a ghost
+            dash_speed: 0.0,
+            depth_speed: 0.0,
+        }
+    }
+}
@@ -1,13 +1,164 @@
+use super::emote::Emote;
 use crate::player::config::*;
 use crate::player::state::StateLogic;
+use rand::Rng;
+use std::ops::Range;
+
+/// Seconds of standing still before the first fidget
+const FIDGET_AFTER_SECONDS: f32 = 8.0;
+/// Seconds of normal idling between fidgets, picked at random
+const FIDGET_GAP_SECONDS: Range<f32> = 3.0..6.0;
+
+/// Alternate idle animation played after the player has stood still for a while
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IdleFidget {
+    Stretch,
+    LookAround,
+}
+
+impl IdleFidget {
+    pub const ALL: [IdleFidget; 2] = [IdleFidget::Stretch, IdleFidget::LookAround];
+
+    /// Stable name used in assets/animations.txt (as `idle_<key>`)
+    pub fn key(self) -> &'static str {
+        match self {
+            IdleFidget::Stretch => "idle_stretch",
+            IdleFidget::LookAround => "idle_look_around",
+        }
+    }
+
+    fn animation_config(self) -> AnimationConfig {
+        match self {
+            IdleFidget::Stretch => AnimationConfig {
+                sprite_path: "player/relax-sheet.png".into(),
+                first_frame: 1,
+                last_frame: 20, // 21 frames total (6720px / 320px)
+                frame_duration: 0.1,
+            },
+            // No dedicated look-around art yet: linger over the start of the relax sheet
+            IdleFidget::LookAround => AnimationConfig {
+                sprite_path: "player/relax-sheet.png".into(),
+                first_frame: 1,
+                last_frame: 8,
+                frame_duration: 0.25,
+            },
+        }
+    }
+}
 
 /// Idle state - player is standing still
-#[derive(Clone, Default, Debug)]
-pub struct IdleStateData;
+///
+/// Tracks how long the player has stood still; after `FIDGET_AFTER_SECONDS`
+/// it plays a random `IdleFidget` every few loops (see `advance`). An `Emote`
+/// started with `play_emote` plays for one loop and takes priority over fidgets.
+#[derive(Clone, Debug)]
+pub struct IdleStateData {
+    /// Seconds since entering Idle (any input leaves Idle)
+    pub idle_seconds: f32,
+    /// Idle time at which the next fidget starts
+    pub next_fidget_at: f32,
+    /// Fidget being played, if any
+    pub fidget: Option<IdleFidget>,
+    /// Emote being played, if any
+    pub emote: Option<Emote>,
+}
+
+impl Default for IdleStateData {
+    fn default() -> Self {
+        Self {
+            idle_seconds: 0.0,
+            next_fidget_at: FIDGET_AFTER_SECONDS,
+            fidget: None,
+            emote: None,
+        }
+    }
+}
+
+impl IdleStateData {
+    /// Idle with a fidget playing (for looking up its animation)
+    pub fn fidgeting(fidget: IdleFidget) -> Self {
+        Self {
+            fidget: Some(fidget),
+            ..Self::default()
+        }
+    }
+
+    /// Idle with an emote playing (for looking up its animation)
+    pub fn emoting(emote: Emote) -> Self {
+        Self {
+            emote: Some(emote),
+            ..Self::default()
+        }
+    }
+
+    /// Start an emote, replacing any fidget or emote already playing
+    pub fn play_emote(&mut self, emote: Emote) {
+        self.fidget = None;
+        self.emote = Some(emote);
+    }
+
+    /// Advance the idle time, starting or ending a fidget (or ending an emote)
+    /// when a loop finishes
+    ///
+    /// Returns true when the animation changed, so the sprite needs updating.
+    pub fn advance(&mut self, delta_seconds: f32, animation_finished: bool) -> bool {
+        self.idle_seconds += delta_seconds;
+        if !animation_finished {
+            return false;
+        }
+
+        let mut rng = rand::thread_rng();
+        if self.emote.take().is_some() {
+            self.next_fidget_at = self.idle_seconds + rng.gen_range(FIDGET_GAP_SECONDS);
+            return true;
+        }
+        match self.fidget {
+            Some(_) => {
+                self.fidget = None;
+                self.next_fidget_at = self.idle_seconds + rng.gen_range(FIDGET_GAP_SECONDS);
+                true
+            }
+            None if self.idle_seconds >= self.next_fidget_at => {
+                self.fidget = Some(IdleFidget::ALL[rng.gen_range(0..IdleFidget::ALL.len())]);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Name used in assets/animations.txt for the animation currently playing
+    pub fn animation_key(&self) -> &'static str {
+        if let Some(emote) = self.emote {
+            return emote.key();
+        }
+        self.fidget
+            .map_or(PlayerStateType::Idle.key(), IdleFidget::key)
+    }
+}
 
 impl StateLogic for IdleStateData {
     fn handle_input(&self, input: &InputContext) -> StateTransition {
-        // Attack inputs have highest priority
+        // Holding Block raises the guard (highest priority on the ground)
+        if input.block {
+            return StateTransition::To(PlayerStateType::Block);
+        }
+
+        // Super spends a full special meter
+        if input.super_attack && input.meter_full {
+            return StateTransition::To(PlayerStateType::Super);
+        }
+
+        // Dash dodges through enemies (invulnerable during the middle frames)
+        if input.dash {
+            return StateTransition::To(PlayerStateType::Dash);
+        }
+
+        // Grab takes hold of a ghost in reach, to throw it
+        if input.grab && input.can_grab {
+            return StateTransition::To(PlayerStateType::Grab);
+        }
+
+        // Attack inputs are next
         if input.up_arrow {
             return StateTransition::To(PlayerStateType::Punch);
         }
@@ -21,7 +172,7 @@ impl StateLogic for IdleStateData {
         }
 
         // Movement inputs (lower priority)
-        if input.left || input.right {
+        if input.is_moving() {
             if input.shift {
                 return StateTransition::To(PlayerStateType::IdleToWalk);
             } else {
@@ -34,13 +185,20 @@ impl StateLogic for IdleStateData {
     }
 
     fn update(&self, _ctx: &UpdateContext) -> StateTransition {
-        // Idle doesn't auto-transition based on animation or physics
+        // Idle doesn't auto-transition based on animation or physics (fidgets
+        // stay in Idle, see `advance`)
         StateTransition::None
     }
 
     fn get_animation_config(&self) -> AnimationConfig {
+        if let Some(emote) = self.emote {
+            return emote.animation_config();
+        }
+        if let Some(fidget) = self.fidget {
+            return fidget.animation_config();
+        }
         AnimationConfig {
-            sprite_path: "player/idle-sheet.png",
+            sprite_path: "player/idle-sheet.png".into(),
             first_frame: 1,
             last_frame: 23, // 24 frames total (7680px / 320px)
             frame_duration: 0.12,
@@ -53,6 +211,8 @@ impl StateLogic for IdleStateData {
             air_control: false,
             apply_gravity: false,
             locks_movement: false,
+            dash_speed: 0.0,
+            depth_speed: 0.0,
         }
     }
 }
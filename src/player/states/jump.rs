@@ -32,7 +32,7 @@ impl StateLogic for JumpStateData {
 
     fn get_animation_config(&self) -> AnimationConfig {
         AnimationConfig {
-            sprite_path: "player/jump-sheet.png",
+            sprite_path: "player/jump-sheet.png".into(),
             first_frame: 1,
             last_frame: 26, // 27 frames total (8640px / 320px)
             frame_duration: 0.05,
@@ -45,6 +45,8 @@ impl StateLogic for JumpStateData {
             air_control: true,   // Can steer with A/D
             apply_gravity: true, // Gravity slows upward velocity
             locks_movement: false,
+            dash_speed: 0.0,
+            depth_speed: 0.0,
         }
     }
 }
@@ -80,7 +82,7 @@ impl StateLogic for FallStateData {
 
     fn get_animation_config(&self) -> AnimationConfig {
         AnimationConfig {
-            sprite_path: "player/falling-sheet.png",
+            sprite_path: "player/falling-sheet.png".into(),
             first_frame: 1,
             last_frame: 19, // 20 frames total
             frame_duration: 0.1,
@@ -93,6 +95,8 @@ impl StateLogic for FallStateData {
             air_control: true,   // Can steer with A/D
             apply_gravity: true, // Gravity accelerates downward
             locks_movement: false,
+            dash_speed: 0.0,
+            depth_speed: 0.0,
         }
     }
 }
@@ -118,7 +122,7 @@ impl StateLogic for LandStateData {
 
     fn get_animation_config(&self) -> AnimationConfig {
         AnimationConfig {
-            sprite_path: "player/landing-sheet.png",
+            sprite_path: "player/landing-sheet.png".into(),
             first_frame: 1,
             last_frame: 20, // 21 frames total
             frame_duration: 0.02,
@@ -131,6 +135,8 @@ impl StateLogic for LandStateData {
             air_control: false,
             apply_gravity: false, // Locked at ground level during landing
             locks_movement: true, // Cannot move during landing animation
+            dash_speed: 0.0,
+            depth_speed: 0.0,
         }
     }
 }
@@ -7,10 +7,10 @@ pub struct KickStateData;
 
 impl StateLogic for KickStateData {
     fn handle_input(&self, input: &InputContext) -> StateTransition {
-        // Check for combo input during second half of attack animation
+        // Check for combo input (hit-confirmed or second half of attack animation)
         if input.down_arrow {
-            // Only allow combo queueing in second half of animation
-            if input.current_frame >= input.total_frames / 2 {
+            // Allow combo queueing on hit, or in second half of a whiffed animation
+            if input.can_queue_combo() {
                 return StateTransition::QueueCombo(PlayerStateType::KickCombo);
             }
         }
@@ -30,7 +30,7 @@ impl StateLogic for KickStateData {
 
     fn get_animation_config(&self) -> AnimationConfig {
         AnimationConfig {
-            sprite_path: "player/kick-sheet.png",
+            sprite_path: "player/kick-sheet.png".into(),
             first_frame: 1,
             last_frame: 20, // 21 frames total
             frame_duration: 0.02,
@@ -43,6 +43,8 @@ impl StateLogic for KickStateData {
             air_control: false,
             apply_gravity: false,
             locks_movement: true, // Cannot move during attack
+            dash_speed: 0.0,
+            depth_speed: 0.0,
         }
     }
 
@@ -77,7 +79,7 @@ impl StateLogic for KickComboStateData {
 
     fn get_animation_config(&self) -> AnimationConfig {
         AnimationConfig {
-            sprite_path: "player/kick-combo-sheet.png",
+            sprite_path: "player/kick-combo-sheet.png".into(),
             first_frame: 1,
             last_frame: 19, // 20 frames total
             frame_duration: 0.02,
@@ -90,6 +92,8 @@ impl StateLogic for KickComboStateData {
             air_control: false,
             apply_gravity: false,
             locks_movement: true,
+            dash_speed: 0.0,
+            depth_speed: 0.0,
         }
     }
 
@@ -1,18 +1,28 @@
 pub mod aerial;
+pub mod block;
 pub mod combo;
+pub mod dash;
 pub mod defeat;
+pub mod emote;
+pub mod grab;
 pub mod idle;
 pub mod jump;
 pub mod kick;
 pub mod movement;
 pub mod punch;
+pub mod super_attack;
 
 // Re-export state data structs
 pub use aerial::*;
+pub use block::*;
 pub use combo::*;
+pub use dash::*;
 pub use defeat::*;
+pub use emote::*;
+pub use grab::*;
 pub use idle::*;
 pub use jump::*;
 pub use kick::*;
 pub use movement::*;
 pub use punch::*;
+pub use super_attack::*;
@@ -7,6 +7,24 @@ pub struct IdleToWalkStateData;
 
 impl StateLogic for IdleToWalkStateData {
     fn handle_input(&self, input: &InputContext) -> StateTransition {
+        // Holding Block raises the guard (highest priority on the ground)
+        if input.block {
+            return StateTransition::To(PlayerStateType::Block);
+        }
+
+        // Super spends a full special meter
+        if input.super_attack && input.meter_full {
+            return StateTransition::To(PlayerStateType::Super);
+        }
+
+        // Dash dodges through enemies (invulnerable during the middle frames)
+        if input.dash {
+            return StateTransition::To(PlayerStateType::Dash);
+        }
+        if input.grab && input.can_grab {
+            return StateTransition::To(PlayerStateType::Grab);
+        }
+
         // Attack inputs can interrupt transition
         if input.up_arrow {
             return StateTransition::To(PlayerStateType::Punch);
@@ -21,7 +39,7 @@ impl StateLogic for IdleToWalkStateData {
         }
 
         // If no movement keys pressed, return to idle
-        if !input.left && !input.right {
+        if !input.is_moving() {
             return StateTransition::To(PlayerStateType::Idle);
         }
 
@@ -38,7 +56,7 @@ impl StateLogic for IdleToWalkStateData {
 
     fn get_animation_config(&self) -> AnimationConfig {
         AnimationConfig {
-            sprite_path: "player/idle-to-walk-sheet.png",
+            sprite_path: "player/idle-to-walk-sheet.png".into(),
             first_frame: 1,
             last_frame: 6, // 7 frames total
             frame_duration: 0.06,
@@ -51,6 +69,8 @@ impl StateLogic for IdleToWalkStateData {
             air_control: false,
             apply_gravity: false,
             locks_movement: false,
+            dash_speed: 0.0,
+            depth_speed: 150.0,
         }
     }
 }
@@ -61,6 +81,24 @@ pub struct IdleToRunStateData;
 
 impl StateLogic for IdleToRunStateData {
     fn handle_input(&self, input: &InputContext) -> StateTransition {
+        // Holding Block raises the guard (highest priority on the ground)
+        if input.block {
+            return StateTransition::To(PlayerStateType::Block);
+        }
+
+        // Super spends a full special meter
+        if input.super_attack && input.meter_full {
+            return StateTransition::To(PlayerStateType::Super);
+        }
+
+        // Dash dodges through enemies (invulnerable during the middle frames)
+        if input.dash {
+            return StateTransition::To(PlayerStateType::Dash);
+        }
+        if input.grab && input.can_grab {
+            return StateTransition::To(PlayerStateType::Grab);
+        }
+
         // Attack inputs can interrupt transition
         if input.up_arrow {
             return StateTransition::To(PlayerStateType::Punch);
@@ -75,7 +113,7 @@ impl StateLogic for IdleToRunStateData {
         }
 
         // If no movement keys pressed, return to idle
-        if !input.left && !input.right {
+        if !input.is_moving() {
             return StateTransition::To(PlayerStateType::Idle);
         }
 
@@ -92,7 +130,7 @@ impl StateLogic for IdleToRunStateData {
 
     fn get_animation_config(&self) -> AnimationConfig {
         AnimationConfig {
-            sprite_path: "player/idle-to-run-sheet.png",
+            sprite_path: "player/idle-to-run-sheet.png".into(),
             first_frame: 1,
             last_frame: 7, // 8 frames total
             frame_duration: 0.06,
@@ -105,6 +143,8 @@ impl StateLogic for IdleToRunStateData {
             air_control: false,
             apply_gravity: false,
             locks_movement: false,
+            dash_speed: 0.0,
+            depth_speed: 250.0,
         }
     }
 }
@@ -115,6 +155,24 @@ pub struct WalkStateData;
 
 impl StateLogic for WalkStateData {
     fn handle_input(&self, input: &InputContext) -> StateTransition {
+        // Holding Block raises the guard (highest priority on the ground)
+        if input.block {
+            return StateTransition::To(PlayerStateType::Block);
+        }
+
+        // Super spends a full special meter
+        if input.super_attack && input.meter_full {
+            return StateTransition::To(PlayerStateType::Super);
+        }
+
+        // Dash dodges through enemies (invulnerable during the middle frames)
+        if input.dash {
+            return StateTransition::To(PlayerStateType::Dash);
+        }
+        if input.grab && input.can_grab {
+            return StateTransition::To(PlayerStateType::Grab);
+        }
+
         // Attack inputs
         if input.up_arrow {
             return StateTransition::To(PlayerStateType::Punch);
@@ -129,7 +187,7 @@ impl StateLogic for WalkStateData {
         }
 
         // If no movement keys pressed, return to idle
-        if !input.left && !input.right {
+        if !input.is_moving() {
             return StateTransition::To(PlayerStateType::Idle);
         }
 
@@ -143,7 +201,7 @@ impl StateLogic for WalkStateData {
 
     fn get_animation_config(&self) -> AnimationConfig {
         AnimationConfig {
-            sprite_path: "player/walk-sheet.png",
+            sprite_path: "player/walk-sheet.png".into(),
             first_frame: 1,
             last_frame: 11, // 12 frames total
             frame_duration: 0.09,
@@ -156,6 +214,8 @@ impl StateLogic for WalkStateData {
             air_control: false,
             apply_gravity: false,
             locks_movement: false,
+            dash_speed: 0.0,
+            depth_speed: 150.0,
         }
     }
 }
@@ -166,6 +226,24 @@ pub struct RunStateData;
 
 impl StateLogic for RunStateData {
     fn handle_input(&self, input: &InputContext) -> StateTransition {
+        // Holding Block raises the guard (highest priority on the ground)
+        if input.block {
+            return StateTransition::To(PlayerStateType::Block);
+        }
+
+        // Super spends a full special meter
+        if input.super_attack && input.meter_full {
+            return StateTransition::To(PlayerStateType::Super);
+        }
+
+        // Dash dodges through enemies (invulnerable during the middle frames)
+        if input.dash {
+            return StateTransition::To(PlayerStateType::Dash);
+        }
+        if input.grab && input.can_grab {
+            return StateTransition::To(PlayerStateType::Grab);
+        }
+
         // Attack inputs
         if input.up_arrow {
             return StateTransition::To(PlayerStateType::Punch);
@@ -180,7 +258,7 @@ impl StateLogic for RunStateData {
         }
 
         // If no movement keys pressed, return to idle
-        if !input.left && !input.right {
+        if !input.is_moving() {
             return StateTransition::To(PlayerStateType::Idle);
         }
 
@@ -194,7 +272,7 @@ impl StateLogic for RunStateData {
 
     fn get_animation_config(&self) -> AnimationConfig {
         AnimationConfig {
-            sprite_path: "player/run-sheet.png",
+            sprite_path: "player/run-sheet.png".into(),
             first_frame: 1,
             last_frame: 7, // 8 frames total
             frame_duration: 0.07,
@@ -207,6 +285,8 @@ impl StateLogic for RunStateData {
             air_control: false,
             apply_gravity: false,
             locks_movement: false,
+            dash_speed: 0.0,
+            depth_speed: 250.0,
         }
     }
 }
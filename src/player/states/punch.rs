@@ -7,10 +7,10 @@ pub struct PunchStateData;
 
 impl StateLogic for PunchStateData {
     fn handle_input(&self, input: &InputContext) -> StateTransition {
-        // Check for combo input during second half of attack animation
+        // Check for combo input (hit-confirmed or second half of attack animation)
         if input.up_arrow {
-            // Only allow combo queueing in second half of animation
-            if input.current_frame >= input.total_frames / 2 {
+            // Allow combo queueing on hit, or in second half of a whiffed animation
+            if input.can_queue_combo() {
                 return StateTransition::QueueCombo(PlayerStateType::PunchCombo);
             }
         }
@@ -31,7 +31,7 @@ impl StateLogic for PunchStateData {
 
     fn get_animation_config(&self) -> AnimationConfig {
         AnimationConfig {
-            sprite_path: "player/punch-sheet.png",
+            sprite_path: "player/punch-sheet.png".into(),
             first_frame: 1,
             last_frame: 12, // 13 frames total
             frame_duration: 0.03,
@@ -44,6 +44,8 @@ impl StateLogic for PunchStateData {
             air_control: false,
             apply_gravity: false,
             locks_movement: true, // Cannot move during attack
+            dash_speed: 0.0,
+            depth_speed: 0.0,
         }
     }
 
@@ -62,10 +64,10 @@ pub struct PunchComboStateData;
 
 impl StateLogic for PunchComboStateData {
     fn handle_input(&self, input: &InputContext) -> StateTransition {
-        // Can chain into kick combo during second half (punch combo → down arrow)
+        // Can chain into kick combo on hit or during second half (punch combo → down arrow)
         if input.down_arrow {
-            // Only allow combo queueing in second half of animation
-            if input.current_frame >= input.total_frames / 2 {
+            // Allow combo queueing on hit, or in second half of a whiffed animation
+            if input.can_queue_combo() {
                 return StateTransition::QueueCombo(PlayerStateType::PunchKickCombo);
             }
         }
@@ -85,7 +87,7 @@ impl StateLogic for PunchComboStateData {
 
     fn get_animation_config(&self) -> AnimationConfig {
         AnimationConfig {
-            sprite_path: "player/punch-combo-sheet.png",
+            sprite_path: "player/punch-combo-sheet.png".into(),
             first_frame: 1,
             last_frame: 7, // 8 frames total
             frame_duration: 0.05,
@@ -98,6 +100,8 @@ impl StateLogic for PunchComboStateData {
             air_control: false,
             apply_gravity: false,
             locks_movement: true,
+            dash_speed: 0.0,
+            depth_speed: 0.0,
         }
     }
 
@@ -0,0 +1,48 @@
+use crate::player::config::*;
+use crate::player::state::StateLogic;
+
+/// Super state - spends a full special meter to hit every enemy on screen
+///
+/// The hits, screen flash, and meter cost are applied by `trigger_super_attack`
+/// on entering the state. Not an `is_attacking` state: it has no hitbox.
+#[derive(Clone, Default, Debug)]
+pub struct SuperStateData;
+
+impl StateLogic for SuperStateData {
+    fn handle_input(&self, _input: &InputContext) -> StateTransition {
+        // Super is committed once started
+        StateTransition::None
+    }
+
+    fn update(&self, ctx: &UpdateContext) -> StateTransition {
+        if ctx.animation_finished {
+            return StateTransition::To(PlayerStateType::Idle);
+        }
+        StateTransition::None
+    }
+
+    fn get_animation_config(&self) -> AnimationConfig {
+        // No dedicated super art yet: play the punch-kick combo at a slower, weightier pace
+        AnimationConfig {
+            sprite_path: "player/punch-kick-combo-sheet.png".into(),
+            first_frame: 1,
+            last_frame: 16, // 17 frames total
+            frame_duration: 0.05,
+        }
+    }
+
+    fn get_physics_config(&self) -> PhysicsConfig {
+        PhysicsConfig {
+            ground_speed: 0.0,
+            air_control: false,
+            apply_gravity: false,
+            locks_movement: true, // Cannot move during the super
+            dash_speed: 0.0,
+            depth_speed: 0.0,
+        }
+    }
+
+    fn get_damage(&self) -> i32 {
+        6 // Dealt to every enemy on screen
+    }
+}
@@ -1,9 +1,16 @@
+use crate::combat::{GrabTarget, GuardBroken, HitTracking, SpecialMeter, Stat, StatModifiers};
 use crate::common::{AnimationIndices, AnimationTimer, Direction};
-use crate::player::components::{ComboWindow, JumpPhysics, Player};
+use crate::game::GameplayEvent;
+use crate::input::{PlayerActions, PlayerTwoActions};
+use crate::player::character::{Characters, Fighter};
+use crate::player::components::{BufferedAttack, ComboWindow, JumpPhysics, Player, PlayerTwo};
 use crate::player::config::{
     InputContext, StateTransition as PlayerStateTransition, UpdateContext,
 };
+use crate::player::sprites::PlayerSpriteSheets;
 use crate::player::state::PlayerState;
+use crate::settings::ComboAssist;
+use crate::world::{MoveIntent, StageGeometry};
 use bevy::prelude::*;
 
 // Type aliases to simplify complex query types
@@ -13,6 +20,11 @@ type PlayerInputQuery<'a> = (
     &'a mut ComboWindow,
     &'a Sprite,
     &'a AnimationIndices,
+    &'a HitTracking,
+    &'a SpecialMeter,
+    &'a GrabTarget,
+    Has<GuardBroken>,
+    Has<PlayerTwo>,
 );
 
 type PlayerStateUpdateQuery<'a> = (
@@ -27,13 +39,11 @@ type PlayerStateUpdateQuery<'a> = (
 
 type JumpPhysicsInitQuery<'a> = (&'a PlayerState, &'a Transform, &'a mut JumpPhysics);
 
-type HitTrackingQuery<'a> = (
-    &'a PlayerState,
-    &'a mut crate::combat::components::HitTracking,
-);
+type HitTrackingQuery<'a> = &'a mut HitTracking;
 
 type SpriteUpdateQuery<'a> = (
     &'a PlayerState,
+    &'a Fighter,
     &'a mut Sprite,
     &'a mut AnimationIndices,
     &'a mut AnimationTimer,
@@ -41,86 +51,142 @@ type SpriteUpdateQuery<'a> = (
 
 /// Phase 1: Handle player input and request state transitions
 ///
-/// This system builds an InputContext from keyboard state and delegates to the
-/// current state's handle_input method to determine transitions.
+/// This system builds an InputContext from the gathered player actions (player
+/// 2 reads `PlayerTwoActions`) and delegates to the current state's
+/// handle_input method to determine transitions.
+/// With an input buffer (see `ComboAssist::buffer_seconds`), an attack press
+/// that doesn't start or queue an attack is retried each frame until it does
+/// or the buffer runs out.
 pub fn player_input_system(
-    keyboard: Res<ButtonInput<KeyCode>>,
+    actions: Res<PlayerActions>,
+    player_two_actions: Res<PlayerTwoActions>,
+    combo_assist: Res<ComboAssist>,
     mut player_query: Query<PlayerInputQuery<'static>, With<Player>>,
     time: Res<Time>,
-    game_state: Res<crate::GameState>,
 ) {
-    // Freeze input when game is over
-    if game_state.game_over {
-        return;
-    }
-
-    let Ok((mut state, jump_physics, mut combo_window, sprite, indices)) =
-        player_query.single_mut()
-    else {
-        return;
-    };
-
-    // Tick combo window timer
-    combo_window.timer.tick(time.delta());
-
-    // Get current animation frame
-    let current_frame = if let Some(atlas) = &sprite.texture_atlas {
-        atlas.index
-    } else {
-        0
-    };
-    let total_frames = indices.last + 1;
-
-    // Build input context from keyboard state
-    let input = InputContext {
-        left: keyboard.pressed(KeyCode::KeyA),
-        right: keyboard.pressed(KeyCode::KeyD),
-        shift: keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight),
-        space: keyboard.just_pressed(KeyCode::Space),
-        up_arrow: keyboard.just_pressed(KeyCode::ArrowUp),
-        down_arrow: keyboard.just_pressed(KeyCode::ArrowDown),
-        has_used_aerial_attack: jump_physics.has_used_aerial_attack,
-        current_frame,
-        total_frames,
-    };
-
-    // Check if input is locked (attack animations)
-    if state.locks_input() {
-        // Even with locked input, check for combo inputs (they bypass lock)
-        if input.up_arrow || input.down_arrow {
-            // Only allow combo if combo window is active
-            if combo_window.timer.is_finished() {
-                return; // Combo window expired, ignore input
-            }
-            // Fall through to state input handler for combo logic
+    for (
+        mut state,
+        jump_physics,
+        mut combo_window,
+        sprite,
+        indices,
+        hit_tracking,
+        special_meter,
+        grab_target,
+        guard_broken,
+        player_two,
+    ) in player_query.iter_mut()
+    {
+        let actions = if player_two {
+            &player_two_actions.0
         } else {
-            return; // Input locked, ignore non-combo inputs
+            &*actions
+        };
+
+        // Tick combo window timer
+        combo_window.timer.tick(time.delta());
+
+        // Drop a buffered attack press once the buffer runs out
+        if combo_window
+            .buffered
+            .as_mut()
+            .is_some_and(|(_, timer)| timer.tick(time.delta()).is_finished())
+        {
+            combo_window.buffered = None;
         }
-    }
+        let pressed = if actions.punch {
+            Some(BufferedAttack::Punch)
+        } else if actions.kick {
+            Some(BufferedAttack::Kick)
+        } else {
+            None
+        };
+        let buffered = combo_window.buffered.as_ref().map(|(attack, _)| *attack);
+
+        // Get current animation frame
+        let current_frame = if let Some(atlas) = &sprite.texture_atlas {
+            atlas.index
+        } else {
+            0
+        };
+        let total_frames = indices.last + 1;
+
+        // Build input context from device-independent actions
+        let input = InputContext {
+            left: actions.left,
+            right: actions.right,
+            depth_up: actions.up,
+            depth_down: actions.down,
+            shift: actions.walk,
+            space: actions.jump,
+            up_arrow: actions.punch || buffered == Some(BufferedAttack::Punch),
+            down_arrow: actions.kick || buffered == Some(BufferedAttack::Kick),
+            block: actions.block && !guard_broken,
+            dash: actions.dash,
+            super_attack: actions.super_attack,
+            meter_full: special_meter.is_full(),
+            grab: actions.grab,
+            can_grab: grab_target.0.is_some(),
+            has_used_aerial_attack: jump_physics.has_used_aerial_attack,
+            current_frame,
+            total_frames,
+            hit_count: hit_tracking.hit_enemies.len(),
+            whiff_queue_fraction: combo_assist.whiff_queue_fraction(),
+        };
 
-    // Delegate to state's input handler
-    match state.handle_input(&input) {
-        PlayerStateTransition::To(new_state_type) => {
-            // Apply immediate state transition
-            let new_state = PlayerState::transition_to(new_state_type);
+        // Attack animations lock input, except for combo inputs while the combo window is open
+        let locked = state.locks_input()
+            && (!(input.up_arrow || input.down_arrow) || combo_window.timer.is_finished());
 
-            // Update combo window for attack states
-            if new_state.is_attacking() {
-                combo_window.last_attack = Some(new_state_type);
-                combo_window.timer.reset();
+        // Delegate to state's input handler
+        let transition = if locked {
+            PlayerStateTransition::None
+        } else {
+            state.handle_input(&input)
+        };
+        let mut attack_used = false;
+        match transition {
+            PlayerStateTransition::To(new_state_type) => {
+                // Apply immediate state transition
+                let new_state = PlayerState::transition_to(new_state_type);
+
+                // Update combo window for attack states
+                if new_state.is_attacking() {
+                    combo_window.last_attack = Some(new_state_type);
+                    combo_window.timer.reset();
+                    attack_used = true;
+                }
+
+                *state = new_state;
             }
-
-            *state = new_state;
-        }
-        PlayerStateTransition::QueueCombo(combo_state_type) => {
-            // Queue combo to execute when current animation finishes
-            // Only queue if combo window is still active
-            if !combo_window.timer.is_finished() {
-                combo_window.queued_combo = Some(combo_state_type);
+            PlayerStateTransition::QueueCombo(combo_state_type) => {
+                // Queue combo to execute when current animation finishes
+                // Only queue if combo window is still active
+                if !combo_window.timer.is_finished() {
+                    combo_window.queued_combo = Some(combo_state_type);
+                    attack_used = true;
+                }
+            }
+            PlayerStateTransition::None => {
+                // Emotes play inside Idle, so they can only start while standing
+                // idle with nothing else pressed
+                if let Some(emote) = actions.emote
+                    && let PlayerState::Idle(idle) = &mut *state
+                {
+                    idle.play_emote(emote);
+                }
             }
         }
-        PlayerStateTransition::None => {
-            // No transition
+
+        // Hold an unused attack press in the buffer (a fresh press replaces an older one)
+        let buffer_seconds = combo_assist.buffer_seconds();
+        if attack_used {
+            combo_window.buffered = None;
+        } else if let Some(attack) = pressed
+            && buffer_seconds > 0.0
+        {
+            combo_window.buffered =
+                Some((attack, Timer::from_seconds(buffer_seconds, TimerMode::Once)));
         }
     }
 }
@@ -130,6 +196,7 @@ pub fn player_input_system(
 /// This system builds an UpdateContext from animation/physics state and delegates
 /// to the current state's update method to determine automatic transitions.
 pub fn player_state_update_system(
+    time: Res<Time>,
     mut player_query: Query<PlayerStateUpdateQuery<'static>, With<Player>>,
 ) {
     for (mut state, timer, indices, sprite, mut combo_window, jump_physics, transform) in
@@ -149,6 +216,14 @@ pub fn player_state_update_system(
             velocity_y: jump_physics.velocity_y,
         };
 
+        // Idle counts how long the player has stood still without flagging a state
+        // change every frame; only starting or ending a fidget or emote swaps the sprite
+        if let PlayerState::Idle(idle) = state.bypass_change_detection()
+            && idle.advance(time.delta_secs(), animation_finished)
+        {
+            state.set_changed();
+        }
+
         // Delegate to state's update handler
         if let PlayerStateTransition::To(new_state_type) = state.update(&ctx) {
             // Check if there's a queued combo that should override this transition
@@ -198,15 +273,78 @@ pub fn initialize_jump_physics(
     }
 }
 
-/// Clear hit tracking when entering a new attack state
-/// This allows each attack to hit enemies independently
+/// Clear hit tracking whenever the state changes
+/// This allows each attack to hit enemies independently, and keeps the hit
+/// count seen by states (hit-confirm) scoped to the current state
 pub fn clear_hit_tracking_on_state_change(
     mut player_query: Query<HitTrackingQuery<'static>, (With<Player>, Changed<PlayerState>)>,
 ) {
-    for (state, mut hit_tracking) in player_query.iter_mut() {
-        // Clear hit tracking when entering any attack state
+    for mut hit_tracking in player_query.iter_mut() {
+        hit_tracking.hit_enemies.clear();
+    }
+}
+
+/// Publish `GameplayEvent::AttackUsed` whenever the player enters an attacking state
+#[allow(clippy::type_complexity)]
+pub fn announce_attacks(
+    player_query: Query<(Entity, &PlayerState), (With<Player>, Changed<PlayerState>)>,
+    mut gameplay_events: MessageWriter<GameplayEvent>,
+) {
+    for (entity, state) in player_query.iter() {
         if state.is_attacking() {
-            hit_tracking.hit_enemies.clear();
+            gameplay_events.write(GameplayEvent::AttackUsed {
+                attacker: entity,
+                attack: state.state_type(),
+            });
+        }
+    }
+}
+
+/// Publish `GameplayEvent::AnimationCue` when a player's animation reaches a cue frame
+///
+/// A frame is reached when the state changes (the sprite has just been reset
+/// to its first frame) or when `animate_sprite` advanced it last frame, so a
+/// frame shown for several updates publishes its cue once. The cue carries
+/// the `Surface` under the player's feet (their x and lane).
+#[allow(clippy::type_complexity)]
+pub fn announce_animation_cues(
+    player_query: Query<
+        (
+            Entity,
+            Ref<PlayerState>,
+            &Sprite,
+            &AnimationTimer,
+            &Transform,
+            &JumpPhysics,
+            &Fighter,
+        ),
+        With<Player>,
+    >,
+    characters: Res<Characters>,
+    geometry: Res<StageGeometry>,
+    mut gameplay_events: MessageWriter<GameplayEvent>,
+) {
+    for (entity, state, sprite, timer, transform, jump_physics, &fighter) in player_query.iter() {
+        let Some(atlas) = &sprite.texture_atlas else {
+            continue;
+        };
+        if !state.is_changed() && !timer.just_finished() {
+            continue;
+        }
+
+        let feet = Vec2::new(transform.translation.x, jump_physics.ground_y);
+        for &(_, cue) in characters
+            .get(fighter)
+            .animations
+            .cues(&state)
+            .iter()
+            .filter(|(frame, _)| *frame == atlas.index)
+        {
+            gameplay_events.write(GameplayEvent::AnimationCue {
+                entity,
+                cue,
+                surface: geometry.surface_at(feet),
+            });
         }
     }
 }
@@ -215,19 +353,21 @@ pub fn clear_hit_tracking_on_state_change(
 ///
 /// Uses preloaded sprite sheet handles to prevent flickering during transitions.
 /// The critical ordering ensures sprites remain visible throughout state changes.
+/// Each player's sheets and frame data come from their `Fighter`'s character.
 pub fn player_sprite_update_system(
     mut player_query: Query<SpriteUpdateQuery<'static>, (With<Player>, Changed<PlayerState>)>,
-    sprite_sheets: Res<crate::PlayerSpriteSheets>,
+    sprite_sheets: Res<PlayerSpriteSheets>,
+    characters: Res<Characters>,
     mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
 ) {
-    for (state, mut sprite, mut indices, mut timer) in player_query.iter_mut() {
-        // Get animation config from state
-        let anim = state.get_animation_config();
+    for (state, &fighter, mut sprite, mut indices, mut timer) in player_query.iter_mut() {
+        // Get animation config for the state (compiled default plus the character's animations file)
+        let anim = characters.get(fighter).animations.config(state);
         let num_columns = (anim.last_frame + 1) as u32;
 
         // Use preloaded sprite handle instead of loading on-demand
         // This prevents flickering since the texture is already in GPU memory
-        sprite.image = sprite_sheets.get_handle(anim.sprite_path);
+        sprite.image = sprite_sheets.get_handle(&anim.sprite_path);
 
         // Update texture atlas
         if let Some(ref mut atlas) = sprite.texture_atlas {
@@ -256,18 +396,27 @@ pub fn player_sprite_update_system(
 
 /// Phase 4: Apply physics based on state configuration
 ///
-/// This system reads the physics config from the current state and applies
-/// gravity, air control, and ground movement accordingly.
+/// This system reads the physics config from the current state and turns
+/// gravity, air control, ground movement, and depth-lane steps into a movement
+/// intent. The displacement itself is applied by `resolve_movement_intents`.
+/// Ground, dash, and depth speeds are scaled by the player's character.
+#[allow(clippy::type_complexity)]
 pub fn player_physics_system(
     time: Res<Time>,
-    keyboard: Res<ButtonInput<KeyCode>>,
+    actions: Res<PlayerActions>,
+    player_two_actions: Res<PlayerTwoActions>,
+    geometry: Res<StageGeometry>,
+    characters: Res<Characters>,
     mut player_query: Query<
         (
             &PlayerState,
-            &mut Transform,
+            &mut MoveIntent,
             &mut Sprite,
             &mut JumpPhysics,
             &mut Direction,
+            Option<&StatModifiers>,
+            &Fighter,
+            Has<PlayerTwo>,
         ),
         With<Player>,
     >,
@@ -275,54 +424,74 @@ pub fn player_physics_system(
     const GRAVITY: f32 = 1800.0;
     const AIR_CONTROL_SPEED: f32 = 250.0;
 
-    for (state, mut transform, mut sprite, mut jump_physics, mut direction) in
-        player_query.iter_mut()
+    for (
+        state,
+        mut intent,
+        mut sprite,
+        mut jump_physics,
+        mut direction,
+        modifiers,
+        &fighter,
+        player_two,
+    ) in player_query.iter_mut()
     {
+        let actions = if player_two {
+            &player_two_actions.0
+        } else {
+            &*actions
+        };
         let physics_config = state.get_physics_config();
+        let character_speed = characters.get(fighter).speed;
 
         // Apply gravity if needed
         if physics_config.apply_gravity {
             jump_physics.velocity_y -= GRAVITY * time.delta_secs();
-            transform.translation.y += jump_physics.velocity_y * time.delta_secs();
-
-            // Ground clamp
-            if transform.translation.y < jump_physics.ground_y {
-                transform.translation.y = jump_physics.ground_y;
-                jump_physics.velocity_y = 0.0;
-            }
+            intent.velocity.y += jump_physics.velocity_y;
         }
 
-        // Apply air control if allowed
+        // Horizontal speed from air control or ground movement
+        let mut horizontal_speed = 0.0;
         if physics_config.air_control {
-            if keyboard.pressed(KeyCode::KeyA) {
-                transform.translation.x -= AIR_CONTROL_SPEED * time.delta_secs();
-                sprite.flip_x = true;
-                *direction = Direction::Left;
-            } else if keyboard.pressed(KeyCode::KeyD) {
-                transform.translation.x += AIR_CONTROL_SPEED * time.delta_secs();
-                sprite.flip_x = false;
-                *direction = Direction::Right;
-            }
+            horizontal_speed = AIR_CONTROL_SPEED;
         }
-
-        // Apply ground movement if not locked and speed > 0
         if !physics_config.locks_movement && physics_config.ground_speed > 0.0 {
-            if keyboard.pressed(KeyCode::KeyA) {
-                transform.translation.x -= physics_config.ground_speed * time.delta_secs();
+            horizontal_speed = physics_config.ground_speed
+                * character_speed
+                * modifiers.map_or(1.0, |modifiers| modifiers.multiplier(Stat::Speed));
+        }
+
+        // Dashing overrides input and bursts in the facing direction
+        if physics_config.dash_speed > 0.0 {
+            let facing = if sprite.flip_x { -1.0 } else { 1.0 };
+            intent.velocity.x += facing * physics_config.dash_speed * character_speed;
+        } else if horizontal_speed > 0.0 {
+            if actions.left {
+                intent.velocity.x -= horizontal_speed;
                 sprite.flip_x = true;
                 *direction = Direction::Left;
-            } else if keyboard.pressed(KeyCode::KeyD) {
-                transform.translation.x += physics_config.ground_speed * time.delta_secs();
+            } else if actions.right {
+                intent.velocity.x += horizontal_speed;
                 sprite.flip_x = false;
                 *direction = Direction::Right;
             }
         }
 
-        // Always enforce ground clamping (even when gravity is not active)
-        // This prevents knockback or other forces from pushing player below ground
-        if transform.translation.y < jump_physics.ground_y {
-            transform.translation.y = jump_physics.ground_y;
-            jump_physics.velocity_y = 0.0;
+        // Stepping along the depth lane carries the ground level with the player,
+        // stopping at the lane's edges
+        if !physics_config.locks_movement && physics_config.depth_speed > 0.0 {
+            let depth_speed = physics_config.depth_speed * character_speed;
+            let depth_velocity = if actions.up {
+                depth_speed
+            } else if actions.down {
+                -depth_speed
+            } else {
+                0.0
+            };
+            let ground_y = jump_physics.ground_y + depth_velocity * time.delta_secs();
+            if depth_velocity != 0.0 && geometry.depth_lane.contains(&ground_y) {
+                jump_physics.ground_y = ground_y;
+                intent.velocity.y += depth_velocity;
+            }
         }
     }
 }
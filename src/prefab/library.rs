@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+
+/// Asset handles shared by every instance of a prefab
+///
+/// Loaded once when the app starts, so spawning a ghost reuses the same sheet
+/// and atlas layout instead of adding a new layout asset per spawn.
+#[derive(Resource)]
+pub struct PrefabLibrary {
+    pub player_sheet: Handle<Image>,
+    pub player_layout: Handle<TextureAtlasLayout>,
+    pub ghost_sheet: Handle<Image>,
+    pub ghost_layout: Handle<TextureAtlasLayout>,
+    pub backdrop: Handle<Image>,
+    pub foreground: Handle<Image>,
+}
+
+impl FromWorld for PrefabLibrary {
+    fn from_world(world: &mut World) -> Self {
+        let mut layouts = world.resource_mut::<Assets<TextureAtlasLayout>>();
+        let player_layout = layouts.add(TextureAtlasLayout::from_grid(
+            UVec2::splat(320),
+            24,
+            1,
+            None,
+            None,
+        ));
+        let ghost_layout = layouts.add(TextureAtlasLayout::from_grid(
+            UVec2::splat(160),
+            12,
+            1,
+            None,
+            None,
+        ));
+
+        let asset_server = world.resource::<AssetServer>();
+        Self {
+            player_sheet: asset_server.load("player/idle-sheet.png"),
+            player_layout,
+            ghost_sheet: asset_server.load("enemies/ghost-sheet.png"),
+            ghost_layout,
+            backdrop: asset_server.load("graveyard.png"),
+            foreground: asset_server.load("graveyard_foreground.png"),
+        }
+    }
+}
@@ -0,0 +1,9 @@
+pub mod library;
+pub mod plugin;
+pub mod prefabs;
+pub mod spawner;
+
+// Re-export commonly used items
+pub use plugin::*;
+pub use prefabs::*;
+pub use spawner::*;
@@ -0,0 +1,11 @@
+use crate::prefab::library::PrefabLibrary;
+use bevy::prelude::*;
+
+/// Shared prefab assets, used by every module that spawns through `PrefabSpawner`
+pub struct PrefabPlugin;
+
+impl Plugin for PrefabPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PrefabLibrary>();
+    }
+}
@@ -0,0 +1,362 @@
+use crate::combat::{
+    ComboCounter, GrabTarget, Guard, Health, HitTracking, Hitbox, HurtBox, OnHitEffects,
+    OnHitStats, SpecialMeter, StatModifiers, StyleMeter, Team,
+};
+use crate::common::{AnimationIndices, AnimationTimer, Direction};
+use crate::debug::debug_name;
+use crate::enemy::coordination::ApproachSlot;
+use crate::enemy::{Enemy, EnemyArchetype, EnemyState, SpawnProtected};
+use crate::game::InGame;
+use crate::pickup::PickupKind;
+use crate::player::{
+    CharacterDefinition, ComboWindow, Costume, CostumeTint, Fighter, JumpPhysics, Player,
+    PlayerState, PlayerStateType,
+};
+use crate::prefab::library::PrefabLibrary;
+use crate::settings::ComboAssist;
+use crate::training::TrainingDummy;
+use crate::world::{ArenaConfined, DepthSorted, MoveIntent, Parallax, StageBackground, TiledLayer};
+use bevy::prelude::*;
+
+/// Prefabs placed when the app starts, one `<prefab> <x> <y>` per line, and
+/// tiled layers, one `layer <image> <x> <y> <z> <factor> <tiles>` per line
+const STAGE_LAYOUT: &str = include_str!("../../assets/stage.txt");
+/// Training dummy health; it heals to full between exchanges and respawns if knocked out
+const DUMMY_HEALTH: i32 = 20;
+
+/// Static stage art
+///
+/// Also a component on the spawned prop, so the levels can swap its image
+/// for the current stage's (see `apply_stage`).
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StageProp {
+    /// The stage's backdrop (the graveyard in menus), behind everything
+    Backdrop,
+    /// The stage's foreground, drawn in front of the ground line
+    Foreground,
+}
+
+impl StageProp {
+    fn z(self) -> f32 {
+        match self {
+            StageProp::Backdrop => 0.0,
+            StageProp::Foreground => 2.0,
+        }
+    }
+
+    /// `Parallax::factor`: the backdrop trails the camera, the foreground slides past it
+    fn parallax(self) -> f32 {
+        match self {
+            StageProp::Backdrop => 0.3,
+            StageProp::Foreground => -0.15,
+        }
+    }
+}
+
+/// A named entity template that `PrefabSpawner` can instantiate
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Prefab {
+    Player,
+    /// The second player in a versus game
+    PlayerTwo,
+    Enemy(EnemyArchetype),
+    TrainingDummy,
+    Pickup(PickupKind),
+    Prop(StageProp),
+}
+
+impl Prefab {
+    /// Look up a prefab by name: "player", "player_two", "training_dummy", "backdrop",
+    /// "foreground", an archetype key from assets/enemies.txt, or a pickup key
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "player" => Some(Prefab::Player),
+            "player_two" => Some(Prefab::PlayerTwo),
+            "training_dummy" => Some(Prefab::TrainingDummy),
+            "backdrop" => Some(Prefab::Prop(StageProp::Backdrop)),
+            "foreground" => Some(Prefab::Prop(StageProp::Foreground)),
+            _ => EnemyArchetype::from_key(name)
+                .map(Prefab::Enemy)
+                .or_else(|| PickupKind::from_key(name).map(Prefab::Pickup)),
+        }
+    }
+}
+
+/// Non-empty, non-comment lines of assets/stage.txt
+fn stage_lines() -> impl Iterator<Item = &'static str> {
+    STAGE_LAYOUT
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+}
+
+/// Prefabs and positions listed in assets/stage.txt
+///
+/// Lines with an unknown prefab name or a bad position are skipped with a warning.
+/// `layer` lines are left to `stage_layers`.
+pub fn stage_layout() -> Vec<(Prefab, Vec2)> {
+    stage_lines()
+        .filter(|line| !line.starts_with("layer "))
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let prefab = fields.next().and_then(Prefab::from_name);
+            let x = fields.next().and_then(|x| x.parse().ok());
+            let y = fields.next().and_then(|y| y.parse().ok());
+            match (prefab, x, y) {
+                (Some(prefab), Some(x), Some(y)) => Some((prefab, Vec2::new(x, y))),
+                _ => {
+                    warn!("Skipping bad stage layout line: {line}");
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// A stage layer listed in assets/stage.txt: an image repeated side by side,
+/// scrolled by its own parallax factor
+///
+/// Lets a level stack as many depths of art as it likes (distant hills, a
+/// tree line, fog) without a `StageProp` per image, and tiles them wide
+/// enough to cover a level larger than one image.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StageLayer {
+    /// Path under assets/
+    pub image: String,
+    /// Center of the middle tile
+    pub position: Vec2,
+    /// Draw order: the backdrop is 0, the fighters 1, the foreground 2
+    pub z: f32,
+    /// `Parallax::factor`
+    pub factor: f32,
+    /// Copies of the image side by side (at least 1)
+    pub tiles: u32,
+}
+
+/// Layers listed in assets/stage.txt, back to front in file order
+///
+/// Malformed `layer` lines are skipped with a warning.
+pub fn stage_layers() -> Vec<StageLayer> {
+    stage_lines()
+        .filter_map(|line| line.strip_prefix("layer "))
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let number = |index: usize| fields.get(index).and_then(|field| field.parse().ok());
+            let layer = match (fields.first(), number(1), number(2), number(3), number(4)) {
+                (Some(image), Some(x), Some(y), Some(z), Some(factor)) => Some(StageLayer {
+                    image: image.to_string(),
+                    position: Vec2::new(x, y),
+                    z,
+                    factor,
+                    tiles: fields
+                        .get(5)
+                        .and_then(|tiles| tiles.parse().ok())
+                        .unwrap_or(1)
+                        .max(1),
+                }),
+                _ => None,
+            };
+            if layer.is_none() {
+                warn!("Skipping bad stage layer line: layer {line}");
+            }
+            layer
+        })
+        .collect()
+}
+
+/// The player, ready for a new match as `fighter` (whose definition is
+/// `character`) wearing `costume`
+pub fn player_prefab(
+    library: &PrefabLibrary,
+    position: Vec2,
+    health: i32,
+    combo_assist: ComboAssist,
+    fighter: Fighter,
+    character: &CharacterDefinition,
+    costume: &Costume,
+) -> impl Bundle {
+    (
+        Sprite {
+            color: costume.tint,
+            ..Sprite::from_atlas_image(
+                library.player_sheet.clone(),
+                TextureAtlas {
+                    layout: library.player_layout.clone(),
+                    index: 1,
+                },
+            )
+        },
+        Transform::from_xyz(position.x, position.y, 1.),
+        MoveIntent::default(),
+        Direction::None,
+        AnimationIndices { first: 1, last: 23 },
+        AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)),
+        PlayerState::transition_to(PlayerStateType::Idle),
+        (Player, fighter, CostumeTint(costume.tint)),
+        debug_name("Player"),
+        ArenaConfined,
+        DepthSorted,
+        // Combat components (grouped to stay within Bevy's bundle tuple size limit)
+        (
+            // No gear or buffs yet, so the player takes hits unmitigated
+            Health {
+                current: health,
+                max: health,
+                armor: 0,
+                resistance: 0.0,
+            },
+            HurtBox {
+                size: Vec2::new(100.0, 150.0),
+            },
+            Hitbox {
+                offset: Vec2::ZERO,
+                size: Vec2::ZERO,
+                active: false,
+            },
+            HitTracking::default(),
+            Team::PlayerOne,
+            fighter_traits(character),
+            Guard::default(),
+            SpecialMeter::default(),
+            ComboCounter::default(),
+            GrabTarget::default(),
+            StatModifiers::default(),
+            StyleMeter::default(),
+        ),
+        ComboWindow::new(combo_assist),
+        JumpPhysics {
+            velocity_y: 0.0,
+            ground_y: -100.0,
+            jump_force: character.jump_force,
+            has_used_aerial_attack: false,
+        },
+        DespawnOnExit(InGame),
+    )
+}
+
+/// On-hit bonuses a fighter starts with, from their `characters.txt` traits
+fn fighter_traits(character: &CharacterDefinition) -> OnHitEffects {
+    let mut on_hit = OnHitEffects::default();
+    if character.on_hit != OnHitStats::default() {
+        on_hit.add_source("trait", character.on_hit);
+    }
+    on_hit
+}
+
+/// Ghost sprite, animation, and movement shared by enemies and the training dummy
+///
+/// Faces the stage center, so a ghost placed right of center starts out facing left.
+fn ghost_body(library: &PrefabLibrary, position: Vec2, scale: f32) -> impl Bundle {
+    let facing_left = position.x > 0.0;
+    let mut sprite = Sprite::from_atlas_image(
+        library.ghost_sheet.clone(),
+        TextureAtlas {
+            layout: library.ghost_layout.clone(),
+            index: 1,
+        },
+    );
+    sprite.flip_x = facing_left;
+    let animation = EnemyState::Move.animation(false);
+    (
+        sprite,
+        Transform::from_xyz(position.x, position.y, 2.0).with_scale(Vec3::splat(scale)),
+        MoveIntent::default(),
+        if facing_left {
+            Direction::Left
+        } else {
+            Direction::Right
+        },
+        AnimationIndices {
+            first: animation.first,
+            last: animation.last,
+        },
+        AnimationTimer(Timer::from_seconds(
+            animation.frame_seconds,
+            TimerMode::Repeating,
+        )),
+        EnemyState::Move,
+        Enemy,
+        Team::Enemy,
+        DepthSorted,
+        HurtBox {
+            size: Vec2::new(80.0, 100.0),
+        },
+        DespawnOnExit(InGame),
+    )
+}
+
+/// An enemy of `archetype` entering the stage, with `health` before armor and resistance
+///
+/// Its role component (`RangedAttacker` or `ApproachSlot`) is added by `PrefabSpawner`.
+pub fn enemy_prefab(
+    library: &PrefabLibrary,
+    archetype: EnemyArchetype,
+    position: Vec2,
+    health: i32,
+    label: String,
+) -> impl Bundle {
+    (
+        ghost_body(library, position, archetype.scale()),
+        SpawnProtected,
+        debug_name(label),
+        archetype,
+        Health {
+            current: health,
+            max: health,
+            armor: archetype.armor(),
+            resistance: archetype.resistance(),
+        },
+    )
+}
+
+/// The training mode dummy, a ghost that stays in the arena
+///
+/// Its behavior components are added by the training systems.
+pub fn training_dummy_prefab(library: &PrefabLibrary, position: Vec2) -> impl Bundle {
+    (
+        ghost_body(library, position, 1.5),
+        TrainingDummy,
+        debug_name("Training Dummy"),
+        ArenaConfined,
+        Health {
+            current: DUMMY_HEALTH,
+            max: DUMMY_HEALTH,
+            armor: 0,
+            resistance: 0.0,
+        },
+        ApproachSlot::facing(position.x.signum()),
+    )
+}
+
+/// A piece of stage art, flipped along with the stage in mirror mode and scrolled
+/// by its parallax as the camera moves
+pub fn stage_prop(library: &PrefabLibrary, prop: StageProp, position: Vec2) -> impl Bundle {
+    let image = match prop {
+        StageProp::Backdrop => library.backdrop.clone(),
+        StageProp::Foreground => library.foreground.clone(),
+    };
+    (
+        Sprite::from_image(image),
+        Transform::from_xyz(position.x, position.y, prop.z()),
+        StageBackground,
+        prop,
+        Parallax {
+            factor: prop.parallax(),
+            anchor: position,
+        },
+    )
+}
+
+/// A tiled stage layer; `size_tiled_layers` repeats the image once it has loaded
+pub fn stage_layer(asset_server: &AssetServer, layer: &StageLayer) -> impl Bundle {
+    (
+        Sprite::from_image(asset_server.load(layer.image.clone())),
+        Transform::from_xyz(layer.position.x, layer.position.y, layer.z),
+        StageBackground,
+        Parallax {
+            factor: layer.factor,
+            anchor: layer.position,
+        },
+        TiledLayer { tiles: layer.tiles },
+    )
+}
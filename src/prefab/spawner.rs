@@ -0,0 +1,100 @@
+use crate::combat::Team;
+use crate::debug::{DebugNameCounters, debug_name};
+use crate::enemy::coordination::ApproachSlot;
+use crate::enemy::{EnemyArchetype, RangedAttacker};
+use crate::pickup::systems::pickup;
+use crate::player::{Characters, PlayerTwo};
+use crate::prefab::library::PrefabLibrary;
+use crate::prefab::prefabs::{
+    Prefab, StageLayer, enemy_prefab, player_prefab, stage_layer, stage_prop, training_dummy_prefab,
+};
+use crate::settings::{ComboAssist, Difficulty, Mutators};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+/// Spawns prefabs by value, filling in stats from the current settings
+///
+/// Players get the fighter and costume picked on the character select screen,
+/// the difficulty's health (adjusted by the mutators), and the combo assist
+/// window; enemies get the difficulty's enemy health and a numbered debug
+/// name. Callers can add to the returned entity.
+#[derive(SystemParam)]
+pub struct PrefabSpawner<'w, 's> {
+    commands: Commands<'w, 's>,
+    library: Res<'w, PrefabLibrary>,
+    asset_server: Res<'w, AssetServer>,
+    debug_names: ResMut<'w, DebugNameCounters>,
+    difficulty: Res<'w, Difficulty>,
+    mutators: Res<'w, Mutators>,
+    combo_assist: Res<'w, ComboAssist>,
+    characters: Res<'w, Characters>,
+}
+
+impl PrefabSpawner<'_, '_> {
+    /// Spawn `prefab` at `position`
+    pub fn spawn(&mut self, prefab: Prefab, position: Vec2) -> EntityCommands<'_> {
+        match prefab {
+            Prefab::Player => self.spawn_player(position, false),
+            Prefab::PlayerTwo => {
+                let mut player = self.spawn_player(position, true);
+                player.insert((PlayerTwo, Team::PlayerTwo, debug_name("Player 2")));
+                player
+            }
+            Prefab::Enemy(archetype) => {
+                let health = self.difficulty.enemy_health();
+                self.spawn_enemy(archetype, position, health)
+            }
+            Prefab::TrainingDummy => self
+                .commands
+                .spawn(training_dummy_prefab(&self.library, position)),
+            Prefab::Pickup(kind) => self.commands.spawn(pickup(kind, position)),
+            Prefab::Prop(prop) => self
+                .commands
+                .spawn(stage_prop(&self.library, prop, position)),
+        }
+    }
+
+    /// Spawn player 1 (or player 2) as the fighter they picked, in their costume
+    fn spawn_player(&mut self, position: Vec2, player_two: bool) -> EntityCommands<'_> {
+        let health = self.mutators.player_health(self.difficulty.player_health());
+        let fighter = self.characters.selected(player_two);
+        self.commands.spawn(player_prefab(
+            &self.library,
+            position,
+            health,
+            *self.combo_assist,
+            fighter,
+            self.characters.get(fighter),
+            self.characters.costume(player_two),
+        ))
+    }
+
+    /// Spawn a tiled stage layer from assets/stage.txt
+    pub fn spawn_layer(&mut self, layer: &StageLayer) -> EntityCommands<'_> {
+        self.commands.spawn(stage_layer(&self.asset_server, layer))
+    }
+
+    /// Spawn an enemy with `health` in place of the difficulty's (endless runs toughen enemies)
+    pub fn spawn_enemy(
+        &mut self,
+        archetype: EnemyArchetype,
+        position: Vec2,
+        health: i32,
+    ) -> EntityCommands<'_> {
+        let label = self.debug_names.numbered(archetype.name());
+        let mut enemy = self.commands.spawn(enemy_prefab(
+            &self.library,
+            archetype,
+            position,
+            health,
+            label,
+        ));
+        if archetype == EnemyArchetype::FireGhost {
+            enemy.insert(RangedAttacker::default());
+        } else {
+            // Melee ghosts start out approaching from the side they entered on
+            enemy.insert(ApproachSlot::facing(position.x.signum()));
+        }
+        enemy
+    }
+}
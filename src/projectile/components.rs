@@ -0,0 +1,18 @@
+use bevy::prelude::*;
+
+/// A moving attack (e.g. a ghost's fireball) that damages the player on contact
+///
+/// Flies at a constant `velocity` through the MoveIntent pipeline and is
+/// despawned when it hits or when `lifetime` runs out.
+#[derive(Component)]
+pub struct Projectile {
+    /// Entity that fired it (reported as the attacker in DamageEvent)
+    pub owner: Entity,
+    /// Where it was fired from (reported as the source in DamageEvent)
+    pub origin: Vec2,
+    pub velocity: Vec2,
+    pub damage: i32,
+    /// Collision box, centered on the projectile
+    pub size: Vec2,
+    pub lifetime: Timer,
+}
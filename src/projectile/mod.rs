@@ -0,0 +1,8 @@
+pub mod components;
+pub mod plugin;
+pub mod systems;
+
+// Re-export commonly used items
+pub use components::*;
+pub use plugin::*;
+pub use systems::*;
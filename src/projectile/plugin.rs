@@ -0,0 +1,24 @@
+use crate::game::GameSet;
+use crate::projectile::systems::{
+    detect_projectile_collisions, move_projectiles, update_projectile_lifetimes,
+};
+use bevy::prelude::*;
+
+/// Projectile movement, hits, and expiry
+pub struct ProjectilePlugin;
+
+impl Plugin for ProjectilePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                detect_projectile_collisions.in_set(GameSet::Collisions),
+                update_projectile_lifetimes.in_set(GameSet::DamageReactions),
+            ),
+        )
+        .add_systems(
+            FixedUpdate,
+            move_projectiles.in_set(GameSet::MovementIntents),
+        );
+    }
+}
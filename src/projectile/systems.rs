@@ -0,0 +1,95 @@
+use crate::combat::{AttackWeight, DamageEvent, HurtBox, Invulnerable, Team};
+use crate::debug::debug_name;
+use crate::game::InGame;
+use crate::projectile::components::Projectile;
+use crate::world::MoveIntent;
+use bevy::prelude::*;
+
+const FIREBALL_SPEED: f32 = 400.0;
+const FIREBALL_SIZE: Vec2 = Vec2::new(30.0, 30.0);
+const FIREBALL_LIFETIME: f32 = 4.0;
+
+/// Fireball bundle flying horizontally from `position` (left if `facing_left`)
+///
+/// Travels at the thrower's height, low enough that jumping clears it, and
+/// only hits hurtboxes off the thrower's `team`.
+pub fn fireball(
+    owner: Entity,
+    team: Team,
+    position: Vec2,
+    facing_left: bool,
+    damage: i32,
+) -> impl Bundle {
+    let direction = if facing_left { -1.0 } else { 1.0 };
+    (
+        Sprite::from_color(Color::srgb(1.0, 0.5, 0.1), FIREBALL_SIZE),
+        Transform::from_xyz(position.x, position.y, 3.0),
+        MoveIntent::default(),
+        debug_name("Fireball"),
+        Projectile {
+            owner,
+            origin: position,
+            velocity: Vec2::new(direction * FIREBALL_SPEED, 0.0),
+            damage,
+            size: FIREBALL_SIZE,
+            lifetime: Timer::from_seconds(FIREBALL_LIFETIME, TimerMode::Once),
+        },
+        team,
+        DespawnOnExit(InGame),
+    )
+}
+
+/// Add each projectile's velocity to its movement intent
+pub fn move_projectiles(mut projectile_query: Query<(&Projectile, &mut MoveIntent)>) {
+    for (projectile, mut intent) in projectile_query.iter_mut() {
+        intent.velocity += projectile.velocity;
+    }
+}
+
+/// Despawn projectiles whose lifetime has run out
+pub fn update_projectile_lifetimes(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut projectile_query: Query<(Entity, &mut Projectile)>,
+) {
+    for (entity, mut projectile) in projectile_query.iter_mut() {
+        projectile.lifetime.tick(time.delta());
+
+        if projectile.lifetime.is_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// AABB collision: projectile vs hurtboxes on a different team, writes DamageEvent and
+/// despawns the projectile on its first hit
+pub fn detect_projectile_collisions(
+    mut commands: Commands,
+    projectile_query: Query<(Entity, &Transform, &Projectile, &Team)>,
+    target_query: Query<(Entity, &Transform, &HurtBox, &Team), Without<Invulnerable>>,
+    mut damage_events: MessageWriter<DamageEvent>,
+) {
+    for (entity, transform, projectile, &team) in projectile_query.iter() {
+        let position = transform.translation.truncate();
+        let hit = target_query
+            .iter()
+            .filter(|(.., target_team)| **target_team != team)
+            .find(|(_, target_transform, hurtbox, _)| {
+                let half = (projectile.size + hurtbox.size) / 2.0;
+                let offset = (position - target_transform.translation.truncate()).abs();
+                offset.x < half.x && offset.y < half.y
+            });
+
+        if let Some((target, ..)) = hit {
+            damage_events.write(DamageEvent {
+                attacker: projectile.owner,
+                target,
+                team,
+                damage: projectile.damage,
+                weight: AttackWeight::Medium,
+                source: projectile.origin,
+            });
+            commands.entity(entity).despawn();
+        }
+    }
+}
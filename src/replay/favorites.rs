@@ -0,0 +1,245 @@
+use crate::persistence::{SaveQueue, list_data_files, read_data_file, unix_timestamp};
+use crate::replay::highlights::HighlightClip;
+use crate::replay::snapshot::Snapshot;
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Folder of the data directory holding saved favorite replays
+const FAVORITES_FOLDER: &str = "replays";
+
+/// A highlight clip saved from the highlight reel, listed on the extras screen
+pub struct FavoriteReplay {
+    /// File name in the data directory (`replays/<saved>-<n>.txt`)
+    pub file: String,
+    /// The clip's caption when it was saved ("Biggest Combo: 12 hits")
+    pub title: String,
+    /// Unix seconds it was saved
+    pub saved: u64,
+}
+
+/// Saved favorite replays, oldest first
+///
+/// Listed from the `replays` folder at startup. Only the titles are kept in
+/// memory; a replay's snapshots are read from its file when it's watched.
+/// Each file has `title=` and `saved=` lines, then a `frame <time>` line per
+/// snapshot followed by a `sprite` line per drawn sprite (see `sprite_line`).
+#[derive(Resource, Default)]
+pub struct FavoriteReplays {
+    pub replays: Vec<FavoriteReplay>,
+}
+
+impl FavoriteReplays {
+    /// List the saved replays, skipping files that can't be read
+    pub fn load() -> Self {
+        let replays = list_data_files(FAVORITES_FOLDER)
+            .into_iter()
+            .filter_map(|name| {
+                let file = format!("{FAVORITES_FOLDER}/{name}");
+                let contents = read_data_file(&file)?;
+                let mut title = None;
+                let mut saved = 0;
+                for line in contents.lines() {
+                    if let Some(value) = line.strip_prefix("title=") {
+                        title = Some(value.to_string());
+                    } else if let Some(value) = line.strip_prefix("saved=") {
+                        saved = value.parse().unwrap_or(0);
+                    } else if line.starts_with("frame ") {
+                        break;
+                    }
+                }
+                Some(FavoriteReplay {
+                    file,
+                    title: title?,
+                    saved,
+                })
+            })
+            .collect();
+        Self { replays }
+    }
+
+    /// Queue a highlight clip to be saved as a favorite and add it to the list
+    ///
+    /// `clip_number` keeps the names of clips saved in the same second apart.
+    pub fn save(
+        &mut self,
+        clip: &HighlightClip,
+        clip_number: usize,
+        asset_server: &AssetServer,
+        layouts: &Assets<TextureAtlasLayout>,
+        save_queue: &mut SaveQueue,
+    ) {
+        let saved = unix_timestamp();
+        let title = clip.kind.label();
+        let mut contents = format!("title={title}\nsaved={saved}\n");
+        for frame in &clip.frames {
+            let _ = writeln!(contents, "frame {}", frame.time);
+            for (sprite, transform) in &frame.sprites {
+                contents.push_str(&sprite_line(sprite, transform, asset_server, layouts));
+                contents.push('\n');
+            }
+        }
+
+        let file = format!("{FAVORITES_FOLDER}/{saved}-{clip_number}.txt");
+        save_queue.queue(&file, contents);
+        self.replays.push(FavoriteReplay { file, title, saved });
+    }
+}
+
+/// One drawn sprite of a saved snapshot:
+/// `sprite <image> <tile w> <tile h> <columns> <rows> <index> <flip x> <flip y>
+/// <r> <g> <b> <a> <width> <height> <x> <y> <z> <angle> <scale x> <scale y>`
+///
+/// The image is its asset path (`-` for a plain colored sprite), the atlas is
+/// the grid it was cut from (all zeros without one), and the size is zero
+/// unless the sprite has a custom size.
+fn sprite_line(
+    sprite: &Sprite,
+    transform: &Transform,
+    asset_server: &AssetServer,
+    layouts: &Assets<TextureAtlasLayout>,
+) -> String {
+    let image = asset_server
+        .get_path(&sprite.image)
+        .map_or("-".to_string(), |path| path.to_string());
+    let (tile, columns, rows, index) = sprite
+        .texture_atlas
+        .as_ref()
+        .and_then(|atlas| {
+            let layout = layouts.get(&atlas.layout)?;
+            let tile = layout.textures.first()?.size();
+            Some((
+                tile,
+                layout.size.x / tile.x,
+                layout.size.y / tile.y,
+                atlas.index,
+            ))
+        })
+        .unwrap_or_default();
+    let color = sprite.color.to_srgba();
+    let size = sprite.custom_size.unwrap_or_default();
+    let angle = transform.rotation.to_euler(EulerRot::XYZ).2;
+    format!(
+        "sprite {image} {} {} {columns} {rows} {index} {} {} {} {} {} {} {} {} {} {} {} {angle} {} {}",
+        tile.x,
+        tile.y,
+        u8::from(sprite.flip_x),
+        u8::from(sprite.flip_y),
+        color.red,
+        color.green,
+        color.blue,
+        color.alpha,
+        size.x,
+        size.y,
+        transform.translation.x,
+        transform.translation.y,
+        transform.translation.z,
+        transform.scale.x,
+        transform.scale.y,
+    )
+}
+
+/// Rebuild a sprite from a `sprite_line`, reusing one atlas layout per grid
+fn parse_sprite(
+    fields: &[&str],
+    asset_server: &AssetServer,
+    layouts: &mut Assets<TextureAtlasLayout>,
+    grids: &mut HashMap<(UVec2, u32, u32), Handle<TextureAtlasLayout>>,
+) -> Option<(Sprite, Transform)> {
+    let [image, numbers @ ..] = fields else {
+        return None;
+    };
+    let numbers = numbers
+        .iter()
+        .map(|field| field.parse::<f32>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    let [
+        tile_x,
+        tile_y,
+        columns,
+        rows,
+        index,
+        flip_x,
+        flip_y,
+        red,
+        green,
+        blue,
+        alpha,
+        width,
+        height,
+        x,
+        y,
+        z,
+        angle,
+        scale_x,
+        scale_y,
+    ] = numbers[..]
+    else {
+        return None;
+    };
+
+    let mut sprite = Sprite {
+        color: Color::srgba(red, green, blue, alpha),
+        flip_x: flip_x != 0.0,
+        flip_y: flip_y != 0.0,
+        custom_size: (width > 0.0).then_some(Vec2::new(width, height)),
+        ..default()
+    };
+    if *image != "-" {
+        sprite.image = asset_server.load(image.to_string());
+    }
+    if columns > 0.0 && rows > 0.0 {
+        let grid = (
+            UVec2::new(tile_x as u32, tile_y as u32),
+            columns as u32,
+            rows as u32,
+        );
+        let layout = grids
+            .entry(grid)
+            .or_insert_with(|| {
+                layouts.add(TextureAtlasLayout::from_grid(
+                    grid.0, grid.1, grid.2, None, None,
+                ))
+            })
+            .clone();
+        sprite.texture_atlas = Some(TextureAtlas {
+            layout,
+            index: index as usize,
+        });
+    }
+    let transform = Transform::from_xyz(x, y, z)
+        .with_rotation(Quat::from_rotation_z(angle))
+        .with_scale(Vec3::new(scale_x, scale_y, 1.0));
+    Some((sprite, transform))
+}
+
+/// Read a saved replay's snapshots back (None if the file is gone or has none)
+///
+/// Reads from disk on the main thread, like the other saves loaded on demand;
+/// a replay is a few hundred kilobytes at most.
+pub fn load_favorite_frames(
+    replay: &FavoriteReplay,
+    asset_server: &AssetServer,
+    layouts: &mut Assets<TextureAtlasLayout>,
+) -> Option<Vec<Snapshot>> {
+    let contents = read_data_file(&replay.file)?;
+    let mut grids = HashMap::new();
+    let mut frames: Vec<Snapshot> = Vec::new();
+    for line in contents.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields.split_first() {
+            Some((&"frame", [time])) => frames.push(Snapshot {
+                time: time.parse().ok()?,
+                sprites: Vec::new(),
+            }),
+            Some((&"sprite", fields)) => {
+                let frame = frames.last_mut()?;
+                if let Some(sprite) = parse_sprite(fields, asset_server, layouts, &mut grids) {
+                    frame.sprites.push(sprite);
+                }
+            }
+            _ => {}
+        }
+    }
+    (!frames.is_empty()).then_some(frames)
+}
@@ -0,0 +1,166 @@
+use crate::combat::{ComboCounter, EnemyDefeatedEvent, PlayerDefeatedEvent};
+use crate::game::RestartGameEvent;
+use crate::player::Player;
+use crate::replay::snapshot::{Snapshot, SnapshotBuffer};
+use bevy::prelude::*;
+
+/// Shortest combo worth a highlight
+const MIN_HIGHLIGHT_COMBO: u32 = 3;
+
+/// Moment of the match worth replaying
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HighlightKind {
+    /// The hit that set the match's longest combo, with its hit count
+    BiggestCombo(u32),
+    /// The last enemy defeated
+    FinalKill,
+    /// A player being knocked out
+    Defeat,
+}
+
+impl HighlightKind {
+    /// Real-time seconds the clip shows before and after the moment
+    fn window(self) -> (f32, f32) {
+        match self {
+            HighlightKind::BiggestCombo(_) => (2.5, 1.0),
+            HighlightKind::FinalKill => (2.0, 1.0),
+            // The knockout ceremony plays in slow motion, so the aftermath runs longer
+            HighlightKind::Defeat => (1.5, 2.0),
+        }
+    }
+
+    /// Caption shown while the clip plays
+    pub fn label(self) -> String {
+        match self {
+            HighlightKind::BiggestCombo(hits) => format!("Biggest Combo: {hits} hits"),
+            HighlightKind::FinalKill => "Final Kill".to_string(),
+            HighlightKind::Defeat => "Knockout".to_string(),
+        }
+    }
+
+    /// Whether a newer highlight of this kind replaces an older one
+    fn replaces(self, other: HighlightKind) -> bool {
+        std::mem::discriminant(&self) == std::mem::discriminant(&other)
+    }
+}
+
+/// Snapshots around one highlight, ready to play back
+pub struct HighlightClip {
+    pub kind: HighlightKind,
+    /// `Time<Real>` seconds the moment happened
+    pub at: f32,
+    pub frames: Vec<Snapshot>,
+}
+
+/// Highlight still waiting for the snapshots after its moment
+struct PendingHighlight {
+    kind: HighlightKind,
+    at: f32,
+}
+
+/// The match's highlight clips: its biggest combo, final kill, and knockout
+///
+/// `mark_highlights` notes each moment as it happens (a newer one of the same
+/// kind replaces the older), and `cut_highlight_clips` copies its snapshots
+/// out of the `SnapshotBuffer` once the time after it has been recorded.
+/// Entering GameOver cuts whatever is still pending, and the results screen
+/// offers the clips as a highlight reel. Reset for every match.
+#[derive(Resource, Default)]
+pub struct Highlights {
+    /// Finished clips, in the order they happened
+    pub clips: Vec<HighlightClip>,
+    pending: Vec<PendingHighlight>,
+    best_combo: u32,
+}
+
+impl Highlights {
+    fn mark(&mut self, kind: HighlightKind, at: f32) {
+        self.pending.retain(|pending| !kind.replaces(pending.kind));
+        self.pending.push(PendingHighlight { kind, at });
+    }
+
+    /// Cut the pending highlights whose clips end by `now` (all of them if `flush`)
+    fn cut(&mut self, buffer: &SnapshotBuffer, now: f32, flush: bool) {
+        let (ready, waiting): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|pending| flush || pending.at + pending.kind.window().1 <= now);
+        self.pending = waiting;
+
+        for PendingHighlight { kind, at } in ready {
+            let (before, after) = kind.window();
+            let frames = buffer.range(at - before, at + after);
+            if frames.is_empty() {
+                continue;
+            }
+            self.clips.retain(|clip| !kind.replaces(clip.kind));
+            self.clips.push(HighlightClip { kind, at, frames });
+        }
+        self.clips.sort_by(|a, b| a.at.total_cmp(&b.at));
+    }
+}
+
+/// Note this frame's highlight moments: a new longest combo, a kill, or a knockout
+pub fn mark_highlights(
+    time: Res<Time<Real>>,
+    mut highlights: ResMut<Highlights>,
+    mut defeated_events: MessageReader<EnemyDefeatedEvent>,
+    mut knockout_events: MessageReader<PlayerDefeatedEvent>,
+    combo_query: Query<&ComboCounter, (With<Player>, Changed<ComboCounter>)>,
+) {
+    let now = time.elapsed_secs();
+    for combo in combo_query.iter() {
+        if combo.hits >= MIN_HIGHLIGHT_COMBO && combo.hits > highlights.best_combo {
+            highlights.best_combo = combo.hits;
+            highlights.mark(HighlightKind::BiggestCombo(combo.hits), now);
+        }
+    }
+    if defeated_events.read().count() > 0 {
+        highlights.mark(HighlightKind::FinalKill, now);
+    }
+    if knockout_events.read().count() > 0 {
+        highlights.mark(HighlightKind::Defeat, now);
+    }
+}
+
+/// Copy out the clips of highlights whose aftermath has been recorded
+pub fn cut_highlight_clips(
+    time: Res<Time<Real>>,
+    buffer: Res<SnapshotBuffer>,
+    mut highlights: ResMut<Highlights>,
+) {
+    highlights.cut(&buffer, time.elapsed_secs(), false);
+}
+
+/// Cut every pending highlight with what was recorded before the match ended
+///
+/// Runs on entering GameOver, when recording stops.
+pub fn finish_highlight_clips(
+    time: Res<Time<Real>>,
+    buffer: Res<SnapshotBuffer>,
+    mut highlights: ResMut<Highlights>,
+) {
+    highlights.cut(&buffer, time.elapsed_secs(), true);
+}
+
+/// Start a new match's highlights and snapshots from scratch
+///
+/// Runs on entering InGame; `restart_highlights` does the same for restarts.
+pub fn reset_highlights(mut highlights: ResMut<Highlights>, mut buffer: ResMut<SnapshotBuffer>) {
+    *highlights = Highlights::default();
+    buffer.clear();
+}
+
+/// Drop the last match's highlights and snapshots when the game restarts
+pub fn restart_highlights(
+    mut restart_events: MessageReader<RestartGameEvent>,
+    mut highlights: ResMut<Highlights>,
+    mut buffer: ResMut<SnapshotBuffer>,
+) {
+    if restart_events.is_empty() {
+        return;
+    }
+    restart_events.clear();
+
+    *highlights = Highlights::default();
+    buffer.clear();
+}
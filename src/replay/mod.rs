@@ -0,0 +1,10 @@
+pub mod favorites;
+pub mod highlights;
+pub mod plugin;
+pub mod snapshot;
+
+// Re-export commonly used items
+pub use favorites::*;
+pub use highlights::*;
+pub use plugin::*;
+pub use snapshot::*;
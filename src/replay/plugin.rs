@@ -0,0 +1,35 @@
+use crate::game::{AppState, GameSet, InGame};
+use crate::replay::favorites::FavoriteReplays;
+use crate::replay::highlights::{
+    Highlights, cut_highlight_clips, finish_highlight_clips, mark_highlights, reset_highlights,
+    restart_highlights,
+};
+use crate::replay::snapshot::{SnapshotBuffer, record_snapshots};
+use bevy::prelude::*;
+
+/// Snapshot recording, the highlight clips cut from it (played back by the
+/// results screen), and the clips saved as favorites (watched from the extras screen)
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SnapshotBuffer>()
+            .init_resource::<Highlights>()
+            .insert_resource(FavoriteReplays::load())
+            .add_systems(OnEnter(InGame), reset_highlights)
+            .add_systems(OnEnter(AppState::GameOver), finish_highlight_clips)
+            .add_systems(
+                Update,
+                (
+                    mark_highlights
+                        .run_if(in_state(AppState::Playing))
+                        .in_set(GameSet::DamageReactions),
+                    (record_snapshots, cut_highlight_clips)
+                        .chain()
+                        .run_if(in_state(AppState::Playing))
+                        .in_set(GameSet::Effects),
+                    restart_highlights.in_set(GameSet::Restart),
+                ),
+            );
+    }
+}
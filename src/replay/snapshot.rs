@@ -0,0 +1,93 @@
+use crate::enemy::Enemy;
+use crate::player::Player;
+use crate::projectile::Projectile;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+/// Real-time seconds of snapshots the buffer keeps (enough for any highlight clip)
+const SNAPSHOT_SECONDS: f32 = 6.0;
+
+/// How the fighters and projectiles looked on one frame
+#[derive(Clone)]
+pub struct Snapshot {
+    /// `Time<Real>` elapsed seconds when it was taken
+    pub time: f32,
+    /// Every recorded sprite as drawn: atlas frame, flip, and tint, with its rendered transform
+    pub sprites: Vec<(Sprite, Transform)>,
+}
+
+/// Rolling buffer of the last `SNAPSHOT_SECONDS` of snapshots, newest last
+///
+/// `record_snapshots` fills it every frame while playing; highlight clips are
+/// copied out of it by `cut_highlight_clips`. Snapshots keep only what is
+/// drawn (no components or simulation state), so a clip can be played back
+/// but not resumed.
+#[derive(Resource, Default)]
+pub struct SnapshotBuffer {
+    frames: VecDeque<Snapshot>,
+}
+
+impl SnapshotBuffer {
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    /// Add a snapshot, dropping ones older than the buffer's window
+    pub fn push(&mut self, snapshot: Snapshot) {
+        let now = snapshot.time;
+        self.frames.push_back(snapshot);
+        while self
+            .frames
+            .front()
+            .is_some_and(|frame| now - frame.time > SNAPSHOT_SECONDS)
+        {
+            self.frames.pop_front();
+        }
+    }
+
+    /// Copies of the snapshots taken between `start` and `end` (inclusive), oldest first
+    pub fn range(&self, start: f32, end: f32) -> Vec<Snapshot> {
+        self.frames
+            .iter()
+            .filter(|frame| (start..=end).contains(&frame.time))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Snapshot the players, enemies, and projectiles as they are drawn this frame
+#[allow(clippy::type_complexity)]
+pub fn record_snapshots(
+    time: Res<Time<Real>>,
+    mut buffer: ResMut<SnapshotBuffer>,
+    sprite_query: Query<
+        (&Sprite, &GlobalTransform, &ViewVisibility),
+        Or<(With<Player>, With<Enemy>, With<Projectile>)>,
+    >,
+) {
+    buffer.push(Snapshot {
+        time: time.elapsed_secs(),
+        sprites: sprite_query
+            .iter()
+            .filter(|(_, _, visibility)| visibility.get())
+            .map(|(sprite, transform, _)| (sprite.clone(), transform.compute_transform()))
+            .collect(),
+    });
+}
+
+/// Real-time seconds from a clip's first snapshot to its last
+pub fn clip_length(frames: &[Snapshot]) -> f32 {
+    match (frames.first(), frames.last()) {
+        (Some(first), Some(last)) => last.time - first.time,
+        _ => 0.0,
+    }
+}
+
+/// The latest snapshot taken by `elapsed` real seconds into a clip (None if it's empty)
+pub fn snapshot_at(frames: &[Snapshot], elapsed: f32) -> Option<&Snapshot> {
+    let time = frames.first()?.time + elapsed;
+    let index = frames
+        .partition_point(|frame| frame.time <= time)
+        .saturating_sub(1);
+    frames.get(index)
+}
@@ -0,0 +1,75 @@
+use crate::game::systems::BackgroundMusic;
+use crate::sound::{MusicStem, MusicStinger};
+use bevy::audio::Volume;
+use bevy::prelude::*;
+
+/// Step of the volume buttons on the advanced settings screen
+const VOLUME_STEP: f32 = 0.25;
+
+/// Master and music volume, saved with the other settings
+///
+/// Both are linear gains from 0 (silent) to 1 (full). The master volume
+/// scales every sound; the music volume scales the background music on top
+/// of it.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct AudioSettings {
+    pub master_volume: f32,
+    pub music_volume: f32,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master_volume: 1.0,
+            music_volume: 1.0,
+        }
+    }
+}
+
+/// Next volume in the menu cycle (0% to 100% in `VOLUME_STEP`s, wrapping around)
+pub fn next_volume(volume: f32) -> f32 {
+    let next = (volume / VOLUME_STEP).round() * VOLUME_STEP + VOLUME_STEP;
+    if next > 1.0 + f32::EPSILON { 0.0 } else { next }
+}
+
+/// Volume as a menu percentage ("75%")
+pub fn volume_label(volume: f32) -> String {
+    format!("{}%", (volume * 100.0).round() as u32)
+}
+
+/// Apply the master volume to new sounds, and both volumes to the background
+/// music, its stems, and stingers
+///
+/// Runs every frame so music started later (a new track from the extras
+/// screen, or a boss's stems) picks the volume up as soon as it starts playing.
+/// While a stinger plays, the background music and stems duck to its
+/// `Stinger::music_volume`, and come back once it's done.
+#[allow(clippy::type_complexity)]
+pub fn apply_audio_settings(
+    settings: Res<AudioSettings>,
+    mut global_volume: ResMut<GlobalVolume>,
+    mut last_duck: Local<Option<f32>>,
+    stinger_query: Query<&MusicStinger>,
+    mut music_query: Query<
+        (&mut AudioSink, Has<MusicStinger>),
+        Or<(With<BackgroundMusic>, With<MusicStem>, With<MusicStinger>)>,
+    >,
+) {
+    if settings.is_changed() {
+        global_volume.volume = Volume::Linear(settings.master_volume);
+    }
+    let duck = stinger_query
+        .iter()
+        .map(|stinger| stinger.0.music_volume())
+        .fold(1.0, f32::min);
+    let duck_changed = *last_duck != Some(duck);
+    *last_duck = Some(duck);
+
+    let music_volume = settings.master_volume * settings.music_volume;
+    for (mut sink, stinger) in music_query.iter_mut() {
+        if settings.is_changed() || duck_changed || sink.is_added() {
+            let duck = if stinger { 1.0 } else { duck };
+            sink.set_volume(Volume::Linear(music_volume * duck));
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+
+/// How forgiving combo timing is, chosen on the main menu
+///
+/// Read when the player spawns (`ComboWindow` duration) and by
+/// `player_input_system` (input buffer and combo queueing). Standard matches
+/// the original timing; Relaxed helps newer players chain attacks, and
+/// Tournament Strict removes every assist.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ComboAssist {
+    Relaxed,
+    #[default]
+    Standard,
+    TournamentStrict,
+}
+
+impl ComboAssist {
+    /// Display name for menus
+    pub fn label(self) -> &'static str {
+        match self {
+            ComboAssist::Relaxed => "Relaxed",
+            ComboAssist::Standard => "Standard",
+            ComboAssist::TournamentStrict => "Tournament Strict",
+        }
+    }
+
+    /// Next preset in the menu cycle (wraps around)
+    pub fn next(self) -> Self {
+        match self {
+            ComboAssist::Relaxed => ComboAssist::Standard,
+            ComboAssist::Standard => ComboAssist::TournamentStrict,
+            ComboAssist::TournamentStrict => ComboAssist::Relaxed,
+        }
+    }
+
+    /// Seconds after an attack starts during which the next hit of a combo can be queued
+    pub fn combo_window_seconds(self) -> f32 {
+        match self {
+            ComboAssist::Relaxed => 0.7,
+            ComboAssist::Standard => 0.5,
+            ComboAssist::TournamentStrict => 0.35,
+        }
+    }
+
+    /// Seconds an attack press that couldn't be used yet is remembered and retried
+    ///
+    /// 0 disables the buffer: a press only counts on the frame it happens.
+    pub fn buffer_seconds(self) -> f32 {
+        match self {
+            ComboAssist::Relaxed => 0.2,
+            ComboAssist::Standard => 0.0,
+            ComboAssist::TournamentStrict => 0.0,
+        }
+    }
+
+    /// Fraction of a whiffed attack's animation that must play before a combo can be queued
+    ///
+    /// A landed hit can always be combo'd right away (hit-confirm cancel);
+    /// above 1.0 a whiff can't be combo'd at all.
+    pub fn whiff_queue_fraction(self) -> f32 {
+        match self {
+            ComboAssist::Relaxed => 0.25,
+            ComboAssist::Standard => 0.5,
+            ComboAssist::TournamentStrict => 1.1,
+        }
+    }
+}
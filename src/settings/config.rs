@@ -0,0 +1,221 @@
+use crate::input::{DeviceSettings, GamepadProfiles, InputMap};
+use crate::persistence::{SaveQueue, parse_key_values, read_data_file};
+use crate::settings::audio::AudioSettings;
+use crate::settings::combo_assist::ComboAssist;
+use crate::settings::difficulty::Difficulty;
+use crate::settings::display::DisplaySettings;
+use crate::settings::game_speed::GameSpeed;
+use crate::settings::particle_budget::ParticleBudget;
+use crate::settings::simulation::{SimulationSettings, TickRate};
+use crate::settings::trade_rule::TradeRule;
+use crate::ui::TextStyles;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// File in the data directory holding the player's settings
+const SETTINGS_FILE: &str = "settings.txt";
+
+/// The menu option whose label matches `value`, cycling through the menu order from `start`
+fn from_label<T: Copy + PartialEq>(
+    start: T,
+    next: impl Fn(T) -> T,
+    label: impl Fn(T) -> &'static str,
+    value: &str,
+) -> Option<T> {
+    let mut option = start;
+    loop {
+        if label(option) == value {
+            return Some(option);
+        }
+        option = next(option);
+        if option == start {
+            return None;
+        }
+    }
+}
+
+/// Parse a saved value, warning (and returning None) if it's malformed
+fn saved<T>(
+    values: &HashMap<&str, &str>,
+    key: &str,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Option<T> {
+    let value = *values.get(key)?;
+    let parsed = parse(value);
+    if parsed.is_none() {
+        warn!("{SETTINGS_FILE}: can't parse {key}={value}");
+    }
+    parsed
+}
+
+/// Apply the settings saved in the data directory, if any, over the defaults
+///
+/// Runs at startup. Anything missing or malformed keeps its default, so a
+/// save from an older version still loads. A saved binding replaces every
+/// default key (or button) of its action.
+#[allow(clippy::too_many_arguments)]
+pub fn load_settings(
+    mut difficulty: ResMut<Difficulty>,
+    mut speed: ResMut<GameSpeed>,
+    mut trade_rule: ResMut<TradeRule>,
+    mut combo_assist: ResMut<ComboAssist>,
+    mut simulation: ResMut<SimulationSettings>,
+    mut particles: ResMut<ParticleBudget>,
+    mut devices: ResMut<DeviceSettings>,
+    mut display: ResMut<DisplaySettings>,
+    mut audio: ResMut<AudioSettings>,
+    mut input_map: ResMut<InputMap>,
+    mut gamepad_profiles: ResMut<GamepadProfiles>,
+    mut styles: ResMut<TextStyles>,
+) {
+    let Some(contents) = read_data_file(SETTINGS_FILE) else {
+        return;
+    };
+    let values = parse_key_values(&contents);
+
+    if let Some(value) = saved(&values, "difficulty", |value| {
+        from_label(
+            Difficulty::default(),
+            Difficulty::next,
+            Difficulty::label,
+            value,
+        )
+    }) {
+        *difficulty = value;
+    }
+    if let Some(value) = saved(&values, "game_speed", GameSpeed::from_key) {
+        *speed = value;
+    }
+    if let Some(value) = saved(&values, "trades", |value| {
+        from_label(
+            TradeRule::default(),
+            TradeRule::next,
+            TradeRule::label,
+            value,
+        )
+    }) {
+        *trade_rule = value;
+    }
+    if let Some(value) = saved(&values, "combos", |value| {
+        from_label(
+            ComboAssist::default(),
+            ComboAssist::next,
+            ComboAssist::label,
+            value,
+        )
+    }) {
+        *combo_assist = value;
+    }
+    if let Some(value) = saved(&values, "tick_rate", |value| {
+        from_label(TickRate::default(), TickRate::next, TickRate::label, value)
+    }) {
+        simulation.tick_rate = value;
+    }
+    if let Some(value) = saved(&values, "smoothing", |value| value.parse().ok()) {
+        simulation.interpolation = value;
+    }
+    if let Some(value) = saved(&values, "particles", |value| {
+        from_label(
+            ParticleBudget::default(),
+            ParticleBudget::next,
+            ParticleBudget::label,
+            value,
+        )
+    }) {
+        *particles = value;
+    }
+    if let Some(value) = saved(&values, "pause_on_disconnect", |value| value.parse().ok()) {
+        devices.pause_on_disconnect = value;
+    }
+    if let Some(value) = saved(&values, "readable_font", |value| value.parse().ok()) {
+        styles.set_readable(value);
+    }
+
+    if let Some(value) = saved(&values, "resolution", |value| {
+        let (width, height) = value.split_once('x')?;
+        let resolution = UVec2::new(width.trim().parse().ok()?, height.trim().parse().ok()?);
+        (resolution.min_element() > 0).then_some(resolution)
+    }) {
+        display.resolution = value;
+    }
+    if let Some(value) = saved(&values, "fullscreen", |value| value.parse().ok()) {
+        display.fullscreen = value;
+    }
+    let volume = |value: &str| {
+        value
+            .parse::<f32>()
+            .ok()
+            .filter(|volume| (0.0..=1.0).contains(volume))
+    };
+    if let Some(value) = saved(&values, "master_volume", volume) {
+        audio.master_volume = value;
+    }
+    if let Some(value) = saved(&values, "music_volume", volume) {
+        audio.music_volume = value;
+    }
+
+    input_map.apply_lines(&values, "key.");
+    gamepad_profiles.apply_lines(&values, "gamepad.");
+}
+
+/// Queue the settings to be saved to the data directory whenever one of them changes
+///
+/// Also runs the first frame (everything counts as changed then), which
+/// writes out a complete file with the defaults filled in.
+#[allow(clippy::too_many_arguments)]
+pub fn save_settings(
+    difficulty: Res<Difficulty>,
+    speed: Res<GameSpeed>,
+    trade_rule: Res<TradeRule>,
+    combo_assist: Res<ComboAssist>,
+    simulation: Res<SimulationSettings>,
+    particles: Res<ParticleBudget>,
+    devices: Res<DeviceSettings>,
+    display: Res<DisplaySettings>,
+    audio: Res<AudioSettings>,
+    input_map: Res<InputMap>,
+    gamepad_profiles: Res<GamepadProfiles>,
+    styles: Res<TextStyles>,
+    mut save_queue: ResMut<SaveQueue>,
+) {
+    let changed = difficulty.is_changed()
+        || speed.is_changed()
+        || trade_rule.is_changed()
+        || combo_assist.is_changed()
+        || simulation.is_changed()
+        || particles.is_changed()
+        || devices.is_changed()
+        || display.is_changed()
+        || audio.is_changed()
+        || input_map.is_changed()
+        || gamepad_profiles.is_changed()
+        || styles.is_changed();
+    if !changed {
+        return;
+    }
+
+    let mut contents = format!(
+        "# Saved settings, rewritten whenever one changes in game\n\
+         difficulty={}\ngame_speed={}\ntrades={}\ncombos={}\ntick_rate={}\nsmoothing={}\n\
+         particles={}\npause_on_disconnect={}\nreadable_font={}\n\
+         resolution={}x{}\nfullscreen={}\nmaster_volume={}\nmusic_volume={}\n",
+        difficulty.label(),
+        speed.key(),
+        trade_rule.label(),
+        combo_assist.label(),
+        simulation.tick_rate.label(),
+        simulation.interpolation,
+        particles.label(),
+        devices.pause_on_disconnect,
+        styles.readable(),
+        display.resolution.x,
+        display.resolution.y,
+        display.fullscreen,
+        audio.master_volume,
+        audio.music_volume,
+    );
+    contents.push_str(&input_map.to_lines("key."));
+    contents.push_str(&gamepad_profiles.to_lines("gamepad."));
+
+    save_queue.queue(SETTINGS_FILE, contents);
+}
@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+
+/// Selected difficulty level, chosen on the main menu
+///
+/// Read when a game starts (GameState, player health) and while playing
+/// (enemy spawning and damage). Normal matches the original tuning.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    /// Display name for menus
+    pub fn label(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+
+    /// Next difficulty in the menu cycle (wraps around)
+    pub fn next(self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Easy,
+        }
+    }
+
+    /// Seconds between enemy spawns
+    pub fn spawn_interval(self) -> f32 {
+        match self {
+            Difficulty::Easy => 3.0,
+            Difficulty::Normal => 2.0,
+            Difficulty::Hard => 1.5,
+        }
+    }
+
+    /// Threat budget of the first match section (total `threat_cost` of enemies alive at once)
+    pub fn threat_budget(self) -> u32 {
+        match self {
+            Difficulty::Easy => 5,
+            Difficulty::Normal => 7,
+            Difficulty::Hard => 10,
+        }
+    }
+
+    /// Starting (and max) health of each enemy
+    pub fn enemy_health(self) -> i32 {
+        match self {
+            Difficulty::Easy => 4,
+            Difficulty::Normal => 6,
+            Difficulty::Hard => 9,
+        }
+    }
+
+    /// Starting (and max) health of the player
+    pub fn player_health(self) -> i32 {
+        match self {
+            Difficulty::Easy => 30,
+            Difficulty::Normal => 20,
+            Difficulty::Hard => 15,
+        }
+    }
+
+    /// Scale damage dealt by enemies to the player (never below 1)
+    pub fn scale_enemy_damage(self, damage: i32) -> i32 {
+        let multiplier = match self {
+            Difficulty::Easy => 0.75,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.5,
+        };
+        ((damage as f32 * multiplier).round() as i32).max(1)
+    }
+}
@@ -0,0 +1,94 @@
+use crate::input::{InputAction, InputMap};
+use bevy::prelude::*;
+use bevy::window::{MonitorSelection, PrimaryWindow, WindowMode};
+
+/// Windowed resolutions offered on the advanced settings screen, in menu order
+///
+/// Not all 16:9: the HUD and arena follow the window's size, so any ratio works.
+pub const RESOLUTIONS: [UVec2; 6] = [
+    UVec2::new(1280, 720),
+    UVec2::new(1600, 900),
+    UVec2::new(1920, 1080),
+    UVec2::new(2560, 1440),
+    UVec2::new(1280, 800),
+    UVec2::new(1024, 768),
+];
+
+/// Window size and mode, saved with the other settings
+///
+/// Both are set on the advanced settings screen, and fullscreen is also
+/// toggled by the Fullscreen key (F11) or Alt+Enter. The saved settings file
+/// accepts any resolution, not just the menu's `RESOLUTIONS`.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DisplaySettings {
+    /// Window size in logical pixels when not fullscreen
+    pub resolution: UVec2,
+    /// Borderless fullscreen on the current monitor
+    pub fullscreen: bool,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self {
+            resolution: UVec2::new(1280, 720),
+            fullscreen: false,
+        }
+    }
+}
+
+impl DisplaySettings {
+    /// Display name of the window mode for menus
+    pub fn mode_label(self) -> &'static str {
+        if self.fullscreen {
+            "Fullscreen"
+        } else {
+            "Windowed"
+        }
+    }
+
+    /// Display name of the windowed resolution for menus ("1280x720")
+    pub fn resolution_label(self) -> String {
+        format!("{}x{}", self.resolution.x, self.resolution.y)
+    }
+
+    /// The next resolution in `RESOLUTIONS` (the first if the current one isn't listed)
+    pub fn next_resolution(self) -> UVec2 {
+        RESOLUTIONS
+            .iter()
+            .position(|&resolution| resolution == self.resolution)
+            .map_or(RESOLUTIONS[0], |index| {
+                RESOLUTIONS[(index + 1) % RESOLUTIONS.len()]
+            })
+    }
+}
+
+/// Toggle fullscreen on the Fullscreen key (F11) or Alt+Enter, in any state
+pub fn toggle_fullscreen(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    mut settings: ResMut<DisplaySettings>,
+) {
+    let alt_enter = keyboard.any_pressed([KeyCode::AltLeft, KeyCode::AltRight])
+        && keyboard.just_pressed(KeyCode::Enter);
+    if alt_enter || input_map.just_pressed(InputAction::Fullscreen, &keyboard) {
+        settings.fullscreen = !settings.fullscreen;
+    }
+}
+
+/// Resize the window and switch its mode whenever the display settings change
+pub fn apply_display_settings(
+    settings: Res<DisplaySettings>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Ok(mut window) = window_query.single_mut() else {
+        return;
+    };
+    window.mode = if settings.fullscreen {
+        WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+    } else {
+        WindowMode::Windowed
+    };
+    window
+        .resolution
+        .set(settings.resolution.x as f32, settings.resolution.y as f32);
+}
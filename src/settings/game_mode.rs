@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+
+/// Which kind of game Play starts, chosen on the main menu
+///
+/// Arcade is the timed score attack through the stages in assets/levels.txt,
+/// each ending in a boss (see src/levels/). Endless drops the timer: spawns speed up
+/// and enemies toughen the longer the run lasts, and only a knockout ends it.
+/// Versus swaps the enemy waves for a second player in best-of-three rounds
+/// (see src/versus/). Training runs the same plugins with a different setup:
+/// no timer, no spawning, and a single `TrainingDummy` to practice on (see
+/// src/training/). Versus and training games don't count toward lifetime
+/// stats, personal records, or the leaderboard.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GameMode {
+    #[default]
+    Arcade,
+    Endless,
+    Versus,
+    Training,
+}
+
+impl GameMode {
+    /// Key used in save files
+    pub fn key(self) -> &'static str {
+        match self {
+            GameMode::Arcade => "arcade",
+            GameMode::Endless => "endless",
+            GameMode::Versus => "versus",
+            GameMode::Training => "training",
+        }
+    }
+
+    /// Parse a save-file key
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "arcade" => Some(GameMode::Arcade),
+            "endless" => Some(GameMode::Endless),
+            "versus" => Some(GameMode::Versus),
+            "training" => Some(GameMode::Training),
+            _ => None,
+        }
+    }
+}
+
+/// Run condition: the current game is an arcade run through the stages
+pub fn arcade_mode(mode: Res<GameMode>) -> bool {
+    *mode == GameMode::Arcade
+}
+
+/// Run condition: the current game is a training session
+pub fn training_mode(mode: Res<GameMode>) -> bool {
+    *mode == GameMode::Training
+}
+
+/// Run condition: the current game is a versus match
+pub fn versus_mode(mode: Res<GameMode>) -> bool {
+    *mode == GameMode::Versus
+}
+
+/// Run condition: the current game fights timed enemy waves (arcade or endless)
+pub fn wave_mode(mode: Res<GameMode>) -> bool {
+    matches!(*mode, GameMode::Arcade | GameMode::Endless)
+}
+
+/// Run condition: the current game counts toward stats and the leaderboard (arcade or endless)
+pub fn scored_mode(mode: Res<GameMode>) -> bool {
+    matches!(*mode, GameMode::Arcade | GameMode::Endless)
+}
@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+
+/// Global simulation speed, like the turbo settings of arcade re-releases
+///
+/// Applied to the virtual clock by `apply_game_speed`, so every gameplay
+/// timer, animation, and movement scales together.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum GameSpeed {
+    Slow,
+    #[default]
+    Normal,
+    Fast,
+    Turbo,
+}
+
+impl GameSpeed {
+    /// Multiplier applied to the virtual clock
+    pub fn multiplier(self) -> f32 {
+        match self {
+            GameSpeed::Slow => 0.75,
+            GameSpeed::Normal => 1.0,
+            GameSpeed::Fast => 1.25,
+            GameSpeed::Turbo => 1.5,
+        }
+    }
+
+    /// Display name for menus
+    pub fn label(self) -> &'static str {
+        match self {
+            GameSpeed::Slow => "0.75x",
+            GameSpeed::Normal => "1x",
+            GameSpeed::Fast => "1.25x",
+            GameSpeed::Turbo => "1.5x Turbo",
+        }
+    }
+
+    /// Stable name used in save files
+    pub fn key(self) -> &'static str {
+        match self {
+            GameSpeed::Slow => "slow",
+            GameSpeed::Normal => "normal",
+            GameSpeed::Fast => "fast",
+            GameSpeed::Turbo => "turbo",
+        }
+    }
+
+    /// Parse a name written by `key`
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "slow" => Some(GameSpeed::Slow),
+            "normal" => Some(GameSpeed::Normal),
+            "fast" => Some(GameSpeed::Fast),
+            "turbo" => Some(GameSpeed::Turbo),
+            _ => None,
+        }
+    }
+
+    /// Next speed in the menu cycle (wraps around)
+    pub fn next(self) -> Self {
+        match self {
+            GameSpeed::Slow => GameSpeed::Normal,
+            GameSpeed::Normal => GameSpeed::Fast,
+            GameSpeed::Fast => GameSpeed::Turbo,
+            GameSpeed::Turbo => GameSpeed::Slow,
+        }
+    }
+}
+
+/// Scale the virtual clock whenever the game speed setting changes
+pub fn apply_game_speed(speed: Res<GameSpeed>, mut time: ResMut<Time<Virtual>>) {
+    time.set_relative_speed(speed.multiplier());
+}
@@ -0,0 +1,98 @@
+use crate::persistence::{parse_key_values, parsed};
+use bevy::prelude::*;
+use std::fs;
+
+/// Match settings, relative to the working directory (like the asset folder)
+const MATCH_SETTINGS_PATH: &str = "assets/match.txt";
+
+/// Match length and pacing, read from assets/match.txt at startup
+///
+/// Read when a game starts (`GameState`) and while playing (enemy spawning and
+/// kill scoring). The defaults match the original tuning; anything missing or
+/// malformed in the file keeps its default.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct MatchSettings {
+    /// Seconds on an arcade stage's clock, unless the stage sets its own (endless runs ignore it)
+    pub duration: f32,
+    /// Most enemies alive at once, on top of the threat budget
+    pub max_enemies: u32,
+    /// Seconds between enemy spawns (None for the difficulty's interval)
+    pub spawn_interval: Option<f32>,
+    /// Base score of a kill, before combo, style, and final rush multipliers
+    pub score_per_kill: u32,
+    /// Lives an arcade or endless run starts with (see `Lives`)
+    pub lives: u32,
+    /// Continues an arcade or endless run can take after losing its last life
+    pub continues: u32,
+}
+
+impl Default for MatchSettings {
+    fn default() -> Self {
+        Self {
+            duration: 120.0,
+            max_enemies: 6,
+            spawn_interval: None,
+            score_per_kill: 10,
+            lives: 3,
+            continues: 3,
+        }
+    }
+}
+
+impl MatchSettings {
+    /// Load assets/match.txt (the defaults if it can't be read)
+    pub fn load() -> Self {
+        let contents = fs::read_to_string(MATCH_SETTINGS_PATH).unwrap_or_else(|err| {
+            warn!("Using the default match settings ({MATCH_SETTINGS_PATH}: {err})");
+            String::new()
+        });
+        Self::from_contents(&contents)
+    }
+
+    fn from_contents(contents: &str) -> Self {
+        let values = parse_key_values(contents);
+        let mut settings = Self::default();
+
+        if let Some(duration) = values
+            .get("duration")
+            .and_then(|value| parsed(("duration".to_string(), value)))
+            .filter(|duration| *duration > 0.0)
+        {
+            settings.duration = duration;
+        }
+        if let Some(max_enemies) = values
+            .get("max_enemies")
+            .and_then(|value| parsed(("max_enemies".to_string(), value)))
+            .filter(|max_enemies| *max_enemies > 0)
+        {
+            settings.max_enemies = max_enemies;
+        }
+        if let Some(interval) = values
+            .get("spawn_interval")
+            .and_then(|value| parsed(("spawn_interval".to_string(), value)))
+            .filter(|interval| *interval > 0.0)
+        {
+            settings.spawn_interval = Some(interval);
+        }
+        if let Some(score) = values
+            .get("score_per_kill")
+            .and_then(|value| parsed(("score_per_kill".to_string(), value)))
+        {
+            settings.score_per_kill = score;
+        }
+        if let Some(lives) = values
+            .get("lives")
+            .and_then(|value| parsed(("lives".to_string(), value)))
+            .filter(|lives| *lives > 0)
+        {
+            settings.lives = lives;
+        }
+        if let Some(continues) = values
+            .get("continues")
+            .and_then(|value| parsed(("continues".to_string(), value)))
+        {
+            settings.continues = continues;
+        }
+        settings
+    }
+}
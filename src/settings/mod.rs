@@ -0,0 +1,27 @@
+pub mod audio;
+pub mod combo_assist;
+pub mod config;
+pub mod difficulty;
+pub mod display;
+pub mod game_mode;
+pub mod game_speed;
+pub mod match_settings;
+pub mod mutators;
+pub mod particle_budget;
+pub mod plugin;
+pub mod simulation;
+pub mod trade_rule;
+
+// Re-export commonly used items
+pub use audio::*;
+pub use combo_assist::*;
+pub use difficulty::*;
+pub use display::*;
+pub use game_mode::*;
+pub use game_speed::*;
+pub use match_settings::*;
+pub use mutators::*;
+pub use particle_budget::*;
+pub use plugin::*;
+pub use simulation::*;
+pub use trade_rule::*;
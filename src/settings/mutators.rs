@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+
+/// Combo window speed-up between attacks under the pressure mutator
+const PRESSURE_COMBO_DECAY: f32 = 4.0;
+/// Score multiplier on every kill and score gem under the hardcore mutator
+const HARDCORE_SCORE_MULTIPLIER: u32 = 3;
+
+/// Optional rule changes for variety, toggled on the main menu
+///
+/// All mutators are off by default, which plays the original game.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Mutators {
+    /// Mirror the stage and swap the left/right controls
+    pub mirror: bool,
+    /// The player has a single point of health, so any hit (even chip damage) is
+    /// fatal, in exchange for triple score and a costume unlocked by clearing a stage
+    pub hardcore: bool,
+    /// Arcade pressure: the combo (and its score multiplier) drains fast between
+    /// attacks, and standing still too long calls in a hurry-up ghost
+    pub pressure: bool,
+}
+
+impl Mutators {
+    /// Mirror a stage X coordinate when mirror mode is on
+    ///
+    /// Use for anything placed at a fixed X (player start, stage art); spawn
+    /// sides follow automatically because they are chosen relative to the player.
+    pub fn mirror_x(self, x: f32) -> f32 {
+        if self.mirror { -x } else { x }
+    }
+
+    /// Player starting health, given the difficulty's normal starting health
+    pub fn player_health(self, health: i32) -> i32 {
+        if self.hardcore { 1 } else { health }
+    }
+
+    /// Lives a run starts with, given the match settings' lives
+    pub fn lives(self, lives: u32) -> u32 {
+        if self.hardcore { 1 } else { lives }
+    }
+
+    /// Multiplier on the score of kills and score gems (triple under hardcore)
+    pub fn score_multiplier(self) -> u32 {
+        if self.hardcore {
+            HARDCORE_SCORE_MULTIPLIER
+        } else {
+            1
+        }
+    }
+
+    /// How fast the combo window runs out while the player isn't attacking
+    pub fn combo_decay_rate(self) -> f32 {
+        if self.pressure {
+            PRESSURE_COMBO_DECAY
+        } else {
+            1.0
+        }
+    }
+}
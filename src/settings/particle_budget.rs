@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+
+/// Most particles alive at once, chosen on the advanced settings screen
+///
+/// Bursts that would go over the budget are cut short, so a busy fight
+/// never costs more than this many sprites. `Off` spawns none at all.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ParticleBudget {
+    Off,
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl ParticleBudget {
+    /// Particles allowed alive at once
+    pub fn max_particles(self) -> usize {
+        match self {
+            ParticleBudget::Off => 0,
+            ParticleBudget::Low => 100,
+            ParticleBudget::Normal => 250,
+            ParticleBudget::High => 500,
+        }
+    }
+
+    /// Display name for menus
+    pub fn label(self) -> &'static str {
+        match self {
+            ParticleBudget::Off => "Off",
+            ParticleBudget::Low => "Low",
+            ParticleBudget::Normal => "Normal",
+            ParticleBudget::High => "High",
+        }
+    }
+
+    /// Next budget in the menu cycle (wraps around)
+    pub fn next(self) -> Self {
+        match self {
+            ParticleBudget::Off => ParticleBudget::Low,
+            ParticleBudget::Low => ParticleBudget::Normal,
+            ParticleBudget::Normal => ParticleBudget::High,
+            ParticleBudget::High => ParticleBudget::Off,
+        }
+    }
+}
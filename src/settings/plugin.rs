@@ -0,0 +1,52 @@
+use crate::game::{GameSet, InGame};
+use crate::settings::audio::{AudioSettings, apply_audio_settings};
+use crate::settings::combo_assist::ComboAssist;
+use crate::settings::config::{load_settings, save_settings};
+use crate::settings::difficulty::Difficulty;
+use crate::settings::display::{DisplaySettings, apply_display_settings, toggle_fullscreen};
+use crate::settings::game_mode::GameMode;
+use crate::settings::game_speed::{GameSpeed, apply_game_speed};
+use crate::settings::match_settings::MatchSettings;
+use crate::settings::mutators::Mutators;
+use crate::settings::particle_budget::ParticleBudget;
+use crate::settings::simulation::{SimulationSettings, apply_tick_rate};
+use crate::settings::trade_rule::TradeRule;
+use bevy::prelude::*;
+
+/// Game mode, difficulty, game speed, match, mutator, trade rule, combo assist, simulation,
+/// particle, display, and audio settings, and saving the player's choices to disk
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameMode>()
+            .init_resource::<Difficulty>()
+            .init_resource::<GameSpeed>()
+            .insert_resource(MatchSettings::load())
+            .init_resource::<Mutators>()
+            .init_resource::<TradeRule>()
+            .init_resource::<ComboAssist>()
+            .init_resource::<SimulationSettings>()
+            .init_resource::<ParticleBudget>()
+            .init_resource::<DisplaySettings>()
+            .init_resource::<AudioSettings>()
+            .add_systems(Startup, load_settings)
+            // Undo any intro-card slow motion when leaving the game
+            .add_systems(OnExit(InGame), apply_game_speed)
+            .add_systems(
+                Update,
+                (
+                    apply_game_speed.run_if(resource_changed::<GameSpeed>),
+                    apply_tick_rate.run_if(resource_changed::<SimulationSettings>),
+                    (
+                        toggle_fullscreen,
+                        apply_display_settings.run_if(resource_changed::<DisplaySettings>),
+                    )
+                        .chain(),
+                    apply_audio_settings,
+                )
+                    .in_set(GameSet::Input),
+            )
+            .add_systems(Update, save_settings.after(GameSet::Menus));
+    }
+}
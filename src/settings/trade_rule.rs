@@ -0,0 +1,31 @@
+use bevy::prelude::*;
+
+/// What happens when the player and an enemy hit each other on the same frame
+///
+/// Toggled on the main menu. Classic keeps the original behavior.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TradeRule {
+    /// Both hits land (a trade)
+    #[default]
+    Classic,
+    /// Only the heavier attack lands; equal weights still trade
+    Priority,
+}
+
+impl TradeRule {
+    /// Display name for menus
+    pub fn label(self) -> &'static str {
+        match self {
+            TradeRule::Classic => "Classic",
+            TradeRule::Priority => "Priority",
+        }
+    }
+
+    /// Next rule in the menu cycle (wraps around)
+    pub fn next(self) -> Self {
+        match self {
+            TradeRule::Classic => TradeRule::Priority,
+            TradeRule::Priority => TradeRule::Classic,
+        }
+    }
+}
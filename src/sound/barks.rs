@@ -0,0 +1,134 @@
+use crate::combat::EnemyDefeatedEvent;
+use crate::enemy::{Enemy, EnemyArchetype};
+use crate::game::GameplayEvent;
+use crate::sound::library::SoundLibrary;
+use crate::sound::mixer::{PlaySound, SoundChannel, SoundPriority};
+use bevy::prelude::*;
+use rand::Rng;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Seconds an enemy stays quiet after any of its barks
+const ENTITY_BARK_COOLDOWN: f32 = 4.0;
+/// Playback speed range for barks, so one voice sounds like a crowd
+const BARK_PITCH_VARIATION: Range<f32> = 0.85..1.15;
+/// Volume range for barks
+const BARK_VOLUME_VARIATION: Range<f32> = 0.4..0.55;
+
+/// Something an enemy says
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Bark {
+    /// Shriek on arriving in the arena
+    Spawn,
+    /// Grunt on landing a hit
+    Attack,
+    /// Wail on being defeated
+    Death,
+}
+
+impl Bark {
+    pub const ALL: [Bark; 3] = [Bark::Spawn, Bark::Attack, Bark::Death];
+
+    /// Key in assets/enemies.txt (`<archetype>.<key>_bark`)
+    pub fn key(self) -> &'static str {
+        match self {
+            Bark::Spawn => "spawn",
+            Bark::Attack => "attack",
+            Bark::Death => "death",
+        }
+    }
+
+    /// Seconds before any enemy can bark this again
+    fn global_cooldown(self) -> f32 {
+        match self {
+            Bark::Spawn => 0.6,
+            Bark::Attack => 0.8,
+            Bark::Death => 0.3,
+        }
+    }
+
+    /// Death wails win over grunts, and grunts over arrival shrieks
+    fn priority(self) -> SoundPriority {
+        match self {
+            Bark::Spawn => SoundPriority::Low,
+            Bark::Attack => SoundPriority::Normal,
+            Bark::Death => SoundPriority::High,
+        }
+    }
+}
+
+/// When each kind of bark can next be heard from any enemy, in elapsed
+/// virtual seconds
+///
+/// Together with `BarkCooldown` and the mixer's voice limit this keeps a wave
+/// of six ghosts from shrieking in unison.
+#[derive(Resource, Default)]
+pub struct BarkCooldowns {
+    ready_at: HashMap<Bark, f32>,
+}
+
+/// When this enemy can next bark, in elapsed virtual seconds
+#[derive(Component)]
+pub struct BarkCooldown {
+    ready_at: f32,
+}
+
+/// Voice enemies as they arrive, hit the player, and are defeated
+///
+/// Each bark is a weighted random take from the archetype's set, skipped
+/// while its kind or the enemy is cooling down (death wails ignore the
+/// enemy's own cooldown). The mixer then caps how many play at once.
+#[allow(clippy::too_many_arguments)]
+pub fn queue_enemy_barks(
+    mut commands: Commands,
+    time: Res<Time>,
+    sounds: Res<SoundLibrary>,
+    mut cooldowns: ResMut<BarkCooldowns>,
+    mut gameplay_events: MessageReader<GameplayEvent>,
+    mut defeated_events: MessageReader<EnemyDefeatedEvent>,
+    mut play_sounds: MessageWriter<PlaySound>,
+    spawned_query: Query<(Entity, &EnemyArchetype), Added<Enemy>>,
+    enemy_query: Query<(&EnemyArchetype, Option<&BarkCooldown>), With<Enemy>>,
+) {
+    let now = time.elapsed_secs();
+    let mut barks: Vec<(Entity, EnemyArchetype, Bark)> = spawned_query
+        .iter()
+        .map(|(entity, archetype)| (entity, *archetype, Bark::Spawn))
+        .collect();
+    for event in gameplay_events.read() {
+        if let GameplayEvent::DamageDealt { attacker, .. } = *event
+            && let Ok((archetype, cooldown)) = enemy_query.get(attacker)
+            && cooldown.is_none_or(|cooldown| cooldown.ready_at <= now)
+        {
+            barks.push((attacker, *archetype, Bark::Attack));
+        }
+    }
+    for event in defeated_events.read() {
+        if let Ok((archetype, _)) = enemy_query.get(event.enemy) {
+            barks.push((event.enemy, *archetype, Bark::Death));
+        }
+    }
+
+    let mut rng = rand::thread_rng();
+    for (entity, archetype, bark) in barks {
+        let ready_at = cooldowns.ready_at.entry(bark).or_default();
+        if *ready_at > now {
+            continue;
+        }
+        let Some(sound) = sounds.random_bark(archetype, bark) else {
+            continue;
+        };
+
+        *ready_at = now + bark.global_cooldown();
+        commands.entity(entity).try_insert(BarkCooldown {
+            ready_at: now + ENTITY_BARK_COOLDOWN,
+        });
+        play_sounds.write(PlaySound {
+            sound,
+            channel: SoundChannel::Barks,
+            priority: bark.priority(),
+            speed: rng.gen_range(BARK_PITCH_VARIATION),
+            volume: rng.gen_range(BARK_VOLUME_VARIATION),
+        });
+    }
+}
@@ -0,0 +1,93 @@
+use crate::enemy::EnemyArchetype;
+use crate::player::AnimationCue;
+use crate::sound::barks::Bark;
+use crate::world::Surface;
+use bevy::prelude::*;
+use rand::Rng;
+use std::collections::HashMap;
+
+/// Recorded takes of each footstep sound, picked between at random
+const FOOTSTEP_TAKES: usize = 2;
+
+/// Sound effect handles, loaded once when the app starts
+///
+/// Each `AnimationCue` has its takes for every `Surface`, from
+/// `sounds/<cue>-<surface>-<take>.wav` (footsteps have `FOOTSTEP_TAKES`
+/// takes, landings one). Each enemy archetype has the weighted takes of its
+/// barks listed in assets/enemies.txt, from `sounds/barks/<archetype>-<take>.wav`.
+#[derive(Resource)]
+#[allow(clippy::type_complexity)]
+pub struct SoundLibrary {
+    cues: HashMap<(AnimationCue, Surface), Vec<Handle<AudioSource>>>,
+    barks: HashMap<(EnemyArchetype, Bark), Vec<(Handle<AudioSource>, u32)>>,
+}
+
+impl SoundLibrary {
+    /// Every take of a cue's sound on a surface
+    pub fn takes(&self, cue: AnimationCue, surface: Surface) -> &[Handle<AudioSource>] {
+        self.cues.get(&(cue, surface)).map_or(&[], Vec::as_slice)
+    }
+
+    /// A take of an archetype's bark, picked by weight (None if it has none)
+    pub fn random_bark(
+        &self,
+        archetype: EnemyArchetype,
+        bark: Bark,
+    ) -> Option<Handle<AudioSource>> {
+        let takes = self.barks.get(&(archetype, bark))?;
+        let total_weight: u32 = takes.iter().map(|(_, weight)| weight).sum();
+        if total_weight == 0 {
+            return None;
+        }
+
+        let mut roll = rand::thread_rng().gen_range(0..total_weight);
+        for (handle, weight) in takes {
+            if roll < *weight {
+                return Some(handle.clone());
+            }
+            roll -= weight;
+        }
+        None
+    }
+}
+
+impl FromWorld for SoundLibrary {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        let mut cues = HashMap::new();
+        for cue in AnimationCue::ALL {
+            let takes = match cue {
+                AnimationCue::Footstep => FOOTSTEP_TAKES,
+                AnimationCue::Landing => 1,
+            };
+            for surface in Surface::ALL {
+                let handles = (1..=takes)
+                    .map(|take| {
+                        asset_server.load(format!(
+                            "sounds/{}-{}-{take}.wav",
+                            cue.key(),
+                            surface.key()
+                        ))
+                    })
+                    .collect();
+                cues.insert((cue, surface), handles);
+            }
+        }
+
+        let mut barks = HashMap::new();
+        for archetype in EnemyArchetype::ALL {
+            for bark in Bark::ALL {
+                let takes = archetype
+                    .bark_takes(bark.key())
+                    .into_iter()
+                    .map(|(take, weight)| {
+                        let path = format!("sounds/barks/{}-{take}.wav", archetype.key());
+                        (asset_server.load(path), weight)
+                    })
+                    .collect();
+                barks.insert((archetype, bark), takes);
+            }
+        }
+        Self { cues, barks }
+    }
+}
@@ -0,0 +1,114 @@
+use bevy::audio::Volume;
+use bevy::prelude::*;
+use std::cmp::Reverse;
+
+/// Group of sounds sharing a voice limit
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SoundChannel {
+    /// Footsteps, landings, and other one-shot effects
+    Effects,
+    /// Enemy voices (see `Bark`)
+    Barks,
+}
+
+impl SoundChannel {
+    /// Most sounds the channel plays at once
+    pub fn max_voices(self) -> usize {
+        match self {
+            SoundChannel::Effects => 8,
+            SoundChannel::Barks => 2,
+        }
+    }
+}
+
+/// How much a sound matters when its channel is full
+///
+/// A full channel cuts off its oldest voice of a lower priority for a new
+/// sound, and drops the new sound if there is none.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SoundPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+/// Request to play a sound through the mixer
+#[derive(Message, Clone, Debug)]
+pub struct PlaySound {
+    pub sound: Handle<AudioSource>,
+    pub channel: SoundChannel,
+    pub priority: SoundPriority,
+    /// Playback speed (1 for the recorded pitch)
+    pub speed: f32,
+    /// Linear volume, before the master volume
+    pub volume: f32,
+}
+
+/// A sound playing through the mixer, despawned when it finishes
+#[derive(Component)]
+pub struct MixerVoice {
+    channel: SoundChannel,
+    priority: SoundPriority,
+    /// Elapsed real time when it started, to find the oldest voice
+    started: f32,
+}
+
+/// Play this frame's `PlaySound` requests, highest priority first, within
+/// each channel's voice limit
+///
+/// Runs after every system that requests sounds.
+pub fn mix_sounds(
+    mut commands: Commands,
+    real_time: Res<Time<Real>>,
+    mut requests: MessageReader<PlaySound>,
+    voice_query: Query<(Entity, &MixerVoice)>,
+) {
+    let mut requests: Vec<&PlaySound> = requests.read().collect();
+    if requests.is_empty() {
+        return;
+    }
+    requests.sort_by_key(|request| Reverse(request.priority));
+
+    let now = real_time.elapsed_secs();
+    let mut voices: Vec<(Entity, SoundChannel, SoundPriority, f32)> = voice_query
+        .iter()
+        .map(|(entity, voice)| (entity, voice.channel, voice.priority, voice.started))
+        .collect();
+    for request in requests {
+        let playing = voices
+            .iter()
+            .filter(|(_, channel, ..)| *channel == request.channel)
+            .count();
+        if playing >= request.channel.max_voices() {
+            let stolen = voices
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, channel, priority, _))| {
+                    *channel == request.channel && *priority < request.priority
+                })
+                .min_by(|(_, a), (_, b)| a.3.total_cmp(&b.3))
+                .map(|(index, _)| index);
+            let Some(index) = stolen else {
+                continue;
+            };
+            let (entity, ..) = voices.swap_remove(index);
+            commands.entity(entity).despawn();
+        }
+
+        let entity = commands
+            .spawn((
+                AudioPlayer::<AudioSource>(request.sound.clone()),
+                PlaybackSettings::DESPAWN
+                    .with_speed(request.speed)
+                    .with_volume(Volume::Linear(request.volume)),
+                MixerVoice {
+                    channel: request.channel,
+                    priority: request.priority,
+                    started: now,
+                },
+            ))
+            .id();
+        voices.push((entity, request.channel, request.priority, now));
+    }
+}
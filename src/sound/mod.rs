@@ -0,0 +1,12 @@
+pub mod barks;
+pub mod library;
+pub mod mixer;
+pub mod plugin;
+pub mod stems;
+pub mod stingers;
+pub mod systems;
+
+// Re-export commonly used items
+pub use plugin::*;
+pub use stems::*;
+pub use stingers::*;
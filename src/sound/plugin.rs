@@ -0,0 +1,37 @@
+use crate::game::GameSet;
+use crate::game::systems::announce_state_entered;
+use crate::sound::barks::{BarkCooldowns, queue_enemy_barks};
+use crate::sound::library::SoundLibrary;
+use crate::sound::mixer::{PlaySound, mix_sounds};
+use crate::sound::stingers::{MusicController, play_stingers, queue_stingers};
+use crate::sound::systems::play_animation_cue_sounds;
+use bevy::prelude::*;
+
+/// Sound effects and enemy voices played in response to gameplay events,
+/// through a mixer that limits how many play at once, and music stingers
+/// coordinated with the music
+pub struct SoundPlugin;
+
+impl Plugin for SoundPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SoundLibrary>()
+            .init_resource::<BarkCooldowns>()
+            .init_resource::<MusicController>()
+            .add_message::<PlaySound>()
+            .add_systems(
+                Update,
+                (
+                    (play_animation_cue_sounds, queue_enemy_barks).in_set(GameSet::Effects),
+                    // After the HUD too, for the countdown ticks
+                    mix_sounds.after(GameSet::Hud),
+                ),
+            )
+            // Any state, so the game over stinger plays on the game over screen
+            .add_systems(
+                Update,
+                (queue_stingers, play_stingers)
+                    .chain()
+                    .after(announce_state_entered),
+            );
+    }
+}
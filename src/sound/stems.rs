@@ -0,0 +1,22 @@
+use bevy::prelude::*;
+
+/// Tempo of every stem under assets/music/stems/ (8-beat, 4 second loops),
+/// which stingers wait for the next beat of
+pub const STEM_BPM: f32 = 120.0;
+
+/// A looping layer of music played over the background music while an
+/// encounter calls for it (see `EncounterAction::Music`)
+///
+/// Stems follow the music volume like the background music, and are
+/// despawned when the encounter ends (see `clear_boss_encounter`).
+#[derive(Component)]
+pub struct MusicStem;
+
+/// Looping music stem bundle, from `music/stems/<key>.wav`
+pub fn music_stem(asset_server: &AssetServer, key: &str) -> impl Bundle {
+    (
+        AudioPlayer::<AudioSource>(asset_server.load(format!("music/stems/{key}.wav"))),
+        PlaybackSettings::LOOP,
+        MusicStem,
+    )
+}
@@ -0,0 +1,189 @@
+use crate::game::{AppState, GameState, GameplayEvent};
+use crate::levels::StageBoss;
+use crate::settings::GameMode;
+use crate::sound::stems::{MusicStem, STEM_BPM};
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// A short musical phrase played over (or in place of) the music at a big moment
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Stinger {
+    /// A stage boss appears
+    BossSpawn,
+    /// The final-rush countdown starts
+    FinalRush,
+    /// A stage is cleared, or a versus match is won
+    Victory,
+    /// The game is over without a win
+    Defeat,
+}
+
+impl Stinger {
+    pub const ALL: [Stinger; 4] = [
+        Stinger::BossSpawn,
+        Stinger::FinalRush,
+        Stinger::Victory,
+        Stinger::Defeat,
+    ];
+
+    /// File name under assets/music/stingers/, without `.wav`
+    pub fn key(self) -> &'static str {
+        match self {
+            Stinger::BossSpawn => "boss",
+            Stinger::FinalRush => "final_rush",
+            Stinger::Victory => "victory",
+            Stinger::Defeat => "defeat",
+        }
+    }
+
+    /// Fraction of the music volume the background music and stems keep while
+    /// the stinger plays: ducked under the ones layered over the music, silent
+    /// for the ones that replace it
+    pub fn music_volume(self) -> f32 {
+        match self {
+            Stinger::BossSpawn => 0.4,
+            Stinger::FinalRush => 0.5,
+            Stinger::Victory | Stinger::Defeat => 0.0,
+        }
+    }
+}
+
+/// A stinger playing over the music, despawned when it finishes
+#[derive(Component)]
+pub struct MusicStinger(pub Stinger);
+
+/// Coordinates stingers with the music: one plays at a time, each waits for
+/// the next beat of the boss stems when they're playing, and the music ducks
+/// under it (see `apply_audio_settings`)
+///
+/// Stinger handles are loaded once at startup, from `music/stingers/<key>.wav`.
+#[derive(Resource)]
+pub struct MusicController {
+    stingers: HashMap<Stinger, Handle<AudioSource>>,
+    /// Stinger waiting for its beat, and the real seconds left until it
+    pending: Option<(Stinger, f32)>,
+}
+
+impl MusicController {
+    /// Whether a stinger is waiting for its beat
+    pub fn is_pending(&self, stinger: Stinger) -> bool {
+        self.pending.is_some_and(|(pending, _)| pending == stinger)
+    }
+
+    /// Play a stinger after `delay` real seconds, replacing any still waiting
+    pub fn queue(&mut self, stinger: Stinger, delay: f32) {
+        self.pending = Some((stinger, delay));
+    }
+}
+
+impl FromWorld for MusicController {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        let stingers = Stinger::ALL
+            .into_iter()
+            .map(|stinger| {
+                let path = format!("music/stingers/{}.wav", stinger.key());
+                (stinger, asset_server.load(path))
+            })
+            .collect();
+        Self {
+            stingers,
+            pending: None,
+        }
+    }
+}
+
+/// Real seconds until the next beat of the playing stems (0 without stems,
+/// since the background music's tempo isn't known)
+fn next_beat_delay(stem_query: &Query<&AudioSink, With<MusicStem>>) -> f32 {
+    let Some(sink) = stem_query.iter().find(|sink| !sink.is_paused()) else {
+        return 0.0;
+    };
+    let beat = 60.0 / STEM_BPM;
+    let position = sink.position().as_secs_f32();
+    (beat - position % beat) % beat
+}
+
+/// Queue stingers for the moments that call for one: a boss spawning, the
+/// final rush starting, a stage cleared or a versus match won, and a game
+/// over without a win
+///
+/// Runs every frame in any state, so the game over stinger plays on the game
+/// over screen. Clearing the last stage opens GameOver too; its victory
+/// stinger isn't replaced by the defeat one.
+#[allow(clippy::too_many_arguments)]
+pub fn queue_stingers(
+    game_state: Option<Res<GameState>>,
+    game_mode: Res<GameMode>,
+    mut controller: ResMut<MusicController>,
+    mut gameplay_events: MessageReader<GameplayEvent>,
+    mut was_final_rush: Local<bool>,
+    boss_query: Query<(), Added<StageBoss>>,
+    stem_query: Query<&AudioSink, With<MusicStem>>,
+    stinger_query: Query<&MusicStinger>,
+) {
+    let mut stinger = None;
+    if !boss_query.is_empty() {
+        stinger = Some(Stinger::BossSpawn);
+    }
+
+    let final_rush = game_state.is_some_and(|game_state| game_state.in_final_rush());
+    if final_rush && !*was_final_rush {
+        stinger = Some(Stinger::FinalRush);
+    }
+    *was_final_rush = final_rush;
+
+    for event in gameplay_events.read() {
+        match event {
+            GameplayEvent::StageCleared { .. } => stinger = Some(Stinger::Victory),
+            GameplayEvent::StateEntered(AppState::GameOver) => {
+                let won = *game_mode == GameMode::Versus
+                    || controller.is_pending(Stinger::Victory)
+                    || stinger == Some(Stinger::Victory)
+                    || stinger_query
+                        .iter()
+                        .any(|playing| playing.0 == Stinger::Victory);
+                stinger = Some(if won {
+                    Stinger::Victory
+                } else {
+                    Stinger::Defeat
+                });
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(stinger) = stinger
+        && !stinger_query.iter().any(|playing| playing.0 == stinger)
+        && !controller.is_pending(stinger)
+    {
+        controller.queue(stinger, next_beat_delay(&stem_query));
+    }
+}
+
+/// Start the waiting stinger once its beat comes, cutting off any still playing
+pub fn play_stingers(
+    mut commands: Commands,
+    real_time: Res<Time<Real>>,
+    mut controller: ResMut<MusicController>,
+    stinger_query: Query<Entity, With<MusicStinger>>,
+) {
+    let Some((stinger, delay)) = controller.pending.as_mut() else {
+        return;
+    };
+    *delay -= real_time.delta_secs();
+    if *delay > 0.0 {
+        return;
+    }
+    let stinger = *stinger;
+    controller.pending = None;
+
+    for entity in stinger_query.iter() {
+        commands.entity(entity).despawn();
+    }
+    commands.spawn((
+        AudioPlayer::<AudioSource>(controller.stingers[&stinger].clone()),
+        PlaybackSettings::DESPAWN,
+        MusicStinger(stinger),
+    ));
+}
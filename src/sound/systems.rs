@@ -0,0 +1,38 @@
+use crate::game::GameplayEvent;
+use crate::sound::library::SoundLibrary;
+use crate::sound::mixer::{PlaySound, SoundChannel, SoundPriority};
+use bevy::prelude::*;
+use rand::Rng;
+use std::ops::Range;
+
+/// Playback speed range for cue sounds, so repeated steps don't sound identical
+const PITCH_VARIATION: Range<f32> = 0.9..1.1;
+/// Volume range for cue sounds, kept under the music
+const VOLUME_VARIATION: Range<f32> = 0.35..0.5;
+
+/// Play a random take of the matching footstep or landing sound for every
+/// `GameplayEvent::AnimationCue`, at a slightly random pitch and volume
+pub fn play_animation_cue_sounds(
+    mut gameplay_events: MessageReader<GameplayEvent>,
+    sounds: Res<SoundLibrary>,
+    mut play_sounds: MessageWriter<PlaySound>,
+) {
+    let mut rng = rand::thread_rng();
+    for event in gameplay_events.read() {
+        let GameplayEvent::AnimationCue { cue, surface, .. } = *event else {
+            continue;
+        };
+        let takes = sounds.takes(cue, surface);
+        if takes.is_empty() {
+            continue;
+        }
+
+        play_sounds.write(PlaySound {
+            sound: takes[rng.gen_range(0..takes.len())].clone(),
+            channel: SoundChannel::Effects,
+            priority: SoundPriority::Normal,
+            speed: rng.gen_range(PITCH_VARIATION),
+            volume: rng.gen_range(VOLUME_VARIATION),
+        });
+    }
+}
@@ -0,0 +1,67 @@
+use crate::enemy::EnemyArchetype;
+use crate::persistence::{SaveQueue, parse_key_values, read_data_file};
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+/// File in the data directory holding the bestiary (`<archetype>=<kills>` lines)
+const BESTIARY_FILE: &str = "bestiary.txt";
+
+/// Enemy archetypes the player has met, with how many of each they have defeated
+///
+/// An archetype is "seen" once it has an entry, even with zero kills. Seen
+/// archetypes never show their intro card again and are listed on the
+/// bestiary screen, which unlocks more detail as kills add up.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct Bestiary {
+    kills: HashMap<EnemyArchetype, u64>,
+}
+
+impl Bestiary {
+    /// Load from disk, starting empty if there is no save yet
+    pub fn load() -> Self {
+        let Some(contents) = read_data_file(BESTIARY_FILE) else {
+            return Self::default();
+        };
+
+        Self {
+            kills: parse_key_values(&contents)
+                .into_iter()
+                .filter_map(|(key, value)| {
+                    Some((EnemyArchetype::from_key(key)?, value.parse().unwrap_or(0)))
+                })
+                .collect(),
+        }
+    }
+
+    /// Queue the bestiary to be saved to disk
+    pub fn save(&self, save_queue: &mut SaveQueue) {
+        let mut contents = String::new();
+        for archetype in EnemyArchetype::ALL {
+            if let Some(kills) = self.kills.get(&archetype) {
+                let _ = writeln!(contents, "{}={}", archetype.key(), kills);
+            }
+        }
+        save_queue.queue(BESTIARY_FILE, contents);
+    }
+
+    /// Mark an archetype as seen, returning true if this is the first time
+    pub fn mark_seen(&mut self, archetype: EnemyArchetype) -> bool {
+        let first_time = !self.kills.contains_key(&archetype);
+        self.kills.entry(archetype).or_insert(0);
+        first_time
+    }
+
+    pub fn is_seen(&self, archetype: EnemyArchetype) -> bool {
+        self.kills.contains_key(&archetype)
+    }
+
+    /// Count a defeated enemy (also marks its archetype as seen)
+    pub fn add_kill(&mut self, archetype: EnemyArchetype) {
+        *self.kills.entry(archetype).or_insert(0) += 1;
+    }
+
+    pub fn kills(&self, archetype: EnemyArchetype) -> u64 {
+        self.kills.get(&archetype).copied().unwrap_or(0)
+    }
+}
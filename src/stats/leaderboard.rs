@@ -0,0 +1,148 @@
+use crate::persistence::{SaveQueue, read_data_file};
+use crate::settings::{GameMode, GameSpeed};
+use bevy::prelude::*;
+use std::fmt::Write as _;
+
+/// File in the data directory holding the leaderboard
+const LEADERBOARD_FILE: &str = "leaderboard.txt";
+/// Scores kept per board (game mode and speed)
+pub const LEADERBOARD_SIZE: usize = 10;
+
+/// One finished game on the leaderboard
+#[derive(Clone, Debug)]
+pub struct LeaderboardEntry {
+    pub mode: GameMode,
+    pub speed: GameSpeed,
+    pub score: u32,
+    /// Unix time (seconds) the game ended
+    pub timestamp: u64,
+}
+
+/// Top scores, kept on a separate board for each game mode and speed and saved after every game
+///
+/// Scores only compete against games of the same mode played at the same
+/// speed, since endless runs and turbo runs are not comparable with normal ones.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct Leaderboard {
+    /// Entries for all boards, each board's entries sorted best first
+    entries: Vec<LeaderboardEntry>,
+    /// Rank (0-based) the most recent game earned on its board, for highlighting
+    pub last_rank: Option<usize>,
+}
+
+impl Leaderboard {
+    /// Load the leaderboard from disk, starting empty if there is no save yet
+    ///
+    /// Each line is `speed score timestamp mode`; malformed lines are skipped.
+    /// Lines without a mode predate endless mode and are arcade scores.
+    pub fn load() -> Self {
+        let Some(contents) = read_data_file(LEADERBOARD_FILE) else {
+            return Self::default();
+        };
+
+        let mut leaderboard = Self::default();
+        for line in contents.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.split_whitespace();
+            let (Some(speed), Some(score), Some(timestamp), Some(mode)) = (
+                fields.next().and_then(GameSpeed::from_key),
+                fields.next().and_then(|v| v.parse().ok()),
+                fields.next().and_then(|v| v.parse().ok()),
+                fields
+                    .next()
+                    .map_or(Some(GameMode::Arcade), GameMode::from_key),
+            ) else {
+                continue;
+            };
+            leaderboard.insert(LeaderboardEntry {
+                mode,
+                speed,
+                score,
+                timestamp,
+            });
+        }
+        leaderboard
+    }
+
+    /// Queue the leaderboard to be saved to disk
+    pub fn save(&self, save_queue: &mut SaveQueue) {
+        let mut contents = String::from("# speed score timestamp mode\n");
+        for entry in &self.entries {
+            let _ = writeln!(
+                contents,
+                "{} {} {} {}",
+                entry.speed.key(),
+                entry.score,
+                entry.timestamp,
+                entry.mode.key()
+            );
+        }
+        save_queue.queue(LEADERBOARD_FILE, contents);
+    }
+
+    /// Entries on one board (game mode and speed), best first
+    pub fn top(&self, mode: GameMode, speed: GameSpeed) -> impl Iterator<Item = &LeaderboardEntry> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.mode == mode && entry.speed == speed)
+    }
+
+    /// Add an entry if it makes its board's top scores
+    ///
+    /// Returns its 0-based rank, or None if it didn't place. Ties rank below
+    /// existing entries.
+    pub fn insert(&mut self, entry: LeaderboardEntry) -> Option<usize> {
+        let rank = self
+            .top(entry.mode, entry.speed)
+            .take_while(|existing| existing.score >= entry.score)
+            .count();
+        if rank >= LEADERBOARD_SIZE {
+            return None;
+        }
+
+        // Insert after the `rank` better entries on the same board
+        let (mode, speed) = (entry.mode, entry.speed);
+        let index = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, existing)| existing.mode == mode && existing.speed == speed)
+            .nth(rank)
+            .map_or(self.entries.len(), |(index, _)| index);
+        self.entries.insert(index, entry);
+
+        // Drop whatever fell off the bottom of this board
+        if let Some((overflow, _)) = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, existing)| existing.mode == mode && existing.speed == speed)
+            .nth(LEADERBOARD_SIZE)
+        {
+            self.entries.remove(overflow);
+        }
+        Some(rank)
+    }
+}
+
+/// Format a Unix timestamp as a UTC `YYYY-MM-DD` date
+pub fn format_date(timestamp: u64) -> String {
+    // Civil-from-days conversion (proleptic Gregorian calendar, 400-year eras)
+    let days = (timestamp / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
@@ -0,0 +1,107 @@
+use crate::persistence::{SaveQueue, parse_key_values, read_data_file};
+use bevy::prelude::*;
+use rand::Rng;
+
+/// File in the data directory holding lifetime stats
+const STATS_FILE: &str = "stats.txt";
+
+/// Totals accumulated across every game, saved to disk after each game
+#[derive(Resource, Clone, Debug, Default)]
+pub struct LifetimeStats {
+    pub games_played: u64,
+    pub ghosts_defeated: u64,
+    pub punches_landed: u64,
+    pub kicks_landed: u64,
+    pub total_score: u64,
+    pub best_score: u64,
+    /// Most hits in a single combo
+    pub best_combo: u64,
+    /// Most ghosts defeated in a row without taking an unblocked hit
+    pub best_no_hit_streak: u64,
+    pub pickups_collected: u64,
+    /// Stages cleared under the hardcore mutator (unlocks its costume)
+    pub hardcore_stages_cleared: u64,
+}
+
+impl LifetimeStats {
+    /// Load stats from disk, starting from zero if there is no save yet
+    pub fn load() -> Self {
+        let Some(contents) = read_data_file(STATS_FILE) else {
+            return Self::default();
+        };
+        let values = parse_key_values(&contents);
+        let get = |key: &str| values.get(key).and_then(|v| v.parse().ok()).unwrap_or(0);
+
+        Self {
+            games_played: get("games_played"),
+            ghosts_defeated: get("ghosts_defeated"),
+            punches_landed: get("punches_landed"),
+            kicks_landed: get("kicks_landed"),
+            total_score: get("total_score"),
+            best_score: get("best_score"),
+            best_combo: get("best_combo"),
+            best_no_hit_streak: get("best_no_hit_streak"),
+            pickups_collected: get("pickups_collected"),
+            hardcore_stages_cleared: get("hardcore_stages_cleared"),
+        }
+    }
+
+    /// Queue the stats to be saved to disk
+    pub fn save(&self, save_queue: &mut SaveQueue) {
+        let contents = format!(
+            "games_played={}\nghosts_defeated={}\npunches_landed={}\nkicks_landed={}\ntotal_score={}\nbest_score={}\nbest_combo={}\nbest_no_hit_streak={}\npickups_collected={}\nhardcore_stages_cleared={}\n",
+            self.games_played,
+            self.ghosts_defeated,
+            self.punches_landed,
+            self.kicks_landed,
+            self.total_score,
+            self.best_score,
+            self.best_combo,
+            self.best_no_hit_streak,
+            self.pickups_collected,
+            self.hardcore_stages_cleared,
+        );
+        save_queue.queue(STATS_FILE, contents);
+    }
+
+    /// A random "did you know" line about a nonzero stat, for the rest screen
+    pub fn random_teaser(&self) -> Option<String> {
+        let teasers = [
+            (self.ghosts_defeated, "You have defeated {} ghosts"),
+            (self.kicks_landed, "You have kicked {} ghosts"),
+            (self.punches_landed, "You have punched {} ghosts"),
+            (
+                self.games_played,
+                "You have fought {} battles in the graveyard",
+            ),
+            (self.total_score, "You have scored {} points in total"),
+            (self.best_score, "Your best score is {}"),
+            (self.best_combo, "Your longest combo is {} hits"),
+            (
+                self.best_no_hit_streak,
+                "You once defeated {} ghosts in a row without getting hit",
+            ),
+            (self.pickups_collected, "You have picked up {} items"),
+        ];
+        let available: Vec<_> = teasers.iter().filter(|(value, _)| *value > 0).collect();
+        if available.is_empty() {
+            return None;
+        }
+
+        let (value, template) = available[rand::thread_rng().gen_range(0..available.len())];
+        Some(template.replace("{}", &with_thousands(*value)))
+    }
+}
+
+/// Format a number with comma thousands separators (4312 -> "4,312")
+fn with_thousands(value: u64) -> String {
+    let digits = value.to_string();
+    let mut formatted = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            formatted.push(',');
+        }
+        formatted.push(digit);
+    }
+    formatted
+}
@@ -0,0 +1,18 @@
+pub mod bestiary;
+pub mod leaderboard;
+pub mod lifetime;
+pub mod plugin;
+pub mod records;
+pub mod systems;
+pub mod unlocks;
+pub mod whiff_punish;
+
+// Re-export commonly used items
+pub use bestiary::*;
+pub use leaderboard::*;
+pub use lifetime::*;
+pub use plugin::*;
+pub use records::*;
+pub use systems::*;
+pub use unlocks::*;
+pub use whiff_punish::*;
@@ -0,0 +1,50 @@
+use crate::game::{AppState, GameSet, InGame};
+use crate::settings::{scored_mode, training_mode};
+use crate::stats::bestiary::Bestiary;
+use crate::stats::leaderboard::Leaderboard;
+use crate::stats::lifetime::LifetimeStats;
+use crate::stats::records::{PersonalBestEvent, RunRecords};
+use crate::stats::systems::{
+    record_game_played, record_high_score, start_run_records, start_whiff_punish_stats,
+    track_lifetime_stats, track_personal_records, track_whiff_punishes,
+};
+use crate::stats::whiff_punish::WhiffPunishStats;
+use bevy::prelude::*;
+
+/// Saved lifetime stats, leaderboard, bestiary, and per-run personal records
+/// (not in training), plus the training session's whiff-punish grades
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(LifetimeStats::load())
+            .insert_resource(Leaderboard::load())
+            .insert_resource(Bestiary::load())
+            .init_resource::<RunRecords>()
+            .init_resource::<WhiffPunishStats>()
+            .add_message::<PersonalBestEvent>()
+            .add_systems(
+                OnEnter(InGame),
+                (
+                    start_run_records,
+                    start_whiff_punish_stats.run_if(training_mode),
+                ),
+            )
+            .add_systems(
+                OnEnter(AppState::GameOver),
+                (record_game_played, record_high_score)
+                    .chain()
+                    .run_if(scored_mode),
+            )
+            .add_systems(
+                Update,
+                (
+                    (track_lifetime_stats, track_personal_records)
+                        .chain()
+                        .run_if(scored_mode),
+                    track_whiff_punishes.run_if(training_mode),
+                )
+                    .in_set(GameSet::DamageReactions),
+            );
+    }
+}
@@ -0,0 +1,72 @@
+use crate::stats::lifetime::LifetimeStats;
+use bevy::prelude::*;
+
+/// A personal record that can be beaten mid-run
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PersonalRecord {
+    /// Most hits in a single combo
+    BestCombo,
+    /// Most ghosts defeated in a row without taking an unblocked hit
+    NoHitStreak,
+}
+
+/// Sent the first time in a run that a saved personal record is beaten
+#[derive(Message)]
+pub struct PersonalBestEvent {
+    pub record: PersonalRecord,
+}
+
+/// Live record tracking for the HUD: the current no-hit streak, the session's
+/// best combo, and the saved records this run is trying to beat
+#[derive(Resource, Default)]
+pub struct RunRecords {
+    /// Ghosts defeated since the player last took an unblocked hit
+    pub no_hit_streak: u64,
+    /// Longest combo since the game was launched (kept across runs)
+    pub session_best_combo: u32,
+    /// Saved best combo when this run started
+    combo_to_beat: u64,
+    /// Saved best no-hit streak when this run started
+    streak_to_beat: u64,
+    combo_beaten: bool,
+    streak_beaten: bool,
+}
+
+impl RunRecords {
+    /// Reset the per-run streak and snapshot the saved records to beat
+    pub fn start_run(&mut self, stats: &LifetimeStats) {
+        self.no_hit_streak = 0;
+        self.combo_to_beat = stats.best_combo;
+        self.streak_to_beat = stats.best_no_hit_streak;
+        self.combo_beaten = false;
+        self.streak_beaten = false;
+    }
+
+    /// Record a combo length; true the first time this run it beats the saved best
+    ///
+    /// A record of zero (nothing saved yet) can't be "beaten", so a first game
+    /// doesn't celebrate every hit.
+    pub fn observe_combo(&mut self, hits: u32) -> bool {
+        self.session_best_combo = self.session_best_combo.max(hits);
+        let beaten =
+            !self.combo_beaten && self.combo_to_beat > 0 && hits as u64 > self.combo_to_beat;
+        self.combo_beaten |= beaten;
+        beaten
+    }
+
+    /// Extend the no-hit streak by one defeated ghost; true the first time this
+    /// run it beats the saved best
+    pub fn add_no_hit_kill(&mut self) -> bool {
+        self.no_hit_streak += 1;
+        let beaten = !self.streak_beaten
+            && self.streak_to_beat > 0
+            && self.no_hit_streak > self.streak_to_beat;
+        self.streak_beaten |= beaten;
+        beaten
+    }
+
+    /// The player took an unblocked hit
+    pub fn break_streak(&mut self) {
+        self.no_hit_streak = 0;
+    }
+}
@@ -0,0 +1,158 @@
+use crate::combat::{ComboCounter, DamageEvent, EnemyDefeatedEvent};
+use crate::enemy::EnemyArchetype;
+use crate::game::{GameState, GameplayEvent};
+use crate::persistence::{SaveQueue, unix_timestamp};
+use crate::player::{Player, PlayerState, PlayerStateType};
+use crate::settings::{GameMode, GameSpeed, Mutators};
+use crate::stats::bestiary::Bestiary;
+use crate::stats::leaderboard::{Leaderboard, LeaderboardEntry};
+use crate::stats::lifetime::LifetimeStats;
+use crate::stats::records::{PersonalBestEvent, PersonalRecord, RunRecords};
+use crate::stats::whiff_punish::WhiffPunishStats;
+use bevy::prelude::*;
+
+/// Count landed player attacks, collected pickups, defeated ghosts, and stages
+/// cleared on hardcore into the lifetime stats and bestiary
+pub fn track_lifetime_stats(
+    mut gameplay_events: MessageReader<GameplayEvent>,
+    mut defeated_events: MessageReader<EnemyDefeatedEvent>,
+    archetype_query: Query<&EnemyArchetype>,
+    mutators: Res<Mutators>,
+    mut stats: ResMut<LifetimeStats>,
+    mut bestiary: ResMut<Bestiary>,
+) {
+    for event in gameplay_events.read() {
+        match event {
+            GameplayEvent::DamageDealt {
+                attack: Some(attack),
+                ..
+            } => match attack {
+                PlayerStateType::Punch
+                | PlayerStateType::PunchCombo
+                | PlayerStateType::JumpPunch => stats.punches_landed += 1,
+                PlayerStateType::Kick
+                | PlayerStateType::KickCombo
+                | PlayerStateType::PunchKickCombo
+                | PlayerStateType::JumpKick => stats.kicks_landed += 1,
+                _ => {}
+            },
+            GameplayEvent::PickupCollected { .. } => stats.pickups_collected += 1,
+            GameplayEvent::StageCleared { .. } if mutators.hardcore => {
+                stats.hardcore_stages_cleared += 1;
+            }
+            _ => {}
+        }
+    }
+
+    for event in defeated_events.read() {
+        stats.ghosts_defeated += 1;
+        if let Ok(&archetype) = archetype_query.get(event.enemy) {
+            bestiary.add_kill(archetype);
+        }
+    }
+}
+
+/// Record the finished game and save the lifetime stats and bestiary kill counts
+///
+/// Runs on entering GameOver.
+pub fn record_game_played(
+    game_state: Res<GameState>,
+    mut stats: ResMut<LifetimeStats>,
+    bestiary: Res<Bestiary>,
+    mut save_queue: ResMut<SaveQueue>,
+) {
+    let score = game_state.score as u64;
+    stats.games_played += 1;
+    stats.total_score += score;
+    stats.best_score = stats.best_score.max(score);
+    stats.save(&mut save_queue);
+    bestiary.save(&mut save_queue);
+}
+
+/// Submit the finished game's score to the leaderboard and save it
+///
+/// Runs on entering GameOver, before the game over screen is spawned so the
+/// screen shows the updated board.
+pub fn record_high_score(
+    game_state: Res<GameState>,
+    mode: Res<GameMode>,
+    speed: Res<GameSpeed>,
+    mut leaderboard: ResMut<Leaderboard>,
+    mut save_queue: ResMut<SaveQueue>,
+) {
+    leaderboard.last_rank = leaderboard.insert(LeaderboardEntry {
+        mode: *mode,
+        speed: *speed,
+        score: game_state.score,
+        timestamp: unix_timestamp(),
+    });
+    leaderboard.save(&mut save_queue);
+}
+
+/// Track the no-hit streak and best combo, keeping the lifetime records current
+///
+/// Runs after `update_combo_counter`. An unblocked hit on the player breaks the
+/// streak; each defeated ghost extends it. Records are raised in
+/// `LifetimeStats` as they are beaten (saved with the other stats at game
+/// over), and the first time a run beats a saved record a `PersonalBestEvent`
+/// is sent for the HUD banner.
+pub fn track_personal_records(
+    mut damage_events: MessageReader<DamageEvent>,
+    mut defeated_events: MessageReader<EnemyDefeatedEvent>,
+    player_query: Query<(&PlayerState, &ComboCounter), With<Player>>,
+    mut records: ResMut<RunRecords>,
+    mut stats: ResMut<LifetimeStats>,
+    mut best_events: MessageWriter<PersonalBestEvent>,
+) {
+    let Ok((state, combo)) = player_query.single() else {
+        return;
+    };
+
+    for event in damage_events.read() {
+        if player_query.contains(event.target) && !state.is_blocking() {
+            records.break_streak();
+        }
+    }
+
+    for _ in defeated_events.read() {
+        if records.add_no_hit_kill() {
+            best_events.write(PersonalBestEvent {
+                record: PersonalRecord::NoHitStreak,
+            });
+        }
+    }
+    stats.best_no_hit_streak = stats.best_no_hit_streak.max(records.no_hit_streak);
+
+    if records.observe_combo(combo.hits) {
+        best_events.write(PersonalBestEvent {
+            record: PersonalRecord::BestCombo,
+        });
+    }
+    stats.best_combo = stats.best_combo.max(combo.hits as u64);
+}
+
+/// Start tracking records for a new run
+///
+/// Runs on entering InGame; `handle_restart` does the same for restarts.
+pub fn start_run_records(mut records: ResMut<RunRecords>, stats: Res<LifetimeStats>) {
+    records.start_run(&stats);
+}
+
+/// Count whiff-punish drill grades into the session's `WhiffPunishStats`
+pub fn track_whiff_punishes(
+    mut gameplay_events: MessageReader<GameplayEvent>,
+    mut whiff_punishes: ResMut<WhiffPunishStats>,
+) {
+    for event in gameplay_events.read() {
+        if let GameplayEvent::WhiffPunishGraded(grade) = event {
+            whiff_punishes.record(*grade);
+        }
+    }
+}
+
+/// Clear the whiff-punish grades for a new training session
+///
+/// Runs on entering InGame in training mode.
+pub fn start_whiff_punish_stats(mut whiff_punishes: ResMut<WhiffPunishStats>) {
+    *whiff_punishes = WhiffPunishStats::default();
+}
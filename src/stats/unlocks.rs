@@ -0,0 +1,48 @@
+use crate::stats::lifetime::LifetimeStats;
+
+/// Lifetime progress that unlocks an extra (gallery art, soundtrack tracks) or a costume
+///
+/// Checked against the saved `LifetimeStats`, so unlocks carry across runs
+/// without a save file of their own.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Unlock {
+    /// Available from the start
+    Always,
+    GamesPlayed(u64),
+    GhostsDefeated(u64),
+    /// Land a combo of at least this many hits
+    BestCombo(u64),
+    /// Score at least this much in one game
+    BestScore(u64),
+    /// Clear this many stages with the hardcore mutator on
+    HardcoreStagesCleared(u64),
+}
+
+impl Unlock {
+    /// Has the player reached it?
+    pub fn is_met(self, stats: &LifetimeStats) -> bool {
+        match self {
+            Unlock::Always => true,
+            Unlock::GamesPlayed(games) => stats.games_played >= games,
+            Unlock::GhostsDefeated(ghosts) => stats.ghosts_defeated >= ghosts,
+            Unlock::BestCombo(hits) => stats.best_combo >= hits,
+            Unlock::BestScore(score) => stats.best_score >= score,
+            Unlock::HardcoreStagesCleared(stages) => stats.hardcore_stages_cleared >= stages,
+        }
+    }
+
+    /// What the player still has to do, shown on locked extras
+    pub fn hint(self) -> String {
+        match self {
+            Unlock::Always => "Unlocked".to_string(),
+            Unlock::GamesPlayed(games) => format!("Play {games} games to unlock"),
+            Unlock::GhostsDefeated(ghosts) => format!("Defeat {ghosts} ghosts to unlock"),
+            Unlock::BestCombo(hits) => format!("Land a {hits}-hit combo to unlock"),
+            Unlock::BestScore(score) => format!("Score {score} in one game to unlock"),
+            Unlock::HardcoreStagesCleared(1) => "Clear a stage on hardcore to unlock".to_string(),
+            Unlock::HardcoreStagesCleared(stages) => {
+                format!("Clear {stages} stages on hardcore to unlock")
+            }
+        }
+    }
+}
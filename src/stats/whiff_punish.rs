@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+
+/// How one whiff-punish chance ended
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PunishGrade {
+    /// Hit the enemy during its recovery, this many seconds after the whiff
+    Punished(f32),
+    /// The recovery ran out before a hit landed
+    TooSlow,
+}
+
+/// Whiff-punish drill grades for the current training session
+///
+/// Filled from `GameplayEvent::WhiffPunishGraded` by `track_whiff_punishes`,
+/// and cleared when a training game starts. A chance is every swipe that
+/// whiffs; swipes that connect, or that the player stuffs during the wind-up,
+/// don't count.
+#[derive(Resource, Default)]
+pub struct WhiffPunishStats {
+    /// Swipes that whiffed
+    pub chances: u32,
+    /// Whiffs punished before the recovery ran out
+    pub punishes: u32,
+    /// Sum of the punish reaction times, in seconds
+    total_reaction: f32,
+    /// Fastest punish, in seconds
+    pub best_reaction: Option<f32>,
+    /// How the latest chance ended
+    pub last: Option<PunishGrade>,
+}
+
+impl WhiffPunishStats {
+    /// Count one graded chance
+    pub fn record(&mut self, grade: PunishGrade) {
+        self.chances += 1;
+        if let PunishGrade::Punished(reaction) = grade {
+            self.punishes += 1;
+            self.total_reaction += reaction;
+            self.best_reaction = Some(
+                self.best_reaction
+                    .map_or(reaction, |best| best.min(reaction)),
+            );
+        }
+        self.last = Some(grade);
+    }
+
+    /// Percentage of whiffs punished (None before the first whiff)
+    pub fn success_rate(&self) -> Option<u32> {
+        (self.chances > 0).then(|| self.punishes * 100 / self.chances)
+    }
+
+    /// Average punish reaction time in seconds (None before the first punish)
+    pub fn average_reaction(&self) -> Option<f32> {
+        (self.punishes > 0).then(|| self.total_reaction / self.punishes as f32)
+    }
+
+    /// One-line session summary for the training overlay
+    pub fn summary(&self) -> String {
+        let mut summary = format!("Whiff punishes {}/{}", self.punishes, self.chances);
+        if let Some(rate) = self.success_rate() {
+            summary.push_str(&format!(" ({rate}%)"));
+        }
+        if let (Some(average), Some(best)) = (self.average_reaction(), self.best_reaction) {
+            summary.push_str(&format!("  avg {average:.2}s  best {best:.2}s"));
+        }
+        match self.last {
+            Some(PunishGrade::Punished(reaction)) => {
+                summary.push_str(&format!("  last: {reaction:.2}s"));
+            }
+            Some(PunishGrade::TooSlow) => summary.push_str("  last: too slow"),
+            None => {}
+        }
+        summary
+    }
+}
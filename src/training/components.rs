@@ -0,0 +1,65 @@
+use crate::training::drill::WhiffDrill;
+use bevy::prelude::*;
+
+/// Seconds without anyone taking damage before both fighters heal to full
+const RECOVERY_SECONDS: f32 = 2.0;
+
+/// What the training dummy does, switched with the DummyBehavior action
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DummyBehavior {
+    /// Takes every hit without moving
+    #[default]
+    StandStill,
+    /// Stands still with its guard up, so hits deal chip damage and can break the guard
+    Block,
+    /// Fights back like a regular ghost
+    Attack,
+    /// Stands still and swipes at random intervals, to practice punishing whiffs
+    WhiffDrill,
+}
+
+impl DummyBehavior {
+    /// Display name for the training overlay
+    pub fn label(self) -> &'static str {
+        match self {
+            DummyBehavior::StandStill => "Stand Still",
+            DummyBehavior::Block => "Block",
+            DummyBehavior::Attack => "Attack",
+            DummyBehavior::WhiffDrill => "Whiff Punish",
+        }
+    }
+
+    /// Next behavior in the cycle (wraps around)
+    pub fn next(self) -> Self {
+        match self {
+            DummyBehavior::StandStill => DummyBehavior::Block,
+            DummyBehavior::Block => DummyBehavior::Attack,
+            DummyBehavior::Attack => DummyBehavior::WhiffDrill,
+            DummyBehavior::WhiffDrill => DummyBehavior::StandStill,
+        }
+    }
+}
+
+/// The enemy the player practices on in training mode
+#[derive(Component)]
+pub struct TrainingDummy;
+
+/// State of the current training game, inserted when it starts
+#[derive(Resource)]
+pub struct TrainingSession {
+    pub behavior: DummyBehavior,
+    /// Restarts whenever someone takes damage; both fighters heal to full when it finishes
+    pub recovery: Timer,
+    /// Swipe timing and grading for the whiff-punish drill
+    pub drill: WhiffDrill,
+}
+
+impl Default for TrainingSession {
+    fn default() -> Self {
+        Self {
+            behavior: DummyBehavior::default(),
+            recovery: Timer::from_seconds(RECOVERY_SECONDS, TimerMode::Once),
+            drill: WhiffDrill::default(),
+        }
+    }
+}
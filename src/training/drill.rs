@@ -0,0 +1,133 @@
+use crate::combat::{HitLandedEvent, Stunned};
+use crate::enemy::EnemyState;
+use crate::game::GameplayEvent;
+use crate::player::Player;
+use crate::stats::PunishGrade;
+use crate::training::components::{DummyBehavior, TrainingDummy, TrainingSession};
+use crate::ui::{TextStyles, score_popup};
+use bevy::prelude::*;
+use bevy::time::Stopwatch;
+use rand::Rng;
+use std::ops::Range;
+
+/// Seconds the drill dummy waits between swipes
+const SWIPE_INTERVAL: Range<f32> = 1.5..3.5;
+
+/// Where the drill dummy's latest swipe stands for grading
+#[derive(Default)]
+enum WhiffChance {
+    /// No whiffed swipe to punish
+    #[default]
+    Closed,
+    /// The dummy is recovering from a whiff, for this long so far
+    Open(Stopwatch),
+    /// This recovery was graded; waiting for it to end
+    Graded,
+}
+
+/// Whiff-punish drill state for the training session
+#[derive(Default)]
+pub struct WhiffDrill {
+    /// Time until the next swipe, rolled once the dummy is back in `Move`
+    next_swipe: Option<Timer>,
+    chance: WhiffChance,
+}
+
+/// Have the drill dummy swipe at the player at random intervals
+///
+/// Runs in `Attacks` in training games while the dummy behavior is the
+/// whiff-punish drill. The swipe is a regular enemy swipe: the dummy turns
+/// toward the player and enters `EnemyState::windup`, and `update_enemy_states`
+/// and `detect_enemy_swipes` play it out.
+#[allow(clippy::type_complexity)]
+pub fn run_whiff_drill(
+    time: Res<Time>,
+    mut session: ResMut<TrainingSession>,
+    player_query: Query<&Transform, With<Player>>,
+    mut dummy_query: Query<
+        (&Transform, &mut EnemyState, &mut Sprite),
+        (With<TrainingDummy>, Without<Stunned>),
+    >,
+) {
+    if session.behavior != DummyBehavior::WhiffDrill {
+        return;
+    }
+    let (Ok(player_transform), Ok((transform, mut state, mut sprite))) =
+        (player_query.single(), dummy_query.single_mut())
+    else {
+        return;
+    };
+    if !state.is_active() {
+        return;
+    }
+
+    let next_swipe = session.drill.next_swipe.get_or_insert_with(|| {
+        let delay = rand::thread_rng().gen_range(SWIPE_INTERVAL);
+        Timer::from_seconds(delay, TimerMode::Once)
+    });
+    if next_swipe.tick(time.delta()).is_finished() {
+        session.drill.next_swipe = None;
+        sprite.flip_x = player_transform.translation.x < transform.translation.x;
+        *state = EnemyState::windup();
+    }
+}
+
+/// Grade the drill's whiff-punish chances
+///
+/// Each time the dummy recovers from a whiffed swipe (`EnemyState::Recovery`
+/// with `whiffed`), a player hit on it before the recovery ends is a punish,
+/// timed from the whiff; a recovery that runs out first is too slow. Runs in
+/// `DamageReactions`, after `handle_damage_events`, and publishes each grade
+/// as `GameplayEvent::WhiffPunishGraded` for `WhiffPunishStats`, with a popup
+/// over the dummy.
+#[allow(clippy::too_many_arguments)]
+pub fn grade_whiff_punishes(
+    mut commands: Commands,
+    time: Res<Time>,
+    styles: Res<TextStyles>,
+    mut session: ResMut<TrainingSession>,
+    mut hit_landed_events: MessageReader<HitLandedEvent>,
+    mut gameplay_events: MessageWriter<GameplayEvent>,
+    player_query: Query<(), With<Player>>,
+    dummy_query: Query<(Entity, &Transform, &EnemyState), With<TrainingDummy>>,
+) {
+    let Ok((dummy, transform, state)) = dummy_query.single() else {
+        return;
+    };
+    let punished = hit_landed_events
+        .read()
+        .any(|event| event.target == dummy && player_query.contains(event.attacker));
+    // A punish that knocks the dummy down has already ended the recovery
+    let whiffed = matches!(state, EnemyState::Recovery { whiffed: true, .. });
+
+    let drill = &mut session.drill;
+    if whiffed && matches!(drill.chance, WhiffChance::Closed) {
+        drill.chance = WhiffChance::Open(Stopwatch::new());
+    }
+    let grade = match &mut drill.chance {
+        WhiffChance::Open(since_whiff) if punished => {
+            Some(PunishGrade::Punished(since_whiff.elapsed_secs()))
+        }
+        WhiffChance::Open(_) if !whiffed => Some(PunishGrade::TooSlow),
+        WhiffChance::Open(since_whiff) => {
+            since_whiff.tick(time.delta());
+            None
+        }
+        WhiffChance::Graded if !whiffed => {
+            drill.chance = WhiffChance::Closed;
+            None
+        }
+        WhiffChance::Closed | WhiffChance::Graded => None,
+    };
+    let Some(grade) = grade else {
+        return;
+    };
+
+    drill.chance = WhiffChance::Graded;
+    gameplay_events.write(GameplayEvent::WhiffPunishGraded(grade));
+    let text = match grade {
+        PunishGrade::Punished(reaction) => format!("PUNISH! {reaction:.2}s"),
+        PunishGrade::TooSlow => "TOO SLOW".to_string(),
+    };
+    commands.spawn(score_popup(&styles, text, transform.translation.truncate()));
+}
@@ -0,0 +1,41 @@
+use crate::game::{AppState, GameSet, InGame};
+use crate::settings::training_mode;
+use crate::training::drill::{grade_whiff_punishes, run_whiff_drill};
+use crate::training::systems::{
+    handle_training_input, recover_training_health, spawn_training_dummy, start_training_session,
+};
+use bevy::prelude::*;
+
+/// Training mode: the practice dummy, its behavior switch, instant resets, and health recovery
+///
+/// The whiff-punish drill has the dummy swipe on its own timer and grades how
+/// fast the player punishes each whiff; `StatsPlugin` keeps the tallies.
+///
+/// Only runs in training games; the arcade timer and spawning are switched off
+/// by their own plugins (`wave_mode`).
+pub struct TrainingPlugin;
+
+impl Plugin for TrainingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(InGame),
+            start_training_session.run_if(training_mode),
+        )
+        .add_systems(
+            Update,
+            (
+                handle_training_input
+                    .run_if(in_state(AppState::Playing))
+                    .in_set(GameSet::PlayerControl),
+                (recover_training_health, grade_whiff_punishes).in_set(GameSet::DamageReactions),
+                run_whiff_drill
+                    .run_if(in_state(AppState::Playing))
+                    .in_set(GameSet::Attacks),
+                spawn_training_dummy
+                    .run_if(in_state(AppState::Playing))
+                    .in_set(GameSet::Spawning),
+            )
+                .run_if(training_mode),
+        );
+    }
+}
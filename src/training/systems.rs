@@ -0,0 +1,104 @@
+use crate::combat::{Guard, Health, HitLandedEvent};
+use crate::enemy::Passive;
+use crate::game::RestartGameEvent;
+use crate::input::{InputAction, InputMap};
+use crate::player::Player;
+use crate::prefab::{Prefab, PrefabSpawner};
+use crate::settings::Mutators;
+use crate::stats::Leaderboard;
+use crate::training::components::{DummyBehavior, TrainingDummy, TrainingSession};
+use crate::training::drill::WhiffDrill;
+use bevy::prelude::*;
+
+/// Where the dummy appears, across from the player's starting spot
+const DUMMY_START: Vec2 = Vec2::new(200.0, -100.0);
+
+/// Start a fresh training session
+///
+/// Runs on entering InGame in training mode. Clears the leaderboard highlight,
+/// since training games never place on the board.
+pub fn start_training_session(mut commands: Commands, mut leaderboard: ResMut<Leaderboard>) {
+    commands.insert_resource(TrainingSession::default());
+    leaderboard.last_rank = None;
+}
+
+/// Give the dummy the components for a behavior
+///
+/// Passive dummies don't move or deal contact damage; a guard without a
+/// player state is always up, so the blocking dummy just keeps a `Guard`. The
+/// drill dummy is passive too: its swipes come from `run_whiff_drill`.
+fn apply_dummy_behavior(dummy: &mut EntityCommands, behavior: DummyBehavior) {
+    match behavior {
+        DummyBehavior::StandStill | DummyBehavior::WhiffDrill => {
+            dummy.insert(Passive).remove::<Guard>();
+        }
+        DummyBehavior::Block => {
+            dummy.insert((Passive, Guard::default()));
+        }
+        DummyBehavior::Attack => {
+            dummy.remove::<(Passive, Guard)>();
+        }
+    }
+}
+
+/// Spawn the dummy whenever there isn't one (at the start, after a reset, or after a knockout)
+pub fn spawn_training_dummy(
+    mut prefabs: PrefabSpawner,
+    mutators: Res<Mutators>,
+    session: Res<TrainingSession>,
+    dummy_query: Query<(), With<TrainingDummy>>,
+) {
+    if !dummy_query.is_empty() {
+        return;
+    }
+
+    let position = Vec2::new(mutators.mirror_x(DUMMY_START.x), DUMMY_START.y);
+    let mut dummy = prefabs.spawn(Prefab::TrainingDummy, position);
+    apply_dummy_behavior(&mut dummy, session.behavior);
+}
+
+/// Switch the dummy's behavior (DummyBehavior action) or reset the exchange
+/// (TrainingReset action)
+///
+/// A reset is a regular restart: `handle_restart` puts the player back and
+/// clears the arena, and `spawn_training_dummy` brings in a fresh dummy. It
+/// also drops any whiff-punish chance the old dummy left open.
+pub fn handle_training_input(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    mut session: ResMut<TrainingSession>,
+    dummy_query: Query<Entity, With<TrainingDummy>>,
+    mut restart_events: MessageWriter<RestartGameEvent>,
+) {
+    if input_map.just_pressed(InputAction::DummyBehavior, &keyboard) {
+        session.behavior = session.behavior.next();
+        for dummy in dummy_query.iter() {
+            apply_dummy_behavior(&mut commands.entity(dummy), session.behavior);
+        }
+    }
+    if input_map.just_pressed(InputAction::TrainingReset, &keyboard) {
+        session.drill = WhiffDrill::default();
+        restart_events.write(RestartGameEvent);
+    }
+}
+
+/// Heal the player and the dummy to full once nobody has taken damage for a moment
+#[allow(clippy::type_complexity)]
+pub fn recover_training_health(
+    time: Res<Time>,
+    mut session: ResMut<TrainingSession>,
+    mut hit_landed_events: MessageReader<HitLandedEvent>,
+    mut health_query: Query<&mut Health, Or<(With<Player>, With<TrainingDummy>)>>,
+) {
+    if hit_landed_events.read().count() > 0 {
+        session.recovery.reset();
+    }
+
+    session.recovery.tick(time.delta());
+    if session.recovery.just_finished() {
+        for mut health in health_query.iter_mut() {
+            health.current = health.max;
+        }
+    }
+}
@@ -0,0 +1,298 @@
+use crate::game::AppState;
+use crate::input::{
+    DeviceSettings, GamepadProfiles, InputMap, PlayerActions, export_binding_profiles,
+    import_binding_profiles,
+};
+use crate::persistence::SaveQueue;
+use crate::settings::{
+    AudioSettings, DisplaySettings, ParticleBudget, SimulationSettings, next_volume, volume_label,
+};
+use crate::ui::text_style::{TextRole, TextStyles};
+use crate::ui::widgets::{menu_root_node, menu_title, set_button_label, spawn_menu_button};
+use bevy::prelude::*;
+
+/// Action performed by an advanced settings screen button
+#[derive(Component, Clone, Copy)]
+pub enum AdvancedButton {
+    /// Cycles the physics tick rate
+    TickRate,
+    /// Toggles render interpolation
+    Interpolation,
+    /// Cycles the particle budget
+    Particles,
+    /// Toggles pausing when a gamepad disconnects
+    DisconnectPause,
+    /// Toggles the readable (dyslexia-friendly) font
+    Font,
+    /// Toggles fullscreen
+    Display,
+    /// Cycles the windowed resolution
+    Resolution,
+    /// Cycles the master volume
+    Volume,
+    /// Cycles the music volume
+    Music,
+    /// Writes the keyboard and gamepad binding profiles to files
+    ExportBindings,
+    /// Reads binding profiles back from those files
+    ImportBindings,
+    Back,
+}
+
+/// Label shown on the tick rate button
+fn tick_rate_label(settings: SimulationSettings) -> String {
+    format!("Tick Rate: {}", settings.tick_rate.label())
+}
+
+/// Label shown on the interpolation button
+fn interpolation_label(settings: SimulationSettings) -> String {
+    let state = if settings.interpolation { "On" } else { "Off" };
+    format!("Smoothing: {state}")
+}
+
+/// Label shown on the particle budget button
+fn particles_label(budget: ParticleBudget) -> String {
+    format!("Particles: {}", budget.label())
+}
+
+/// Label shown on the disconnect pause button
+fn disconnect_pause_label(devices: DeviceSettings) -> String {
+    let state = if devices.pause_on_disconnect {
+        "On"
+    } else {
+        "Off"
+    };
+    format!("Pause on Disconnect: {state}")
+}
+
+/// Label shown on the font button
+fn font_label(styles: &TextStyles) -> String {
+    let font = if styles.readable() {
+        "Readable"
+    } else {
+        "Game"
+    };
+    format!("Font: {font}")
+}
+
+/// Label shown on the display mode button
+fn display_label(display: DisplaySettings) -> String {
+    format!("Display: {}", display.mode_label())
+}
+
+/// Label shown on the resolution button
+fn resolution_label(display: DisplaySettings) -> String {
+    format!("Resolution: {}", display.resolution_label())
+}
+
+/// Label shown on the master volume button
+fn volume_button_label(audio: AudioSettings) -> String {
+    format!("Volume: {}", volume_label(audio.master_volume))
+}
+
+/// Label shown on the music volume button
+fn music_label(audio: AudioSettings) -> String {
+    format!("Music: {}", volume_label(audio.music_volume))
+}
+
+/// Label shown on the export/import buttons after use ("Exported 3 Files")
+fn binding_files_label(verb: &str, files: usize) -> String {
+    match files {
+        0 => "No Binding Files Found".to_string(),
+        1 => format!("{verb} 1 File"),
+        _ => format!("{verb} {files} Files"),
+    }
+}
+
+/// Spawn the advanced settings screen (simulation tick rate, render interpolation, particle
+/// budget, disconnect pause, font, display mode and resolution, volumes, and binding
+/// profile export and import)
+///
+/// Runs on entering Advanced; the screen is despawned automatically on exit.
+pub fn spawn_advanced_screen(
+    mut commands: Commands,
+    styles: Res<TextStyles>,
+    settings: Res<SimulationSettings>,
+    budget: Res<ParticleBudget>,
+    devices: Res<DeviceSettings>,
+    display: Res<DisplaySettings>,
+    audio: Res<AudioSettings>,
+) {
+    commands
+        .spawn((
+            menu_root_node(),
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+            DespawnOnExit(AppState::Advanced),
+        ))
+        .with_children(|parent| {
+            parent.spawn(menu_title(&styles, "ADVANCED"));
+            parent.spawn((
+                Text::new(
+                    "Lower tick rates and particle budgets are easier on slow machines; smoothing draws motion between physics steps; the readable font is easier on dyslexic players",
+                ),
+                styles.font(TextRole::Detail),
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+            ));
+            // Two columns, so every option fits on a 720p screen
+            parent
+                .spawn(Node {
+                    width: Val::Px(540.0),
+                    flex_wrap: FlexWrap::Wrap,
+                    justify_content: JustifyContent::Center,
+                    column_gap: Val::Px(20.0),
+                    row_gap: Val::Px(12.0),
+                    ..default()
+                })
+                .with_children(|grid| {
+                    spawn_menu_button(
+                        grid,
+                        &styles,
+                        &tick_rate_label(*settings),
+                        AdvancedButton::TickRate,
+                    );
+                    spawn_menu_button(
+                        grid,
+                        &styles,
+                        &interpolation_label(*settings),
+                        AdvancedButton::Interpolation,
+                    );
+                    spawn_menu_button(
+                        grid,
+                        &styles,
+                        &particles_label(*budget),
+                        AdvancedButton::Particles,
+                    );
+                    spawn_menu_button(
+                        grid,
+                        &styles,
+                        &disconnect_pause_label(*devices),
+                        AdvancedButton::DisconnectPause,
+                    );
+                    spawn_menu_button(
+                        grid,
+                        &styles,
+                        &font_label(&styles),
+                        AdvancedButton::Font,
+                    );
+                    spawn_menu_button(
+                        grid,
+                        &styles,
+                        &display_label(*display),
+                        AdvancedButton::Display,
+                    );
+                    spawn_menu_button(
+                        grid,
+                        &styles,
+                        &resolution_label(*display),
+                        AdvancedButton::Resolution,
+                    );
+                    spawn_menu_button(
+                        grid,
+                        &styles,
+                        &volume_button_label(*audio),
+                        AdvancedButton::Volume,
+                    );
+                    spawn_menu_button(
+                        grid,
+                        &styles,
+                        &music_label(*audio),
+                        AdvancedButton::Music,
+                    );
+                    spawn_menu_button(
+                        grid,
+                        &styles,
+                        "Export Bindings",
+                        AdvancedButton::ExportBindings,
+                    );
+                    spawn_menu_button(
+                        grid,
+                        &styles,
+                        "Import Bindings",
+                        AdvancedButton::ImportBindings,
+                    );
+                    spawn_menu_button(grid, &styles, "Back", AdvancedButton::Back);
+                });
+        });
+}
+
+/// Cycle the simulation settings, particle budget, disconnect pause, font, display mode,
+/// resolution, and volumes, export or import binding profiles, and return to the main
+/// menu (Back button or the Back action)
+#[allow(clippy::too_many_arguments)]
+pub fn handle_advanced_input(
+    actions: Res<PlayerActions>,
+    button_query: Query<(&Interaction, &AdvancedButton, &Children), Changed<Interaction>>,
+    mut text_query: Query<&mut Text>,
+    mut settings: ResMut<SimulationSettings>,
+    mut budget: ResMut<ParticleBudget>,
+    mut devices: ResMut<DeviceSettings>,
+    mut styles: ResMut<TextStyles>,
+    mut display: ResMut<DisplaySettings>,
+    mut audio: ResMut<AudioSettings>,
+    mut input_map: ResMut<InputMap>,
+    mut gamepad_profiles: ResMut<GamepadProfiles>,
+    mut save_queue: ResMut<SaveQueue>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if actions.back {
+        next_state.set(AppState::MainMenu);
+        return;
+    }
+
+    for (interaction, button, children) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match button {
+            AdvancedButton::TickRate => {
+                settings.tick_rate = settings.tick_rate.next();
+                set_button_label(children, &mut text_query, tick_rate_label(*settings));
+            }
+            AdvancedButton::Interpolation => {
+                settings.interpolation = !settings.interpolation;
+                set_button_label(children, &mut text_query, interpolation_label(*settings));
+            }
+            AdvancedButton::Particles => {
+                *budget = budget.next();
+                set_button_label(children, &mut text_query, particles_label(*budget));
+            }
+            AdvancedButton::DisconnectPause => {
+                devices.pause_on_disconnect = !devices.pause_on_disconnect;
+                set_button_label(children, &mut text_query, disconnect_pause_label(*devices));
+            }
+            AdvancedButton::Font => {
+                let readable = !styles.readable();
+                styles.set_readable(readable);
+                set_button_label(children, &mut text_query, font_label(&styles));
+            }
+            AdvancedButton::Display => {
+                display.fullscreen = !display.fullscreen;
+                set_button_label(children, &mut text_query, display_label(*display));
+            }
+            AdvancedButton::Resolution => {
+                display.resolution = display.next_resolution();
+                set_button_label(children, &mut text_query, resolution_label(*display));
+            }
+            AdvancedButton::Volume => {
+                audio.master_volume = next_volume(audio.master_volume);
+                set_button_label(children, &mut text_query, volume_button_label(*audio));
+            }
+            AdvancedButton::Music => {
+                audio.music_volume = next_volume(audio.music_volume);
+                set_button_label(children, &mut text_query, music_label(*audio));
+            }
+            AdvancedButton::ExportBindings => {
+                let files = export_binding_profiles(&input_map, &gamepad_profiles, &mut save_queue);
+                let label = binding_files_label("Exported", files);
+                set_button_label(children, &mut text_query, label);
+            }
+            AdvancedButton::ImportBindings => {
+                let files = import_binding_profiles(&mut input_map, &mut gamepad_profiles);
+                let label = binding_files_label("Imported", files);
+                set_button_label(children, &mut text_query, label);
+            }
+            AdvancedButton::Back => next_state.set(AppState::MainMenu),
+        }
+    }
+}
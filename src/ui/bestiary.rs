@@ -0,0 +1,171 @@
+use crate::combat::{MAX_RESISTANCE, mitigate_damage};
+use crate::enemy::EnemyArchetype;
+use crate::game::AppState;
+use crate::input::PlayerActions;
+use crate::settings::Difficulty;
+use crate::stats::Bestiary;
+use crate::ui::text_style::{TextRole, TextStyles};
+use crate::ui::widgets::{menu_root_node, menu_title, spawn_menu_button};
+use bevy::prelude::*;
+
+/// Kills of an archetype needed to reveal its stats
+const STATS_UNLOCK_KILLS: u64 = 5;
+/// Kills of an archetype needed to reveal its lore
+const LORE_UNLOCK_KILLS: u64 = 25;
+
+/// Action performed by a bestiary screen button
+#[derive(Component, Clone, Copy)]
+pub enum BestiaryButton {
+    Back,
+}
+
+/// Small bestiary text line
+fn entry_text(
+    styles: &TextStyles,
+    text: impl Into<String>,
+    role: TextRole,
+    color: Color,
+) -> impl Bundle {
+    (
+        Text::new(text),
+        styles.font(role),
+        TextColor(color),
+        TextLayout::new_with_justify(Justify::Center),
+    )
+}
+
+/// Spawn the bestiary screen listing every archetype
+///
+/// Unseen archetypes are hidden. Seen ones show their kill count and threat,
+/// then their stats and lore once enough have been defeated. Runs on entering
+/// Bestiary; the screen is despawned automatically on exit.
+pub fn spawn_bestiary_screen(
+    mut commands: Commands,
+    styles: Res<TextStyles>,
+    bestiary: Res<Bestiary>,
+    difficulty: Res<Difficulty>,
+) {
+    let muted = Color::srgb(0.6, 0.6, 0.6);
+
+    commands
+        .spawn((
+            menu_root_node(),
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+            DespawnOnExit(AppState::Bestiary),
+        ))
+        .with_children(|parent| {
+            parent.spawn(menu_title(&styles, "BESTIARY"));
+
+            for archetype in EnemyArchetype::ALL {
+                parent
+                    .spawn(Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        max_width: Val::Percent(70.0),
+                        row_gap: Val::Px(4.0),
+                        ..default()
+                    })
+                    .with_children(|entry| {
+                        if !bestiary.is_seen(archetype) {
+                            entry.spawn(entry_text(&styles, "???", TextRole::Emphasis, muted));
+                            entry.spawn(entry_text(
+                                &styles,
+                                "Not yet encountered",
+                                TextRole::Detail,
+                                muted,
+                            ));
+                            return;
+                        }
+
+                        let kills = bestiary.kills(archetype);
+                        entry.spawn(entry_text(
+                            &styles,
+                            format!("{} - defeated {}", archetype.name(), kills),
+                            TextRole::Emphasis,
+                            Color::srgb(1.0, 0.4, 0.2),
+                        ));
+                        entry.spawn(entry_text(
+                            &styles,
+                            archetype.threat(),
+                            TextRole::Detail,
+                            Color::WHITE,
+                        ));
+
+                        if kills < STATS_UNLOCK_KILLS {
+                            entry.spawn(entry_text(
+                                &styles,
+                                format!(
+                                    "Defeat {} more to reveal its stats",
+                                    STATS_UNLOCK_KILLS - kills
+                                ),
+                                TextRole::Small,
+                                muted,
+                            ));
+                            return;
+                        }
+                        entry.spawn(entry_text(
+                            &styles,
+                            format!(
+                                "Health {} ({})  |  {}  |  {}",
+                                difficulty.enemy_health(),
+                                difficulty.label(),
+                                archetype.attack(),
+                                archetype.movement()
+                            ),
+                            TextRole::Detail,
+                            Color::srgb(0.85, 0.85, 0.85),
+                        ));
+                        // Effective mitigation against a punch (2) and a kick (3)
+                        let (armor, resistance) = (archetype.armor(), archetype.resistance());
+                        entry.spawn(entry_text(
+                            &styles,
+                            format!(
+                                "Armor {}  |  Resistance {:.0}%  |  Punch hits for {}, kick for {}",
+                                armor,
+                                resistance.clamp(0.0, MAX_RESISTANCE) * 100.0,
+                                mitigate_damage(2, armor, resistance),
+                                mitigate_damage(3, armor, resistance)
+                            ),
+                            TextRole::Small,
+                            Color::srgb(0.85, 0.85, 0.85),
+                        ));
+
+                        if kills < LORE_UNLOCK_KILLS {
+                            entry.spawn(entry_text(
+                                &styles,
+                                format!(
+                                    "Defeat {} more to reveal its lore",
+                                    LORE_UNLOCK_KILLS - kills
+                                ),
+                                TextRole::Small,
+                                muted,
+                            ));
+                        } else {
+                            entry.spawn(entry_text(
+                                &styles,
+                                archetype.flavor(),
+                                TextRole::Small,
+                                Color::srgb(1.0, 0.85, 0.2),
+                            ));
+                        }
+                    });
+            }
+
+            spawn_menu_button(parent, &styles, "Back", BestiaryButton::Back);
+        });
+}
+
+/// Return to the main menu via the Back button or the Back action (Escape / gamepad East)
+pub fn handle_bestiary_input(
+    actions: Res<PlayerActions>,
+    button_query: Query<(&Interaction, &BestiaryButton), Changed<Interaction>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let back_pressed = button_query.iter().any(|(interaction, button)| {
+        matches!(button, BestiaryButton::Back) && *interaction == Interaction::Pressed
+    });
+
+    if back_pressed || actions.back {
+        next_state.set(AppState::MainMenu);
+    }
+}
@@ -0,0 +1,179 @@
+use crate::game::AppState;
+use crate::input::PlayerActions;
+use crate::player::{CharacterDefinition, Characters, Fighter, PlayerState, PlayerStateType};
+use crate::settings::GameMode;
+use crate::stats::LifetimeStats;
+use crate::ui::text_style::{TextRole, TextStyles};
+use crate::ui::widgets::{menu_root_node, set_button_label, spawn_menu_button};
+use bevy::prelude::*;
+
+/// Action performed by a character select screen button
+#[derive(Component, Clone, Copy)]
+pub enum CharacterSelectButton {
+    /// Picks this fighter for the player choosing
+    Pick(Fighter),
+    /// Cycles the costume of the player choosing
+    Costume,
+    Back,
+}
+
+/// Root of the character select screen, tracking who is choosing
+#[derive(Component, Default)]
+pub struct CharacterSelectScreen {
+    /// Player 1 has picked and player 2 is choosing (versus games only)
+    player_two_choosing: bool,
+}
+
+/// Title naming the player who is choosing
+#[derive(Component)]
+pub struct CharacterSelectTitle;
+
+/// Title for the player who is choosing
+fn choosing_title(game_mode: GameMode, player_two: bool) -> &'static str {
+    match (game_mode, player_two) {
+        (GameMode::Versus, false) => "PLAYER 1: CHOOSE YOUR FIGHTER",
+        (GameMode::Versus, true) => "PLAYER 2: CHOOSE YOUR FIGHTER",
+        _ => "CHOOSE YOUR FIGHTER",
+    }
+}
+
+/// Label shown on the costume button for the player choosing
+fn costume_label(characters: &Characters, player_two: bool) -> String {
+    format!("Costume: {}", characters.costume(player_two).name)
+}
+
+/// Speed, jump, and basic hit damage of a fighter, under its description
+fn stats_line(character: &CharacterDefinition) -> String {
+    let damage =
+        |state_type: PlayerStateType| character.damage(&PlayerState::transition_to(state_type));
+    format!(
+        "Speed {:.0}%  |  Jump {:.0}  |  Punch {}, kick {}, super {}",
+        character.speed * 100.0,
+        character.jump_force,
+        damage(PlayerStateType::Punch),
+        damage(PlayerStateType::Kick),
+        damage(PlayerStateType::Super)
+    )
+}
+
+/// Spawn the character select screen: a button per fighter in assets/characters.txt,
+/// each with its description and stats, and a costume button
+///
+/// Runs on entering CharacterSelect, after a mode is picked on the main menu;
+/// the screen is despawned automatically on exit.
+pub fn spawn_character_select_screen(
+    mut commands: Commands,
+    styles: Res<TextStyles>,
+    characters: Res<Characters>,
+    game_mode: Res<GameMode>,
+) {
+    commands
+        .spawn((
+            menu_root_node(),
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+            CharacterSelectScreen::default(),
+            DespawnOnExit(AppState::CharacterSelect),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(choosing_title(*game_mode, false)),
+                styles.font(TextRole::Title),
+                TextColor(Color::WHITE),
+                CharacterSelectTitle,
+            ));
+
+            for (fighter, character) in characters.iter() {
+                spawn_menu_button(
+                    parent,
+                    &styles,
+                    &character.name,
+                    CharacterSelectButton::Pick(fighter),
+                );
+                parent.spawn((
+                    Text::new(character.description.clone()),
+                    styles.font(TextRole::Detail),
+                    TextColor(Color::WHITE),
+                ));
+                parent.spawn((
+                    Text::new(stats_line(character)),
+                    styles.font(TextRole::Small),
+                    TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                ));
+            }
+
+            spawn_menu_button(
+                parent,
+                &styles,
+                &costume_label(&characters, false),
+                CharacterSelectButton::Costume,
+            );
+            spawn_menu_button(parent, &styles, "Back", CharacterSelectButton::Back);
+        });
+}
+
+/// Pick a fighter and start the game, cycle the costume, or go back (Back
+/// button or the Back action)
+///
+/// In versus games player 1 picks first, then the title and costume button
+/// switch to player 2 for their pick; backing out of player 2's pick returns
+/// to player 1's.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_character_select_input(
+    actions: Res<PlayerActions>,
+    game_mode: Res<GameMode>,
+    stats: Res<LifetimeStats>,
+    button_query: Query<(&Interaction, &CharacterSelectButton), Changed<Interaction>>,
+    label_query: Query<(&CharacterSelectButton, &Children)>,
+    mut screen_query: Query<&mut CharacterSelectScreen>,
+    title_query: Query<Entity, With<CharacterSelectTitle>>,
+    mut text_query: Query<&mut Text>,
+    mut characters: ResMut<Characters>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let Ok(mut screen) = screen_query.single_mut() else {
+        return;
+    };
+
+    let mut back = actions.back;
+    for (interaction, button) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match *button {
+            CharacterSelectButton::Pick(fighter) => {
+                characters.select(fighter, screen.player_two_choosing, &stats);
+                if *game_mode == GameMode::Versus && !screen.player_two_choosing {
+                    screen.player_two_choosing = true;
+                } else {
+                    next_state.set(AppState::Playing);
+                }
+            }
+            CharacterSelectButton::Costume => {
+                characters.select_next_costume(screen.player_two_choosing, &stats);
+            }
+            CharacterSelectButton::Back => back = true,
+        }
+    }
+
+    if back {
+        if screen.player_two_choosing {
+            screen.player_two_choosing = false;
+        } else {
+            next_state.set(AppState::MainMenu);
+        }
+    }
+    if !screen.is_changed() && !characters.is_changed() {
+        return;
+    }
+    if let Ok(title) = title_query.single()
+        && let Ok(mut text) = text_query.get_mut(title)
+    {
+        **text = choosing_title(*game_mode, screen.player_two_choosing).to_string();
+    }
+    for (button, children) in label_query.iter() {
+        if matches!(button, CharacterSelectButton::Costume) {
+            let label = costume_label(&characters, screen.player_two_choosing);
+            set_button_label(children, &mut text_query, label);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use crate::combat::ComboCounter;
+use crate::game::InGame;
+use crate::player::Player;
+use crate::ui::text_style::{TextRole, TextStyles};
+use bevy::prelude::*;
+
+/// Fewest hits shown on the combo display
+const MIN_DISPLAYED_HITS: u32 = 2;
+
+/// "N HITS!" combo display on the left side of the screen
+#[derive(Component)]
+pub struct ComboText;
+
+/// Spawn the (initially empty) combo display
+///
+/// Runs on entering InGame alongside the HUD.
+pub fn spawn_combo_display(mut commands: Commands, styles: Res<TextStyles>) {
+    commands.spawn((
+        Text::new(""),
+        styles.font(TextRole::Heading),
+        TextColor(Color::srgb(1.0, 0.6, 0.1)),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(20.0),
+            top: Val::Percent(30.0),
+            ..default()
+        },
+        ComboText,
+        DespawnOnExit(InGame),
+    ));
+}
+
+/// Show the current combo and its score multiplier, fading as the window runs out
+pub fn update_combo_display(
+    combo_query: Query<&ComboCounter, With<Player>>,
+    mut text_query: Query<(&mut Text, &mut TextColor), With<ComboText>>,
+) {
+    let (Ok(combo), Ok((mut text, mut color))) = (combo_query.single(), text_query.single_mut())
+    else {
+        return;
+    };
+
+    if combo.hits < MIN_DISPLAYED_HITS {
+        text.clear();
+        return;
+    }
+
+    let multiplier = combo.score_multiplier();
+    **text = if multiplier > 1 {
+        format!("{} HITS! x{}", combo.hits, multiplier)
+    } else {
+        format!("{} HITS!", combo.hits)
+    };
+    color.0.set_alpha(1.0 - 0.7 * combo.window.fraction());
+}
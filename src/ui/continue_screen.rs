@@ -0,0 +1,81 @@
+use crate::game::{GameState, InGame, Round, RoundPhase};
+use crate::ui::text_style::{TextRole, TextStyles};
+use crate::ui::widgets::menu_root_node;
+use bevy::prelude::*;
+
+/// Root of the continue screen, shown over the fallen player during the
+/// continue countdown
+#[derive(Component)]
+pub struct ContinueScreen;
+
+/// Seconds left to continue
+#[derive(Component)]
+pub struct ContinueCountdownText;
+
+/// How to continue, and how many continues are left
+#[derive(Component)]
+pub struct ContinuePromptText;
+
+/// Spawn the (initially hidden) continue screen
+///
+/// Runs on entering InGame alongside the HUD; `update_continue_screen` shows
+/// it during `RoundPhase::Continue`.
+pub fn spawn_continue_screen(mut commands: Commands, styles: Res<TextStyles>) {
+    commands
+        .spawn((
+            menu_root_node(),
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+            GlobalZIndex(10),
+            Visibility::Hidden,
+            ContinueScreen,
+            DespawnOnExit(InGame),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("CONTINUE?"),
+                styles.font(TextRole::Title),
+                TextColor(Color::srgb(1.0, 0.85, 0.2)),
+            ));
+            parent.spawn((
+                Text::new(""),
+                styles.font(TextRole::Countdown),
+                TextColor(Color::WHITE),
+                ContinueCountdownText,
+            ));
+            parent.spawn((
+                Text::new(""),
+                styles.font(TextRole::Body),
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                ContinuePromptText,
+            ));
+        });
+}
+
+/// Show the continue screen during the continue countdown, with the seconds
+/// and continues left
+pub fn update_continue_screen(
+    round: Res<Round>,
+    game_state: Res<GameState>,
+    mut screen_query: Query<&mut Visibility, With<ContinueScreen>>,
+    mut countdown_query: Query<&mut Text, With<ContinueCountdownText>>,
+    mut prompt_query: Query<&mut Text, (With<ContinuePromptText>, Without<ContinueCountdownText>)>,
+) {
+    let Ok(mut visibility) = screen_query.single_mut() else {
+        return;
+    };
+    if round.phase != RoundPhase::Continue {
+        *visibility = Visibility::Hidden;
+        return;
+    }
+
+    *visibility = Visibility::Inherited;
+    if let Ok(mut text) = countdown_query.single_mut() {
+        **text = round.continue_seconds_left().to_string();
+    }
+    if let Ok(mut text) = prompt_query.single_mut() {
+        **text = format!(
+            "Press Enter to Continue ({} left)",
+            game_state.continues_left
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use crate::game::{GameState, InGame};
+use crate::sound::mixer::{PlaySound, SoundChannel, SoundPriority};
+use crate::ui::text_style::{TextRole, TextStyles};
+use bevy::prelude::*;
+
+/// Seconds left on the match timer when the final rush begins
+pub const FINAL_RUSH_SECONDS: f32 = 10.0;
+/// Score gains are multiplied by this during the final rush
+pub const FINAL_RUSH_MULTIPLIER: u32 = 2;
+
+const POPUP_DURATION: f32 = 0.8;
+const POPUP_RISE_SPEED: f32 = 80.0;
+/// Volume of the final-rush countdown ticks
+const COUNTDOWN_TICK_VOLUME: f32 = 0.6;
+/// Seconds left from which the countdown ticks are pitched up
+const COUNTDOWN_URGENT_SECONDS: u32 = 3;
+/// Playback speed of the urgent countdown ticks
+const COUNTDOWN_URGENT_SPEED: f32 = 1.3;
+
+/// Big centered countdown number shown during the final rush
+#[derive(Component)]
+pub struct CountdownText;
+
+/// "FINAL RUSH x2" banner shown under the countdown
+#[derive(Component)]
+pub struct FinalRushBanner;
+
+/// Floating world-space text (score gains, damage numbers) that rises and fades out
+#[derive(Component)]
+pub struct ScorePopup {
+    pub timer: Timer,
+}
+
+/// Spawn the (initially empty) final-rush countdown overlay
+///
+/// Runs on entering InGame alongside the HUD; the texts stay empty until
+/// the final rush starts.
+pub fn spawn_countdown(mut commands: Commands, styles: Res<TextStyles>) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            DespawnOnExit(InGame),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                styles.font(TextRole::Countdown),
+                TextColor(Color::srgb(1.0, 0.85, 0.2)),
+                CountdownText,
+            ));
+            parent.spawn((
+                Text::new(""),
+                styles.font(TextRole::Subtitle),
+                TextColor(Color::srgb(1.0, 0.4, 0.2)),
+                FinalRushBanner,
+            ));
+        });
+}
+
+/// Show the remaining whole seconds during the final rush
+///
+/// Each number starts opaque and fades as its second runs out.
+pub fn update_countdown(
+    game_state: Res<GameState>,
+    mut countdown_query: Query<(&mut Text, &mut TextColor), With<CountdownText>>,
+    mut banner_query: Query<&mut Text, (With<FinalRushBanner>, Without<CountdownText>)>,
+) {
+    let active = game_state.in_final_rush();
+    let time_remaining = game_state.time_remaining();
+
+    if let Ok((mut text, mut color)) = countdown_query.single_mut() {
+        if active {
+            **text = format!("{}", time_remaining.ceil() as u32);
+            // Fraction of the current second still left (1.0 right after the number changes)
+            let second_left = time_remaining - time_remaining.ceil() + 1.0;
+            color.0.set_alpha(0.3 + 0.7 * second_left);
+        } else {
+            text.clear();
+        }
+    }
+
+    if let Ok(mut text) = banner_query.single_mut() {
+        if active {
+            **text = format!("FINAL RUSH x{}", FINAL_RUSH_MULTIPLIER);
+        } else {
+            text.clear();
+        }
+    }
+}
+
+/// Tick once for each whole second shown by the final-rush countdown,
+/// higher-pitched for the last `COUNTDOWN_URGENT_SECONDS`
+///
+/// Follows the number `update_countdown` shows, so the ticks hold while
+/// paused and never play outside the final rush.
+pub fn play_countdown_ticks(
+    asset_server: Res<AssetServer>,
+    mut sounds: MessageWriter<PlaySound>,
+    game_state: Res<GameState>,
+    mut last_second: Local<Option<u32>>,
+) {
+    if !game_state.in_final_rush() {
+        *last_second = None;
+        return;
+    }
+    let second = game_state.time_remaining().ceil() as u32;
+    if *last_second == Some(second) {
+        return;
+    }
+    *last_second = Some(second);
+
+    let speed = if second <= COUNTDOWN_URGENT_SECONDS {
+        COUNTDOWN_URGENT_SPEED
+    } else {
+        1.0
+    };
+    sounds.write(PlaySound {
+        sound: asset_server.load("sounds/countdown-tick.wav"),
+        channel: SoundChannel::Effects,
+        priority: SoundPriority::High,
+        speed,
+        volume: COUNTDOWN_TICK_VOLUME,
+    });
+}
+
+/// Floating score text bundle spawned at a world position
+pub fn score_popup(styles: &TextStyles, text: String, position: Vec2) -> impl Bundle {
+    (
+        Text2d::new(text),
+        styles.font(TextRole::Emphasis),
+        TextColor(Color::srgb(1.0, 0.85, 0.2)),
+        Transform::from_xyz(position.x, position.y, 5.0),
+        ScorePopup {
+            timer: Timer::from_seconds(POPUP_DURATION, TimerMode::Once),
+        },
+        DespawnOnExit(InGame),
+    )
+}
+
+/// Rise and fade score popups, despawning them when they expire
+pub fn update_score_popups(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut popup_query: Query<(Entity, &mut ScorePopup, &mut Transform, &mut TextColor)>,
+) {
+    for (entity, mut popup, mut transform, mut color) in popup_query.iter_mut() {
+        popup.timer.tick(time.delta());
+
+        // Purely visual, so it moves the Transform directly instead of using MoveIntent
+        transform.translation.y += POPUP_RISE_SPEED * time.delta_secs();
+        color.0.set_alpha(1.0 - popup.timer.fraction());
+
+        if popup.timer.is_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
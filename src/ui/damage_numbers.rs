@@ -0,0 +1,58 @@
+use crate::game::InGame;
+use crate::ui::countdown::ScorePopup;
+use crate::ui::text_style::{TextRole, TextStyles};
+use bevy::prelude::*;
+
+/// Seconds a damage number stays up while drifting and fading
+const DAMAGE_NUMBER_DURATION: f32 = 0.8;
+/// Height above the target's center where the number appears
+const DAMAGE_NUMBER_RISE: f32 = 60.0;
+/// Max random horizontal offset, so numbers from quick hits don't stack
+const DAMAGE_NUMBER_JITTER: f32 = 20.0;
+
+/// What kind of hit a damage number reports, which sets its color
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DamageNumberKind {
+    /// Regular hit on an enemy (white)
+    Normal,
+    /// Combo finisher landing on an enemy (yellow)
+    Finisher,
+    /// Damage taken by the player (red)
+    ToPlayer,
+    /// Burn damage ticking on a target (orange)
+    Burn,
+}
+
+impl DamageNumberKind {
+    fn color(self) -> Color {
+        match self {
+            DamageNumberKind::Normal => Color::WHITE,
+            DamageNumberKind::Finisher => Color::srgb(1.0, 0.85, 0.2),
+            DamageNumberKind::ToPlayer => Color::srgb(1.0, 0.3, 0.3),
+            DamageNumberKind::Burn => Color::srgb(1.0, 0.55, 0.1),
+        }
+    }
+}
+
+/// Floating damage number bundle spawned above a hit target
+///
+/// Reuses `ScorePopup`, so `update_score_popups` drifts it upward, fades it
+/// out, and despawns it.
+pub fn damage_number(
+    styles: &TextStyles,
+    damage: i32,
+    position: Vec2,
+    kind: DamageNumberKind,
+) -> impl Bundle {
+    let jitter = (rand::random::<f32>() * 2.0 - 1.0) * DAMAGE_NUMBER_JITTER;
+    (
+        Text2d::new(damage.to_string()),
+        styles.font(TextRole::DamageNumber),
+        TextColor(kind.color()),
+        Transform::from_xyz(position.x + jitter, position.y + DAMAGE_NUMBER_RISE, 5.0),
+        ScorePopup {
+            timer: Timer::from_seconds(DAMAGE_NUMBER_DURATION, TimerMode::Once),
+        },
+        DespawnOnExit(InGame),
+    )
+}
@@ -0,0 +1,75 @@
+use crate::combat::Health;
+use crate::enemy::Enemy;
+use bevy::prelude::*;
+
+/// Size of an enemy health bar, in world units
+const BAR_SIZE: Vec2 = Vec2::new(64.0, 7.0);
+/// Where the bar sits relative to the enemy's center (z draws it over the characters)
+const BAR_OFFSET: Vec3 = Vec3::new(0.0, 95.0, 0.5);
+
+/// World-space health bar of an enemy, shown once it's first damaged
+///
+/// Lives on the enemy and points at the bar (a child of the enemy, so it
+/// follows it and despawns with it) and the bar's fill.
+#[derive(Component)]
+pub struct EnemyHealthBar {
+    pub bar: Entity,
+    pub fill: Entity,
+}
+
+/// Give enemies a health bar the first time they lose health
+#[allow(clippy::type_complexity)]
+pub fn spawn_enemy_health_bars(
+    mut commands: Commands,
+    enemy_query: Query<(Entity, &Health), (With<Enemy>, Changed<Health>, Without<EnemyHealthBar>)>,
+) {
+    for (entity, health) in enemy_query.iter() {
+        if health.current >= health.max {
+            continue;
+        }
+
+        let fill = commands
+            .spawn((
+                Sprite::from_color(Color::srgb(0.9, 0.15, 0.15), BAR_SIZE),
+                Transform::from_xyz(0.0, 0.0, 0.01),
+            ))
+            .id();
+        let bar = commands
+            .spawn((
+                Sprite::from_color(Color::srgba(0.0, 0.0, 0.0, 0.7), BAR_SIZE),
+                Transform::from_translation(BAR_OFFSET),
+            ))
+            .add_child(fill)
+            .id();
+        commands
+            .entity(entity)
+            .add_child(bar)
+            .insert(EnemyHealthBar { bar, fill });
+    }
+}
+
+/// Size each health bar's fill to its enemy's health, and keep the bar level
+///
+/// The bar is a child of the enemy, so it undoes the enemy's scale and
+/// knockdown tilt to stay the same size and upright above it.
+pub fn update_enemy_health_bars(
+    enemy_query: Query<(&Transform, &Health, &EnemyHealthBar), With<Enemy>>,
+    mut bar_query: Query<&mut Transform, Without<Enemy>>,
+) {
+    for (enemy_transform, health, health_bar) in enemy_query.iter() {
+        let inverse_rotation = enemy_transform.rotation.inverse();
+        let inverse_scale = enemy_transform.scale.recip();
+        if let Ok(mut bar) = bar_query.get_mut(health_bar.bar) {
+            bar.translation = inverse_rotation * BAR_OFFSET * inverse_scale;
+            bar.rotation = inverse_rotation;
+            bar.scale = inverse_scale;
+        }
+
+        // Shrink the fill toward the bar's left edge
+        let fraction = (health.current as f32 / health.max.max(1) as f32).clamp(0.0, 1.0);
+        if let Ok(mut fill) = bar_query.get_mut(health_bar.fill) {
+            fill.scale.x = fraction;
+            fill.translation.x = -BAR_SIZE.x * (1.0 - fraction) / 2.0;
+        }
+    }
+}
@@ -0,0 +1,447 @@
+use crate::game::AppState;
+use crate::game::systems::BackgroundMusic;
+use crate::input::PlayerActions;
+use crate::replay::{FavoriteReplays, Snapshot, clip_length, load_favorite_frames, snapshot_at};
+use crate::stats::{LifetimeStats, Unlock, format_date};
+use crate::ui::highlight_reel::{ReplaySprite, spawn_snapshot_sprites};
+use crate::ui::text_style::{TextRole, TextStyles};
+use crate::ui::widgets::{menu_root_node, menu_title, set_button_label, spawn_menu_button};
+use bevy::prelude::*;
+
+/// Size of the gallery frame, in pixels
+const GALLERY_FRAME: Vec2 = Vec2::new(480.0, 270.0);
+
+/// A piece of art in the extras gallery
+pub struct GalleryArt {
+    pub title: &'static str,
+    /// Image path under assets/
+    pub path: &'static str,
+    pub unlock: Unlock,
+}
+
+/// Gallery art, in display order
+pub const GALLERY: [GalleryArt; 5] = [
+    GalleryArt {
+        title: "The Graveyard",
+        path: "graveyard.png",
+        unlock: Unlock::Always,
+    },
+    GalleryArt {
+        title: "Ghost Studies",
+        path: "enemies/ghost-sheet.png",
+        unlock: Unlock::GhostsDefeated(50),
+    },
+    GalleryArt {
+        title: "A Well-Earned Rest",
+        path: "player/relax-sheet.png",
+        unlock: Unlock::BestCombo(15),
+    },
+    GalleryArt {
+        title: "The Desert That Never Was",
+        path: "desert.png",
+        unlock: Unlock::GamesPlayed(10),
+    },
+    GalleryArt {
+        title: "Martial Magicka",
+        path: "martial-magicka-demo.png",
+        unlock: Unlock::BestScore(1000),
+    },
+];
+
+/// A music track in the extras soundtrack player
+pub struct SoundtrackTrack {
+    pub title: &'static str,
+    /// Audio path under assets/
+    pub path: &'static str,
+    pub unlock: Unlock,
+}
+
+/// Soundtrack, in player order (the first track is the background music)
+pub const SOUNDTRACK: [SoundtrackTrack; 1] = [SoundtrackTrack {
+    title: "Pixel Showdown",
+    path: "music/pixel_showdown.mp3",
+    unlock: Unlock::Always,
+}];
+
+/// Action performed by an extras screen button
+#[derive(Component, Clone, Copy)]
+pub enum ExtrasButton {
+    /// Shows the next gallery piece
+    NextArt,
+    /// Switches the background music to the next unlocked track
+    NextTrack,
+    /// Pauses or resumes the background music
+    PauseMusic,
+    /// Selects the next saved favorite replay
+    NextReplay,
+    /// Plays the selected favorite replay
+    WatchReplay,
+    Back,
+}
+
+/// Root of the extras screen, hidden while a favorite replay plays
+#[derive(Component)]
+pub struct ExtrasScreen;
+
+/// Gallery frame, showing the `GALLERY` piece at `index`
+#[derive(Component)]
+pub struct GalleryImage {
+    pub index: usize,
+}
+
+/// Title (or unlock hint) under the gallery frame
+#[derive(Component)]
+pub struct GalleryCaption;
+
+/// "Now playing" line of the soundtrack player
+#[derive(Component)]
+pub struct NowPlayingText;
+
+/// Line naming the selected favorite replay, the `FavoriteReplays` entry at `index`
+#[derive(Component)]
+pub struct FavoriteReplayText {
+    pub index: usize,
+}
+
+/// Favorite replay playing over the stage from the extras screen
+#[derive(Resource)]
+pub struct FavoritePlayback {
+    pub title: String,
+    pub frames: Vec<Snapshot>,
+    /// Real-time seconds into the replay
+    pub elapsed: f32,
+}
+
+/// Caption naming the favorite replay playing
+#[derive(Component)]
+pub struct FavoriteCaption;
+
+/// Show gallery art, or its silhouette while it's locked
+fn gallery_image(asset_server: &AssetServer, stats: &LifetimeStats, index: usize) -> ImageNode {
+    let art = &GALLERY[index];
+    let image = ImageNode::new(asset_server.load(art.path));
+    if art.unlock.is_met(stats) {
+        image
+    } else {
+        image.with_color(Color::BLACK)
+    }
+}
+
+/// Title of gallery art, or how to unlock it
+fn gallery_caption(stats: &LifetimeStats, index: usize) -> String {
+    let art = &GALLERY[index];
+    if art.unlock.is_met(stats) {
+        format!("{} ({}/{})", art.title, index + 1, GALLERY.len())
+    } else {
+        format!("??? - {}", art.unlock.hint())
+    }
+}
+
+/// Line shown by the soundtrack player
+fn now_playing(track: usize) -> String {
+    format!("Now playing: {}", SOUNDTRACK[track].title)
+}
+
+/// Line naming a saved favorite replay and when it was saved
+fn favorite_line(favorites: &FavoriteReplays, index: usize) -> String {
+    match favorites.replays.get(index) {
+        Some(replay) => format!(
+            "{} - {} ({}/{})",
+            replay.title,
+            format_date(replay.saved),
+            index + 1,
+            favorites.replays.len()
+        ),
+        None => "Press F during a highlight reel to save a replay here".to_string(),
+    }
+}
+
+/// Label shown on the pause button
+fn pause_label(paused: bool) -> &'static str {
+    if paused {
+        "Resume Music"
+    } else {
+        "Pause Music"
+    }
+}
+
+/// Spawn the extras screen: the art gallery, the soundtrack player, and the
+/// saved favorite replays
+///
+/// Locked art shows as a silhouette with the progress needed to unlock it.
+/// Runs on entering Extras; the screen is despawned automatically on exit.
+pub fn spawn_extras_screen(
+    mut commands: Commands,
+    styles: Res<TextStyles>,
+    asset_server: Res<AssetServer>,
+    stats: Res<LifetimeStats>,
+    favorites: Res<FavoriteReplays>,
+    music_query: Query<(&BackgroundMusic, Option<&AudioSink>)>,
+) {
+    let (track, paused) = music_query.single().map_or((0, false), |(music, sink)| {
+        (music.track, sink.is_some_and(|sink| sink.is_paused()))
+    });
+    let unlocked = GALLERY
+        .iter()
+        .filter(|art| art.unlock.is_met(&stats))
+        .count();
+
+    commands
+        .spawn((
+            menu_root_node(),
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+            ExtrasScreen,
+            DespawnOnExit(AppState::Extras),
+        ))
+        .with_children(|parent| {
+            parent.spawn(menu_title(&styles, "EXTRAS"));
+            parent.spawn((
+                Text::new(format!("Gallery - {unlocked}/{} unlocked", GALLERY.len())),
+                styles.font(TextRole::Caption),
+                TextColor(Color::srgb(1.0, 0.85, 0.2)),
+            ));
+            parent.spawn((
+                gallery_image(&asset_server, &stats, 0),
+                Node {
+                    width: Val::Px(GALLERY_FRAME.x),
+                    height: Val::Px(GALLERY_FRAME.y),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.8)),
+                GalleryImage { index: 0 },
+            ));
+            parent.spawn((
+                Text::new(gallery_caption(&stats, 0)),
+                styles.font(TextRole::Detail),
+                TextColor(Color::WHITE),
+                GalleryCaption,
+            ));
+            parent.spawn((
+                Text::new(now_playing(track)),
+                styles.font(TextRole::Detail),
+                TextColor(Color::srgb(0.6, 0.8, 1.0)),
+                NowPlayingText,
+            ));
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(20.0),
+                    ..default()
+                })
+                .with_children(|buttons| {
+                    spawn_menu_button(buttons, &styles, "Next Art", ExtrasButton::NextArt);
+                    spawn_menu_button(buttons, &styles, "Next Track", ExtrasButton::NextTrack);
+                    spawn_menu_button(
+                        buttons,
+                        &styles,
+                        pause_label(paused),
+                        ExtrasButton::PauseMusic,
+                    );
+                });
+            parent.spawn((
+                Text::new(format!(
+                    "Favorite Replays - {} saved",
+                    favorites.replays.len()
+                )),
+                styles.font(TextRole::Caption),
+                TextColor(Color::srgb(1.0, 0.85, 0.2)),
+            ));
+            parent.spawn((
+                Text::new(favorite_line(&favorites, 0)),
+                styles.font(TextRole::Detail),
+                TextColor(Color::WHITE),
+                FavoriteReplayText { index: 0 },
+            ));
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(20.0),
+                    ..default()
+                })
+                .with_children(|buttons| {
+                    spawn_menu_button(buttons, &styles, "Next Replay", ExtrasButton::NextReplay);
+                    spawn_menu_button(buttons, &styles, "Watch Replay", ExtrasButton::WatchReplay);
+                    spawn_menu_button(buttons, &styles, "Back", ExtrasButton::Back);
+                });
+        });
+}
+
+/// Browse the gallery, control the music, pick and watch a favorite replay,
+/// and return to the main menu (Back button or the Back action)
+///
+/// Switching tracks replaces the background music entity, so the choice keeps
+/// playing after leaving the screen. Paused while a favorite replay plays.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_extras_input(
+    mut commands: Commands,
+    actions: Res<PlayerActions>,
+    asset_server: Res<AssetServer>,
+    stats: Res<LifetimeStats>,
+    favorites: Res<FavoriteReplays>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+    button_query: Query<(&Interaction, &ExtrasButton, &Children), Changed<Interaction>>,
+    mut text_query: Query<&mut Text>,
+    mut gallery_query: Query<(&mut GalleryImage, &mut ImageNode)>,
+    caption_query: Query<Entity, With<GalleryCaption>>,
+    now_playing_query: Query<Entity, With<NowPlayingText>>,
+    mut favorite_query: Query<(Entity, &mut FavoriteReplayText)>,
+    music_query: Query<(Entity, &BackgroundMusic, Option<&AudioSink>)>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if actions.back {
+        next_state.set(AppState::MainMenu);
+        return;
+    }
+
+    for (interaction, button, children) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match button {
+            ExtrasButton::NextArt => {
+                let Ok((mut gallery, mut image)) = gallery_query.single_mut() else {
+                    continue;
+                };
+                gallery.index = (gallery.index + 1) % GALLERY.len();
+                *image = gallery_image(&asset_server, &stats, gallery.index);
+                if let Ok(caption) = caption_query.single()
+                    && let Ok(mut text) = text_query.get_mut(caption)
+                {
+                    **text = gallery_caption(&stats, gallery.index);
+                }
+            }
+            ExtrasButton::NextTrack => {
+                let current = music_query.single().map_or(0, |(_, music, _)| music.track);
+                let next = (1..=SOUNDTRACK.len())
+                    .map(|offset| (current + offset) % SOUNDTRACK.len())
+                    .find(|&track| SOUNDTRACK[track].unlock.is_met(&stats))
+                    .unwrap_or(current);
+                for (entity, _, _) in music_query.iter() {
+                    commands.entity(entity).despawn();
+                }
+                commands.spawn(background_music(&asset_server, next));
+                if let Ok(line) = now_playing_query.single()
+                    && let Ok(mut text) = text_query.get_mut(line)
+                {
+                    **text = now_playing(next);
+                }
+            }
+            ExtrasButton::PauseMusic => {
+                let Ok((_, _, Some(sink))) = music_query.single() else {
+                    continue;
+                };
+                if sink.is_paused() {
+                    sink.play();
+                } else {
+                    sink.pause();
+                }
+                set_button_label(
+                    children,
+                    &mut text_query,
+                    pause_label(sink.is_paused()).to_string(),
+                );
+            }
+            ExtrasButton::NextReplay => {
+                let Ok((line, mut favorite)) = favorite_query.single_mut() else {
+                    continue;
+                };
+                favorite.index = (favorite.index + 1) % favorites.replays.len().max(1);
+                if let Ok(mut text) = text_query.get_mut(line) {
+                    **text = favorite_line(&favorites, favorite.index);
+                }
+            }
+            ExtrasButton::WatchReplay => {
+                let Some(replay) = favorite_query
+                    .single()
+                    .ok()
+                    .and_then(|(_, favorite)| favorites.replays.get(favorite.index))
+                else {
+                    continue;
+                };
+                let Some(frames) = load_favorite_frames(replay, &asset_server, &mut layouts) else {
+                    warn!("Couldn't read the replay {}", replay.file);
+                    continue;
+                };
+                commands.insert_resource(FavoritePlayback {
+                    title: replay.title.clone(),
+                    frames,
+                    elapsed: 0.0,
+                });
+            }
+            ExtrasButton::Back => next_state.set(AppState::MainMenu),
+        }
+    }
+}
+
+/// Play the favorite replay picked on the extras screen on real time, then
+/// bring the screen back
+///
+/// The extras screen is hidden while it plays, and each frame redraws the
+/// replay's snapshot for the current moment, like the highlight reel. Back
+/// stops it early.
+#[allow(clippy::too_many_arguments)]
+pub fn run_favorite_playback(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    actions: Res<PlayerActions>,
+    styles: Res<TextStyles>,
+    playback: Option<ResMut<FavoritePlayback>>,
+    replay_query: Query<Entity, With<ReplaySprite>>,
+    caption_query: Query<Entity, With<FavoriteCaption>>,
+    mut screen_query: Query<&mut Visibility, With<ExtrasScreen>>,
+) {
+    let Some(mut playback) = playback else {
+        return;
+    };
+    for entity in replay_query.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    if playback.is_added() {
+        for mut screen in screen_query.iter_mut() {
+            *screen = Visibility::Hidden;
+        }
+        commands.spawn((
+            Text::new(format!("REPLAY: {}\nEsc: Back", playback.title)),
+            styles.font(TextRole::Emphasis),
+            TextColor(Color::srgb(1.0, 0.85, 0.2)),
+            TextLayout::new_with_justify(Justify::Center),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(40.0),
+                width: Val::Percent(100.0),
+                ..default()
+            },
+            GlobalZIndex(10),
+            FavoriteCaption,
+            DespawnOnExit(AppState::Extras),
+        ));
+    } else {
+        playback.elapsed += time.delta_secs();
+    }
+
+    if actions.back || playback.elapsed > clip_length(&playback.frames) {
+        commands.remove_resource::<FavoritePlayback>();
+        for caption in caption_query.iter() {
+            commands.entity(caption).despawn();
+        }
+        for mut screen in screen_query.iter_mut() {
+            *screen = Visibility::Inherited;
+        }
+        return;
+    }
+
+    if let Some(snapshot) = snapshot_at(&playback.frames, playback.elapsed) {
+        spawn_snapshot_sprites(&mut commands, snapshot, AppState::Extras);
+    }
+}
+
+/// Looping background music playing a `SOUNDTRACK` track
+pub fn background_music(asset_server: &AssetServer, track: usize) -> impl Bundle {
+    (
+        AudioPlayer::<AudioSource>(asset_server.load(SOUNDTRACK[track].path)),
+        PlaybackSettings::LOOP,
+        BackgroundMusic { track },
+    )
+}
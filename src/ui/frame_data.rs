@@ -0,0 +1,91 @@
+use crate::combat::systems::attack_active_frames;
+use crate::common::AnimationIndices;
+use crate::game::InGame;
+use crate::input::{InputAction, InputMap};
+use crate::player::{Player, PlayerState};
+use crate::stats::WhiffPunishStats;
+use crate::training::{DummyBehavior, TrainingSession};
+use crate::ui::text_style::{TextRole, TextStyles};
+use bevy::prelude::*;
+
+/// Training overlay: dummy behavior, the training keys, and the player's frame data
+#[derive(Component)]
+pub struct FrameDataText;
+
+/// Spawn the training overlay in the bottom-left corner
+///
+/// Runs on entering InGame in training mode.
+pub fn spawn_frame_data_overlay(mut commands: Commands, styles: Res<TextStyles>) {
+    commands.spawn((
+        Text::new(""),
+        styles.font(TextRole::Detail),
+        TextColor(Color::WHITE),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(20.0),
+            bottom: Val::Px(20.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+        FrameDataText,
+        DespawnOnExit(InGame),
+    ));
+}
+
+/// Show the dummy behavior and the player's current animation frame
+///
+/// Attacks also show their startup, active (hitbox out), and recovery frames,
+/// from the same `attack_active_frames` split the hitboxes use. During the
+/// whiff-punish drill, a third line shows the session's punish tallies.
+pub fn update_frame_data_overlay(
+    session: Res<TrainingSession>,
+    whiff_punishes: Res<WhiffPunishStats>,
+    input_map: Res<InputMap>,
+    player_query: Query<(&PlayerState, &Sprite, &AnimationIndices), With<Player>>,
+    mut text_query: Query<(&mut Text, &mut TextColor), With<FrameDataText>>,
+) {
+    let (Ok((state, sprite, indices)), Ok((mut text, mut color))) =
+        (player_query.single(), text_query.single_mut())
+    else {
+        return;
+    };
+    let Some(atlas) = &sprite.texture_atlas else {
+        return;
+    };
+
+    let header = format!(
+        "TRAINING  Dummy: {} ({})  Reset ({})",
+        session.behavior.label(),
+        input_map.key_label(InputAction::DummyBehavior),
+        input_map.key_label(InputAction::TrainingReset),
+    );
+    let frame = atlas.index.saturating_sub(indices.first) + 1;
+    let total = indices.last - indices.first + 1;
+    let mut line = format!("{:?}  frame {frame}/{total}", state.state_type());
+
+    color.0 = Color::WHITE;
+    if state.is_attacking() {
+        let active = attack_active_frames(indices);
+        let (start, end) = (
+            active.start() - indices.first + 1,
+            active.end() - indices.first + 1,
+        );
+        let phase = if active.contains(&atlas.index) {
+            color.0 = Color::srgb(1.0, 0.4, 0.4);
+            "ACTIVE"
+        } else if atlas.index < *active.start() {
+            "startup"
+        } else {
+            "recovery"
+        };
+        line.push_str(&format!(
+            "  {phase}  (startup 1-{}, active {start}-{end}, recovery {}-{total})",
+            start - 1,
+            end + 1
+        ));
+    }
+    if session.behavior == DummyBehavior::WhiffDrill {
+        line.push_str(&format!("\n{}", whiff_punishes.summary()));
+    }
+    **text = format!("{header}\n{line}");
+}
@@ -0,0 +1,148 @@
+use crate::combat::StyleMeter;
+use crate::enemy::Enemy;
+use crate::game::{AppState, GameState, RestartGameEvent};
+use crate::input::PlayerActions;
+use crate::levels::{Levels, StageProgress};
+use crate::pickup::Pickup;
+use crate::player::Player;
+use crate::projectile::Projectile;
+use crate::replay::Highlights;
+use crate::settings::{GameMode, GameSpeed};
+use crate::stats::Leaderboard;
+use crate::ui::highlight_reel::{ResultsScreen, highlight_reel_hint};
+use crate::ui::hud::format_clock;
+use crate::ui::leaderboard::{board_mode, leaderboard_panel, spawn_leaderboard_rows};
+use crate::ui::text_style::{TextRole, TextStyles};
+use crate::ui::widgets::menu_root_node;
+use bevy::prelude::*;
+
+/// Despawn enemies and show the game over screen with the run's style rank and the leaderboard
+///
+/// Arcade runs show the stage reached (or that every stage was cleared),
+/// endless runs how long the player survived, and matches with highlights
+/// offer the highlight reel. Runs on entering GameOver; the screen
+/// is despawned automatically on exit.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn spawn_game_over_screen(
+    mut commands: Commands,
+    styles: Res<TextStyles>,
+    game_state: Res<GameState>,
+    mode: Res<GameMode>,
+    speed: Res<GameSpeed>,
+    leaderboard: Res<Leaderboard>,
+    highlights: Res<Highlights>,
+    levels: Res<Levels>,
+    progress: Res<StageProgress>,
+    enemy_query: Query<Entity, Or<(With<Enemy>, With<Projectile>, With<Pickup>)>>,
+    style_query: Query<&StyleMeter, With<Player>>,
+) {
+    // Despawn all enemies, their projectiles, and any dropped pickups
+    for enemy_entity in enemy_query.iter() {
+        commands.entity(enemy_entity).despawn();
+    }
+
+    commands
+        .spawn((
+            menu_root_node(),
+            GlobalZIndex(10),
+            ResultsScreen,
+            DespawnOnExit(AppState::GameOver),
+        ))
+        .with_children(|parent| {
+            let won = *mode == GameMode::Arcade && progress.completed(&levels);
+            parent.spawn((
+                Text::new(if won {
+                    "ALL STAGES CLEARED!"
+                } else {
+                    "GAME OVER"
+                }),
+                styles.font(TextRole::Title),
+                TextColor(if won {
+                    Color::srgb(1.0, 0.85, 0.2)
+                } else {
+                    Color::srgb(1.0, 0.0, 0.0)
+                }),
+            ));
+            parent.spawn((
+                Text::new(format!("Final Score: {}", game_state.score)),
+                styles.font(TextRole::Subtitle),
+                TextColor(Color::WHITE),
+            ));
+            if *mode == GameMode::Arcade && !won {
+                parent.spawn((
+                    Text::new(format!(
+                        "Reached Stage {}: {}",
+                        progress.number(),
+                        levels.stage(progress.index).name
+                    )),
+                    styles.font(TextRole::Emphasis),
+                    TextColor(Color::WHITE),
+                ));
+            }
+            if *mode == GameMode::Endless {
+                parent.spawn((
+                    Text::new(format!(
+                        "Survived: {}",
+                        format_clock(game_state.timer.elapsed_secs())
+                    )),
+                    styles.font(TextRole::Emphasis),
+                    TextColor(Color::WHITE),
+                ));
+            }
+            if let Ok(style) = style_query.single() {
+                let rank = style.run_rank();
+                parent.spawn((
+                    Text::new(format!("Style Rank: {}", rank.label())),
+                    styles.font(TextRole::Emphasis),
+                    TextColor(rank.color()),
+                ));
+            }
+            if let Some(rank) = leaderboard.last_rank {
+                parent.spawn((
+                    Text::new(if rank == 0 {
+                        "NEW HIGH SCORE!".to_string()
+                    } else {
+                        format!("You placed #{}!", rank + 1)
+                    }),
+                    styles.font(TextRole::Emphasis),
+                    TextColor(Color::srgb(1.0, 0.85, 0.2)),
+                ));
+            }
+            parent.spawn(leaderboard_panel()).with_children(|panel| {
+                spawn_leaderboard_rows(
+                    panel,
+                    &styles,
+                    &leaderboard,
+                    board_mode(*mode),
+                    *speed,
+                    leaderboard.last_rank,
+                );
+            });
+            if let Some(hint) = highlight_reel_hint(&highlights) {
+                parent.spawn((
+                    Text::new(hint),
+                    styles.font(TextRole::Body),
+                    TextColor(Color::srgb(1.0, 0.85, 0.2)),
+                ));
+            }
+            parent.spawn((
+                Text::new("Press R to Restart, M to Continue"),
+                styles.font(TextRole::Body),
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+            ));
+        });
+}
+
+/// Restart (R / confirm) or continue to the rest screen (M / back) from the game over screen
+pub fn handle_game_over_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    actions: Res<PlayerActions>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut restart_events: MessageWriter<RestartGameEvent>,
+) {
+    if keyboard.just_pressed(KeyCode::KeyR) || actions.confirm {
+        restart_events.write(RestartGameEvent);
+    } else if keyboard.just_pressed(KeyCode::KeyM) || actions.back {
+        next_state.set(AppState::Rest);
+    }
+}
@@ -0,0 +1,60 @@
+use crate::game::{InGame, Round, RoundPhase};
+use crate::levels::{StagePhase, StageProgress};
+use crate::ui::text_style::{TextRole, TextStyles};
+use bevy::prelude::*;
+
+/// Flashes per second of the "GO" prompt
+const GO_FLASH_RATE: f32 = 2.5;
+/// Fraction of each flash the prompt is shown for
+const GO_FLASH_DUTY: f32 = 0.6;
+
+/// Flashing "GO →" at the right edge of the screen, calling the players on
+/// to the next zone of a scrolling stage
+#[derive(Component)]
+pub struct GoPrompt;
+
+/// Spawn the (initially hidden) "GO" prompt
+///
+/// Runs on entering InGame alongside the HUD; `update_go_prompt` shows it
+/// while a scrolling stage is advancing.
+pub fn spawn_go_prompt(mut commands: Commands, styles: Res<TextStyles>) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Percent(6.0),
+                top: Val::Percent(40.0),
+                ..default()
+            },
+            DespawnOnExit(InGame),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("GO →"),
+                styles.font(TextRole::Banner),
+                TextColor(Color::srgb(1.0, 0.85, 0.2)),
+                Visibility::Hidden,
+                GoPrompt,
+            ));
+        });
+}
+
+/// Flash the "GO" prompt while the players walk on to the next zone (not
+/// during the round call or a knockout)
+pub fn update_go_prompt(
+    time: Res<Time>,
+    progress: Res<StageProgress>,
+    round: Res<Round>,
+    mut prompt_query: Query<&mut Visibility, With<GoPrompt>>,
+) {
+    let Ok(mut visibility) = prompt_query.single_mut() else {
+        return;
+    };
+    let advancing = progress.phase == StagePhase::Advancing && round.phase == RoundPhase::Fight;
+    let lit = (time.elapsed_secs() * GO_FLASH_RATE).fract() < GO_FLASH_DUTY;
+    *visibility = if advancing && lit {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+}
@@ -0,0 +1,178 @@
+use crate::game::AppState;
+use crate::input::PlayerActions;
+use crate::persistence::SaveQueue;
+use crate::player::Player;
+use crate::replay::{FavoriteReplays, Highlights, Snapshot, clip_length, snapshot_at};
+use crate::ui::text_style::{TextRole, TextStyles};
+use bevy::prelude::*;
+
+/// Root of a results screen (game over or versus win), hidden while the highlight reel plays
+#[derive(Component)]
+pub struct ResultsScreen;
+
+/// Highlight reel playing over the results screen
+#[derive(Resource)]
+pub struct HighlightReel {
+    /// Index into `Highlights::clips` of the clip playing
+    pub clip: usize,
+    /// Real-time seconds into the clip
+    pub elapsed: f32,
+    /// Indices of the clips saved as favorites
+    pub saved: Vec<usize>,
+}
+
+/// Sprite drawn from a highlight clip snapshot, replaced every frame
+#[derive(Component)]
+pub struct ReplaySprite;
+
+/// Caption naming the clip playing
+#[derive(Component)]
+pub struct ReelCaption;
+
+/// Results screen line offering the reel, if the match left any highlights
+pub fn highlight_reel_hint(highlights: &Highlights) -> Option<String> {
+    (!highlights.clips.is_empty())
+        .then(|| format!("Press H to Watch Highlights ({})", highlights.clips.len()))
+}
+
+fn caption_text(highlights: &Highlights, reel: &HighlightReel) -> String {
+    let save = if reel.saved.contains(&reel.clip) {
+        "Saved to Extras"
+    } else {
+        "F: Save"
+    };
+    format!(
+        "REPLAY {}/{}: {}\nH: Next   {save}   Esc: Back",
+        reel.clip + 1,
+        highlights.clips.len(),
+        highlights.clips[reel.clip].kind.label()
+    )
+}
+
+/// Draw a snapshot's sprites as `ReplaySprite`s, despawned when `state` exits
+pub fn spawn_snapshot_sprites(commands: &mut Commands, snapshot: &Snapshot, state: AppState) {
+    for (sprite, transform) in &snapshot.sprites {
+        commands.spawn((
+            sprite.clone(),
+            *transform,
+            ReplaySprite,
+            DespawnOnExit(state),
+        ));
+    }
+}
+
+/// Swap between the results screen with the live players and the reel
+fn show_results(
+    visible: bool,
+    screen_query: &mut Query<&mut Visibility, With<ResultsScreen>>,
+    player_query: &mut Query<&mut Visibility, (With<Player>, Without<ResultsScreen>)>,
+) {
+    let visibility = if visible {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    for mut screen in screen_query.iter_mut() {
+        *screen = visibility;
+    }
+    for mut player in player_query.iter_mut() {
+        *player = visibility;
+    }
+}
+
+/// Start the highlight reel from the results screen (H), then play its clips
+/// in order on real time and return to the results screen
+///
+/// The results screen and the live players are hidden while it plays, so the
+/// replayed fighters stand alone on the stage. Each frame redraws the clip's
+/// snapshot for the current moment. H skips to the next clip, F saves the
+/// clip playing as a favorite (watched again from the extras screen), and
+/// Back ends the reel early.
+#[allow(clippy::too_many_arguments)]
+pub fn run_highlight_reel(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    actions: Res<PlayerActions>,
+    styles: Res<TextStyles>,
+    highlights: Res<Highlights>,
+    asset_server: Res<AssetServer>,
+    layouts: Res<Assets<TextureAtlasLayout>>,
+    mut favorites: ResMut<FavoriteReplays>,
+    mut save_queue: ResMut<SaveQueue>,
+    reel: Option<ResMut<HighlightReel>>,
+    replay_query: Query<Entity, With<ReplaySprite>>,
+    mut caption_query: Query<(Entity, &mut Text), With<ReelCaption>>,
+    mut screen_query: Query<&mut Visibility, With<ResultsScreen>>,
+    mut player_query: Query<&mut Visibility, (With<Player>, Without<ResultsScreen>)>,
+) {
+    let skip = keyboard.just_pressed(KeyCode::KeyH);
+    let Some(mut reel) = reel else {
+        if skip && !highlights.clips.is_empty() {
+            let reel = HighlightReel {
+                clip: 0,
+                elapsed: 0.0,
+                saved: Vec::new(),
+            };
+            commands.spawn((
+                Text::new(caption_text(&highlights, &reel)),
+                styles.font(TextRole::Emphasis),
+                TextColor(Color::srgb(1.0, 0.85, 0.2)),
+                TextLayout::new_with_justify(Justify::Center),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(40.0),
+                    width: Val::Percent(100.0),
+                    ..default()
+                },
+                GlobalZIndex(10),
+                ReelCaption,
+                DespawnOnExit(AppState::GameOver),
+            ));
+            commands.insert_resource(reel);
+            show_results(false, &mut screen_query, &mut player_query);
+        }
+        return;
+    };
+
+    for entity in replay_query.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    reel.elapsed += time.delta_secs();
+    if keyboard.just_pressed(KeyCode::KeyF) && !reel.saved.contains(&reel.clip) {
+        favorites.save(
+            &highlights.clips[reel.clip],
+            reel.clip,
+            &asset_server,
+            &layouts,
+            &mut save_queue,
+        );
+        let clip = reel.clip;
+        reel.saved.push(clip);
+    }
+    if skip || reel.elapsed > clip_length(&highlights.clips[reel.clip].frames) {
+        reel.clip += 1;
+        reel.elapsed = 0.0;
+    }
+
+    if actions.back || reel.clip >= highlights.clips.len() {
+        commands.remove_resource::<HighlightReel>();
+        for (caption, _) in caption_query.iter() {
+            commands.entity(caption).despawn();
+        }
+        show_results(true, &mut screen_query, &mut player_query);
+        return;
+    }
+
+    let caption = caption_text(&highlights, &reel);
+    for (_, mut text) in caption_query.iter_mut() {
+        if text.0 != caption {
+            text.0 = caption.clone();
+        }
+    }
+
+    if let Some(snapshot) = snapshot_at(&highlights.clips[reel.clip].frames, reel.elapsed) {
+        spawn_snapshot_sprites(&mut commands, snapshot, AppState::GameOver);
+    }
+}
@@ -0,0 +1,146 @@
+use crate::combat::HitLandedEvent;
+use crate::game::InGame;
+use crate::player::Player;
+use crate::world::CameraShake;
+use bevy::prelude::*;
+
+/// How long an edge indicator takes to fade out after a hit
+const INDICATOR_SECONDS: f32 = 0.6;
+/// Thickness of an edge indicator
+const INDICATOR_THICKNESS: f32 = 24.0;
+/// Opacity of an edge indicator right after a hit
+const INDICATOR_ALPHA: f32 = 0.7;
+
+/// Side of the screen a hit came from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScreenEdge {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+impl ScreenEdge {
+    pub const ALL: [ScreenEdge; 4] = [
+        ScreenEdge::Left,
+        ScreenEdge::Right,
+        ScreenEdge::Top,
+        ScreenEdge::Bottom,
+    ];
+
+    /// Edge of `view` that `point` lies beyond (None if it's on screen)
+    ///
+    /// A point past a corner picks the edge it overshoots the most.
+    pub fn beyond(view: Rect, point: Vec2) -> Option<Self> {
+        if view.contains(point) {
+            return None;
+        }
+        let offset = point - view.center();
+        let overshoot = offset.abs() - view.half_size();
+        Some(if overshoot.x >= overshoot.y {
+            if offset.x < 0.0 {
+                ScreenEdge::Left
+            } else {
+                ScreenEdge::Right
+            }
+        } else if offset.y < 0.0 {
+            ScreenEdge::Bottom
+        } else {
+            ScreenEdge::Top
+        })
+    }
+
+    /// Bar of the given thickness hugging this edge of the screen
+    pub fn bar(self, thickness: f32) -> Node {
+        let thickness = Val::Px(thickness);
+        let mut node = Node {
+            position_type: PositionType::Absolute,
+            ..default()
+        };
+        match self {
+            ScreenEdge::Left | ScreenEdge::Right => {
+                node.width = thickness;
+                node.height = Val::Percent(100.0);
+                node.top = Val::Px(0.0);
+            }
+            ScreenEdge::Top | ScreenEdge::Bottom => {
+                node.width = Val::Percent(100.0);
+                node.height = thickness;
+                node.left = Val::Px(0.0);
+            }
+        }
+        match self {
+            ScreenEdge::Left => node.left = Val::Px(0.0),
+            ScreenEdge::Right => node.right = Val::Px(0.0),
+            ScreenEdge::Top => node.top = Val::Px(0.0),
+            ScreenEdge::Bottom => node.bottom = Val::Px(0.0),
+        }
+        node
+    }
+}
+
+/// Red bar on one screen edge that flashes when the player is hit from beyond it
+#[derive(Component)]
+pub struct HitIndicator {
+    pub edge: ScreenEdge,
+    pub timer: Timer,
+}
+
+/// Spawn the (initially invisible) hit indicators, one per screen edge
+///
+/// Runs on entering InGame alongside the HUD.
+pub fn spawn_hit_indicators(mut commands: Commands) {
+    for edge in ScreenEdge::ALL {
+        let mut timer = Timer::from_seconds(INDICATOR_SECONDS, TimerMode::Once);
+        timer.finish();
+        commands.spawn((
+            edge.bar(INDICATOR_THICKNESS),
+            BackgroundColor(Color::srgba(1.0, 0.1, 0.1, 0.0)),
+            HitIndicator { edge, timer },
+            DespawnOnExit(InGame),
+        ));
+    }
+}
+
+/// Flash the edge indicator toward off-screen sources of hits on the player, and fade them out
+///
+/// Hits from anything on screen don't need pointing out, so they leave the
+/// indicators alone.
+pub fn update_hit_indicators(
+    time: Res<Time>,
+    mut hit_landed_events: MessageReader<HitLandedEvent>,
+    shake: Res<CameraShake>,
+    player_query: Query<(), With<Player>>,
+    camera_query: Query<&Transform, With<Camera2d>>,
+    window_query: Query<&Window>,
+    mut indicator_query: Query<(&mut HitIndicator, &mut BackgroundColor)>,
+) {
+    let view = match (camera_query.single(), window_query.single()) {
+        (Ok(camera), Ok(window)) => Some(Rect::from_center_size(
+            camera.translation.truncate() - shake.offset(),
+            window.size(),
+        )),
+        _ => None,
+    };
+
+    for event in hit_landed_events.read() {
+        if !player_query.contains(event.target) {
+            continue;
+        }
+        let Some(edge) = view.and_then(|view| ScreenEdge::beyond(view, event.source)) else {
+            continue;
+        };
+        for (mut indicator, _) in indicator_query.iter_mut() {
+            if indicator.edge == edge {
+                indicator.timer.reset();
+            }
+        }
+    }
+
+    for (mut indicator, mut color) in indicator_query.iter_mut() {
+        indicator.timer.tick(time.delta());
+        color
+            .0
+            .set_alpha(INDICATOR_ALPHA * indicator.timer.fraction_remaining());
+    }
+}
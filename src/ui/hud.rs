@@ -0,0 +1,328 @@
+use crate::combat::{Health, Shield, SpecialMeter};
+use crate::game::{GameState, InGame, Lives};
+use crate::player::Player;
+use crate::settings::{Difficulty, GameMode, MatchSettings, Mutators};
+use crate::ui::hud_theme::HudThemes;
+use crate::ui::text_style::TextStyles;
+use bevy::prelude::*;
+
+/// Gap between health bar segments, in pixels
+const HEALTH_SEGMENT_GAP: f32 = 2.0;
+/// Health (as a fraction of max) below which the health bar flashes
+const LOW_HEALTH_FRACTION: f32 = 0.25;
+/// Low-health flashes per second
+const LOW_HEALTH_FLASH_RATE: f32 = 3.0;
+/// Color of the shield overlay on the health bar
+const SHIELD_OVERLAY_COLOR: Color = Color::srgba(0.3, 0.6, 1.0, 0.7);
+
+#[derive(Component)]
+pub struct ScoreText;
+
+/// Current/max health number drawn over the health bar
+#[derive(Component)]
+pub struct HealthText;
+
+/// Blue overlay on the health bar showing the player's `Shield`, as a fraction of max health
+#[derive(Component)]
+pub struct ShieldOverlay;
+
+/// One point of health in the player's health bar (`0` is the leftmost)
+#[derive(Component)]
+pub struct HealthSegment(pub i32);
+
+#[derive(Component)]
+pub struct TimeText;
+
+/// Lives left, under the time (arcade and endless runs)
+#[derive(Component)]
+pub struct LivesText;
+
+/// Filled portion of the special meter bar
+#[derive(Component)]
+pub struct SpecialMeterFill;
+
+/// Margin that centers a node of `width` pixels placed at `left: 50%`, at any window size
+fn centered(width: f32) -> UiRect {
+    UiRect::left(Val::Px(-width / 2.0))
+}
+
+/// Spawn the score, lives, health bar, special meter, and time HUD in the selected theme
+///
+/// Elements the theme hides aren't spawned; the update systems skip them.
+/// Lives are shown in the modes that have them (see `Lives`).
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_hud(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    styles: Res<TextStyles>,
+    themes: Res<HudThemes>,
+    difficulty: Res<Difficulty>,
+    mode: Res<GameMode>,
+    mutators: Res<Mutators>,
+    match_settings: Res<MatchSettings>,
+) {
+    let theme = themes.current();
+    let player_health = mutators.player_health(difficulty.player_health());
+    let text_font = theme.text_font(&asset_server, &styles);
+    let text_background = BackgroundColor(theme.text_background.unwrap_or(Color::NONE));
+    let text_padding = if theme.text_background.is_some() {
+        UiRect::axes(Val::Px(10.0), Val::Px(4.0))
+    } else {
+        UiRect::ZERO
+    };
+
+    // Score text - top left
+    if theme.show_score {
+        commands.spawn((
+            Text::new("Score: 0"),
+            text_font.clone(),
+            TextColor(theme.text_color),
+            text_background,
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(20.0),
+                top: Val::Px(20.0),
+                padding: text_padding,
+                ..default()
+            },
+            ScoreText,
+            DespawnOnExit(InGame),
+        ));
+    }
+
+    // Health bar - top center, one segment per point of max health
+    if theme.show_health {
+        commands
+            .spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(50.0),
+                    top: Val::Px(20.0),
+                    width: Val::Px(theme.health_size.x),
+                    margin: centered(theme.health_size.x),
+                    height: Val::Px(theme.health_size.y),
+                    column_gap: Val::Px(HEALTH_SEGMENT_GAP),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                DespawnOnExit(InGame),
+            ))
+            .with_children(|parent| {
+                for index in 0..player_health {
+                    parent.spawn((
+                        Node {
+                            flex_grow: 1.0,
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(theme.health_color),
+                        HealthSegment(index),
+                    ));
+                }
+                parent.spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(0.0),
+                        width: Val::Percent(0.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(SHIELD_OVERLAY_COLOR),
+                    ShieldOverlay,
+                ));
+                if theme.show_health_number {
+                    parent.spawn((
+                        Text::new(format!("{player_health}/{player_health}")),
+                        text_font.clone(),
+                        TextColor(theme.text_color),
+                        TextShadow::default(),
+                        Node {
+                            position_type: PositionType::Absolute,
+                            ..default()
+                        },
+                        HealthText,
+                    ));
+                }
+            });
+    }
+
+    // Special meter bar - under the health bar
+    if theme.show_meter {
+        commands
+            .spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(50.0),
+                    top: Val::Px(30.0 + theme.health_size.y),
+                    width: Val::Px(theme.meter_size.x),
+                    margin: centered(theme.meter_size.x),
+                    height: Val::Px(theme.meter_size.y),
+                    ..default()
+                },
+                BackgroundColor(theme.meter_background),
+                DespawnOnExit(InGame),
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Node {
+                        width: Val::Percent(0.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(theme.meter_fill),
+                    SpecialMeterFill,
+                ));
+            });
+    }
+
+    // Time text - top right
+    if theme.show_time {
+        commands.spawn((
+            Text::new(format!("Time: {}", match_settings.duration.ceil() as u32)),
+            text_font.clone(),
+            TextColor(theme.text_color),
+            text_background,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(20.0),
+                top: Val::Px(20.0),
+                padding: text_padding,
+                ..default()
+            },
+            TimeText,
+            DespawnOnExit(InGame),
+        ));
+    }
+
+    // Lives - under the time
+    if matches!(*mode, GameMode::Arcade | GameMode::Endless) {
+        let lives = Lives::new(*mode, &match_settings, *mutators);
+        commands.spawn((
+            Text::new(format!("Lives: {}", lives.remaining)),
+            text_font,
+            TextColor(theme.text_color),
+            text_background,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(20.0),
+                top: Val::Px(60.0),
+                padding: text_padding,
+                ..default()
+            },
+            LivesText,
+            DespawnOnExit(InGame),
+        ));
+    }
+}
+
+/// Update UI elements with current game state
+#[allow(clippy::type_complexity)]
+pub fn update_ui(
+    game_state: Res<GameState>,
+    mode: Res<GameMode>,
+    player_query: Query<&Health, With<Player>>,
+    mut score_text: Query<&mut Text, (With<ScoreText>, Without<HealthText>, Without<TimeText>)>,
+    mut health_text: Query<&mut Text, (With<HealthText>, Without<ScoreText>, Without<TimeText>)>,
+    mut time_text: Query<&mut Text, (With<TimeText>, Without<ScoreText>, Without<HealthText>)>,
+) {
+    // Update score
+    if let Ok(mut text) = score_text.single_mut() {
+        **text = format!("Score: {}", game_state.score);
+    }
+
+    // Update health number
+    if let Ok(health) = player_query.single()
+        && let Ok(mut text) = health_text.single_mut()
+    {
+        **text = format!("{}/{}", health.current.max(0), health.max);
+    }
+
+    // Update time remaining (endless runs and scrolling stages count up instead, training
+    // has no time limit; versus shows its round clock on its own HUD)
+    if let Ok(mut text) = time_text.single_mut() {
+        **text = match *mode {
+            GameMode::Arcade if game_state.game_duration.is_finite() => {
+                format!("Time: {}", game_state.time_remaining().ceil() as u32)
+            }
+            GameMode::Arcade | GameMode::Endless => {
+                format!("Time: {}", format_clock(game_state.timer.elapsed_secs()))
+            }
+            GameMode::Versus | GameMode::Training => "Time: --".to_string(),
+        };
+    }
+}
+
+/// Keep the lives count in sync with `Lives`
+pub fn update_lives_text(lives: Res<Lives>, mut lives_text: Query<&mut Text, With<LivesText>>) {
+    if let Ok(mut text) = lives_text.single_mut() {
+        **text = format!("Lives: {}", lives.remaining);
+    }
+}
+
+/// Format seconds as `m:ss`
+pub fn format_clock(seconds: f32) -> String {
+    let seconds = seconds as u32;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// Fill the health bar segments up to the player's health, flashing them when it
+/// runs low, and cover it with the shield overlay while the player has a `Shield`
+pub fn update_health_bar(
+    time: Res<Time>,
+    themes: Res<HudThemes>,
+    player_query: Query<(&Health, Option<&Shield>), With<Player>>,
+    mut segment_query: Query<(&HealthSegment, &mut BackgroundColor)>,
+    mut overlay_query: Query<&mut Node, With<ShieldOverlay>>,
+) {
+    let Ok((health, shield)) = player_query.single() else {
+        return;
+    };
+
+    if let Ok(mut node) = overlay_query.single_mut() {
+        let shielded = shield.map_or(0, |shield| shield.current);
+        let fraction = (shielded as f32 / health.max.max(1) as f32).min(1.0);
+        let width = Val::Percent(fraction * 100.0);
+        if node.width != width {
+            node.width = width;
+        }
+    }
+
+    let theme = themes.current();
+    let low = (health.current as f32) < health.max as f32 * LOW_HEALTH_FRACTION;
+    let flash_on = low && (time.elapsed_secs() * LOW_HEALTH_FLASH_RATE).fract() < 0.5;
+    let fill = if flash_on {
+        Color::WHITE
+    } else {
+        theme.health_color
+    };
+
+    for (segment, mut background) in segment_query.iter_mut() {
+        background.0 = if segment.0 < health.current {
+            fill
+        } else {
+            theme.health_background
+        };
+    }
+}
+
+/// Resize the special meter bar, switching to the theme's full color when the meter is full
+pub fn update_special_meter_bar(
+    themes: Res<HudThemes>,
+    player_query: Query<&SpecialMeter, With<Player>>,
+    mut fill_query: Query<(&mut Node, &mut BackgroundColor), With<SpecialMeterFill>>,
+) {
+    let (Ok(meter), Ok((mut node, mut background))) =
+        (player_query.single(), fill_query.single_mut())
+    else {
+        return;
+    };
+
+    node.width = Val::Percent(meter.fraction() * 100.0);
+    let theme = themes.current();
+    background.0 = if meter.is_full() {
+        theme.meter_full
+    } else {
+        theme.meter_fill
+    };
+}
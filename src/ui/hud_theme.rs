@@ -0,0 +1,216 @@
+use crate::persistence::{parse_key_values, parsed};
+use crate::ui::text_style::{TextRole, TextStyles};
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::fs;
+
+/// HUD theme definitions, relative to the working directory (like the asset folder)
+const HUD_THEMES_PATH: &str = "assets/hud_themes.txt";
+
+/// Colors, font, bar style, and visible elements of the in-game HUD
+#[derive(Clone, Debug)]
+pub struct HudTheme {
+    pub name: String,
+    /// Font path under assets/ (None for the game font from `TextStyles`)
+    pub font: Option<String>,
+    /// Size of the HUD texts (None for the `Hud` text style size)
+    pub font_size: Option<f32>,
+    pub text_color: Color,
+    /// Filled health bar segments (and the health number)
+    pub health_color: Color,
+    pub health_size: Vec2,
+    /// Empty health bar segments
+    pub health_background: Color,
+    /// Backing panel behind the HUD texts (None for bare text)
+    pub text_background: Option<Color>,
+    pub meter_size: Vec2,
+    pub meter_background: Color,
+    pub meter_fill: Color,
+    pub meter_full: Color,
+    pub show_score: bool,
+    pub show_health: bool,
+    /// Current/max health number over the health bar
+    pub show_health_number: bool,
+    pub show_time: bool,
+    pub show_meter: bool,
+}
+
+impl Default for HudTheme {
+    /// The classic arcade look
+    fn default() -> Self {
+        Self {
+            name: "Classic Arcade".to_string(),
+            font: None,
+            font_size: None,
+            text_color: Color::WHITE,
+            health_color: Color::srgb(1.0, 0.3, 0.3),
+            health_size: Vec2::new(240.0, 20.0),
+            health_background: Color::srgba(0.1, 0.1, 0.1, 0.8),
+            text_background: None,
+            meter_size: Vec2::new(200.0, 14.0),
+            meter_background: Color::srgba(0.1, 0.1, 0.1, 0.8),
+            meter_fill: Color::srgb(0.3, 0.6, 1.0),
+            meter_full: Color::srgb(1.0, 0.85, 0.2),
+            show_score: true,
+            show_health: true,
+            show_health_number: true,
+            show_time: true,
+            show_meter: true,
+        }
+    }
+}
+
+impl HudTheme {
+    /// Size of the HUD texts
+    pub fn font_size(&self, styles: &TextStyles) -> f32 {
+        self.font_size.unwrap_or_else(|| styles.size(TextRole::Hud))
+    }
+
+    /// `TextFont` for the HUD texts, loading the theme's font if it has one
+    pub fn text_font(&self, asset_server: &AssetServer, styles: &TextStyles) -> TextFont {
+        let text_font = styles.sized_font(self.font_size(styles));
+        // The readable font replaces the theme's own font too
+        match self.font.as_ref().filter(|_| !styles.readable()) {
+            Some(path) => TextFont {
+                font: asset_server.load(path.clone()),
+                ..text_font
+            },
+            None => text_font,
+        }
+    }
+}
+
+/// Every HUD theme plus the one selected on the main menu
+///
+/// Loaded once at startup from assets/hud_themes.txt; each theme starts from
+/// the compiled classic look (`HudTheme::default`) and applies the values set
+/// for it, so themes can be added or tuned without recompiling. The HUD is
+/// built from the selected theme when a game starts.
+#[derive(Resource)]
+pub struct HudThemes {
+    themes: Vec<HudTheme>,
+    selected: usize,
+}
+
+impl HudThemes {
+    /// Load assets/hud_themes.txt (just the classic theme if it can't be read)
+    pub fn load() -> Self {
+        let contents = fs::read_to_string(HUD_THEMES_PATH).unwrap_or_else(|err| {
+            warn!("Using the compiled HUD theme ({HUD_THEMES_PATH}: {err})");
+            String::new()
+        });
+        Self::from_key_values(&parse_key_values(&contents))
+    }
+
+    /// The selected theme
+    pub fn current(&self) -> &HudTheme {
+        &self.themes[self.selected]
+    }
+
+    /// Select the next theme in the menu cycle (wraps around)
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1) % self.themes.len();
+    }
+
+    fn from_key_values(values: &HashMap<&str, &str>) -> Self {
+        let mut themes: Vec<HudTheme> = values
+            .get("themes")
+            .into_iter()
+            .flat_map(|keys| keys.split(','))
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(|key| theme_from_key_values(key, values))
+            .collect();
+        if themes.is_empty() {
+            themes.push(HudTheme::default());
+        }
+
+        Self {
+            themes,
+            selected: 0,
+        }
+    }
+}
+
+/// Build one theme from its `<key>.<field>` values on top of the classic look
+fn theme_from_key_values(key: &str, values: &HashMap<&str, &str>) -> HudTheme {
+    let field = |name: &str| {
+        let field_key = format!("{key}.{name}");
+        values
+            .get(field_key.as_str())
+            .map(|value| (field_key, *value))
+    };
+    let mut theme = HudTheme {
+        name: key.to_string(),
+        ..default()
+    };
+
+    if let Some((_, name)) = field("name") {
+        theme.name = name.to_string();
+    }
+    if let Some((_, font)) = field("font") {
+        theme.font = Some(font.to_string());
+    }
+    if let Some(size) = field("font_size").and_then(parsed) {
+        theme.font_size = Some(size);
+    }
+    if let Some(color) = field("text_color").and_then(parsed_color) {
+        theme.text_color = color;
+    }
+    if let Some(color) = field("health_color").and_then(parsed_color) {
+        theme.health_color = color;
+    }
+    if let Some(width) = field("health_width").and_then(parsed) {
+        theme.health_size.x = width;
+    }
+    if let Some(height) = field("health_height").and_then(parsed) {
+        theme.health_size.y = height;
+    }
+    if let Some(color) = field("health_background").and_then(parsed_color) {
+        theme.health_background = color;
+    }
+    if let Some(color) = field("text_background").and_then(parsed_color) {
+        theme.text_background = Some(color);
+    }
+    if let Some(width) = field("meter_width").and_then(parsed) {
+        theme.meter_size.x = width;
+    }
+    if let Some(height) = field("meter_height").and_then(parsed) {
+        theme.meter_size.y = height;
+    }
+    if let Some(color) = field("meter_background").and_then(parsed_color) {
+        theme.meter_background = color;
+    }
+    if let Some(color) = field("meter_fill").and_then(parsed_color) {
+        theme.meter_fill = color;
+    }
+    if let Some(color) = field("meter_full").and_then(parsed_color) {
+        theme.meter_full = color;
+    }
+    if let Some(show) = field("show_score").and_then(parsed) {
+        theme.show_score = show;
+    }
+    if let Some(show) = field("show_health").and_then(parsed) {
+        theme.show_health = show;
+    }
+    if let Some(show) = field("show_health_number").and_then(parsed) {
+        theme.show_health_number = show;
+    }
+    if let Some(show) = field("show_time").and_then(parsed) {
+        theme.show_time = show;
+    }
+    if let Some(show) = field("show_meter").and_then(parsed) {
+        theme.show_meter = show;
+    }
+
+    theme
+}
+
+/// Parse a `#rrggbb` or `#rrggbbaa` color, warning (and returning None) if it's malformed
+fn parsed_color((key, value): (String, &str)) -> Option<Color> {
+    let color = Srgba::hex(value).ok().map(Color::from);
+    if color.is_none() {
+        warn!("{key}: can't parse color {value:?}");
+    }
+    color
+}
@@ -0,0 +1,124 @@
+use crate::enemy::EnemyArchetype;
+use crate::game::InGame;
+use crate::persistence::SaveQueue;
+use crate::settings::GameSpeed;
+use crate::stats::Bestiary;
+use crate::ui::text_style::{TextRole, TextStyles};
+use crate::world::{CameraDirective, CameraDirector};
+use bevy::prelude::*;
+
+/// Game speed multiplier while an intro card is on screen
+const INTRO_SLOW_MOTION: f32 = 0.3;
+/// Real-time seconds an intro card stays up
+const INTRO_CARD_SECONDS: f32 = 2.0;
+/// Fraction of the way to the newcomer's spawn point the reveal pans the camera
+const REVEAL_PAN_FRACTION: f32 = 0.75;
+/// Camera zoom (orthographic scale) while the newcomer is revealed
+const REVEAL_ZOOM: f32 = 0.85;
+
+/// Name card introducing an enemy archetype the player hasn't met before
+///
+/// Time runs in slow motion while it is up; the timer uses real time so the
+/// card lasts the same regardless of the slowdown.
+#[derive(Component)]
+pub struct IntroCard {
+    pub timer: Timer,
+}
+
+/// Show an intro card, slow time, and pan the camera to reveal the newcomer the
+/// first time each archetype spawns
+///
+/// Newly seen archetypes are saved immediately, so a card never repeats even
+/// if the game is quit mid-run. A new card replaces any card still showing.
+#[allow(clippy::too_many_arguments)]
+pub fn introduce_new_enemies(
+    mut commands: Commands,
+    styles: Res<TextStyles>,
+    new_enemy_query: Query<(&EnemyArchetype, &Transform), Added<EnemyArchetype>>,
+    card_query: Query<Entity, With<IntroCard>>,
+    camera_query: Query<&Transform, With<Camera2d>>,
+    mut bestiary: ResMut<Bestiary>,
+    mut save_queue: ResMut<SaveQueue>,
+    mut director: ResMut<CameraDirector>,
+    speed: Res<GameSpeed>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    for (&archetype, transform) in new_enemy_query.iter() {
+        if !bestiary.mark_seen(archetype) {
+            continue;
+        }
+        bestiary.save(&mut save_queue);
+
+        for card in card_query.iter() {
+            commands.entity(card).despawn();
+        }
+        time.set_relative_speed(speed.multiplier() * INTRO_SLOW_MOTION);
+
+        // Reveal: pan toward the newcomer's entry, push in, hold, and come back
+        let camera_y = camera_query
+            .single()
+            .map_or(0.0, |camera| camera.translation.y);
+        let target = Vec2::new(transform.translation.x * REVEAL_PAN_FRACTION, camera_y);
+        director.play([
+            CameraDirective::PanTo {
+                target,
+                seconds: 0.5,
+            },
+            CameraDirective::Zoom {
+                scale: REVEAL_ZOOM,
+                seconds: 0.3,
+            },
+            CameraDirective::Shake { amplitude: 4.0 },
+            CameraDirective::Hold { seconds: 0.6 },
+            CameraDirective::Return { seconds: 0.5 },
+        ]);
+
+        commands
+            .spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Percent(15.0),
+                    width: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(8.0),
+                    ..default()
+                },
+                IntroCard {
+                    timer: Timer::from_seconds(INTRO_CARD_SECONDS, TimerMode::Once),
+                },
+                DespawnOnExit(InGame),
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Text::new(archetype.name().to_uppercase()),
+                    styles.font(TextRole::Heading),
+                    TextColor(Color::srgb(1.0, 0.4, 0.2)),
+                ));
+                parent.spawn((
+                    Text::new(archetype.threat()),
+                    styles.font(TextRole::Caption),
+                    TextColor(Color::WHITE),
+                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+                ));
+            });
+    }
+}
+
+/// Remove intro cards when their time is up and return to normal speed
+pub fn update_intro_cards(
+    mut commands: Commands,
+    real_time: Res<Time<Real>>,
+    speed: Res<GameSpeed>,
+    mut time: ResMut<Time<Virtual>>,
+    mut card_query: Query<(Entity, &mut IntroCard)>,
+) {
+    for (entity, mut card) in card_query.iter_mut() {
+        card.timer.tick(real_time.delta());
+
+        if card.timer.is_finished() {
+            commands.entity(entity).despawn();
+            time.set_relative_speed(speed.multiplier());
+        }
+    }
+}
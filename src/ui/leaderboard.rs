@@ -0,0 +1,110 @@
+use crate::settings::{GameMode, GameSpeed};
+use crate::stats::{Leaderboard, format_date};
+use crate::ui::text_style::{TextRole, TextStyles};
+use bevy::prelude::*;
+
+const HIGHLIGHT_COLOR: Color = Color::srgb(1.0, 0.85, 0.2);
+
+/// Column listing the top scores on one board (game mode and speed)
+///
+/// On the main menu it is rebuilt by `refresh_leaderboard_panel` whenever the
+/// speed setting changes.
+#[derive(Component)]
+pub struct LeaderboardPanel;
+
+/// Root node for a leaderboard panel; fill it with `spawn_leaderboard_rows`
+pub fn leaderboard_panel() -> impl Bundle {
+    (
+        Node {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(4.0),
+            ..default()
+        },
+        LeaderboardPanel,
+    )
+}
+
+/// Board shown for a game mode: versus and training have none, so they show the arcade board
+pub fn board_mode(mode: GameMode) -> GameMode {
+    match mode {
+        GameMode::Versus | GameMode::Training => GameMode::Arcade,
+        mode => mode,
+    }
+}
+
+/// Spawn the heading and one text row per leaderboard entry on a board
+///
+/// The row at `highlight` (a 0-based rank) is drawn in gold.
+pub fn spawn_leaderboard_rows(
+    parent: &mut ChildSpawnerCommands,
+    styles: &TextStyles,
+    leaderboard: &Leaderboard,
+    mode: GameMode,
+    speed: GameSpeed,
+    highlight: Option<usize>,
+) {
+    let heading = match mode {
+        GameMode::Endless => format!("ENDLESS TOP SCORES ({})", speed.label()),
+        _ => format!("TOP SCORES ({})", speed.label()),
+    };
+    parent.spawn((
+        Text::new(heading),
+        styles.font(TextRole::Caption),
+        TextColor(Color::WHITE),
+    ));
+
+    let mut entries = leaderboard.top(mode, speed).enumerate().peekable();
+    if entries.peek().is_none() {
+        parent.spawn((
+            Text::new("No scores yet"),
+            styles.font(TextRole::Detail),
+            TextColor(Color::srgb(0.7, 0.7, 0.7)),
+        ));
+        return;
+    }
+
+    for (rank, entry) in entries {
+        let color = if highlight == Some(rank) {
+            HIGHLIGHT_COLOR
+        } else {
+            Color::srgb(0.85, 0.85, 0.85)
+        };
+        parent.spawn((
+            Text::new(format!(
+                "{:>2}.  {:>6}   {}",
+                rank + 1,
+                entry.score,
+                format_date(entry.timestamp)
+            )),
+            styles.font(TextRole::Detail),
+            TextColor(color),
+        ));
+    }
+}
+
+/// Rebuild leaderboard panels to show the currently selected speed
+pub fn refresh_leaderboard_panel(
+    mut commands: Commands,
+    styles: Res<TextStyles>,
+    leaderboard: Res<Leaderboard>,
+    mode: Res<GameMode>,
+    speed: Res<GameSpeed>,
+    panel_query: Query<Entity, With<LeaderboardPanel>>,
+) {
+    for panel in panel_query.iter() {
+        commands
+            .entity(panel)
+            .despawn_children()
+            .with_children(|parent| {
+                spawn_leaderboard_rows(
+                    parent,
+                    &styles,
+                    &leaderboard,
+                    board_mode(*mode),
+                    *speed,
+                    None,
+                )
+            });
+    }
+}
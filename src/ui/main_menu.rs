@@ -0,0 +1,282 @@
+use crate::game::AppState;
+use crate::settings::{ComboAssist, Difficulty, GameMode, GameSpeed, Mutators, TradeRule};
+use crate::stats::Leaderboard;
+use crate::ui::hud_theme::HudThemes;
+use crate::ui::leaderboard::{board_mode, leaderboard_panel, spawn_leaderboard_rows};
+use crate::ui::text_style::TextStyles;
+use crate::ui::widgets::{menu_root_node, menu_title, set_button_label, spawn_menu_button};
+use bevy::prelude::*;
+
+/// Action performed by a main menu button
+#[derive(Component, Clone, Copy)]
+pub enum MainMenuButton {
+    Play,
+    /// Starts an endless run (no timer, ever-harder waves)
+    Endless,
+    /// Starts a versus match (two players, best of three rounds)
+    Versus,
+    /// Starts a training game (no timer, a practice dummy, frame data)
+    Training,
+    /// Cycles through the difficulty levels
+    Difficulty,
+    /// Cycles through the game speed settings
+    Speed,
+    /// Toggles the mirror mode mutator
+    Mirror,
+    /// Toggles the one-hit hardcore mutator
+    Hardcore,
+    /// Toggles the arcade pressure mutator
+    Pressure,
+    /// Toggles between classic trades and attack priority
+    Trades,
+    /// Cycles through the combo assist presets
+    Combos,
+    /// Cycles through the HUD themes from assets/hud_themes.txt
+    HudTheme,
+    /// Opens the bestiary screen
+    Bestiary,
+    /// Opens the extras screen (gallery and soundtrack)
+    Extras,
+    /// Opens the advanced settings screen (tick rate, interpolation)
+    Advanced,
+    Quit,
+}
+
+/// Label shown on the difficulty button
+fn difficulty_label(difficulty: Difficulty) -> String {
+    format!("Difficulty: {}", difficulty.label())
+}
+
+/// Label shown on the game speed button
+fn speed_label(speed: GameSpeed) -> String {
+    format!("Speed: {}", speed.label())
+}
+
+/// "On" / "Off" for a mutator button label
+fn on_off(enabled: bool) -> &'static str {
+    if enabled { "On" } else { "Off" }
+}
+
+/// Label shown on the mirror mode button
+fn mirror_label(mutators: Mutators) -> String {
+    format!("Mirror: {}", on_off(mutators.mirror))
+}
+
+/// Label shown on the hardcore mode button
+fn hardcore_label(mutators: Mutators) -> String {
+    format!("Hardcore (x3 score): {}", on_off(mutators.hardcore))
+}
+
+/// Label shown on the pressure mode button
+fn pressure_label(mutators: Mutators) -> String {
+    format!("Pressure: {}", on_off(mutators.pressure))
+}
+
+/// Label shown on the trade rule button
+fn trades_label(rule: TradeRule) -> String {
+    format!("Trades: {}", rule.label())
+}
+
+/// Label shown on the combo assist button
+fn combos_label(assist: ComboAssist) -> String {
+    format!("Combos: {}", assist.label())
+}
+
+/// Label shown on the HUD theme button
+fn hud_theme_label(themes: &HudThemes) -> String {
+    format!("HUD: {}", themes.current().name)
+}
+
+/// Spawn the title screen: buttons on the left, the leaderboard for the
+/// selected speed on the right (the endless board after an endless run)
+///
+/// Runs on entering MainMenu; the menu is despawned automatically on exit.
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_main_menu(
+    mut commands: Commands,
+    styles: Res<TextStyles>,
+    difficulty: Res<Difficulty>,
+    speed: Res<GameSpeed>,
+    mutators: Res<Mutators>,
+    trade_rule: Res<TradeRule>,
+    combo_assist: Res<ComboAssist>,
+    hud_themes: Res<HudThemes>,
+    leaderboard: Res<Leaderboard>,
+    game_mode: Res<GameMode>,
+) {
+    commands
+        .spawn((
+            menu_root_node(),
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.4)),
+            DespawnOnExit(AppState::MainMenu),
+        ))
+        .with_children(|parent| {
+            parent.spawn(menu_title(&styles, "MARTIAL MAGICKA"));
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(80.0),
+                    ..default()
+                })
+                .with_children(|row| {
+                    row.spawn(Node {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(20.0),
+                        ..default()
+                    })
+                    .with_children(|buttons| {
+                        spawn_menu_button(buttons, &styles, "Play", MainMenuButton::Play);
+                        spawn_menu_button(buttons, &styles, "Endless", MainMenuButton::Endless);
+                        spawn_menu_button(buttons, &styles, "Versus", MainMenuButton::Versus);
+                        spawn_menu_button(buttons, &styles, "Training", MainMenuButton::Training);
+                        spawn_menu_button(
+                            buttons,
+                            &styles,
+                            &difficulty_label(*difficulty),
+                            MainMenuButton::Difficulty,
+                        );
+                        spawn_menu_button(
+                            buttons,
+                            &styles,
+                            &speed_label(*speed),
+                            MainMenuButton::Speed,
+                        );
+                        spawn_menu_button(
+                            buttons,
+                            &styles,
+                            &mirror_label(*mutators),
+                            MainMenuButton::Mirror,
+                        );
+                        spawn_menu_button(
+                            buttons,
+                            &styles,
+                            &hardcore_label(*mutators),
+                            MainMenuButton::Hardcore,
+                        );
+                        spawn_menu_button(
+                            buttons,
+                            &styles,
+                            &pressure_label(*mutators),
+                            MainMenuButton::Pressure,
+                        );
+                        spawn_menu_button(
+                            buttons,
+                            &styles,
+                            &trades_label(*trade_rule),
+                            MainMenuButton::Trades,
+                        );
+                        spawn_menu_button(
+                            buttons,
+                            &styles,
+                            &combos_label(*combo_assist),
+                            MainMenuButton::Combos,
+                        );
+                        spawn_menu_button(
+                            buttons,
+                            &styles,
+                            &hud_theme_label(&hud_themes),
+                            MainMenuButton::HudTheme,
+                        );
+                        spawn_menu_button(buttons, &styles, "Bestiary", MainMenuButton::Bestiary);
+                        spawn_menu_button(buttons, &styles, "Extras", MainMenuButton::Extras);
+                        spawn_menu_button(buttons, &styles, "Advanced", MainMenuButton::Advanced);
+                        spawn_menu_button(buttons, &styles, "Quit", MainMenuButton::Quit);
+                    });
+                    row.spawn(leaderboard_panel()).with_children(|panel| {
+                        spawn_leaderboard_rows(
+                            panel,
+                            &styles,
+                            &leaderboard,
+                            board_mode(*game_mode),
+                            *speed,
+                            None,
+                        );
+                    });
+                });
+        });
+}
+
+/// Handle main menu button presses (clicked, or confirmed while focused)
+///
+/// Play is focused when the menu opens, so Enter, Space, or gamepad South
+/// heads straight to the character select screen. The mode buttons all pick
+/// a fighter there before the game starts. The difficulty, speed, mutator, trade, combo
+/// assist, and HUD theme buttons cycle their setting and relabel themselves.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_main_menu_input(
+    button_query: Query<(&Interaction, &MainMenuButton, &Children), Changed<Interaction>>,
+    mut text_query: Query<&mut Text>,
+    mut difficulty: ResMut<Difficulty>,
+    mut speed: ResMut<GameSpeed>,
+    mut mutators: ResMut<Mutators>,
+    mut trade_rule: ResMut<TradeRule>,
+    mut combo_assist: ResMut<ComboAssist>,
+    mut hud_themes: ResMut<HudThemes>,
+    mut game_mode: ResMut<GameMode>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut app_exit: MessageWriter<AppExit>,
+) {
+    for (interaction, button, children) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match button {
+            MainMenuButton::Play => {
+                *game_mode = GameMode::Arcade;
+                next_state.set(AppState::CharacterSelect);
+            }
+            MainMenuButton::Endless => {
+                *game_mode = GameMode::Endless;
+                next_state.set(AppState::CharacterSelect);
+            }
+            MainMenuButton::Versus => {
+                *game_mode = GameMode::Versus;
+                next_state.set(AppState::CharacterSelect);
+            }
+            MainMenuButton::Training => {
+                *game_mode = GameMode::Training;
+                next_state.set(AppState::CharacterSelect);
+            }
+            MainMenuButton::Difficulty => {
+                *difficulty = difficulty.next();
+                set_button_label(children, &mut text_query, difficulty_label(*difficulty));
+            }
+            MainMenuButton::Speed => {
+                *speed = speed.next();
+                set_button_label(children, &mut text_query, speed_label(*speed));
+            }
+            MainMenuButton::Mirror => {
+                mutators.mirror = !mutators.mirror;
+                set_button_label(children, &mut text_query, mirror_label(*mutators));
+            }
+            MainMenuButton::Hardcore => {
+                mutators.hardcore = !mutators.hardcore;
+                set_button_label(children, &mut text_query, hardcore_label(*mutators));
+            }
+            MainMenuButton::Pressure => {
+                mutators.pressure = !mutators.pressure;
+                set_button_label(children, &mut text_query, pressure_label(*mutators));
+            }
+            MainMenuButton::Trades => {
+                *trade_rule = trade_rule.next();
+                set_button_label(children, &mut text_query, trades_label(*trade_rule));
+            }
+            MainMenuButton::Combos => {
+                *combo_assist = combo_assist.next();
+                set_button_label(children, &mut text_query, combos_label(*combo_assist));
+            }
+            MainMenuButton::HudTheme => {
+                hud_themes.select_next();
+                set_button_label(children, &mut text_query, hud_theme_label(&hud_themes));
+            }
+            MainMenuButton::Bestiary => next_state.set(AppState::Bestiary),
+            MainMenuButton::Extras => next_state.set(AppState::Extras),
+            MainMenuButton::Advanced => next_state.set(AppState::Advanced),
+            MainMenuButton::Quit => {
+                app_exit.write(AppExit::Success);
+            }
+        }
+    }
+}
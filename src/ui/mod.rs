@@ -0,0 +1,37 @@
+pub mod advanced;
+pub mod bestiary;
+pub mod character_select;
+pub mod combo;
+pub mod continue_screen;
+pub mod countdown;
+pub mod damage_numbers;
+pub mod enemy_health_bar;
+pub mod extras;
+pub mod frame_data;
+pub mod game_over;
+pub mod go_prompt;
+pub mod highlight_reel;
+pub mod hit_indicator;
+pub mod hud;
+pub mod hud_theme;
+pub mod intro_card;
+pub mod leaderboard;
+pub mod main_menu;
+pub mod pause;
+pub mod plugin;
+pub mod records;
+pub mod rest;
+pub mod round_banner;
+pub mod stage_clear;
+pub mod style;
+pub mod text_style;
+pub mod toast;
+pub mod versus;
+pub mod vignette;
+pub mod widgets;
+
+// Re-export commonly used items
+pub use countdown::*;
+pub use damage_numbers::*;
+pub use plugin::*;
+pub use text_style::*;
@@ -0,0 +1,91 @@
+use crate::game::{AppState, RestartGameEvent};
+use crate::input::{ControllerDisconnected, PlayerActions};
+use crate::ui::text_style::{TextRole, TextStyles};
+use crate::ui::widgets::{menu_root_node, menu_title, spawn_menu_button};
+use bevy::prelude::*;
+
+/// Action performed by a pause menu button
+#[derive(Component, Clone, Copy)]
+pub enum PauseMenuButton {
+    Resume,
+    Restart,
+    Quit,
+}
+
+/// Toggle between Playing and Paused with the Pause action (Escape / gamepad Start)
+pub fn toggle_pause(
+    actions: Res<PlayerActions>,
+    state: Res<State<AppState>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if !actions.pause {
+        return;
+    }
+
+    match state.get() {
+        AppState::Playing => next_state.set(AppState::Paused),
+        AppState::Paused => next_state.set(AppState::Playing),
+        _ => {}
+    }
+}
+
+/// Handle pause menu button presses; the Back action (gamepad East) resumes
+pub fn handle_pause_menu_buttons(
+    actions: Res<PlayerActions>,
+    button_query: Query<(&Interaction, &PauseMenuButton), Changed<Interaction>>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut restart_events: MessageWriter<RestartGameEvent>,
+) {
+    if actions.back {
+        next_state.set(AppState::Playing);
+        return;
+    }
+
+    for (interaction, button) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match button {
+            PauseMenuButton::Resume => next_state.set(AppState::Playing),
+            PauseMenuButton::Restart => {
+                restart_events.write(RestartGameEvent);
+                next_state.set(AppState::Playing);
+            }
+            PauseMenuButton::Quit => next_state.set(AppState::MainMenu),
+        }
+    }
+}
+
+/// Spawn a dimmed full-screen overlay with the pause menu buttons
+///
+/// Runs on entering Paused; the overlay is despawned automatically on exit.
+/// A pause caused by a gamepad disconnecting shows a reconnect prompt instead.
+pub fn spawn_pause_overlay(
+    mut commands: Commands,
+    styles: Res<TextStyles>,
+    disconnected: Option<Res<ControllerDisconnected>>,
+) {
+    commands
+        .spawn((
+            menu_root_node(),
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            GlobalZIndex(10),
+            DespawnOnExit(AppState::Paused),
+        ))
+        .with_children(|parent| {
+            if disconnected.is_some() {
+                parent.spawn(menu_title(&styles, "CONTROLLER DISCONNECTED"));
+                parent.spawn((
+                    Text::new("Reconnect or press any key"),
+                    styles.font(TextRole::Detail),
+                    TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                ));
+                return;
+            }
+            parent.spawn(menu_title(&styles, "PAUSED"));
+            spawn_menu_button(parent, &styles, "Resume", PauseMenuButton::Resume);
+            spawn_menu_button(parent, &styles, "Restart", PauseMenuButton::Restart);
+            spawn_menu_button(parent, &styles, "Quit to Menu", PauseMenuButton::Quit);
+        });
+}
@@ -0,0 +1,159 @@
+use crate::game::{AppState, GameSet, InGame};
+use crate::replay::finish_highlight_clips;
+use crate::settings::{GameSpeed, training_mode, versus_mode};
+use crate::stats::record_high_score;
+use crate::ui::advanced::{handle_advanced_input, spawn_advanced_screen};
+use crate::ui::bestiary::{handle_bestiary_input, spawn_bestiary_screen};
+use crate::ui::character_select::{handle_character_select_input, spawn_character_select_screen};
+use crate::ui::combo::{spawn_combo_display, update_combo_display};
+use crate::ui::continue_screen::{spawn_continue_screen, update_continue_screen};
+use crate::ui::countdown::{
+    play_countdown_ticks, spawn_countdown, update_countdown, update_score_popups,
+};
+use crate::ui::enemy_health_bar::{spawn_enemy_health_bars, update_enemy_health_bars};
+use crate::ui::extras::{
+    FavoritePlayback, handle_extras_input, run_favorite_playback, spawn_extras_screen,
+};
+use crate::ui::frame_data::{spawn_frame_data_overlay, update_frame_data_overlay};
+use crate::ui::game_over::{handle_game_over_input, spawn_game_over_screen};
+use crate::ui::go_prompt::{spawn_go_prompt, update_go_prompt};
+use crate::ui::highlight_reel::{HighlightReel, run_highlight_reel};
+use crate::ui::hit_indicator::{spawn_hit_indicators, update_hit_indicators};
+use crate::ui::hud::{
+    spawn_hud, update_health_bar, update_lives_text, update_special_meter_bar, update_ui,
+};
+use crate::ui::hud_theme::HudThemes;
+use crate::ui::intro_card::{introduce_new_enemies, update_intro_cards};
+use crate::ui::leaderboard::refresh_leaderboard_panel;
+use crate::ui::main_menu::{handle_main_menu_input, spawn_main_menu};
+use crate::ui::pause::{handle_pause_menu_buttons, spawn_pause_overlay, toggle_pause};
+use crate::ui::records::{spawn_records_hud, update_record_banner, update_records_hud};
+use crate::ui::rest::{handle_rest_input, rotate_tips, spawn_rest_screen};
+use crate::ui::round_banner::{spawn_round_banner, update_round_banner};
+use crate::ui::stage_clear::{handle_stage_clear_input, spawn_stage_clear_screen};
+use crate::ui::style::{spawn_style_display, update_style_display};
+use crate::ui::text_style::{TextStyles, restyle_text};
+use crate::ui::toast::{show_save_failure_toasts, show_unlock_toasts, update_toasts};
+use crate::ui::versus::{spawn_versus_hud, spawn_versus_win_screen, update_versus_hud};
+use crate::ui::vignette::{spawn_damage_vignette, update_damage_vignette};
+use crate::ui::widgets::{MenuFocus, navigate_menu_focus, update_button_colors};
+use bevy::prelude::*;
+
+/// Menu screens, the in-game HUD, overlays, and toasts
+pub struct UiPlugin;
+
+impl Plugin for UiPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MenuFocus>()
+            .init_resource::<TextStyles>()
+            .insert_resource(HudThemes::load())
+            // State setup/teardown (entities tagged DespawnOnExit are removed automatically)
+            .add_systems(OnEnter(AppState::MainMenu), spawn_main_menu)
+            .add_systems(
+                OnEnter(InGame),
+                (
+                    (
+                        spawn_hud,
+                        spawn_countdown,
+                        spawn_combo_display,
+                        spawn_style_display,
+                        spawn_records_hud,
+                        spawn_damage_vignette,
+                        spawn_go_prompt,
+                        spawn_continue_screen,
+                    )
+                        .run_if(not(versus_mode)),
+                    spawn_versus_hud.run_if(versus_mode),
+                    spawn_round_banner,
+                    spawn_hit_indicators,
+                    spawn_frame_data_overlay.run_if(training_mode),
+                ),
+            )
+            .add_systems(OnEnter(AppState::Paused), spawn_pause_overlay)
+            .add_systems(
+                OnEnter(AppState::GameOver),
+                (
+                    spawn_game_over_screen
+                        .after(record_high_score)
+                        .run_if(not(versus_mode)),
+                    spawn_versus_win_screen.run_if(versus_mode),
+                )
+                    .after(finish_highlight_clips),
+            )
+            .add_systems(OnEnter(AppState::StageClear), spawn_stage_clear_screen)
+            .add_systems(OnEnter(AppState::Rest), spawn_rest_screen)
+            .add_systems(OnEnter(AppState::Bestiary), spawn_bestiary_screen)
+            .add_systems(OnEnter(AppState::Extras), spawn_extras_screen)
+            .add_systems(OnEnter(AppState::Advanced), spawn_advanced_screen)
+            .add_systems(
+                OnEnter(AppState::CharacterSelect),
+                spawn_character_select_screen,
+            )
+            .add_systems(
+                Update,
+                (
+                    (
+                        navigate_menu_focus,
+                        update_button_colors,
+                        handle_main_menu_input.run_if(in_state(AppState::MainMenu)),
+                        refresh_leaderboard_panel.run_if(
+                            in_state(AppState::MainMenu).and(resource_changed::<GameSpeed>),
+                        ),
+                        toggle_pause
+                            .run_if(in_state(AppState::Playing).or(in_state(AppState::Paused))),
+                        handle_pause_menu_buttons.run_if(in_state(AppState::Paused)),
+                        (
+                            handle_game_over_input.run_if(not(resource_exists::<HighlightReel>)),
+                            run_highlight_reel,
+                        )
+                            .chain()
+                            .run_if(in_state(AppState::GameOver)),
+                        handle_stage_clear_input.run_if(in_state(AppState::StageClear)),
+                        (rotate_tips, handle_rest_input).run_if(in_state(AppState::Rest)),
+                        handle_bestiary_input.run_if(in_state(AppState::Bestiary)),
+                        (
+                            handle_extras_input.run_if(not(resource_exists::<FavoritePlayback>)),
+                            run_favorite_playback,
+                        )
+                            .chain()
+                            .run_if(in_state(AppState::Extras)),
+                        handle_advanced_input.run_if(in_state(AppState::Advanced)),
+                        handle_character_select_input.run_if(in_state(AppState::CharacterSelect)),
+                    )
+                        .chain()
+                        .in_set(GameSet::Menus),
+                    restyle_text
+                        .run_if(resource_changed::<TextStyles>)
+                        .after(GameSet::Menus),
+                    (show_save_failure_toasts, show_unlock_toasts, update_toasts)
+                        .chain()
+                        .after(GameSet::Menus),
+                    update_score_popups.in_set(GameSet::Effects),
+                    (
+                        introduce_new_enemies,
+                        update_intro_cards,
+                        update_ui,
+                        update_lives_text,
+                        update_health_bar,
+                        update_special_meter_bar,
+                        update_combo_display,
+                        update_style_display,
+                        update_records_hud,
+                        update_record_banner,
+                        (update_countdown, play_countdown_ticks).chain(),
+                        update_round_banner,
+                        update_go_prompt,
+                        update_continue_screen,
+                        update_hit_indicators,
+                        update_damage_vignette.run_if(not(versus_mode)),
+                        spawn_enemy_health_bars,
+                        update_enemy_health_bars,
+                        update_frame_data_overlay.run_if(training_mode),
+                        update_versus_hud.run_if(versus_mode),
+                    )
+                        .chain()
+                        .in_set(GameSet::Hud),
+                ),
+            );
+    }
+}
@@ -0,0 +1,98 @@
+use crate::game::InGame;
+use crate::stats::{PersonalBestEvent, PersonalRecord, RunRecords};
+use crate::ui::text_style::{TextRole, TextStyles};
+use bevy::prelude::*;
+
+/// Seconds a personal-best banner stays up (fading over the whole time)
+const BANNER_SECONDS: f32 = 2.5;
+
+/// Small HUD line under the score with the no-hit streak and session best combo
+#[derive(Component)]
+pub struct RecordsText;
+
+/// Banner announcing a beaten personal record
+#[derive(Component)]
+pub struct RecordBanner {
+    pub timer: Timer,
+}
+
+/// Spawn the records line and the (initially empty) personal-best banner
+///
+/// Runs on entering InGame alongside the HUD.
+pub fn spawn_records_hud(mut commands: Commands, styles: Res<TextStyles>) {
+    commands.spawn((
+        Text::new(""),
+        styles.font(TextRole::Small),
+        TextColor(Color::srgb(0.75, 0.75, 0.75)),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(20.0),
+            top: Val::Px(58.0),
+            ..default()
+        },
+        RecordsText,
+        DespawnOnExit(InGame),
+    ));
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(22.0),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            DespawnOnExit(InGame),
+        ))
+        .with_children(|parent| {
+            let mut timer = Timer::from_seconds(BANNER_SECONDS, TimerMode::Once);
+            timer.finish();
+            parent.spawn((
+                Text::new(""),
+                styles.font(TextRole::Emphasis),
+                TextColor(Color::srgb(0.4, 1.0, 0.6)),
+                RecordBanner { timer },
+            ));
+        });
+}
+
+/// Keep the records line in sync with the run's streak and session best
+pub fn update_records_hud(
+    records: Res<RunRecords>,
+    mut text_query: Query<&mut Text, With<RecordsText>>,
+) {
+    if let Ok(mut text) = text_query.single_mut() {
+        **text = format!(
+            "No-hit streak: {}   Best combo: {}",
+            records.no_hit_streak, records.session_best_combo
+        );
+    }
+}
+
+/// Show a banner for each beaten personal record, fading it out over time
+pub fn update_record_banner(
+    time: Res<Time>,
+    mut best_events: MessageReader<PersonalBestEvent>,
+    mut banner_query: Query<(&mut Text, &mut TextColor, &mut RecordBanner)>,
+) {
+    let Ok((mut text, mut color, mut banner)) = banner_query.single_mut() else {
+        return;
+    };
+
+    for event in best_events.read() {
+        **text = match event.record {
+            PersonalRecord::BestCombo => "NEW PERSONAL BEST COMBO!",
+            PersonalRecord::NoHitStreak => "NEW NO-HIT STREAK RECORD!",
+        }
+        .to_string();
+        banner.timer.reset();
+    }
+
+    banner.timer.tick(time.delta());
+    if banner.timer.is_finished() {
+        text.clear();
+    } else {
+        color.0.set_alpha(1.0 - banner.timer.fraction());
+    }
+}
@@ -0,0 +1,109 @@
+use crate::game::AppState;
+use crate::input::PlayerActions;
+use crate::stats::LifetimeStats;
+use crate::ui::text_style::{TextRole, TextStyles};
+use crate::ui::widgets::{menu_root_node, menu_title};
+use bevy::prelude::*;
+use rand::Rng;
+
+/// Seconds each tip stays on screen before rotating to the next
+const TIP_ROTATE_SECONDS: f32 = 6.0;
+
+/// Gameplay tips shown on the rest screen, one per line
+const TIPS: &str = include_str!("../../assets/tips.txt");
+
+fn tips() -> Vec<&'static str> {
+    TIPS.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// Tip text on the rest screen, rotating through the tips file
+#[derive(Component)]
+pub struct TipText {
+    pub index: usize,
+    pub timer: Timer,
+}
+
+/// Spawn the between-run rest screen (tip, lifetime stat teaser, restart prompt)
+///
+/// Runs on entering Rest; the screen is despawned automatically on exit.
+pub fn spawn_rest_screen(
+    mut commands: Commands,
+    styles: Res<TextStyles>,
+    stats: Res<LifetimeStats>,
+) {
+    let tips = tips();
+    let index = if tips.is_empty() {
+        0
+    } else {
+        rand::thread_rng().gen_range(0..tips.len())
+    };
+    let teaser = stats.random_teaser();
+
+    commands
+        .spawn((
+            menu_root_node(),
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            DespawnOnExit(AppState::Rest),
+        ))
+        .with_children(|parent| {
+            parent.spawn(menu_title(&styles, "CATCH YOUR BREATH"));
+            parent.spawn((
+                Text::new(tips.get(index).copied().unwrap_or_default()),
+                styles.font(TextRole::Body),
+                TextColor(Color::WHITE),
+                TextLayout::new_with_justify(Justify::Center),
+                Node {
+                    max_width: Val::Percent(70.0),
+                    ..default()
+                },
+                TipText {
+                    index,
+                    timer: Timer::from_seconds(TIP_ROTATE_SECONDS, TimerMode::Repeating),
+                },
+            ));
+            if let Some(teaser) = teaser {
+                parent.spawn((
+                    Text::new(teaser),
+                    styles.font(TextRole::Caption),
+                    TextColor(Color::srgb(1.0, 0.85, 0.2)),
+                ));
+            }
+            parent.spawn((
+                Text::new("Press R to Fight Again, Enter for Main Menu"),
+                styles.font(TextRole::Body),
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+            ));
+        });
+}
+
+/// Advance to the next tip every few seconds
+pub fn rotate_tips(time: Res<Time>, mut tip_query: Query<(&mut Text, &mut TipText)>) {
+    let tips = tips();
+    if tips.is_empty() {
+        return;
+    }
+
+    for (mut text, mut tip) in tip_query.iter_mut() {
+        tip.timer.tick(time.delta());
+        if tip.timer.just_finished() {
+            tip.index = (tip.index + 1) % tips.len();
+            **text = tips[tip.index].to_string();
+        }
+    }
+}
+
+/// Quick-restart (R or the Jump action) or continue to the main menu (Enter / M / Back)
+pub fn handle_rest_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    actions: Res<PlayerActions>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if keyboard.just_pressed(KeyCode::KeyR) || actions.jump {
+        next_state.set(AppState::Playing);
+    } else if actions.confirm || keyboard.just_pressed(KeyCode::KeyM) || actions.back {
+        next_state.set(AppState::MainMenu);
+    }
+}
@@ -0,0 +1,43 @@
+use crate::game::{InGame, Round};
+use crate::ui::text_style::{TextRole, TextStyles};
+use bevy::prelude::*;
+
+/// Big centered round call ("ROUND 1", "FIGHT!", "K.O.")
+#[derive(Component)]
+pub struct RoundBannerText;
+
+/// Spawn the (initially empty) round banner overlay
+///
+/// Runs on entering InGame alongside the HUD; `update_round_banner` fills it
+/// in from the `Round` ceremony.
+pub fn spawn_round_banner(mut commands: Commands, styles: Res<TextStyles>) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(30.0),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            DespawnOnExit(InGame),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                styles.font(TextRole::Banner),
+                TextColor(Color::srgb(1.0, 0.85, 0.2)),
+                RoundBannerText,
+            ));
+        });
+}
+
+/// Show the banner for the current phase of the round ceremony
+pub fn update_round_banner(
+    round: Res<Round>,
+    mut banner_query: Query<&mut Text, With<RoundBannerText>>,
+) {
+    if let Ok(mut text) = banner_query.single_mut() {
+        **text = round.banner().unwrap_or_default();
+    }
+}
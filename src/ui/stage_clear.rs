@@ -0,0 +1,103 @@
+use crate::combat::Health;
+use crate::game::{AppState, GameState};
+use crate::input::PlayerActions;
+use crate::levels::{Levels, StageProgress};
+use crate::player::Player;
+use crate::ui::text_style::{TextRole, TextStyles};
+use crate::ui::widgets::menu_root_node;
+use bevy::prelude::*;
+
+/// Real-time seconds before the stage clear screen takes input, so a button
+/// mashed at the end of the fight doesn't skip it
+const STAGE_CLEAR_INPUT_DELAY: f32 = 1.0;
+
+/// Root of the stage clear screen, timing how long it has been up
+#[derive(Component)]
+pub struct StageClearScreen {
+    shown: Timer,
+}
+
+/// Show the cleared stage, the score so far, and the stage that comes next
+///
+/// Runs on entering StageClear, over the frozen world; the screen is
+/// despawned automatically on exit.
+pub fn spawn_stage_clear_screen(
+    mut commands: Commands,
+    styles: Res<TextStyles>,
+    levels: Res<Levels>,
+    progress: Res<StageProgress>,
+    game_state: Res<GameState>,
+    health_query: Query<&Health, With<Player>>,
+) {
+    let stage = levels.stage(progress.index);
+    let next = levels.stage(progress.index + 1);
+    commands
+        .spawn((
+            menu_root_node(),
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            GlobalZIndex(10),
+            StageClearScreen {
+                shown: Timer::from_seconds(STAGE_CLEAR_INPUT_DELAY, TimerMode::Once),
+            },
+            DespawnOnExit(AppState::StageClear),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(format!("STAGE {} CLEAR!", progress.number())),
+                styles.font(TextRole::Title),
+                TextColor(Color::srgb(1.0, 0.85, 0.2)),
+            ));
+            parent.spawn((
+                Text::new(stage.name.clone()),
+                styles.font(TextRole::Subtitle),
+                TextColor(Color::WHITE),
+            ));
+            parent.spawn((
+                Text::new(format!("Score: {}", game_state.score)),
+                styles.font(TextRole::Emphasis),
+                TextColor(Color::WHITE),
+            ));
+            if let Ok(health) = health_query.single() {
+                parent.spawn((
+                    Text::new(format!(
+                        "Health: {}/{} (carried into the next stage)",
+                        health.current, health.max
+                    )),
+                    styles.font(TextRole::Body),
+                    TextColor(Color::WHITE),
+                ));
+            }
+            parent.spawn((
+                Text::new(format!(
+                    "Next: Stage {} - {}",
+                    progress.number() + 1,
+                    next.name
+                )),
+                styles.font(TextRole::Emphasis),
+                TextColor(Color::srgb(1.0, 0.85, 0.2)),
+            ));
+            parent.spawn((
+                Text::new("Press Enter to Continue"),
+                styles.font(TextRole::Body),
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+            ));
+        });
+}
+
+/// Continue to the next stage on confirm, once the screen has been up for
+/// `STAGE_CLEAR_INPUT_DELAY`
+///
+/// `enter_next_stage` sets the next stage up on the way back to Playing.
+pub fn handle_stage_clear_input(
+    real_time: Res<Time<Real>>,
+    actions: Res<PlayerActions>,
+    mut screen_query: Query<&mut StageClearScreen>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let Ok(mut screen) = screen_query.single_mut() else {
+        return;
+    };
+    if screen.shown.tick(real_time.delta()).is_finished() && actions.confirm {
+        next_state.set(AppState::Playing);
+    }
+}
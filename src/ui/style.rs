@@ -0,0 +1,48 @@
+use crate::combat::StyleMeter;
+use crate::game::InGame;
+use crate::player::Player;
+use crate::ui::text_style::{TextRole, TextStyles};
+use bevy::prelude::*;
+
+/// "STYLE: A x2" display on the right side of the screen
+#[derive(Component)]
+pub struct StyleText;
+
+/// Spawn the style rank display, mirroring the combo display
+///
+/// Runs on entering InGame alongside the HUD.
+pub fn spawn_style_display(mut commands: Commands, styles: Res<TextStyles>) {
+    commands.spawn((
+        Text::new(""),
+        styles.font(TextRole::Heading),
+        TextColor(Color::WHITE),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(20.0),
+            top: Val::Percent(30.0),
+            ..default()
+        },
+        StyleText,
+        DespawnOnExit(InGame),
+    ));
+}
+
+/// Show the current style rank and its score multiplier, fading as the meter empties
+pub fn update_style_display(
+    style_query: Query<&StyleMeter, With<Player>>,
+    mut text_query: Query<(&mut Text, &mut TextColor), With<StyleText>>,
+) {
+    let (Ok(style), Ok((mut text, mut color))) = (style_query.single(), text_query.single_mut())
+    else {
+        return;
+    };
+
+    let rank = style.rank();
+    let multiplier = rank.score_multiplier();
+    **text = if multiplier > 1 {
+        format!("STYLE: {} x{}", rank.label(), multiplier)
+    } else {
+        format!("STYLE: {}", rank.label())
+    };
+    color.0 = rank.color().with_alpha(0.4 + 0.6 * style.fraction());
+}
@@ -0,0 +1,274 @@
+use crate::persistence::{parse_key_values, parsed};
+use bevy::prelude::*;
+use bevy::text::{FontSmoothing, LineHeight};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Font and text size definitions, relative to the working directory (like the asset folder)
+const TEXT_STYLES_PATH: &str = "assets/text_styles.txt";
+/// Asset folder the font paths are relative to
+const ASSETS_DIR: &str = "assets";
+/// Line height of the readable font, relative to its size, used when assets/text_styles.txt doesn't set one
+const READABLE_LINE_HEIGHT: f32 = 1.5;
+
+/// What a piece of text is for, which decides its size
+///
+/// Every UI text picks a role instead of an ad-hoc size, so new screens
+/// line up with the existing ones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TextRole {
+    /// Final-rush countdown number
+    Countdown,
+    /// Round call and knockout banner
+    Banner,
+    /// Menu and screen titles
+    Title,
+    /// Enemy intro card names and the combo counter
+    Heading,
+    /// Final score and the final-rush banner
+    Subtitle,
+    /// Callouts: high score placement, personal bests, score popups
+    Emphasis,
+    /// Menu button labels
+    Button,
+    /// Tips and prompts on menu screens
+    Body,
+    /// Score, health, and time (the HUD theme sets its own size)
+    Hud,
+    /// Floating damage numbers
+    DamageNumber,
+    /// Section headings and one-line descriptions
+    Caption,
+    /// Leaderboard rows and bestiary details
+    Detail,
+    /// Prompts spoken by things in the world (interactables)
+    Dialogue,
+    /// Secondary HUD lines
+    Small,
+}
+
+impl TextRole {
+    pub const ALL: [TextRole; 14] = [
+        TextRole::Countdown,
+        TextRole::Banner,
+        TextRole::Title,
+        TextRole::Heading,
+        TextRole::Subtitle,
+        TextRole::Emphasis,
+        TextRole::Button,
+        TextRole::Body,
+        TextRole::Hud,
+        TextRole::DamageNumber,
+        TextRole::Caption,
+        TextRole::Detail,
+        TextRole::Dialogue,
+        TextRole::Small,
+    ];
+
+    /// Key of the role in assets/text_styles.txt
+    fn key(self) -> &'static str {
+        match self {
+            TextRole::Countdown => "countdown",
+            TextRole::Banner => "banner",
+            TextRole::Title => "title",
+            TextRole::Heading => "heading",
+            TextRole::Subtitle => "subtitle",
+            TextRole::Emphasis => "emphasis",
+            TextRole::Button => "button",
+            TextRole::Body => "body",
+            TextRole::Hud => "hud",
+            TextRole::DamageNumber => "damage_number",
+            TextRole::Caption => "caption",
+            TextRole::Detail => "detail",
+            TextRole::Dialogue => "dialogue",
+            TextRole::Small => "small",
+        }
+    }
+
+    /// Compiled size, used when assets/text_styles.txt doesn't set one
+    fn default_size(self) -> f32 {
+        match self {
+            TextRole::Countdown => 200.0,
+            TextRole::Banner => 120.0,
+            TextRole::Title => 80.0,
+            TextRole::Heading => 52.0,
+            TextRole::Subtitle => 40.0,
+            TextRole::Emphasis => 36.0,
+            TextRole::Button => 32.0,
+            TextRole::Body => 30.0,
+            TextRole::Hud => 30.0,
+            TextRole::DamageNumber => 28.0,
+            TextRole::Caption => 26.0,
+            TextRole::Detail => 22.0,
+            TextRole::Dialogue => 22.0,
+            TextRole::Small => 20.0,
+        }
+    }
+}
+
+/// The game's font and the text size of every `TextRole`
+///
+/// Loaded once at startup from assets/text_styles.txt. `fonts` lists font
+/// files under assets/ in order of preference; the first one that exists is
+/// used, and if none do the text falls back to Bevy's built-in font. Sizes
+/// start from the compiled defaults, so they can be tuned without
+/// recompiling.
+///
+/// The readable font (`readable_fonts`, a dyslexia-friendly face, drawn
+/// anti-aliased with taller lines) can be switched on at runtime with
+/// `set_readable`; `restyle_text` then swaps it into every text already on
+/// screen.
+#[derive(Resource)]
+pub struct TextStyles {
+    font: Handle<Font>,
+    smoothing: FontSmoothing,
+    readable_font: Handle<Font>,
+    readable_line_height: f32,
+    readable: bool,
+    sizes: HashMap<TextRole, f32>,
+}
+
+impl FromWorld for TextStyles {
+    fn from_world(world: &mut World) -> Self {
+        let contents = fs::read_to_string(TEXT_STYLES_PATH).unwrap_or_else(|err| {
+            warn!("Using the default font and text sizes ({TEXT_STYLES_PATH}: {err})");
+            String::new()
+        });
+        Self::from_key_values(
+            &parse_key_values(&contents),
+            world.resource::<AssetServer>(),
+        )
+    }
+}
+
+impl TextStyles {
+    /// `TextFont` for a role
+    pub fn font(&self, role: TextRole) -> TextFont {
+        self.sized_font(self.size(role))
+    }
+
+    /// `TextFont` in the game's font at an explicit size (e.g. from a HUD theme)
+    pub fn sized_font(&self, font_size: f32) -> TextFont {
+        self.restyled(TextFont {
+            font_size,
+            ..default()
+        })
+    }
+
+    /// Whether the readable font is in use
+    pub fn readable(&self) -> bool {
+        self.readable
+    }
+
+    /// Switch between the game font and the readable font
+    pub fn set_readable(&mut self, readable: bool) {
+        self.readable = readable;
+    }
+
+    /// Whether a text was styled from this registry (in either font)
+    fn styles(&self, text_font: &TextFont) -> bool {
+        text_font.font == self.font || text_font.font == self.readable_font
+    }
+
+    /// A text's `TextFont` switched to the font in use, keeping its size
+    fn restyled(&self, text_font: TextFont) -> TextFont {
+        if self.readable {
+            TextFont {
+                font: self.readable_font.clone(),
+                font_smoothing: FontSmoothing::AntiAliased,
+                line_height: LineHeight::RelativeToFont(self.readable_line_height),
+                ..text_font
+            }
+        } else {
+            TextFont {
+                font: self.font.clone(),
+                font_smoothing: self.smoothing,
+                line_height: LineHeight::default(),
+                ..text_font
+            }
+        }
+    }
+
+    /// Font size of a role
+    pub fn size(&self, role: TextRole) -> f32 {
+        self.sizes
+            .get(&role)
+            .copied()
+            .unwrap_or_else(|| role.default_size())
+    }
+
+    fn from_key_values(values: &HashMap<&str, &str>, asset_server: &AssetServer) -> Self {
+        let path = first_font(values, "fonts");
+        let font = path.map_or_else(Handle::default, |path| asset_server.load(path.to_string()));
+        let readable_font = first_font(values, "readable_fonts")
+            .map_or_else(Handle::default, |path| asset_server.load(path.to_string()));
+        let readable_line_height = values
+            .get("readable_line_height")
+            .and_then(|value| parsed(("readable_line_height".to_string(), value)))
+            .unwrap_or(READABLE_LINE_HEIGHT);
+
+        // Pixel-art fonts stay crisp without anti-aliasing; the built-in font needs it
+        let pixel_font = values
+            .get("pixel_font")
+            .and_then(|value| parsed(("pixel_font".to_string(), value)))
+            .unwrap_or(true);
+        let smoothing = if path.is_some() && pixel_font {
+            FontSmoothing::None
+        } else {
+            FontSmoothing::AntiAliased
+        };
+
+        let sizes = TextRole::ALL
+            .into_iter()
+            .filter_map(|role| {
+                let key = format!("{}.size", role.key());
+                let size = values
+                    .get(key.as_str())
+                    .and_then(|value| parsed((key.clone(), value)))?;
+                Some((role, size))
+            })
+            .collect();
+
+        Self {
+            font,
+            smoothing,
+            readable_font,
+            readable_line_height,
+            readable: false,
+            sizes,
+        }
+    }
+}
+
+/// First font listed under `key` that exists under assets/
+fn first_font<'a>(values: &HashMap<&str, &'a str>, key: &str) -> Option<&'a str> {
+    values
+        .get(key)
+        .copied()
+        .into_iter()
+        .flat_map(|paths| paths.split(','))
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .find(|path| {
+            let found = Path::new(ASSETS_DIR).join(path).is_file();
+            if !found {
+                info!("Font {path} not found under {ASSETS_DIR}/, trying the next one");
+            }
+            found
+        })
+}
+
+/// Swap the font in use into every text styled from `TextStyles`, so switching
+/// to (or from) the readable font re-lays out what's already on screen
+///
+/// Runs whenever `TextStyles` changes. Texts in a HUD theme's own font are
+/// left alone; the HUD picks the readable font up when it's next spawned.
+pub fn restyle_text(styles: Res<TextStyles>, mut text_query: Query<&mut TextFont>) {
+    for mut text_font in text_query.iter_mut() {
+        if styles.styles(&text_font) {
+            let restyled = styles.restyled(text_font.clone());
+            *text_font = restyled;
+        }
+    }
+}
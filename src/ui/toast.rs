@@ -0,0 +1,156 @@
+use crate::persistence::SaveCompletedEvent;
+use crate::player::Characters;
+use crate::stats::LifetimeStats;
+use crate::ui::text_style::{TextRole, TextStyles};
+use bevy::prelude::*;
+
+/// Real-time seconds a toast stays up
+const TOAST_SECONDS: f32 = 4.0;
+/// Real-time seconds a toast takes to fade out at the end
+const TOAST_FADE_SECONDS: f32 = 1.0;
+/// Background of a failure toast
+const FAILURE_BACKGROUND: Color = Color::srgba(0.5, 0.05, 0.05, 0.85);
+/// Background of an unlock toast
+const UNLOCK_BACKGROUND: Color = Color::srgba(0.45, 0.32, 0.05, 0.85);
+
+/// Short message at the bottom of the screen that fades away on its own
+///
+/// Lives across state changes (a save that fails while the game over screen
+/// closes still shows) and runs on real time, so it fades while paused.
+#[derive(Component)]
+pub struct Toast {
+    pub timer: Timer,
+}
+
+/// Show a toast naming the files whose save failed
+///
+/// Saves finish off the main thread, so this is the only place a failed
+/// save reaches the player; the error itself is in the log.
+pub fn show_save_failure_toasts(
+    mut commands: Commands,
+    styles: Res<TextStyles>,
+    mut completed_events: MessageReader<SaveCompletedEvent>,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    let failed: Vec<&str> = completed_events
+        .read()
+        .filter(|event| event.error.is_some())
+        .map(|event| event.file.as_str())
+        .collect();
+    if failed.is_empty() {
+        return;
+    }
+
+    spawn_toast(
+        &mut commands,
+        &styles,
+        &toast_query,
+        format!("Couldn't save {} (see the log)", failed.join(", ")),
+        FAILURE_BACKGROUND,
+    );
+}
+
+/// Show a toast naming the costumes unlocked since the last check (the
+/// hardcore costume, when a stage is cleared on hardcore)
+///
+/// Checked whenever the lifetime stats change. The first check only notes
+/// what is already unlocked, so nothing toasts at startup.
+pub fn show_unlock_toasts(
+    mut commands: Commands,
+    styles: Res<TextStyles>,
+    stats: Res<LifetimeStats>,
+    characters: Res<Characters>,
+    toast_query: Query<Entity, With<Toast>>,
+    mut unlocked: Local<Option<Vec<bool>>>,
+) {
+    if unlocked.is_some() && !stats.is_changed() {
+        return;
+    }
+    let costumes = characters.costumes();
+    let now: Vec<bool> = costumes
+        .iter()
+        .map(|costume| costume.unlock.is_met(&stats))
+        .collect();
+    let Some(before) = unlocked.replace(now.clone()) else {
+        return;
+    };
+
+    let names: Vec<&str> = costumes
+        .iter()
+        .zip(before.iter().zip(&now))
+        .filter(|(_, (was, is))| !**was && **is)
+        .map(|(costume, _)| costume.name.as_str())
+        .collect();
+    if names.is_empty() {
+        return;
+    }
+    spawn_toast(
+        &mut commands,
+        &styles,
+        &toast_query,
+        format!("Costume unlocked: {}", names.join(", ")),
+        UNLOCK_BACKGROUND,
+    );
+}
+
+/// Spawn a toast, replacing any still showing
+fn spawn_toast(
+    commands: &mut Commands,
+    styles: &TextStyles,
+    toast_query: &Query<Entity, With<Toast>>,
+    text: String,
+    background: Color,
+) {
+    for toast in toast_query.iter() {
+        commands.entity(toast).despawn();
+    }
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(40.0),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            GlobalZIndex(20),
+            Toast {
+                timer: Timer::from_seconds(TOAST_SECONDS, TimerMode::Once),
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(text),
+                styles.font(TextRole::Detail),
+                TextColor(Color::WHITE),
+                BackgroundColor(background),
+                Node {
+                    padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                    ..default()
+                },
+            ));
+        });
+}
+
+/// Fade toasts out over their last `TOAST_FADE_SECONDS` and despawn them when done
+pub fn update_toasts(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut toast_query: Query<(Entity, &mut Toast, &Children)>,
+    mut text_query: Query<(&mut TextColor, &mut BackgroundColor)>,
+) {
+    for (entity, mut toast, children) in toast_query.iter_mut() {
+        toast.timer.tick(time.delta());
+        if toast.timer.is_finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let alpha = (toast.timer.remaining_secs() / TOAST_FADE_SECONDS).min(1.0);
+        for child in children.iter() {
+            if let Ok((mut text_color, mut background)) = text_query.get_mut(child) {
+                text_color.0.set_alpha(alpha);
+                background.0 = FAILURE_BACKGROUND.with_alpha(FAILURE_BACKGROUND.alpha() * alpha);
+            }
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use crate::combat::{Health, SpecialMeter};
+use crate::game::{AppState, InGame};
+use crate::player::{Player, PlayerTwo};
+use crate::replay::Highlights;
+use crate::ui::highlight_reel::{ResultsScreen, highlight_reel_hint};
+use crate::ui::hud_theme::HudThemes;
+use crate::ui::text_style::{TextRole, TextStyles};
+use crate::ui::widgets::menu_root_node;
+use crate::versus::{VersusMatch, player_slot};
+use bevy::prelude::*;
+
+/// Distance of each player's bars from their side of the screen, in pixels
+const BAR_INSET: f32 = 20.0;
+/// Versus health bars are wider than the single-player bar; they're the HUD's centerpiece
+const HEALTH_BAR_SCALE: f32 = 1.5;
+
+/// Filled portion of a player's versus health bar (by player slot)
+#[derive(Component)]
+pub struct VersusHealthFill(pub usize);
+
+/// Filled portion of a player's versus special meter bar (by player slot)
+#[derive(Component)]
+pub struct VersusMeterFill(pub usize);
+
+/// Round clock between the health bars
+#[derive(Component)]
+pub struct VersusTimerText;
+
+/// Rounds taken by each player, under the round clock
+#[derive(Component)]
+pub struct VersusWinsText;
+
+/// Spawn the versus HUD: player 1's bars on the left, player 2's on the right,
+/// and the round clock and round wins between them
+///
+/// Replaces the score HUD in versus games; colors and sizes come from the selected theme.
+pub fn spawn_versus_hud(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    styles: Res<TextStyles>,
+    themes: Res<HudThemes>,
+) {
+    let theme = themes.current();
+    let text_font = theme.text_font(&asset_server, &styles);
+    let health_width = theme.health_size.x * HEALTH_BAR_SCALE;
+
+    for slot in 0..2 {
+        // Player 2's bars are anchored to the right edge and drain toward it
+        let (left, right, justify) = if slot == 0 {
+            (Val::Px(BAR_INSET), Val::Auto, JustifyContent::FlexStart)
+        } else {
+            (Val::Auto, Val::Px(BAR_INSET), JustifyContent::FlexEnd)
+        };
+
+        commands
+            .spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    left,
+                    right,
+                    top: Val::Px(20.0),
+                    width: Val::Px(health_width),
+                    height: Val::Px(theme.health_size.y),
+                    justify_content: justify,
+                    ..default()
+                },
+                BackgroundColor(theme.health_background),
+                DespawnOnExit(InGame),
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Node {
+                        width: Val::Percent(100.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(theme.health_color),
+                    VersusHealthFill(slot),
+                ));
+            });
+
+        commands
+            .spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    left,
+                    right,
+                    top: Val::Px(30.0 + theme.health_size.y),
+                    width: Val::Px(theme.meter_size.x),
+                    height: Val::Px(theme.meter_size.y),
+                    justify_content: justify,
+                    ..default()
+                },
+                BackgroundColor(theme.meter_background),
+                DespawnOnExit(InGame),
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Node {
+                        width: Val::Percent(0.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(theme.meter_fill),
+                    VersusMeterFill(slot),
+                ));
+            });
+    }
+
+    // Round clock and wins - top center
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                top: Val::Px(14.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            DespawnOnExit(InGame),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                text_font.clone(),
+                TextColor(theme.text_color),
+                TextShadow::default(),
+                VersusTimerText,
+            ));
+            parent.spawn((
+                Text::new(""),
+                text_font,
+                TextColor(theme.text_color),
+                TextShadow::default(),
+                VersusWinsText,
+            ));
+        });
+}
+
+/// Resize each player's health and meter fills, and refresh the round clock and wins
+pub fn update_versus_hud(
+    themes: Res<HudThemes>,
+    versus: Option<Res<VersusMatch>>,
+    player_query: Query<(&Health, &SpecialMeter, Has<PlayerTwo>), With<Player>>,
+    mut health_fills: Query<(&VersusHealthFill, &mut Node), Without<VersusMeterFill>>,
+    mut meter_fills: Query<(&VersusMeterFill, &mut Node, &mut BackgroundColor)>,
+    mut timer_text: Query<&mut Text, (With<VersusTimerText>, Without<VersusWinsText>)>,
+    mut wins_text: Query<&mut Text, (With<VersusWinsText>, Without<VersusTimerText>)>,
+) {
+    let theme = themes.current();
+
+    for (health, meter, player_two) in player_query.iter() {
+        let slot = player_slot(player_two);
+        let health_fraction = health.current.max(0) as f32 / health.max as f32;
+        for (fill, mut node) in health_fills.iter_mut() {
+            if fill.0 == slot {
+                node.width = Val::Percent(health_fraction * 100.0);
+            }
+        }
+        for (fill, mut node, mut background) in meter_fills.iter_mut() {
+            if fill.0 == slot {
+                node.width = Val::Percent(meter.fraction() * 100.0);
+                background.0 = if meter.is_full() {
+                    theme.meter_full
+                } else {
+                    theme.meter_fill
+                };
+            }
+        }
+    }
+
+    let Some(versus) = versus else {
+        return;
+    };
+    if let Ok(mut text) = timer_text.single_mut() {
+        **text = format!("{}", versus.time_remaining().ceil() as u32);
+    }
+    if let Ok(mut text) = wins_text.single_mut() {
+        **text = format!("{} - {}", versus.wins[0], versus.wins[1]);
+    }
+}
+
+/// Show who took the versus match, replacing the score and leaderboard game over screen
+///
+/// Runs on entering GameOver in versus games; the screen is despawned automatically on exit.
+pub fn spawn_versus_win_screen(
+    mut commands: Commands,
+    styles: Res<TextStyles>,
+    versus: Res<VersusMatch>,
+    highlights: Res<Highlights>,
+) {
+    let headline = match versus.winner() {
+        Some(slot) => format!("PLAYER {} WINS!", slot + 1),
+        None => "DRAW GAME".to_string(),
+    };
+
+    commands
+        .spawn((
+            menu_root_node(),
+            GlobalZIndex(10),
+            ResultsScreen,
+            DespawnOnExit(AppState::GameOver),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(headline),
+                styles.font(TextRole::Title),
+                TextColor(Color::srgb(1.0, 0.85, 0.2)),
+            ));
+            parent.spawn((
+                Text::new(format!("Rounds {} - {}", versus.wins[0], versus.wins[1])),
+                styles.font(TextRole::Subtitle),
+                TextColor(Color::WHITE),
+            ));
+            if let Some(hint) = highlight_reel_hint(&highlights) {
+                parent.spawn((
+                    Text::new(hint),
+                    styles.font(TextRole::Body),
+                    TextColor(Color::srgb(1.0, 0.85, 0.2)),
+                ));
+            }
+            parent.spawn((
+                Text::new("Press R for a Rematch, M to Continue"),
+                styles.font(TextRole::Body),
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+            ));
+        });
+}
@@ -0,0 +1,119 @@
+use crate::combat::{DamageEvent, Health};
+use crate::game::InGame;
+use crate::player::Player;
+use crate::ui::hit_indicator::ScreenEdge;
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+
+/// Health fraction below which the vignette pulses
+pub const LOW_HEALTH_FRACTION: f32 = 0.3;
+/// Vignette intensity right after the player takes a hit
+const FLASH_INTENSITY: f32 = 0.8;
+/// How long a hit's flash takes to fade
+const FLASH_SECONDS: f32 = 0.35;
+/// Pulse intensity just under the threshold and at 1 health
+const PULSE_INTENSITY: (f32, f32) = (0.3, 0.7);
+/// Pulses per second just under the threshold and at 1 health
+const PULSE_RATE: (f32, f32) = (0.8, 2.0);
+/// Bands stacked on each edge as (thickness, opacity at full intensity), so the
+/// red is deepest at the edge and fades toward the middle
+const BANDS: [(f32, f32); 3] = [(120.0, 0.2), (70.0, 0.25), (30.0, 0.3)];
+
+/// Red vignette around the screen edges: pulses while the player is low on
+/// health and flashes whenever they take a hit
+#[derive(Component)]
+pub struct DamageVignette {
+    /// Fade of the last hit's flash
+    pub flash: Timer,
+    /// Position in the pulse cycle, in cycles
+    pub phase: f32,
+}
+
+/// One band of the vignette on one screen edge
+#[derive(Component)]
+pub struct VignetteBand {
+    /// Band opacity at full intensity
+    pub alpha: f32,
+}
+
+/// Spawn the (initially invisible) damage vignette
+///
+/// Runs on entering InGame alongside the HUD, underneath it so the health bar
+/// stays readable.
+pub fn spawn_damage_vignette(mut commands: Commands) {
+    let mut flash = Timer::from_seconds(FLASH_SECONDS, TimerMode::Once);
+    flash.finish();
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            GlobalZIndex(-1),
+            DamageVignette { flash, phase: 0.0 },
+            DespawnOnExit(InGame),
+        ))
+        .with_children(|parent| {
+            for edge in ScreenEdge::ALL {
+                for (thickness, alpha) in BANDS {
+                    parent.spawn((
+                        edge.bar(thickness),
+                        BackgroundColor(Color::srgba(0.8, 0.0, 0.0, 0.0)),
+                        VignetteBand { alpha },
+                    ));
+                }
+            }
+        });
+}
+
+/// Drive the vignette from the player's `Health`: a pulse that grows deeper
+/// and faster the closer they are to defeat once below `LOW_HEALTH_FRACTION`,
+/// and a flash on every `DamageEvent` they take
+///
+/// Whichever is stronger shows, so a hit at low health still reads as a hit.
+pub fn update_damage_vignette(
+    time: Res<Time>,
+    mut damage_events: MessageReader<DamageEvent>,
+    player_query: Query<(Entity, &Health), With<Player>>,
+    mut vignette_query: Query<&mut DamageVignette>,
+    mut band_query: Query<(&VignetteBand, &mut BackgroundColor)>,
+) {
+    let Ok(mut vignette) = vignette_query.single_mut() else {
+        return;
+    };
+    let player = player_query.single().ok();
+
+    if damage_events
+        .read()
+        .any(|event| player.is_some_and(|(entity, _)| event.target == entity))
+    {
+        vignette.flash.reset();
+    }
+    vignette.flash.tick(time.delta());
+    let flash = FLASH_INTENSITY * vignette.flash.fraction_remaining();
+
+    // 0 at the threshold, 1 at a single point of health
+    let danger = player
+        .map(|(_, health)| health.current as f32 / health.max as f32)
+        .filter(|&fraction| fraction > 0.0 && fraction < LOW_HEALTH_FRACTION)
+        .map(|fraction| 1.0 - fraction / LOW_HEALTH_FRACTION);
+    let pulse = match danger {
+        Some(danger) => {
+            let rate = PULSE_RATE.0 + (PULSE_RATE.1 - PULSE_RATE.0) * danger;
+            vignette.phase = (vignette.phase + rate * time.delta_secs()).fract();
+            let peak = PULSE_INTENSITY.0 + (PULSE_INTENSITY.1 - PULSE_INTENSITY.0) * danger;
+            peak * (0.6 + 0.4 * (vignette.phase * TAU).sin())
+        }
+        None => {
+            vignette.phase = 0.0;
+            0.0
+        }
+    };
+
+    let intensity = flash.max(pulse);
+    for (band, mut color) in band_query.iter_mut() {
+        color.0.set_alpha(band.alpha * intensity);
+    }
+}
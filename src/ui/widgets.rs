@@ -0,0 +1,170 @@
+use crate::input::PlayerActions;
+use crate::ui::text_style::{TextRole, TextStyles};
+use bevy::prelude::*;
+
+pub const BUTTON_NORMAL: Color = Color::srgb(0.15, 0.15, 0.15);
+pub const BUTTON_HOVERED: Color = Color::srgb(0.3, 0.3, 0.3);
+pub const BUTTON_PRESSED: Color = Color::srgb(0.5, 0.2, 0.2);
+pub const BUTTON_FOCUS_OUTLINE: Color = Color::srgb(1.0, 0.85, 0.2);
+
+/// Full-screen column node used as the root of menus and overlays
+pub fn menu_root_node() -> Node {
+    Node {
+        position_type: PositionType::Absolute,
+        width: Val::Percent(100.0),
+        height: Val::Percent(100.0),
+        flex_direction: FlexDirection::Column,
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        row_gap: Val::Px(20.0),
+        ..default()
+    }
+}
+
+/// Large centered title text for menus
+pub fn menu_title(styles: &TextStyles, text: &str) -> impl Bundle {
+    (
+        Text::new(text),
+        styles.font(TextRole::Title),
+        TextColor(Color::WHITE),
+    )
+}
+
+/// Spawn a standard menu button with a text label and an action marker
+pub fn spawn_menu_button(
+    parent: &mut ChildSpawnerCommands,
+    styles: &TextStyles,
+    label: &str,
+    action: impl Component,
+) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(260.0),
+                height: Val::Px(60.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(BUTTON_NORMAL),
+            action,
+        ))
+        .with_children(|button| {
+            button.spawn((
+                Text::new(label),
+                styles.font(TextRole::Button),
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// Replace the text label of a button spawned by `spawn_menu_button`
+pub fn set_button_label(children: &Children, text_query: &mut Query<&mut Text>, label: String) {
+    for child in children.iter() {
+        if let Ok(mut text) = text_query.get_mut(child) {
+            **text = label.clone();
+        }
+    }
+}
+
+/// Highlight every menu button according to its interaction state
+#[allow(clippy::type_complexity)]
+pub fn update_button_colors(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<Button>),
+    >,
+) {
+    for (interaction, mut background) in button_query.iter_mut() {
+        background.0 = match *interaction {
+            Interaction::Pressed => BUTTON_PRESSED,
+            Interaction::Hovered => BUTTON_HOVERED,
+            Interaction::None => BUTTON_NORMAL,
+        };
+    }
+}
+
+/// Keyboard/gamepad focus for menu buttons
+///
+/// Every screen with buttons always has one focused, outlined button (the
+/// first one on entering the screen). Confirm presses it by setting its
+/// `Interaction` to `Pressed`, so menu handlers treat controller and mouse
+/// input the same way.
+#[derive(Resource, Default)]
+pub struct MenuFocus {
+    pub focused: Option<Entity>,
+    /// Button pressed by confirm last frame, released on the next
+    pressed: Option<Entity>,
+}
+
+/// Move menu focus with up/down (wrapping) and press the focused button on confirm
+///
+/// Buttons are ordered by their position among their parent's children, i.e.
+/// the order they were spawned in. Runs before the menu handlers and
+/// `update_button_colors`.
+pub fn navigate_menu_focus(
+    mut commands: Commands,
+    actions: Res<PlayerActions>,
+    mut focus: ResMut<MenuFocus>,
+    mut button_query: Query<(Entity, &ChildOf, &mut Interaction), With<Button>>,
+    children_query: Query<&Children>,
+) {
+    // Release the button pressed by last frame's confirm
+    if let Some(pressed) = focus.pressed.take()
+        && let Ok((_, _, mut interaction)) = button_query.get_mut(pressed)
+        && *interaction == Interaction::Pressed
+    {
+        *interaction = Interaction::None;
+    }
+
+    let mut buttons: Vec<(Entity, Entity, usize)> = button_query
+        .iter()
+        .map(|(entity, child_of, _)| {
+            let parent = child_of.parent();
+            let index = children_query
+                .get(parent)
+                .ok()
+                .and_then(|children| children.iter().position(|child| child == entity))
+                .unwrap_or(0);
+            (entity, parent, index)
+        })
+        .collect();
+    buttons.sort_by_key(|&(_, parent, index)| (parent, index));
+    let order: Vec<Entity> = buttons.into_iter().map(|(entity, _, _)| entity).collect();
+
+    let previous = focus.focused;
+    let current = previous.and_then(|focused| order.iter().position(|&entity| entity == focused));
+    let next = match current {
+        _ if order.is_empty() => None,
+        // New screen (or the focused button is gone): focus its first button
+        None => Some(0),
+        Some(index) if actions.menu_down => Some((index + 1) % order.len()),
+        Some(index) if actions.menu_up => Some((index + order.len() - 1) % order.len()),
+        Some(index) => Some(index),
+    };
+    focus.focused = next.map(|index| order[index]);
+
+    if focus.focused != previous {
+        if let Some(previous) = previous
+            && let Ok(mut entity) = commands.get_entity(previous)
+        {
+            entity.remove::<Outline>();
+        }
+        if let Some(focused) = focus.focused {
+            commands.entity(focused).insert(Outline::new(
+                Val::Px(3.0),
+                Val::ZERO,
+                BUTTON_FOCUS_OUTLINE,
+            ));
+        }
+    }
+
+    if actions.confirm
+        && let Some(focused) = focus.focused
+        && let Ok((_, _, mut interaction)) = button_query.get_mut(focused)
+    {
+        *interaction = Interaction::Pressed;
+        focus.pressed = Some(focused);
+    }
+}
@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+
+/// Rounds a player needs to take the match (best of three)
+pub const ROUNDS_TO_WIN: u32 = 2;
+/// Seconds on the clock for each versus round
+pub const VERSUS_ROUND_SECONDS: f32 = 60.0;
+
+/// Slot of a player in `VersusMatch::wins`: 0 for player 1, 1 for player 2
+pub fn player_slot(player_two: bool) -> usize {
+    usize::from(player_two)
+}
+
+/// Score and round clock of the current versus match, inserted when it starts
+#[derive(Resource)]
+pub struct VersusMatch {
+    /// Rounds taken by player 1 and player 2
+    pub wins: [u32; 2],
+    /// Counts down while the round is fought; health decides the round when it runs out
+    pub round_timer: Timer,
+    /// The current round has been scored, so a late knockout can't score it again
+    pub round_over: bool,
+}
+
+impl Default for VersusMatch {
+    fn default() -> Self {
+        Self {
+            wins: [0, 0],
+            round_timer: Timer::from_seconds(VERSUS_ROUND_SECONDS, TimerMode::Once),
+            round_over: false,
+        }
+    }
+}
+
+impl VersusMatch {
+    /// Seconds left on the round clock
+    pub fn time_remaining(&self) -> f32 {
+        self.round_timer.remaining_secs()
+    }
+
+    /// Score the current round for `winner` (a player slot)
+    ///
+    /// A draw (`None`) scores the round for both players, so a match always
+    /// ends within three rounds.
+    pub fn score_round(&mut self, winner: Option<usize>) {
+        match winner {
+            Some(slot) => self.wins[slot] += 1,
+            None => self.wins.iter_mut().for_each(|wins| *wins += 1),
+        }
+        self.round_over = true;
+    }
+
+    /// Has either player taken enough rounds to end the match?
+    pub fn decided(&self) -> bool {
+        self.wins.iter().any(|wins| *wins >= ROUNDS_TO_WIN)
+    }
+
+    /// Slot of the match winner, or `None` if both players got there together
+    pub fn winner(&self) -> Option<usize> {
+        match self.wins {
+            [first, second] if first > second => Some(0),
+            [first, second] if second > first => Some(1),
+            _ => None,
+        }
+    }
+
+    /// Wind the clock back for the next round
+    pub fn next_round(&mut self) {
+        self.round_timer.reset();
+        self.round_over = false;
+    }
+}
@@ -0,0 +1,7 @@
+pub mod components;
+pub mod plugin;
+pub mod systems;
+
+// Re-export commonly used items
+pub use components::*;
+pub use plugin::*;
@@ -0,0 +1,43 @@
+use crate::game::systems::handle_player_defeat;
+use crate::game::{AppState, GameSet, InGame, round_fighting};
+use crate::settings::versus_mode;
+use crate::versus::systems::{
+    count_down_round, face_opponents, record_round_result, reset_versus_match, spawn_player_two,
+    start_next_round, start_versus_match,
+};
+use bevy::prelude::*;
+
+/// Versus mode: player 2 and the best-of-three round flow
+///
+/// Player-vs-player hits need nothing extra: the players are on different
+/// `Team`s, so the shared combat systems already let them hit each other.
+///
+/// Only runs in versus games; enemy spawning and the arcade timer are switched
+/// off by their own plugins (`wave_mode`).
+pub struct VersusPlugin;
+
+impl Plugin for VersusPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(InGame),
+            (start_versus_match, spawn_player_two).run_if(versus_mode),
+        )
+        .add_systems(
+            Update,
+            (
+                face_opponents
+                    .run_if(in_state(AppState::Playing))
+                    .in_set(GameSet::Arena),
+                record_round_result
+                    .after(handle_player_defeat)
+                    .in_set(GameSet::Defeat),
+                (count_down_round.run_if(round_fighting), start_next_round)
+                    .chain()
+                    .run_if(in_state(AppState::Playing))
+                    .in_set(GameSet::Spawning),
+                reset_versus_match.in_set(GameSet::Restart),
+            )
+                .run_if(versus_mode),
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use crate::combat::{Health, PlayerDefeatedEvent};
+use crate::common::Direction;
+use crate::game::{RestartGameEvent, Round, RoundPhase};
+use crate::player::spawn::player_start;
+use crate::player::{Player, PlayerState, PlayerStateType, PlayerTwo};
+use crate::prefab::{Prefab, PrefabSpawner};
+use crate::settings::Mutators;
+use crate::versus::components::{VersusMatch, player_slot};
+use crate::world::CameraDirector;
+use bevy::prelude::*;
+
+/// Fresh score and round clock for a new versus match
+pub fn start_versus_match(mut commands: Commands) {
+    commands.insert_resource(VersusMatch::default());
+}
+
+/// Spawn player 2 on the right of the stage at the start of a versus match
+///
+/// Player 1 is spawned by `spawn_player` as in every other mode.
+pub fn spawn_player_two(mut prefabs: PrefabSpawner, mutators: Res<Mutators>) {
+    prefabs.spawn(Prefab::PlayerTwo, player_start(&mutators, true));
+}
+
+/// Turn standing players toward each other
+///
+/// Only idle and blocking players turn, so a player running or attacking away
+/// keeps their facing (and a block always faces the opponent).
+#[allow(clippy::type_complexity)]
+pub fn face_opponents(
+    mut player_query: Query<
+        (
+            &Transform,
+            &PlayerState,
+            &mut Sprite,
+            &mut Direction,
+            Has<PlayerTwo>,
+        ),
+        With<Player>,
+    >,
+) {
+    let positions: Vec<(bool, f32)> = player_query
+        .iter()
+        .map(|(transform, _, _, _, player_two)| (player_two, transform.translation.x))
+        .collect();
+
+    for (transform, state, mut sprite, mut direction, player_two) in player_query.iter_mut() {
+        if !matches!(
+            state.state_type(),
+            PlayerStateType::Idle | PlayerStateType::Block
+        ) {
+            continue;
+        }
+        let Some(&(_, opponent_x)) = positions.iter().find(|(other, _)| *other != player_two)
+        else {
+            continue;
+        };
+        let facing_left = opponent_x < transform.translation.x;
+        sprite.flip_x = facing_left;
+        *direction = if facing_left {
+            Direction::Left
+        } else {
+            Direction::Right
+        };
+    }
+}
+
+/// Score the round for whoever is left standing after a knockout
+///
+/// Runs after `handle_player_defeat`. A double knockout in the same frame is a
+/// draw. Knockouts after the round has been scored (during the ceremony) don't
+/// count.
+pub fn record_round_result(
+    mut events: MessageReader<PlayerDefeatedEvent>,
+    player_query: Query<Has<PlayerTwo>, With<Player>>,
+    mut versus: ResMut<VersusMatch>,
+    mut round: ResMut<Round>,
+) {
+    let mut knocked_out = [false; 2];
+    for event in events.read() {
+        if let Ok(player_two) = player_query.get(event.player) {
+            knocked_out[player_slot(player_two)] = true;
+        }
+    }
+    if versus.round_over || !knocked_out.contains(&true) {
+        return;
+    }
+
+    let winner = match knocked_out {
+        [true, false] => Some(1),
+        [false, true] => Some(0),
+        _ => None,
+    };
+    end_round(&mut versus, &mut round, winner);
+}
+
+/// Run the round clock; when it runs out, the player with more health left
+/// (as a fraction of their max) takes the round
+pub fn count_down_round(
+    time: Res<Time>,
+    player_query: Query<(&Health, Has<PlayerTwo>), With<Player>>,
+    mut versus: ResMut<VersusMatch>,
+    mut round: ResMut<Round>,
+    mut director: ResMut<CameraDirector>,
+) {
+    if versus.round_over || !versus.round_timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let mut health_left = [0.0; 2];
+    for (health, player_two) in player_query.iter() {
+        health_left[player_slot(player_two)] = health.current as f32 / health.max as f32;
+    }
+    let winner = match health_left {
+        [first, second] if first > second => Some(0),
+        [first, second] if second > first => Some(1),
+        _ => None,
+    };
+    round.time_up(&mut director);
+    end_round(&mut versus, &mut round, winner);
+}
+
+/// Score a finished round and tell the round ceremony whether another follows
+fn end_round(versus: &mut VersusMatch, round: &mut Round, winner: Option<usize>) {
+    versus.score_round(winner);
+    round.continues = !versus.decided();
+    match winner {
+        Some(slot) => info!("Player {} takes round {}!", slot + 1, round.number),
+        None => info!("Round {} is a draw!", round.number),
+    }
+}
+
+/// Put fresh fighters back in their corners and call the next round
+///
+/// Runs once the previous round's ceremony has finished (`RoundPhase::Over`
+/// with another round to go).
+pub fn start_next_round(
+    mut commands: Commands,
+    mut prefabs: PrefabSpawner,
+    mutators: Res<Mutators>,
+    player_query: Query<Entity, With<Player>>,
+    mut versus: ResMut<VersusMatch>,
+    mut round: ResMut<Round>,
+    mut director: ResMut<CameraDirector>,
+) {
+    if round.phase != RoundPhase::Over || !round.continues {
+        return;
+    }
+
+    for entity in player_query.iter() {
+        commands.entity(entity).despawn();
+    }
+    prefabs.spawn(Prefab::Player, player_start(&mutators, false));
+    prefabs.spawn(Prefab::PlayerTwo, player_start(&mutators, true));
+
+    *round = Round::begin(round.number + 1, &mut director);
+    versus.next_round();
+}
+
+/// Start the score over on a rematch (the fighters are reset by `handle_restart`)
+pub fn reset_versus_match(
+    mut restart_events: MessageReader<RestartGameEvent>,
+    mut versus: ResMut<VersusMatch>,
+) {
+    if restart_events.is_empty() {
+        return;
+    }
+    restart_events.clear();
+    *versus = VersusMatch::default();
+}
@@ -0,0 +1,80 @@
+use crate::combat::HurtBox;
+use crate::enemy::Enemy;
+use crate::world::camera_director::Cinematic;
+use crate::world::camera_follow::CameraLock;
+use crate::world::camera_shake::CameraShake;
+use crate::world::geometry::StageGeometry;
+use bevy::prelude::*;
+
+/// Visible playfield that `ArenaConfined` bodies are kept inside
+///
+/// Recomputed every frame by `update_arena_bounds` as the level's
+/// `StageGeometry::arena` clipped to what the camera currently shows, so the
+/// player can never walk off screen whatever the window size.
+#[derive(Resource)]
+pub struct ArenaBounds {
+    pub rect: Rect,
+}
+
+impl Default for ArenaBounds {
+    fn default() -> Self {
+        Self {
+            rect: StageGeometry::default().arena,
+        }
+    }
+}
+
+/// Marker for bodies kept inside the `ArenaBounds` rather than just the stage bounds
+///
+/// The player has it from spawn. Enemies spawn off screen and only get it once
+/// they have walked fully into the arena, after which knockback can't push
+/// them back out.
+#[derive(Component)]
+pub struct ArenaConfined;
+
+/// Clip the level's arena to the camera's view (and to the `CameraLock` area,
+/// while there is one)
+///
+/// Runs at the start of the movement phase, before any intent writer. Uses the
+/// camera's position without any screen shake, so shakes don't jostle the player,
+/// and keeps the last bounds during a `Cinematic` camera sequence.
+pub fn update_arena_bounds(
+    geometry: Res<StageGeometry>,
+    lock: Res<CameraLock>,
+    shake: Res<CameraShake>,
+    cinematic: Option<Res<Cinematic>>,
+    camera_query: Query<&Transform, With<Camera2d>>,
+    window_query: Query<&Window>,
+    mut arena: ResMut<ArenaBounds>,
+) {
+    if cinematic.is_some() {
+        return;
+    }
+
+    let (Ok(camera), Ok(window)) = (camera_query.single(), window_query.single()) else {
+        arena.rect = geometry.arena;
+        return;
+    };
+
+    let center = camera.translation.truncate() - shake.offset();
+    let view = Rect::from_center_size(center, window.size());
+    arena.rect = geometry.arena.intersect(view);
+    if let Some(area) = lock.area {
+        arena.rect = arena.rect.intersect(area);
+    }
+}
+
+/// Confine enemies to the arena once they have fully entered it
+#[allow(clippy::type_complexity)]
+pub fn confine_arrived_enemies(
+    mut commands: Commands,
+    arena: Res<ArenaBounds>,
+    enemy_query: Query<(Entity, &Transform, &HurtBox), (With<Enemy>, Without<ArenaConfined>)>,
+) {
+    for (entity, transform, hurtbox) in enemy_query.iter() {
+        let body = Rect::from_center_size(transform.translation.truncate(), hurtbox.size);
+        if arena.rect.contains(body.min) && arena.rect.contains(body.max) {
+            commands.entity(entity).insert(ArenaConfined);
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use crate::world::camera_shake::CameraShake;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+/// One step of a scripted camera moment
+#[derive(Clone, Copy, Debug)]
+pub enum CameraDirective {
+    /// Pan the camera center to a world point over `seconds`
+    PanTo { target: Vec2, seconds: f32 },
+    /// Change the zoom over `seconds` (orthographic scale: 1.0 is normal, smaller is closer)
+    Zoom { scale: f32, seconds: f32 },
+    /// Keep the current framing for `seconds`
+    Hold { seconds: f32 },
+    /// Start a screen shake (instant; later directives don't wait for it)
+    Shake { amplitude: f32 },
+    /// Pan and zoom back to the framing the sequence started from, over `seconds`
+    Return { seconds: f32 },
+}
+
+/// Queue of scripted camera directives, played in order
+///
+/// Encounter scripts call `play` with a sequence (e.g. pan to a reveal, hold,
+/// return). While a sequence runs, the `Cinematic` resource exists and player
+/// input is suspended. Directives run on real time, so slow motion doesn't
+/// stretch them.
+#[derive(Resource, Default)]
+pub struct CameraDirector {
+    queue: VecDeque<CameraDirective>,
+    /// Seconds into the current directive
+    elapsed: f32,
+    /// Camera (center, zoom) when the current directive started
+    from: Option<(Vec2, f32)>,
+    /// Camera (center, zoom) when the sequence started, for `Return`
+    home: Option<(Vec2, f32)>,
+}
+
+impl CameraDirector {
+    /// Append directives to the running sequence (or start a new one)
+    pub fn play(&mut self, directives: impl IntoIterator<Item = CameraDirective>) {
+        self.queue.extend(directives);
+    }
+}
+
+/// Present while a scripted camera sequence is playing; player input is ignored
+#[derive(Resource)]
+pub struct Cinematic;
+
+/// Advance the current camera directive, easing position and zoom toward its target
+///
+/// Works on the camera's position without screen shake and re-adds the shake
+/// offset, so shakes keep working during (and after) a sequence. Inserts
+/// `Cinematic` while directives remain and removes it when the queue empties.
+pub fn run_camera_directives(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut director: ResMut<CameraDirector>,
+    mut shake: ResMut<CameraShake>,
+    cinematic: Option<Res<Cinematic>>,
+    mut camera_query: Query<(&mut Transform, &mut Projection), With<Camera2d>>,
+) {
+    let Some(&directive) = director.queue.front() else {
+        if cinematic.is_some() {
+            commands.remove_resource::<Cinematic>();
+        }
+        return;
+    };
+    if cinematic.is_none() {
+        commands.insert_resource(Cinematic);
+    }
+
+    let Ok((mut transform, mut projection)) = camera_query.single_mut() else {
+        return;
+    };
+    let Projection::Orthographic(ortho) = projection.as_mut() else {
+        return;
+    };
+
+    let current = (
+        transform.translation.truncate() - shake.offset(),
+        ortho.scale,
+    );
+    let home = *director.home.get_or_insert(current);
+    let from = *director.from.get_or_insert(current);
+    director.elapsed += time.delta_secs();
+
+    let (target, seconds) = match directive {
+        CameraDirective::PanTo { target, seconds } => ((target, from.1), seconds),
+        CameraDirective::Zoom { scale, seconds } => ((from.0, scale), seconds),
+        CameraDirective::Hold { seconds } => (from, seconds),
+        CameraDirective::Shake { amplitude } => {
+            shake.trigger(amplitude);
+            (from, 0.0)
+        }
+        CameraDirective::Return { seconds } => (home, seconds),
+    };
+
+    // Smoothstep easing so moves start and stop gently
+    let t = if seconds > 0.0 {
+        (director.elapsed / seconds).min(1.0)
+    } else {
+        1.0
+    };
+    let eased = t * t * (3.0 - 2.0 * t);
+
+    let center = from.0.lerp(target.0, eased);
+    transform.translation = (center + shake.offset()).extend(transform.translation.z);
+    ortho.scale = from.1 + (target.1 - from.1) * eased;
+
+    if t >= 1.0 {
+        director.queue.pop_front();
+        director.elapsed = 0.0;
+        director.from = None;
+        if director.queue.is_empty() {
+            director.home = None;
+        }
+    }
+}
+
+/// Abandon any camera sequence, snapping back to where it started
+///
+/// Runs on leaving InGame (before `clear_camera_shake`) so menus never open
+/// on a panned or zoomed camera.
+pub fn reset_camera_directives(
+    mut commands: Commands,
+    mut director: ResMut<CameraDirector>,
+    shake: Res<CameraShake>,
+    mut camera_query: Query<(&mut Transform, &mut Projection), With<Camera2d>>,
+) {
+    if let Some((center, scale)) = director.home
+        && let Ok((mut transform, mut projection)) = camera_query.single_mut()
+    {
+        transform.translation = (center + shake.offset()).extend(transform.translation.z);
+        if let Projection::Orthographic(ortho) = projection.as_mut() {
+            ortho.scale = scale;
+        }
+    }
+
+    *director = CameraDirector::default();
+    commands.remove_resource::<Cinematic>();
+}
@@ -0,0 +1,129 @@
+use crate::player::Player;
+use crate::world::camera_director::Cinematic;
+use crate::world::camera_shake::CameraShake;
+use crate::world::geometry::StageGeometry;
+use bevy::prelude::*;
+
+/// Camera tracking of the players, within the level
+///
+/// The camera stays put while the players' center is inside the dead zone
+/// around the camera center, then eases after them. It never shows past the
+/// edge of `StageGeometry::arena` (the stage art).
+#[derive(Resource)]
+pub struct CameraFollow {
+    /// Exponential catch-up rate per second (higher is snappier)
+    pub smoothing: f32,
+    /// Half-size of the box around the camera center the players move in freely
+    pub dead_zone: Vec2,
+}
+
+impl Default for CameraFollow {
+    fn default() -> Self {
+        Self {
+            smoothing: 6.0,
+            dead_zone: Vec2::new(120.0, 60.0),
+        }
+    }
+}
+
+/// Area the camera is held inside while a scrolling stage's combat zone is
+/// fought (None while it's free to follow the players across the level)
+///
+/// Takes the place of `StageGeometry::arena` as the camera's limit, and also
+/// clips the `ArenaBounds`, so the players can't leave the zone until it's
+/// cleared.
+#[derive(Resource, Default)]
+pub struct CameraLock {
+    pub area: Option<Rect>,
+}
+
+/// Move `center` just far enough that `point` is inside the dead zone around it
+fn dead_zone_target(center: Vec2, point: Vec2, dead_zone: Vec2) -> Vec2 {
+    let offset = point - center;
+    center + offset - offset.clamp(-dead_zone, dead_zone)
+}
+
+/// Clamp a camera center so a view of `view_size` stays inside `level`
+///
+/// On an axis where the view is bigger than the level, the level is centered.
+fn clamp_to_level(center: Vec2, view_size: Vec2, level: Rect) -> Vec2 {
+    let min = level.min + view_size / 2.0;
+    let max = level.max - view_size / 2.0;
+    Vec2::new(
+        if min.x <= max.x {
+            center.x.clamp(min.x, max.x)
+        } else {
+            level.center().x
+        },
+        if min.y <= max.y {
+            center.y.clamp(min.y, max.y)
+        } else {
+            level.center().y
+        },
+    )
+}
+
+/// Ease the camera after the players (their midpoint in versus), kept inside
+/// the level (or the `CameraLock` area)
+///
+/// Runs in the effects phase, before the camera directives and screen shake.
+/// Works on the camera's position without shake, like the directives, and
+/// steps aside while a `Cinematic` sequence owns the camera.
+#[allow(clippy::too_many_arguments)]
+pub fn follow_players(
+    time: Res<Time>,
+    follow: Res<CameraFollow>,
+    lock: Res<CameraLock>,
+    geometry: Res<StageGeometry>,
+    shake: Res<CameraShake>,
+    cinematic: Option<Res<Cinematic>>,
+    player_query: Query<&Transform, (With<Player>, Without<Camera2d>)>,
+    mut camera_query: Query<(&mut Transform, &Projection), With<Camera2d>>,
+    window_query: Query<&Window>,
+) {
+    if cinematic.is_some() {
+        return;
+    }
+    let (Ok((mut camera, projection)), Ok(window)) =
+        (camera_query.single_mut(), window_query.single())
+    else {
+        return;
+    };
+    let positions: Vec<Vec2> = player_query
+        .iter()
+        .map(|transform| transform.translation.truncate())
+        .collect();
+    if positions.is_empty() {
+        return;
+    }
+    let players = positions.iter().sum::<Vec2>() / positions.len() as f32;
+
+    let scale = match projection {
+        Projection::Orthographic(ortho) => ortho.scale,
+        _ => 1.0,
+    };
+    let view_size = window.size() * scale;
+    let center = camera.translation.truncate() - shake.offset();
+    let target = clamp_to_level(
+        dead_zone_target(center, players, follow.dead_zone),
+        view_size,
+        lock.area.unwrap_or(geometry.arena),
+    );
+    let eased = center.lerp(target, 1.0 - (-follow.smoothing * time.delta_secs()).exp());
+    camera.translation = (eased + shake.offset()).extend(camera.translation.z);
+}
+
+/// Release any camera lock and center the camera on the stage again
+///
+/// Runs on leaving InGame, after the directives and shake are undone, so
+/// menus and the next game start from the middle of the stage.
+pub fn reset_camera_follow(
+    mut lock: ResMut<CameraLock>,
+    mut camera_query: Query<&mut Transform, With<Camera2d>>,
+) {
+    lock.area = None;
+    if let Ok(mut camera) = camera_query.single_mut() {
+        camera.translation.x = 0.0;
+        camera.translation.y = 0.0;
+    }
+}
@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+
+/// Shake strength (in pixels) below which the shake counts as finished
+const MIN_AMPLITUDE: f32 = 0.1;
+
+/// Screen shake applied to the Camera2d on heavy impacts
+///
+/// `trigger` raises the amplitude; `apply_camera_shake` wobbles the camera at
+/// `frequency` and lets the amplitude decay exponentially. The applied offset
+/// is tracked so the camera's own position is never lost.
+#[derive(Resource)]
+pub struct CameraShake {
+    /// Current peak offset in pixels
+    pub amplitude: f32,
+    /// Wobbles per second
+    pub frequency: f32,
+    /// Exponential decay rate of the amplitude (per second)
+    pub decay: f32,
+    /// Seconds since the shake started, driving the wobble phase
+    elapsed: f32,
+    /// Offset currently added to the camera translation
+    offset: Vec2,
+}
+
+impl Default for CameraShake {
+    fn default() -> Self {
+        Self {
+            amplitude: 0.0,
+            frequency: 25.0,
+            decay: 12.0,
+            elapsed: 0.0,
+            offset: Vec2::ZERO,
+        }
+    }
+}
+
+impl CameraShake {
+    /// Combo finisher landing on an enemy
+    pub const COMBO_FINISHER: f32 = 8.0;
+    /// Player taking an unblocked hit
+    pub const PLAYER_HIT: f32 = 12.0;
+    /// Two attacks clashing
+    pub const CLASH: f32 = 5.0;
+
+    /// Start (or strengthen) a shake; weaker triggers never cut a stronger shake short
+    pub fn trigger(&mut self, amplitude: f32) {
+        if self.amplitude < MIN_AMPLITUDE {
+            self.elapsed = 0.0;
+        }
+        self.amplitude = self.amplitude.max(amplitude);
+    }
+
+    /// Offset currently added to the camera translation
+    pub fn offset(&self) -> Vec2 {
+        self.offset
+    }
+}
+
+/// Wobble the camera by the current shake and decay it
+///
+/// Removes last frame's offset before adding the new one, so the shake never
+/// drifts the camera.
+pub fn apply_camera_shake(
+    time: Res<Time>,
+    mut shake: ResMut<CameraShake>,
+    mut camera_query: Query<&mut Transform, With<Camera2d>>,
+) {
+    let Ok(mut camera) = camera_query.single_mut() else {
+        return;
+    };
+
+    shake.elapsed += time.delta_secs();
+    shake.amplitude *= (-shake.decay * time.delta_secs()).exp();
+    if shake.amplitude < MIN_AMPLITUDE {
+        shake.amplitude = 0.0;
+    }
+
+    // Out-of-step sines on each axis give an irregular wobble
+    let phase = shake.elapsed * shake.frequency * TAU;
+    let offset = shake.amplitude * Vec2::new(phase.sin(), (phase * 1.3 + 1.0).sin());
+
+    let previous = shake.offset;
+    camera.translation += (offset - previous).extend(0.0);
+    shake.offset = offset;
+}
+
+/// Stop any shake and put the camera back where it belongs
+///
+/// Runs on leaving InGame so a shake in progress never outlives the game.
+pub fn clear_camera_shake(
+    mut shake: ResMut<CameraShake>,
+    mut camera_query: Query<&mut Transform, With<Camera2d>>,
+) {
+    if let Ok(mut camera) = camera_query.single_mut() {
+        camera.translation -= shake.offset.extend(0.0);
+    }
+    shake.amplitude = 0.0;
+    shake.offset = Vec2::ZERO;
+}
@@ -0,0 +1,37 @@
+use crate::player::JumpPhysics;
+use bevy::prelude::*;
+
+/// Max depth difference (in units) at which two bodies share a lane and can touch
+pub const LANE_TOLERANCE: f32 = 50.0;
+/// Draw order given to a body standing at depth 0
+const DEPTH_Z_CENTER: f32 = 1.5;
+/// Draw order change per unit of depth (keeps characters between the background and foreground)
+const DEPTH_Z_SCALE: f32 = 1.0 / 2000.0;
+
+/// Marker for characters layered by depth (lower on screen is drawn in front)
+#[derive(Component)]
+pub struct DepthSorted;
+
+/// Position of a body on the depth axis
+///
+/// Bodies with jump physics stand at their ground level, so jumping doesn't
+/// change their lane; everything else (floating ghosts) is at its height.
+pub fn depth(transform: &Transform, jump_physics: Option<&JumpPhysics>) -> f32 {
+    jump_physics.map_or(transform.translation.y, |jump_physics| {
+        jump_physics.ground_y
+    })
+}
+
+/// Are two depths close enough for the bodies to hit each other?
+pub fn same_lane(a: f32, b: f32) -> bool {
+    (a - b).abs() < LANE_TOLERANCE
+}
+
+/// Layer characters by depth so nearer ones overlap farther ones
+///
+/// Runs after `resolve_movement_intents`, once this frame's positions are final.
+pub fn sort_by_depth(mut query: Query<(&mut Transform, Option<&JumpPhysics>), With<DepthSorted>>) {
+    for (mut transform, jump_physics) in query.iter_mut() {
+        transform.translation.z = DEPTH_Z_CENTER - depth(&transform, jump_physics) * DEPTH_Z_SCALE;
+    }
+}
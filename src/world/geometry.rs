@@ -0,0 +1,152 @@
+use crate::world::surface::Surface;
+use bevy::prelude::*;
+use std::ops::RangeInclusive;
+
+/// Fraction of knockback speed kept when bouncing off a wall or the floor
+pub const WALL_BOUNCE_DAMPING: f32 = 0.5;
+/// Shortfall (in units) below which a displacement counts as unblocked
+const BLOCKED_EPSILON: f32 = 0.01;
+
+/// Solid world geometry that every displacement source must respect
+///
+/// The default is the graveyard; each arcade stage replaces it with its own
+/// (`Stage::geometry`).
+///
+/// Player movement, enemy AI, and knockback all route their displacement
+/// through `resolve_movement`, so nothing can be pushed off-stage or through
+/// an obstacle regardless of which system moved it.
+#[derive(Resource)]
+pub struct StageGeometry {
+    /// Outer limits of the stage - bodies are kept fully inside
+    pub bounds: Rect,
+    /// The level's playfield (the extent of the stage art); `ArenaBounds` clips
+    /// it to the camera view and keeps `ArenaConfined` bodies inside
+    pub arena: Rect,
+    /// Ground levels the player can step between on the depth axis (W/S)
+    pub depth_lane: RangeInclusive<f32>,
+    /// Solid obstacles (walls, platforms) that block movement
+    pub obstacles: Vec<Rect>,
+    /// Areas bodies can enter but enemies route around (pits, damaging ground)
+    pub hazards: Vec<Rect>,
+    /// What the ground is made of outside every patch in `surfaces`
+    pub ground: Surface,
+    /// Patches of other ground; where they overlap, the later one wins
+    pub surfaces: Vec<(Rect, Surface)>,
+}
+
+impl Default for StageGeometry {
+    fn default() -> Self {
+        Self {
+            // Wide enough to include the off-screen enemy spawn points (±1600,
+            // `SPAWN_MARGIN` past the arena)
+            bounds: Rect::new(-1700.0, -400.0, 1700.0, 400.0),
+            // The 1600x800 graveyard background, centered on the origin
+            arena: Rect::new(-800.0, -400.0, 800.0, 400.0),
+            // The graveyard path in the lower half of the background
+            depth_lane: -250.0..=-50.0,
+            obstacles: Vec::new(),
+            hazards: Vec::new(),
+            // Graveyard grass, with the dark churned earth on the left and the
+            // stone landing at the foot of the steps on the right
+            ground: Surface::Grass,
+            surfaces: vec![
+                (Rect::new(-800.0, -250.0, -450.0, -160.0), Surface::Mud),
+                (Rect::new(100.0, -250.0, 240.0, -200.0), Surface::Stone),
+            ],
+        }
+    }
+}
+
+impl StageGeometry {
+    /// What the ground is made of at a point (a body's feet: its x and lane)
+    pub fn surface_at(&self, point: Vec2) -> Surface {
+        self.surfaces
+            .iter()
+            .rev()
+            .find(|(area, _)| area.contains(point))
+            .map_or(self.ground, |&(_, surface)| surface)
+    }
+
+    /// Resolve a desired displacement for a body centered at `position`
+    ///
+    /// Moves along X then Y separately so bodies slide along obstacle faces,
+    /// stopping flush against any obstacle in the way, then clamps the result
+    /// to the stage bounds and, if given, the `confine` rect (the arena for
+    /// `ArenaConfined` bodies). Returns the new center position.
+    pub fn resolve_movement(
+        &self,
+        position: Vec2,
+        size: Vec2,
+        delta: Vec2,
+        confine: Option<Rect>,
+    ) -> Vec2 {
+        let half = size / 2.0;
+        let mut resolved = position;
+
+        // Horizontal pass
+        resolved.x += delta.x;
+        for obstacle in &self.obstacles {
+            if overlaps(resolved, half, obstacle) {
+                if delta.x > 0.0 {
+                    resolved.x = obstacle.min.x - half.x;
+                } else if delta.x < 0.0 {
+                    resolved.x = obstacle.max.x + half.x;
+                }
+            }
+        }
+
+        // Vertical pass
+        resolved.y += delta.y;
+        for obstacle in &self.obstacles {
+            if overlaps(resolved, half, obstacle) {
+                if delta.y > 0.0 {
+                    resolved.y = obstacle.min.y - half.y;
+                } else if delta.y < 0.0 {
+                    resolved.y = obstacle.max.y + half.y;
+                }
+            }
+        }
+
+        // Keep the body inside the stage
+        resolved.x = resolved
+            .x
+            .clamp(self.bounds.min.x + half.x, self.bounds.max.x - half.x);
+        resolved.y = resolved
+            .y
+            .clamp(self.bounds.min.y + half.y, self.bounds.max.y - half.y);
+
+        // Keep confined bodies inside the arena (max/min rather than clamp, since
+        // an arena narrower than the body would make clamp panic)
+        if let Some(confine) = confine {
+            resolved = resolved.max(confine.min + half).min(confine.max - half);
+        }
+
+        resolved
+    }
+
+    /// Which axes of a displacement would be stopped by the bounds or an obstacle
+    ///
+    /// Used to bounce knockback off walls and the floor instead of letting it
+    /// grind against them.
+    pub fn blocked_axes(
+        &self,
+        position: Vec2,
+        size: Vec2,
+        delta: Vec2,
+        confine: Option<Rect>,
+    ) -> BVec2 {
+        let moved = self.resolve_movement(position, size, delta, confine) - position;
+        BVec2::new(
+            delta.x != 0.0 && (moved.x - delta.x).abs() > BLOCKED_EPSILON,
+            delta.y != 0.0 && (moved.y - delta.y).abs() > BLOCKED_EPSILON,
+        )
+    }
+}
+
+/// Strict AABB overlap between a body and an obstacle (touching edges don't overlap)
+fn overlaps(center: Vec2, half: Vec2, obstacle: &Rect) -> bool {
+    center.x - half.x < obstacle.max.x
+        && center.x + half.x > obstacle.min.x
+        && center.y - half.y < obstacle.max.y
+        && center.y + half.y > obstacle.min.y
+}
@@ -0,0 +1,134 @@
+use crate::combat::{AttackWeight, DamageEvent, Invulnerable, Team};
+use crate::debug::debug_name;
+use crate::player::{JumpPhysics, Player};
+use crate::world::depth::depth;
+use bevy::prelude::*;
+
+/// Height of a flame pillar's sprite above the ground it burns
+const FLAME_HEIGHT: f32 = 220.0;
+/// How far above its ground a player must be to clear a pit
+const PIT_CLEARANCE: f32 = 1.0;
+
+/// What a stage hazard does to the players standing in it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HazardKind {
+    /// Collapsed ground: hurts players who walk into it, but not ones jumping over it
+    Pit,
+    /// A column of fire: hurts everyone in it, jumping or not
+    Flame,
+}
+
+impl HazardKind {
+    /// Parse a hazard from its key in assets/levels.txt
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "pit" => Some(HazardKind::Pit),
+            "flame" => Some(HazardKind::Flame),
+            _ => None,
+        }
+    }
+
+    /// Damage per hit
+    fn damage(self) -> i32 {
+        match self {
+            HazardKind::Pit => 1,
+            HazardKind::Flame => 2,
+        }
+    }
+
+    /// Seconds between hits on a player who stays in it
+    fn interval(self) -> f32 {
+        match self {
+            HazardKind::Pit => 0.5,
+            HazardKind::Flame => 1.0,
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            HazardKind::Pit => Color::srgba(0.03, 0.02, 0.06, 0.9),
+            HazardKind::Flame => Color::srgba(1.0, 0.45, 0.1, 0.45),
+        }
+    }
+}
+
+/// A patch of the arena that hurts the players in it
+///
+/// `area` is on the ground, in x and depth, like the stage's surface patches.
+/// Hazards deal damage as the enemy team, so blocking, armor, and
+/// invulnerability apply as for any hit.
+#[derive(Component)]
+pub struct Hazard {
+    pub kind: HazardKind,
+    pub area: Rect,
+    /// Time until it can hit again
+    cooldown: Timer,
+}
+
+/// Hazard bundle covering `area`, drawn behind the fighters
+///
+/// Whatever spawns a hazard despawns it (see `clear_boss_encounter`), since
+/// its area is also listed in `StageGeometry::hazards` until then.
+pub fn hazard(kind: HazardKind, area: Rect) -> impl Bundle {
+    let (size, center) = match kind {
+        HazardKind::Pit => (area.size(), area.center()),
+        HazardKind::Flame => (
+            Vec2::new(area.width(), area.height() + FLAME_HEIGHT),
+            area.center() + Vec2::Y * FLAME_HEIGHT / 2.0,
+        ),
+    };
+    let mut cooldown = Timer::from_seconds(kind.interval(), TimerMode::Once);
+    cooldown.finish();
+    (
+        Sprite::from_color(kind.color(), size),
+        Transform::from_xyz(center.x, center.y, 1.0),
+        debug_name(format!("{kind:?} Hazard")),
+        Hazard {
+            kind,
+            area,
+            cooldown,
+        },
+    )
+}
+
+/// Hurt the players standing in each hazard, at most once per its interval
+///
+/// A player is in a hazard when their x and lane (see `depth`) are inside its
+/// area; pits let players in the air pass over.
+#[allow(clippy::type_complexity)]
+pub fn apply_hazards(
+    time: Res<Time>,
+    mut hazard_query: Query<(Entity, &mut Hazard)>,
+    player_query: Query<(Entity, &Transform, &JumpPhysics), (With<Player>, Without<Invulnerable>)>,
+    mut damage_events: MessageWriter<DamageEvent>,
+) {
+    for (entity, mut hazard) in hazard_query.iter_mut() {
+        if !hazard.cooldown.tick(time.delta()).is_finished() {
+            continue;
+        }
+
+        let mut hit = false;
+        for (target, transform, jump_physics) in player_query.iter() {
+            let position = Vec2::new(
+                transform.translation.x,
+                depth(transform, Some(jump_physics)),
+            );
+            let airborne = transform.translation.y > jump_physics.ground_y + PIT_CLEARANCE;
+            if !hazard.area.contains(position) || (hazard.kind == HazardKind::Pit && airborne) {
+                continue;
+            }
+            damage_events.write(DamageEvent {
+                attacker: entity,
+                target,
+                team: Team::Enemy,
+                damage: hazard.kind.damage(),
+                weight: AttackWeight::Light,
+                source: hazard.area.center(),
+            });
+            hit = true;
+        }
+        if hit {
+            hazard.cooldown.reset();
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+
+/// Physics steps per second until `apply_tick_rate` applies the `SimulationSettings` tick rate
+pub const PHYSICS_HZ: f64 = 60.0;
+
+/// Positions of a moving body at the last two physics steps
+///
+/// `Transform` stays the simulation's position, so gameplay systems in
+/// `Update` read and write it as before. Only what's drawn is smoothed:
+/// `interpolate_rendered_positions` blends between these two positions by how
+/// far the frame is into the next step. Required by `MoveIntent`, so every
+/// moving body has one.
+#[derive(Component, Default)]
+pub struct MotionInterpolation {
+    pub previous: Vec2,
+    pub current: Vec2,
+}
+
+/// Record where each body starts the physics step
+pub fn store_previous_positions(mut query: Query<(&Transform, &mut MotionInterpolation)>) {
+    for (transform, mut interpolation) in query.iter_mut() {
+        interpolation.previous = transform.translation.truncate();
+    }
+}
+
+/// Record where each body ends the physics step
+pub fn store_current_positions(mut query: Query<(&Transform, &mut MotionInterpolation)>) {
+    for (transform, mut interpolation) in query.iter_mut() {
+        interpolation.current = transform.translation.truncate();
+    }
+}
+
+/// Draw moving bodies between their last two physics positions
+///
+/// Skipped when interpolation is switched off in the `SimulationSettings`,
+/// leaving bodies drawn at their simulation position. Runs after transform propagation and overrides only the rendered
+/// `GlobalTransform` (moving bodies are never parented). A body moved outside
+/// the physics step (spawned, or teleported on restart) snaps to its new
+/// position instead of sliding there.
+pub fn interpolate_rendered_positions(
+    fixed_time: Res<Time<Fixed>>,
+    mut query: Query<(&Transform, &mut GlobalTransform, &mut MotionInterpolation)>,
+) {
+    let alpha = fixed_time.overstep_fraction();
+
+    for (transform, mut global_transform, mut interpolation) in query.iter_mut() {
+        let position = transform.translation.truncate();
+        if position != interpolation.current {
+            interpolation.previous = position;
+            interpolation.current = position;
+        }
+
+        let rendered = interpolation.previous.lerp(interpolation.current, alpha);
+        *global_transform = GlobalTransform::from(
+            transform.with_translation(rendered.extend(transform.translation.z)),
+        );
+    }
+}
@@ -0,0 +1,27 @@
+pub mod arena;
+pub mod camera_director;
+pub mod camera_follow;
+pub mod camera_shake;
+pub mod depth;
+pub mod geometry;
+pub mod hazard;
+pub mod interpolation;
+pub mod movement;
+pub mod navigation;
+pub mod plugin;
+pub mod stage;
+pub mod surface;
+
+// Re-export commonly used items
+pub use arena::*;
+pub use camera_director::*;
+pub use camera_follow::*;
+pub use camera_shake::*;
+pub use depth::*;
+pub use geometry::*;
+pub use hazard::*;
+pub use movement::*;
+pub use navigation::*;
+pub use plugin::*;
+pub use stage::*;
+pub use surface::*;
@@ -0,0 +1,61 @@
+use crate::combat::HurtBox;
+use crate::player::JumpPhysics;
+use crate::world::arena::{ArenaBounds, ArenaConfined};
+use crate::world::geometry::StageGeometry;
+use crate::world::interpolation::MotionInterpolation;
+use bevy::prelude::*;
+
+/// Desired velocity for this physics step, in units per second
+///
+/// Input, AI, and knockback systems add to this instead of moving the
+/// Transform directly. `resolve_movement_intents` is the single place that
+/// turns intent into displacement and clears it afterwards. All of them run
+/// in `FixedUpdate`, so movement doesn't depend on the frame rate.
+#[derive(Component, Default)]
+#[require(MotionInterpolation)]
+pub struct MoveIntent {
+    pub velocity: Vec2,
+}
+
+/// Apply every entity's movement intent for this physics step
+///
+/// Scales intent by the fixed timestep, resolves the displacement against stage bounds,
+/// obstacles, and (for `ArenaConfined` bodies) the arena, then clamps entities
+/// with jump physics to their ground level.
+/// Runs after all intent writers (player physics, enemy AI, knockback).
+#[allow(clippy::type_complexity)]
+pub fn resolve_movement_intents(
+    time: Res<Time>,
+    geometry: Res<StageGeometry>,
+    arena: Res<ArenaBounds>,
+    mut query: Query<(
+        &mut Transform,
+        &mut MoveIntent,
+        Option<&HurtBox>,
+        Option<&mut JumpPhysics>,
+        Has<ArenaConfined>,
+    )>,
+) {
+    for (mut transform, mut intent, hurtbox, jump_physics, confined) in query.iter_mut() {
+        let delta = intent.velocity * time.delta_secs();
+        intent.velocity = Vec2::ZERO;
+
+        let size = hurtbox.map_or(Vec2::ZERO, |hurtbox| hurtbox.size);
+        let resolved = geometry.resolve_movement(
+            transform.translation.truncate(),
+            size,
+            delta,
+            confined.then_some(arena.rect),
+        );
+        transform.translation.x = resolved.x;
+        transform.translation.y = resolved.y;
+
+        // Ground clamp - nothing (gravity, knockback, or otherwise) pushes below ground
+        if let Some(mut jump_physics) = jump_physics
+            && transform.translation.y < jump_physics.ground_y
+        {
+            transform.translation.y = jump_physics.ground_y;
+            jump_physics.velocity_y = 0.0;
+        }
+    }
+}
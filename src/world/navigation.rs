@@ -0,0 +1,164 @@
+use crate::player::{JumpPhysics, Player};
+use crate::world::depth::depth;
+use crate::world::geometry::StageGeometry;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+/// Side of a navigation cell, in world units
+const NAV_CELL_SIZE: f32 = 50.0;
+/// Room kept between a walking body's center and anything it routes around (about half a ghost)
+const NAV_CLEARANCE: f32 = 40.0;
+/// Distance of cells the goal can't be reached from
+const UNREACHABLE: u32 = u32::MAX;
+
+/// Flow field leading enemies around obstacles and hazards to the player
+///
+/// The stage bounds are split into `NAV_CELL_SIZE` cells; a cell is blocked
+/// if it comes within `NAV_CLEARANCE` of an obstacle or hazard. A breadth-first
+/// search from the player's cell stores each open cell's step distance, so
+/// `waypoint` only has to pick the neighbor one step closer. The grid is
+/// rebuilt when `StageGeometry` changes and the search reruns when the player
+/// moves to another cell. On a stage with nothing to route around it does no
+/// work and `waypoint` returns the target itself.
+#[derive(Resource, Default)]
+pub struct NavigationField {
+    /// World position of the bottom-left corner of cell 0
+    origin: Vec2,
+    columns: usize,
+    rows: usize,
+    blocked: Vec<bool>,
+    /// Steps from each cell to the goal cell
+    distance: Vec<u32>,
+    goal: Option<usize>,
+}
+
+impl NavigationField {
+    /// Lay the grid over the stage and mark the cells bodies can't pass
+    pub fn rebuild(&mut self, geometry: &StageGeometry) {
+        let size = geometry.bounds.size();
+        self.origin = geometry.bounds.min;
+        self.columns = (size.x / NAV_CELL_SIZE).ceil() as usize;
+        self.rows = (size.y / NAV_CELL_SIZE).ceil() as usize;
+
+        let avoided: Vec<Rect> = geometry
+            .obstacles
+            .iter()
+            .chain(&geometry.hazards)
+            .map(|rect| rect.inflate(NAV_CLEARANCE))
+            .collect();
+        self.blocked = (0..self.columns * self.rows)
+            .map(|cell| {
+                let half = Vec2::splat(NAV_CELL_SIZE / 2.0);
+                let cell_rect = Rect::from_center_half_size(self.center(cell), half);
+                avoided
+                    .iter()
+                    .any(|rect| !rect.intersect(cell_rect).is_empty())
+            })
+            .collect();
+        self.distance = vec![UNREACHABLE; self.blocked.len()];
+        self.goal = None;
+    }
+
+    /// Point the flow field at `goal`, searching again only if it moved to another cell
+    pub fn set_goal(&mut self, goal: Vec2) {
+        let Some(goal_cell) = self.cell(goal) else {
+            return;
+        };
+        if self.goal == Some(goal_cell) || !self.blocked.contains(&true) {
+            return;
+        }
+        self.goal = Some(goal_cell);
+
+        self.distance.fill(UNREACHABLE);
+        self.distance[goal_cell] = 0;
+        let mut frontier = VecDeque::from([goal_cell]);
+        while let Some(cell) = frontier.pop_front() {
+            let next = self.distance[cell] + 1;
+            for neighbor in self.neighbors(cell) {
+                if !self.blocked[neighbor] && self.distance[neighbor] == UNREACHABLE {
+                    self.distance[neighbor] = next;
+                    frontier.push_back(neighbor);
+                }
+            }
+        }
+    }
+
+    /// Where a body at `position` heading for `target` should walk next
+    ///
+    /// The target itself if nothing avoided lies on the straight line to it,
+    /// otherwise the center of the neighboring cell one step closer to the
+    /// goal. Falls back to the target when the field can't help (outside the
+    /// grid, or cut off from the goal).
+    pub fn waypoint(&self, position: Vec2, target: Vec2) -> Vec2 {
+        if self.goal.is_none() || self.is_clear(position, target) {
+            return target;
+        }
+        let Some(cell) = self.cell(position) else {
+            return target;
+        };
+
+        self.neighbors(cell)
+            .filter(|&neighbor| self.distance[neighbor] < self.distance[cell])
+            .min_by_key(|&neighbor| self.distance[neighbor])
+            .map_or(target, |neighbor| self.center(neighbor))
+    }
+
+    /// Index of the cell containing `position`, if it's on the grid
+    fn cell(&self, position: Vec2) -> Option<usize> {
+        let offset = (position - self.origin) / NAV_CELL_SIZE;
+        if offset.x < 0.0 || offset.y < 0.0 {
+            return None;
+        }
+        let (column, row) = (offset.x as usize, offset.y as usize);
+        (column < self.columns && row < self.rows).then_some(row * self.columns + column)
+    }
+
+    /// World position of a cell's center
+    fn center(&self, cell: usize) -> Vec2 {
+        let column = (cell % self.columns) as f32;
+        let row = (cell / self.columns) as f32;
+        self.origin + (Vec2::new(column, row) + 0.5) * NAV_CELL_SIZE
+    }
+
+    /// Cells sharing an edge with `cell`
+    fn neighbors(&self, cell: usize) -> impl Iterator<Item = usize> + use<> {
+        let (column, row) = (cell % self.columns, cell / self.columns);
+        let columns = self.columns;
+        [
+            (column > 0).then(|| cell - 1),
+            (column + 1 < columns).then(|| cell + 1),
+            (row > 0).then(|| cell - columns),
+            (row + 1 < self.rows).then(|| cell + columns),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// Does the straight line between two points stay out of blocked cells?
+    fn is_clear(&self, from: Vec2, to: Vec2) -> bool {
+        let steps = (from.distance(to) / (NAV_CELL_SIZE / 2.0)).ceil() as usize;
+        (0..=steps).all(|step| {
+            let point = from.lerp(to, step as f32 / steps.max(1) as f32);
+            self.cell(point).is_none_or(|cell| !self.blocked[cell])
+        })
+    }
+}
+
+/// Keep the navigation field current with the stage and the player's position
+///
+/// Runs in `Update`; enemy steering reads it in the next fixed steps.
+pub fn update_navigation_field(
+    geometry: Res<StageGeometry>,
+    mut field: ResMut<NavigationField>,
+    player_query: Query<(&Transform, &JumpPhysics), With<Player>>,
+) {
+    if geometry.is_changed() {
+        field.rebuild(&geometry);
+    }
+    if let Ok((transform, jump_physics)) = player_query.single() {
+        field.set_goal(Vec2::new(
+            transform.translation.x,
+            depth(transform, Some(jump_physics)),
+        ));
+    }
+}
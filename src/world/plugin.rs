@@ -0,0 +1,88 @@
+use crate::game::{GameSet, InGame};
+use crate::settings::{Mutators, interpolation_enabled};
+use crate::world::arena::{ArenaBounds, confine_arrived_enemies, update_arena_bounds};
+use crate::world::camera_director::{
+    CameraDirector, reset_camera_directives, run_camera_directives,
+};
+use crate::world::camera_follow::{CameraFollow, CameraLock, follow_players, reset_camera_follow};
+use crate::world::camera_shake::{CameraShake, apply_camera_shake, clear_camera_shake};
+use crate::world::depth::sort_by_depth;
+use crate::world::geometry::StageGeometry;
+use crate::world::hazard::apply_hazards;
+use crate::world::interpolation::{
+    PHYSICS_HZ, interpolate_rendered_positions, store_current_positions, store_previous_positions,
+};
+use crate::world::movement::resolve_movement_intents;
+use crate::world::navigation::{NavigationField, update_navigation_field};
+use crate::world::stage::{
+    apply_parallax, apply_stage_mirroring, reset_parallax, size_tiled_layers,
+};
+use bevy::prelude::*;
+use bevy::transform::TransformSystems;
+
+/// Stage geometry, arena bounds, enemy navigation, fixed-step movement resolution, depth sorting,
+/// stage hazards, the camera, and stage parallax
+pub struct WorldPlugin;
+
+impl Plugin for WorldPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StageGeometry>()
+            .init_resource::<ArenaBounds>()
+            .init_resource::<NavigationField>()
+            .init_resource::<CameraShake>()
+            .init_resource::<CameraDirector>()
+            .init_resource::<CameraFollow>()
+            .init_resource::<CameraLock>()
+            .insert_resource(Time::<Fixed>::from_hz(PHYSICS_HZ))
+            // Undo any camera sequence, screen shake, follow, or lock when leaving the game
+            .add_systems(
+                OnExit(InGame),
+                (
+                    reset_camera_directives,
+                    clear_camera_shake,
+                    reset_camera_follow,
+                    reset_parallax,
+                )
+                    .chain(),
+            )
+            .add_systems(
+                Update,
+                (
+                    apply_stage_mirroring
+                        .run_if(resource_changed::<Mutators>)
+                        .in_set(GameSet::Input),
+                    size_tiled_layers.in_set(GameSet::Input),
+                    (
+                        update_arena_bounds,
+                        confine_arrived_enemies,
+                        update_navigation_field,
+                    )
+                        .chain()
+                        .in_set(GameSet::Arena),
+                    sort_by_depth.in_set(GameSet::Movement),
+                    apply_hazards.in_set(GameSet::Collisions),
+                    (
+                        follow_players,
+                        run_camera_directives,
+                        apply_camera_shake,
+                        apply_parallax,
+                    )
+                        .chain()
+                        .in_set(GameSet::Effects),
+                ),
+            )
+            // Fixed-step physics: bodies move once per step, whatever the frame rate
+            .add_systems(FixedFirst, store_previous_positions)
+            .add_systems(
+                FixedUpdate,
+                resolve_movement_intents.in_set(GameSet::Movement),
+            )
+            .add_systems(FixedLast, store_current_positions)
+            .add_systems(
+                PostUpdate,
+                interpolate_rendered_positions
+                    .run_if(interpolation_enabled)
+                    .after(TransformSystems::Propagate),
+            );
+    }
+}
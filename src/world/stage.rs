@@ -0,0 +1,115 @@
+use crate::settings::Mutators;
+use crate::world::camera_shake::CameraShake;
+use bevy::prelude::*;
+
+/// Static stage art (background and foreground layers)
+#[derive(Component)]
+pub struct StageBackground;
+
+/// Depth scrolling of a stage layer as the camera moves
+///
+/// The layer is offset from `anchor` (where it was placed) by `factor` times
+/// the camera's distance from the stage center: between 0 and 1 it trails
+/// behind the camera and reads as far away, and below 0 it slides past faster
+/// than the fighters and reads as close to the screen. 0 moves with the world.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Parallax {
+    pub factor: f32,
+    pub anchor: Vec2,
+}
+
+/// A stage layer drawn as `tiles` copies of its image side by side, centered
+/// on its position
+///
+/// The sprite's size is only known once the image loads, so the tiling is set
+/// up by `size_tiled_layers`.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct TiledLayer {
+    pub tiles: u32,
+}
+
+/// Flip the stage art to match mirror mode
+///
+/// Runs whenever the mutators change.
+pub fn apply_stage_mirroring(
+    mutators: Res<Mutators>,
+    mut stage_query: Query<&mut Sprite, With<StageBackground>>,
+) {
+    for mut sprite in stage_query.iter_mut() {
+        sprite.flip_x = mutators.mirror;
+    }
+}
+
+/// Size each `TiledLayer` to its tile count once its image has loaded, and
+/// repeat the image across it
+///
+/// Runs every frame, but only touches layers that haven't been sized yet.
+pub fn size_tiled_layers(
+    images: Res<Assets<Image>>,
+    mut layer_query: Query<(&mut Sprite, &TiledLayer)>,
+) {
+    for (mut sprite, layer) in layer_query.iter_mut() {
+        if sprite.custom_size.is_some() {
+            continue;
+        }
+        let Some(image) = images.get(&sprite.image) else {
+            continue;
+        };
+        let size = image.size_f32();
+        sprite.custom_size = Some(Vec2::new(size.x * layer.tiles as f32, size.y));
+        sprite.image_mode = SpriteImageMode::Tiled {
+            tile_x: true,
+            tile_y: false,
+            stretch_value: 1.0,
+        };
+    }
+}
+
+/// Offset each `Parallax` layer by its factor of the camera's position
+///
+/// Runs after every camera move (follow, directives, shake), using the
+/// position without shake so the layers shake together with the world. A
+/// layer is never slid far enough to show its edge, as long as its art (all
+/// of its tiles, for a `TiledLayer`) is wider than the view.
+pub fn apply_parallax(
+    shake: Res<CameraShake>,
+    images: Res<Assets<Image>>,
+    camera_query: Query<(&Transform, &Projection), With<Camera2d>>,
+    window_query: Query<&Window>,
+    mut layer_query: Query<(&mut Transform, &Parallax, &Sprite), Without<Camera2d>>,
+) {
+    let (Ok((camera, projection)), Ok(window)) = (camera_query.single(), window_query.single())
+    else {
+        return;
+    };
+    let scale = match projection {
+        Projection::Orthographic(ortho) => ortho.scale,
+        _ => 1.0,
+    };
+    let center = camera.translation.truncate() - shake.offset();
+    let half_view = window.size() * scale / 2.0;
+
+    for (mut transform, parallax, sprite) in layer_query.iter_mut() {
+        let mut position = parallax.anchor + center * parallax.factor;
+        let size = sprite
+            .custom_size
+            .or_else(|| images.get(&sprite.image).map(Image::size_f32));
+        if let Some(size) = size {
+            let half_layer = size * transform.scale.truncate().abs() / 2.0;
+            let slack = (half_layer - half_view).max(Vec2::ZERO);
+            position = position.clamp(center - slack, center + slack);
+        }
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+    }
+}
+
+/// Put every `Parallax` layer back where it was placed
+///
+/// Runs on leaving InGame, along with `reset_camera_follow`.
+pub fn reset_parallax(mut layer_query: Query<(&mut Transform, &Parallax)>) {
+    for (mut transform, parallax) in layer_query.iter_mut() {
+        transform.translation.x = parallax.anchor.x;
+        transform.translation.y = parallax.anchor.y;
+    }
+}
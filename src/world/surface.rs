@@ -0,0 +1,27 @@
+/// What the ground is made of, which decides how steps and landings sound
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Surface {
+    Grass,
+    Stone,
+    Mud,
+}
+
+impl Surface {
+    pub const ALL: [Surface; 3] = [Surface::Grass, Surface::Stone, Surface::Mud];
+
+    /// Stable name used in sound file names and logs
+    pub fn key(self) -> &'static str {
+        match self {
+            Surface::Grass => "grass",
+            Surface::Stone => "stone",
+            Surface::Mud => "mud",
+        }
+    }
+
+    /// Parse a name written by `key`
+    pub fn from_key(key: &str) -> Option<Self> {
+        Surface::ALL
+            .into_iter()
+            .find(|surface| surface.key() == key)
+    }
+}